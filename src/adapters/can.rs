@@ -0,0 +1,879 @@
+//! CAN frame decoding and encoding driven by protocol specs.
+//!
+//! Turns raw frames (identifier + payload bytes) into physical signal values
+//! using a [`ProtocolSpec`]'s message and signal definitions, and builds
+//! frames back from values.
+//!
+//! ## Bit numbering
+//!
+//! - **Intel** (`little_endian`): `start_bit` is the signal's least
+//!   significant bit, numbered `byte * 8 + bit` with bit 0 the LSB of each
+//!   byte. Higher bits continue into the following bytes. This matches DBC.
+//! - **Motorola** (`big_endian`): `start_bit` is the signal's most
+//!   significant bit, numbered sequentially from the MSB of byte 0 (bit 0 is
+//!   the MSB of byte 0, bit 8 the MSB of byte 1). Lower bits follow in
+//!   order, so a 16-bit value in bytes 0-1 has `start_bit: 0`. DBC numbers
+//!   the same bit `byte * 8 + (7 - bit)`; see [`motorola_to_dbc_start`].
+//!
+//! ## Multi-frame messages
+//!
+//! A message longer than one frame's payload (8 bytes on classic CAN, 64 on
+//! CAN FD) is sent as one frame per payload-sized part: part 0 on the
+//! message ID and the rest on the IDs the spec lists in `part_ids`. Parts
+//! without a listed ID aren't sent, and a frame ID must not belong to two
+//! messages ([`check_message_layout`]). [`CanDecoder`] collects the parts
+//! and decodes the message once all of them have arrived.
+
+use std::collections::HashMap;
+use thiserror::Error;
+
+use super::types::{
+    ByteOrder, MessageSpec, ProtocolInfo, ProtocolSpec, ProtocolType, SignalDataType, SignalSpec,
+};
+
+/// Payload bytes in a classic CAN frame
+pub const CAN_PAYLOAD: usize = 8;
+
+/// Payload bytes in a CAN FD frame
+pub const CAN_FD_PAYLOAD: usize = 64;
+
+/// Largest 11-bit standard identifier
+pub const MAX_STANDARD_ID: u32 = 0x7FF;
+
+/// Largest 29-bit extended identifier
+pub const MAX_EXTENDED_ID: u32 = 0x1FFF_FFFF;
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+/// Errors from encoding or checking CAN signals
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum CanError {
+    /// Signal bits run past the end of the message
+    #[error("Signal '{signal}' does not fit in a {bytes}-byte message")]
+    SignalOutOfBounds { signal: String, bytes: usize },
+
+    /// Signal length or data type can't be represented
+    #[error("Signal '{signal}' has an invalid layout: {reason}")]
+    InvalidLayout { signal: String, reason: String },
+
+    /// Physical value can't be represented in the signal's bits
+    #[error("Value {value} is out of range for signal '{signal}'")]
+    ValueOutOfRange { signal: String, value: f64 },
+
+    /// Identifier doesn't fit the identifier format
+    #[error("CAN ID 0x{id:X} does not fit a {bits}-bit identifier")]
+    InvalidId { id: u32, bits: u8 },
+
+    /// Multi-frame message lists fewer part IDs than it has parts
+    #[error("Message '{message}' needs {parts} frames but lists {listed} part IDs")]
+    MissingParts {
+        message: String,
+        parts: usize,
+        listed: usize,
+    },
+
+    /// Frame identifier belongs to more than one message
+    #[error("CAN ID 0x{id:X} of message '{message}' is also used by '{other}'")]
+    OverlappingId {
+        id: u32,
+        message: String,
+        other: String,
+    },
+}
+
+// ============================================================================
+// Frames
+// ============================================================================
+
+/// A raw CAN frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanFrame {
+    /// Frame identifier
+    pub id: u32,
+    /// Whether the identifier is a 29-bit extended ID
+    pub extended: bool,
+    /// Payload bytes (up to 8 for CAN, 64 for CAN FD)
+    pub data: Vec<u8>,
+}
+
+impl CanFrame {
+    pub fn new(id: u32, extended: bool, data: Vec<u8>) -> Self {
+        Self { id, extended, data }
+    }
+}
+
+/// Check an identifier fits its 11-bit or 29-bit format
+pub fn check_id(id: u32, extended: bool) -> Result<(), CanError> {
+    let (max, bits) = if extended {
+        (MAX_EXTENDED_ID, 29)
+    } else {
+        (MAX_STANDARD_ID, 11)
+    };
+    if id > max {
+        return Err(CanError::InvalidId { id, bits });
+    }
+    Ok(())
+}
+
+/// Payload bytes per frame for a protocol
+pub fn frame_payload(info: &ProtocolInfo) -> usize {
+    if info.fd_enabled || info.protocol_type == ProtocolType::Canfd {
+        CAN_FD_PAYLOAD
+    } else {
+        CAN_PAYLOAD
+    }
+}
+
+/// Whether a message uses extended identifiers. IDs above the 11-bit range
+/// are always extended, so protocols that mix both still work.
+pub fn is_extended(info: &ProtocolInfo, message: &MessageSpec) -> bool {
+    info.extended_id || message.id > MAX_STANDARD_ID
+}
+
+/// Number of frames a message is split across: one per payload-sized part
+/// the spec gives an ID
+pub fn frame_count(info: &ProtocolInfo, message: &MessageSpec) -> usize {
+    (message.length as usize)
+        .div_ceil(frame_payload(info))
+        .clamp(1, message.part_ids.len() + 1)
+}
+
+/// Identifiers of the frames carrying a message, in part order
+pub fn frame_ids<'a>(
+    info: &ProtocolInfo,
+    message: &'a MessageSpec,
+) -> impl Iterator<Item = u32> + 'a {
+    std::iter::once(message.id)
+        .chain(message.part_ids.iter().copied())
+        .take(frame_count(info, message))
+}
+
+// ============================================================================
+// Signals
+// ============================================================================
+
+/// Convert a Motorola `start_bit` from spec numbering to DBC numbering
+pub fn motorola_to_dbc_start(start_bit: u16) -> u16 {
+    (start_bit / 8) * 8 + (7 - start_bit % 8)
+}
+
+/// Convert a Motorola DBC start bit to spec numbering
+pub fn dbc_to_motorola_start(start_bit: u16) -> u16 {
+    // The mapping flips the bit within its byte, so it is its own inverse
+    motorola_to_dbc_start(start_bit)
+}
+
+/// Check a signal's bits fit a message of `message_length` bytes and its
+/// length suits its data type
+pub fn check_layout(signal: &SignalSpec, message_length: usize) -> Result<(), CanError> {
+    let invalid = |reason: &str| CanError::InvalidLayout {
+        signal: signal.name.clone(),
+        reason: reason.to_string(),
+    };
+
+    match signal.data_type {
+        SignalDataType::Float if signal.length != 32 => {
+            return Err(invalid("float signals must be 32 bits"))
+        }
+        SignalDataType::Double if signal.length != 64 => {
+            return Err(invalid("double signals must be 64 bits"))
+        }
+        _ if signal.length == 0 || signal.length > 64 => {
+            return Err(invalid("length must be 1 to 64 bits"))
+        }
+        _ => {}
+    }
+    if signal.scale == 0.0 || !signal.scale.is_finite() {
+        return Err(invalid("scale must be a non-zero number"));
+    }
+
+    if signal.start_bit as usize + signal.length as usize > message_length * 8 {
+        return Err(CanError::SignalOutOfBounds {
+            signal: signal.name.clone(),
+            bytes: message_length,
+        });
+    }
+    Ok(())
+}
+
+/// Check the protocol's message at `index`: every part has a frame ID, the
+/// IDs are valid and used by no other message, and every signal fits
+pub fn check_message_layout(protocol: &ProtocolSpec, index: usize) -> Result<(), CanError> {
+    let info = &protocol.protocol;
+    let message = &protocol.messages[index];
+
+    let parts = (message.length as usize)
+        .div_ceil(frame_payload(info))
+        .max(1);
+    if frame_count(info, message) < parts {
+        return Err(CanError::MissingParts {
+            message: message.name.clone(),
+            parts,
+            listed: message.part_ids.len(),
+        });
+    }
+
+    let extended = is_extended(info, message);
+    for id in frame_ids(info, message) {
+        check_id(id, extended)?;
+        let other = protocol.messages.iter().enumerate().find(|(i, other)| {
+            let uses = frame_ids(info, other).filter(|&other_id| other_id == id);
+            // A message repeating one of its own IDs overlaps itself
+            let allowed = usize::from(*i == index);
+            is_extended(info, other) == extended && uses.count() > allowed
+        });
+        if let Some((_, other)) = other {
+            return Err(CanError::OverlappingId {
+                id,
+                message: message.name.clone(),
+                other: other.name.clone(),
+            });
+        }
+    }
+
+    for signal in &message.signals {
+        check_layout(signal, message.length as usize)?;
+    }
+    Ok(())
+}
+
+/// Byte index and bit within the byte of a signal's `k`th bit, counting
+/// from the least significant
+fn bit_location(signal: &SignalSpec, k: usize) -> (usize, u8) {
+    let start = signal.start_bit as usize;
+    match signal.byte_order {
+        ByteOrder::LittleEndian => {
+            let position = start + k;
+            (position / 8, (position % 8) as u8)
+        }
+        ByteOrder::BigEndian => {
+            let position = start + signal.length as usize - 1 - k;
+            (position / 8, 7 - (position % 8) as u8)
+        }
+    }
+}
+
+fn fits(data: &[u8], signal: &SignalSpec) -> bool {
+    signal.length > 0
+        && signal.length <= 64
+        && signal.start_bit as usize + signal.length as usize <= data.len() * 8
+}
+
+/// Read a signal's raw bits. Returns `None` if the signal runs past the
+/// end of `data`.
+pub fn extract_raw(data: &[u8], signal: &SignalSpec) -> Option<u64> {
+    if !fits(data, signal) {
+        return None;
+    }
+    let raw = (0..signal.length as usize).fold(0u64, |raw, k| {
+        let (byte, bit) = bit_location(signal, k);
+        raw | (((data[byte] >> bit) & 1) as u64) << k
+    });
+    Some(raw)
+}
+
+/// Write a signal's raw bits, leaving the other bits of `data` untouched
+pub fn insert_raw(data: &mut [u8], signal: &SignalSpec, raw: u64) -> Result<(), CanError> {
+    if !fits(data, signal) {
+        return Err(CanError::SignalOutOfBounds {
+            signal: signal.name.clone(),
+            bytes: data.len(),
+        });
+    }
+    for k in 0..signal.length as usize {
+        let (byte, bit) = bit_location(signal, k);
+        if (raw >> k) & 1 == 1 {
+            data[byte] |= 1 << bit;
+        } else {
+            data[byte] &= !(1 << bit);
+        }
+    }
+    Ok(())
+}
+
+/// Physical value of a raw signal reading
+pub fn raw_to_physical(signal: &SignalSpec, raw: u64) -> f64 {
+    let length = signal.length as u32;
+    let value = match signal.data_type {
+        SignalDataType::Unsigned => raw as f64,
+        SignalDataType::Signed => {
+            let shift = 64 - length.clamp(1, 64);
+            (((raw << shift) as i64) >> shift) as f64
+        }
+        SignalDataType::Float => f32::from_bits(raw as u32) as f64,
+        SignalDataType::Double => f64::from_bits(raw),
+    };
+    value * signal.scale + signal.offset
+}
+
+/// Raw bits for a physical value, rounded to the nearest step
+pub fn physical_to_raw(signal: &SignalSpec, value: f64) -> Result<u64, CanError> {
+    let scaled = (value - signal.offset) / signal.scale;
+    let out_of_range = || CanError::ValueOutOfRange {
+        signal: signal.name.clone(),
+        value,
+    };
+    let length = signal.length as u32;
+
+    match signal.data_type {
+        SignalDataType::Float => Ok((scaled as f32).to_bits() as u64),
+        SignalDataType::Double => Ok(scaled.to_bits()),
+        _ if !scaled.is_finite() || length == 0 || length > 64 => Err(out_of_range()),
+        SignalDataType::Unsigned => {
+            let rounded = scaled.round();
+            let max = 2f64.powi(length as i32) - 1.0;
+            if rounded < 0.0 || rounded > max {
+                return Err(out_of_range());
+            }
+            Ok(rounded as u64)
+        }
+        SignalDataType::Signed => {
+            let rounded = scaled.round();
+            let half = 2f64.powi(length as i32 - 1);
+            if rounded < -half || rounded > half - 1.0 {
+                return Err(out_of_range());
+            }
+            Ok((rounded as i64 as u64) & mask(length))
+        }
+    }
+}
+
+fn mask(length: u32) -> u64 {
+    if length >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << length) - 1
+    }
+}
+
+/// Decode one signal from a message's bytes
+pub fn decode_signal(data: &[u8], signal: &SignalSpec) -> Option<f64> {
+    extract_raw(data, signal).map(|raw| raw_to_physical(signal, raw))
+}
+
+/// Encode one signal's physical value into a message's bytes
+pub fn encode_signal(data: &mut [u8], signal: &SignalSpec, value: f64) -> Result<(), CanError> {
+    let raw = physical_to_raw(signal, value)?;
+    insert_raw(data, signal, raw)
+}
+
+// ============================================================================
+// Messages
+// ============================================================================
+
+/// Decode every signal of a message, in signal order. Signals that don't
+/// fit in `data` (a short frame) decode as NaN.
+pub fn decode_message(message: &MessageSpec, data: &[u8]) -> Vec<f64> {
+    message
+        .signals
+        .iter()
+        .map(|signal| decode_signal(data, signal).unwrap_or(f64::NAN))
+        .collect()
+}
+
+/// Encode values, given in signal order, into a message's bytes. NaN
+/// values leave their signal's bits at zero.
+pub fn encode_message(message: &MessageSpec, values: &[f64]) -> Result<Vec<u8>, CanError> {
+    let mut data = vec![0u8; message.length as usize];
+    for (signal, &value) in message.signals.iter().zip(values) {
+        if !value.is_nan() {
+            encode_signal(&mut data, signal, value)?;
+        }
+    }
+    Ok(data)
+}
+
+/// Split a message's bytes into the frames that carry it
+pub fn message_frames(
+    info: &ProtocolInfo,
+    message: &MessageSpec,
+    data: &[u8],
+) -> Result<Vec<CanFrame>, CanError> {
+    let extended = is_extended(info, message);
+    let payload = frame_payload(info);
+
+    frame_ids(info, message)
+        .enumerate()
+        .map(|(part, id)| {
+            check_id(id, extended)?;
+            let start = (part * payload).min(data.len());
+            let end = (start + payload).min(data.len());
+            Ok(CanFrame::new(id, extended, data[start..end].to_vec()))
+        })
+        .collect()
+}
+
+/// Encode values, given in signal order, into the frames for a message
+pub fn encode_frames(
+    protocol: &ProtocolSpec,
+    message: &MessageSpec,
+    values: &[f64],
+) -> Result<Vec<CanFrame>, CanError> {
+    let data = encode_message(message, values)?;
+    message_frames(&protocol.protocol, message, &data)
+}
+
+/// A message decoded from one or more frames
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedMessage {
+    /// Index of the message in the protocol's `messages`
+    pub message: usize,
+    /// Physical values in signal order (NaN where the frame was too short)
+    pub values: Vec<f64>,
+}
+
+/// Parts of a multi-frame message received so far
+#[derive(Debug, Clone, Default)]
+struct PendingMessage {
+    data: Vec<u8>,
+    received: u64,
+}
+
+/// Decodes a stream of frames against a protocol, reassembling multi-frame
+/// messages
+#[derive(Debug, Clone)]
+pub struct CanDecoder {
+    protocol: ProtocolSpec,
+    payload: usize,
+    /// (id, extended) of each frame to (message index, part)
+    routes: HashMap<(u32, bool), (usize, usize)>,
+    pending: HashMap<usize, PendingMessage>,
+}
+
+impl CanDecoder {
+    pub fn new(protocol: ProtocolSpec) -> Self {
+        let payload = frame_payload(&protocol.protocol);
+        let mut routes = HashMap::new();
+        // Messages' own IDs go first, so a listed part ID never takes over a
+        // message of its own. Otherwise the first message claiming an ID wins.
+        for (index, message) in protocol.messages.iter().enumerate() {
+            let extended = is_extended(&protocol.protocol, message);
+            routes.entry((message.id, extended)).or_insert((index, 0));
+        }
+        for (index, message) in protocol.messages.iter().enumerate() {
+            let extended = is_extended(&protocol.protocol, message);
+            for (part, id) in frame_ids(&protocol.protocol, message).enumerate().skip(1) {
+                if let Some(&(other, _)) = routes.get(&(id, extended)) {
+                    tracing::warn!(
+                        "{}: part {} of {} shares CAN ID 0x{:X} with {}",
+                        protocol.id,
+                        part,
+                        message.name,
+                        id,
+                        protocol.messages[other].name
+                    );
+                    continue;
+                }
+                routes.insert((id, extended), (index, part));
+            }
+        }
+
+        Self {
+            protocol,
+            payload,
+            routes,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// The protocol frames are decoded against
+    pub fn protocol(&self) -> &ProtocolSpec {
+        &self.protocol
+    }
+
+    /// Whether the protocol defines frames with this identifier
+    pub fn defines(&self, id: u32, extended: bool) -> bool {
+        self.routes.contains_key(&(id, extended))
+    }
+
+    /// Decode a frame. Returns `None` for frames the protocol doesn't
+    /// define and for parts of a multi-frame message that isn't complete
+    /// yet.
+    pub fn decode(&mut self, frame: &CanFrame) -> Option<DecodedMessage> {
+        let &(index, part) = self.routes.get(&(frame.id, frame.extended))?;
+        let message = &self.protocol.messages[index];
+        let parts = frame_count(&self.protocol.protocol, message);
+
+        if parts == 1 {
+            let length = frame.data.len().min(message.length as usize);
+            return Some(DecodedMessage {
+                message: index,
+                values: decode_message(message, &frame.data[..length]),
+            });
+        }
+
+        let length = message.length as usize;
+        let start = part * self.payload;
+        let end = (start + self.payload).min(length);
+        // A short part would leave a hole in the message, so drop it
+        if frame.data.len() < end - start {
+            return None;
+        }
+
+        let pending = self.pending.entry(index).or_default();
+        if part == 0 || pending.data.len() != length {
+            pending.data = vec![0; length];
+            pending.received = 0;
+        }
+        pending.data[start..end].copy_from_slice(&frame.data[..end - start]);
+        pending.received |= 1 << part;
+
+        let complete = mask(parts as u32);
+        if pending.received != complete {
+            return None;
+        }
+        pending.received = 0;
+        Some(DecodedMessage {
+            message: index,
+            values: decode_message(message, &pending.data),
+        })
+    }
+
+    /// Enum label for a decoded value, if the signal references an enum
+    /// that names it
+    pub fn enum_label(&self, message: usize, signal: usize, value: f64) -> Option<&str> {
+        let signal = self.protocol.messages.get(message)?.signals.get(signal)?;
+        enum_label(&self.protocol, signal, value)
+    }
+}
+
+/// Enum label for a signal value, if the signal references an enum that
+/// names it
+pub fn enum_label<'a>(
+    protocol: &'a ProtocolSpec,
+    signal: &SignalSpec,
+    value: f64,
+) -> Option<&'a str> {
+    let name = signal.enum_ref.as_deref()?;
+    let spec = protocol.enums.as_ref()?.iter().find(|e| e.name == name)?;
+    if value.fract() != 0.0 {
+        return None;
+    }
+    spec.values
+        .get(&(value as i64).to_string())
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::registry::get_protocols;
+
+    const TEST_PROTOCOL: &str = r#"
+openecualliance: "1.0"
+type: protocol
+id: test-broadcast
+name: Test Broadcast
+version: "1.0.0"
+vendor: test
+protocol:
+  type: can
+  baudrate: 500000
+messages:
+  - id: "0x360"
+    name: Engine
+    length: 8
+    signals:
+      - { name: RPM, start_bit: 0, length: 16, byte_order: big_endian, data_type: unsigned }
+      - { name: MAP, start_bit: 16, length: 16, byte_order: big_endian, data_type: unsigned, scale: 0.1, unit: kPa }
+      - { name: Ignition, start_bit: 32, length: 12, byte_order: big_endian, data_type: signed, scale: 0.1 }
+      - { name: Gear, start_bit: 48, length: 4, byte_order: little_endian, data_type: unsigned, enum_ref: gears }
+      - { name: Launch, start_bit: 63, length: 1, byte_order: little_endian, data_type: unsigned }
+  - id: "0x361"
+    name: Intel
+    length: 8
+    signals:
+      - { name: Speed, start_bit: 4, length: 16, byte_order: little_endian, data_type: unsigned }
+      - { name: Lambda, start_bit: 32, length: 32, byte_order: little_endian, data_type: float }
+  - id: "0x370"
+    name: Long
+    length: 16
+    part_ids: ["0x371"]
+    signals:
+      - { name: First, start_bit: 0, length: 32, byte_order: big_endian, data_type: signed }
+      - { name: Last, start_bit: 64, length: 64, byte_order: little_endian, data_type: double }
+  - id: "0x1F0A000"
+    name: Extended
+    length: 2
+    signals:
+      - { name: Temp, start_bit: 0, length: 16, byte_order: big_endian, data_type: signed, offset: -40 }
+enums:
+  - name: gears
+    values:
+      "0": Neutral
+      "1": First
+"#;
+
+    fn test_protocol() -> ProtocolSpec {
+        serde_yaml::from_str(TEST_PROTOCOL).unwrap()
+    }
+
+    fn signal(protocol: &ProtocolSpec, name: &str) -> SignalSpec {
+        protocol
+            .messages
+            .iter()
+            .flat_map(|m| &m.signals)
+            .find(|s| s.name == name)
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_motorola_bit_numbering() {
+        let protocol = test_protocol();
+        let data = [0x1B, 0x58, 0x03, 0xE8, 0xF9, 0xC0, 0x00, 0x00];
+
+        assert_eq!(
+            decode_signal(&data, &signal(&protocol, "RPM")),
+            Some(7000.0)
+        );
+        assert_eq!(decode_signal(&data, &signal(&protocol, "MAP")), Some(100.0));
+        // 0xF9C (12 bits, two's complement) = -100
+        let ignition = decode_signal(&data, &signal(&protocol, "Ignition")).unwrap();
+        assert!((ignition + 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_intel_bit_numbering() {
+        let protocol = test_protocol();
+        let speed = signal(&protocol, "Speed");
+        let mut data = [0u8; 8];
+        insert_raw(&mut data, &speed, 0xABCD).unwrap();
+        // Starts at bit 4, so the value straddles three bytes
+        assert_eq!(&data[..3], &[0xD0, 0xBC, 0x0A]);
+        assert_eq!(extract_raw(&data, &speed), Some(0xABCD));
+
+        let launch = signal(&protocol, "Launch");
+        let mut data = [0u8; 8];
+        encode_signal(&mut data, &launch, 1.0).unwrap();
+        assert_eq!(data[7], 0x80);
+    }
+
+    #[test]
+    fn test_dbc_start_bit_conversion() {
+        assert_eq!(motorola_to_dbc_start(0), 7);
+        assert_eq!(motorola_to_dbc_start(16), 23);
+        assert_eq!(motorola_to_dbc_start(35), 36);
+        for bit in 0..64 {
+            assert_eq!(dbc_to_motorola_start(motorola_to_dbc_start(bit)), bit);
+        }
+    }
+
+    #[test]
+    fn test_signed_and_float_round_trip() {
+        let protocol = test_protocol();
+        let ignition = signal(&protocol, "Ignition");
+        let lambda = signal(&protocol, "Lambda");
+        let mut data = [0u8; 8];
+
+        encode_signal(&mut data, &ignition, -204.8).unwrap();
+        assert!((decode_signal(&data, &ignition).unwrap() + 204.8).abs() < 1e-9);
+        assert!(matches!(
+            encode_signal(&mut data, &ignition, 204.8),
+            Err(CanError::ValueOutOfRange { .. })
+        ));
+
+        encode_signal(&mut data, &lambda, 0.98).unwrap();
+        assert!((decode_signal(&data, &lambda).unwrap() - 0.98).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_check_layout() {
+        let protocol = test_protocol();
+        let mut rpm = signal(&protocol, "RPM");
+        assert!(check_layout(&rpm, 8).is_ok());
+        assert!(matches!(
+            check_layout(&rpm, 1),
+            Err(CanError::SignalOutOfBounds { .. })
+        ));
+
+        rpm.data_type = SignalDataType::Float;
+        assert!(matches!(
+            check_layout(&rpm, 8),
+            Err(CanError::InvalidLayout { .. })
+        ));
+    }
+
+    #[test]
+    fn test_decoder_single_frame_and_enum() {
+        let mut decoder = CanDecoder::new(test_protocol());
+        let frame = CanFrame::new(0x360, false, vec![0x0B, 0xB8, 0, 0, 0, 0, 0x01, 0]);
+        let decoded = decoder.decode(&frame).unwrap();
+
+        assert_eq!(decoded.message, 0);
+        assert_eq!(decoded.values[0], 3000.0);
+        assert_eq!(decoded.values[3], 1.0);
+        assert_eq!(decoder.enum_label(0, 3, decoded.values[3]), Some("First"));
+
+        // A short frame decodes what it carries
+        let short = decoder
+            .decode(&CanFrame::new(0x360, false, vec![0x0B, 0xB8]))
+            .unwrap();
+        assert_eq!(short.values[0], 3000.0);
+        assert!(short.values[1].is_nan());
+
+        // Unknown IDs and the wrong ID format are ignored
+        assert!(decoder
+            .decode(&CanFrame::new(0x123, false, vec![0; 8]))
+            .is_none());
+        assert!(decoder
+            .decode(&CanFrame::new(0x360, true, vec![0; 8]))
+            .is_none());
+    }
+
+    #[test]
+    fn test_multi_frame_message() {
+        let protocol = test_protocol();
+        let long = protocol.messages[2].clone();
+        let frames = encode_frames(&protocol, &long, &[-123_456.0, 1.5e-3]).unwrap();
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].id, 0x370);
+        assert_eq!(frames[1].id, 0x371);
+
+        let mut decoder = CanDecoder::new(protocol);
+        assert!(decoder.decode(&frames[0]).is_none());
+        let decoded = decoder.decode(&frames[1]).unwrap();
+        assert_eq!(decoded.message, 2);
+        assert_eq!(decoded.values, vec![-123_456.0, 1.5e-3]);
+
+        // A second part on its own doesn't complete a new message
+        assert!(decoder.decode(&frames[1]).is_none());
+    }
+
+    #[test]
+    fn test_unlisted_parts_are_not_reassembled() {
+        let mut protocol = test_protocol();
+        protocol.messages[2].part_ids.clear();
+        let mut next: MessageSpec = serde_yaml::from_str(
+            "{ id: 0x371, name: Next, length: 2, signals: \
+             [{ name: Boost, start_bit: 0, length: 16, byte_order: big_endian, data_type: unsigned }] }",
+        )
+        .unwrap();
+        protocol.messages.push(next.clone());
+        assert!(matches!(
+            check_message_layout(&protocol, 2),
+            Err(CanError::MissingParts {
+                parts: 2,
+                listed: 0,
+                ..
+            })
+        ));
+
+        // Without a listed part ID, only the first frame of the long message is
+        // sent and the message on the next ID decodes as itself alone
+        let long = protocol.messages[2].clone();
+        let frames = encode_frames(&protocol, &long, &[-5.0, 1.0]).unwrap();
+        assert_eq!(frames.len(), 1);
+        let mut decoder = CanDecoder::new(protocol.clone());
+        let decoded = decoder.decode(&frames[0]).unwrap();
+        assert_eq!(decoded.values[0], -5.0);
+        assert!(decoded.values[1].is_nan());
+        let decoded = decoder
+            .decode(&CanFrame::new(0x371, false, vec![0x01, 0x00]))
+            .unwrap();
+        assert_eq!(
+            decoded,
+            DecodedMessage {
+                message: 4,
+                values: vec![256.0]
+            }
+        );
+
+        // Listing a part ID another message uses is an overlap, and the other
+        // message keeps its ID
+        protocol.messages[2].part_ids = vec![0x371];
+        assert!(matches!(
+            check_message_layout(&protocol, 2),
+            Err(CanError::OverlappingId { id: 0x371, .. })
+        ));
+        let mut decoder = CanDecoder::new(protocol.clone());
+        assert_eq!(
+            decoder
+                .decode(&CanFrame::new(0x371, false, vec![0x01, 0x00]))
+                .map(|d| d.message),
+            Some(4)
+        );
+
+        next.id = 0x372;
+        protocol.messages[4] = next;
+        assert!(check_message_layout(&protocol, 2).is_ok());
+    }
+
+    #[test]
+    fn test_extended_ids() {
+        let protocol = test_protocol();
+        let message = protocol.messages[3].clone();
+        let frames = encode_frames(&protocol, &message, &[85.0]).unwrap();
+
+        assert!(frames[0].extended);
+        assert_eq!(frames[0].id, 0x1F0A000);
+        assert_eq!(frames[0].data, vec![0x00, 0x7D]);
+
+        let mut decoder = CanDecoder::new(protocol);
+        assert_eq!(decoder.decode(&frames[0]).unwrap().values, vec![85.0]);
+        assert!(matches!(
+            check_id(0x800, false),
+            Err(CanError::InvalidId { bits: 11, .. })
+        ));
+        assert!(check_id(MAX_EXTENDED_ID, true).is_ok());
+    }
+
+    /// A raw value exercising every bit position of a signal
+    fn sample_raw(signal: &SignalSpec) -> u64 {
+        match signal.data_type {
+            SignalDataType::Float => (-12.375f32).to_bits() as u64,
+            SignalDataType::Double => (-12.375f64).to_bits(),
+            _ => 0xA5A5_A5A5_A5A5_A5A5 & mask(signal.length as u32),
+        }
+    }
+
+    #[test]
+    fn test_protocols_round_trip() {
+        // The test protocol keeps this meaningful when the spec submodule
+        // isn't checked out
+        for protocol in std::iter::once(test_protocol()).chain(get_protocols()) {
+            let mut decoder = CanDecoder::new(protocol.clone());
+
+            for (index, message) in protocol.messages.iter().enumerate() {
+                check_message_layout(&protocol, index)
+                    .unwrap_or_else(|e| panic!("{} 0x{:X}: {}", protocol.id, message.id, e));
+
+                for signal in &message.signals {
+                    let expected = raw_to_physical(signal, sample_raw(signal));
+                    let mut data = vec![0u8; message.length as usize];
+                    encode_signal(&mut data, signal, expected).unwrap();
+                    let decoded = decode_signal(&data, signal).unwrap();
+                    assert!(
+                        (decoded - expected).abs() <= expected.abs() * 1e-9,
+                        "{} {}: {} != {}",
+                        protocol.id,
+                        signal.name,
+                        decoded,
+                        expected
+                    );
+                }
+
+                // The message's frames reach the decoder and reassemble
+                let frames = encode_frames(&protocol, message, &[]).unwrap();
+                let decoded: Vec<DecodedMessage> = frames
+                    .iter()
+                    .filter_map(|frame| decoder.decode(frame))
+                    .collect();
+                let zeros = vec![0u8; message.length as usize];
+                assert_eq!(
+                    decoded,
+                    vec![DecodedMessage {
+                        message: index,
+                        values: decode_message(message, &zeros),
+                    }],
+                    "{} 0x{:X}",
+                    protocol.id,
+                    message.id
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,1436 @@
+//! DBC (Vector CAN database) import and export of protocol specs.
+//!
+//! [`write_dbc`] writes a [`ProtocolSpec`] as a DBC file for configuring
+//! dashes, data loggers and bus tools:
+//!
+//! - `BO_` / `SG_` lines with DBC byte-order (`@1` Intel, `@0` Motorola) and
+//!   sign (`+` / `-`) notation, scale, offset, min/max and unit
+//! - `VAL_` tables from the protocol's enums
+//! - `CM_` comments from descriptions and signal notes
+//! - `BA_` attributes for the bus type, baud rates and broadcast intervals
+//! - `SIG_VALTYPE_` for float and double signals
+//!
+//! Motorola start bits are converted from the spec's sequential numbering
+//! with [`motorola_to_dbc_start`]. DBC names must be C identifiers, so other
+//! characters in message, signal and node names become underscores.
+//!
+//! DBC has no notion of a message spread over several identifiers, so
+//! each part of a multi-frame message is written as its own message
+//! (`Name_Part0`, `Name_Part1`, ...). Signals crossing a part boundary
+//! can't be described and are left out.
+//!
+//! [`parse_dbc`] reads the same statements back into a protocol spec, so
+//! broadcasts that vendors only publish as a DBC can be decoded like the
+//! embedded protocols.
+
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::sync::LazyLock;
+use thiserror::Error;
+
+use super::can::{
+    check_layout, dbc_to_motorola_start, frame_count, frame_ids, frame_payload, is_extended,
+    motorola_to_dbc_start, CAN_PAYLOAD, MAX_STANDARD_ID,
+};
+use super::types::{
+    ByteOrder, EnumSpec, MessageSpec, ProtocolInfo, ProtocolSpec, ProtocolType, SignalDataType,
+    SignalSpec,
+};
+
+/// Node name DBC uses for "no transmitter" / "no receiver"
+pub const NO_NODE: &str = "Vector__XXX";
+
+/// Flag set on `BO_` identifiers of extended (29-bit) frames
+pub const EXTENDED_ID_FLAG: u32 = 0x8000_0000;
+
+/// `VFrameFormat` values for CAN FD frames
+const FD_STANDARD_FRAME: u32 = 14;
+const FD_EXTENDED_FRAME: u32 = 15;
+
+/// Sections of the `NS_` block written by common tools
+const NEW_SYMBOLS: &[&str] = &[
+    "NS_DESC_",
+    "CM_",
+    "BA_DEF_",
+    "BA_",
+    "VAL_",
+    "CAT_DEF_",
+    "CAT_",
+    "FILTER",
+    "BA_DEF_DEF_",
+    "EV_DATA_",
+    "ENVVAR_DATA_",
+    "SGTYPE_",
+    "SGTYPE_VAL_",
+    "BA_DEF_SGTYPE_",
+    "BA_SGTYPE_",
+    "SIG_TYPE_REF_",
+    "VAL_TABLE_",
+    "SIG_GROUP_",
+    "SIG_VALTYPE_",
+    "SIGTYPE_VALTYPE_",
+    "BO_TX_BU_",
+    "BA_DEF_REL_",
+    "BA_REL_",
+    "BA_DEF_DEF_REL_",
+    "BU_SG_REL_",
+    "BU_EV_REL_",
+    "BU_BO_REL_",
+    "SG_MUL_VAL_",
+];
+
+// ============================================================================
+// Export
+// ============================================================================
+
+/// A message as it is written to the DBC: one per frame of the spec message
+struct DbcMessage<'a> {
+    /// `BO_` identifier, with [`EXTENDED_ID_FLAG`] set for extended frames
+    id: u32,
+    name: String,
+    length: usize,
+    transmitter: String,
+    spec: &'a MessageSpec,
+    signals: Vec<DbcSignal<'a>>,
+}
+
+/// A signal placed in a [`DbcMessage`]
+struct DbcSignal<'a> {
+    name: String,
+    /// Start bit in DBC numbering, relative to the message's frame
+    start_bit: u16,
+    spec: &'a SignalSpec,
+}
+
+/// Write a protocol spec as DBC text
+pub fn write_dbc(protocol: &ProtocolSpec) -> String {
+    let messages = plan_messages(protocol);
+
+    let mut nodes: Vec<&str> = Vec::new();
+    for message in &messages {
+        if message.transmitter != NO_NODE && !nodes.contains(&message.transmitter.as_str()) {
+            nodes.push(&message.transmitter);
+        }
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(out, "VERSION \"{}\"", escape(&protocol.version));
+    out.push_str("\n\nNS_ :\n");
+    for symbol in NEW_SYMBOLS {
+        let _ = writeln!(out, "\t{}", symbol);
+    }
+    out.push_str("\nBS_:\n\n");
+    let _ = writeln!(out, "BU_: {}", nodes.join(" "));
+    out.push('\n');
+
+    for message in &messages {
+        write_message(&mut out, message);
+    }
+
+    write_comments(&mut out, protocol, &messages);
+    write_attributes(&mut out, protocol, &messages);
+    write_value_tables(&mut out, protocol, &messages);
+
+    // Float and double signals are flagged after the value tables
+    for message in &messages {
+        for signal in &message.signals {
+            let value_type = match signal.spec.data_type {
+                SignalDataType::Float => 1,
+                SignalDataType::Double => 2,
+                _ => continue,
+            };
+            let _ = writeln!(
+                out,
+                "SIG_VALTYPE_ {} {} : {};",
+                message.id, signal.name, value_type
+            );
+        }
+    }
+
+    out
+}
+
+/// Lay the protocol's messages and signals out as DBC messages, splitting
+/// multi-frame messages into one message per frame
+fn plan_messages(protocol: &ProtocolSpec) -> Vec<DbcMessage<'_>> {
+    let info = &protocol.protocol;
+    let payload = frame_payload(info);
+    let mut message_names = HashSet::new();
+    let mut messages = Vec::new();
+
+    for spec in &protocol.messages {
+        let parts = frame_count(info, spec);
+        let flag = if is_extended(info, spec) {
+            EXTENDED_ID_FLAG
+        } else {
+            0
+        };
+        let transmitter = spec
+            .transmitter
+            .as_deref()
+            .map(identifier)
+            .unwrap_or_else(|| NO_NODE.to_string());
+
+        for (part, id) in frame_ids(info, spec).enumerate() {
+            let base_name = if parts > 1 {
+                format!("{}_Part{}", identifier(&spec.name), part)
+            } else {
+                identifier(&spec.name)
+            };
+            let first_bit = part * payload * 8;
+            let length = (spec.length as usize - part * payload).min(payload);
+
+            let mut signal_names = HashSet::new();
+            let mut signals = Vec::new();
+            for signal in &spec.signals {
+                if let Err(e) = check_layout(signal, spec.length as usize) {
+                    tracing::warn!("Leaving {} out of the DBC: {}", signal.name, e);
+                    continue;
+                }
+                let start = signal.start_bit as usize;
+                let end = start + signal.length as usize;
+                if start < first_bit || start >= first_bit + payload * 8 {
+                    continue;
+                }
+                if end > first_bit + payload * 8 {
+                    tracing::warn!(
+                        "Leaving {} out of the DBC: it crosses a frame boundary of {}",
+                        signal.name,
+                        spec.name
+                    );
+                    continue;
+                }
+
+                let local = (start - first_bit) as u16;
+                let start_bit = match signal.byte_order {
+                    ByteOrder::LittleEndian => local,
+                    ByteOrder::BigEndian => motorola_to_dbc_start(local),
+                };
+                signals.push(DbcSignal {
+                    name: unique(identifier(&signal.name), &mut signal_names),
+                    start_bit,
+                    spec: signal,
+                });
+            }
+
+            messages.push(DbcMessage {
+                id: id | flag,
+                name: unique(base_name, &mut message_names),
+                length,
+                transmitter: transmitter.clone(),
+                spec,
+                signals,
+            });
+        }
+    }
+    messages
+}
+
+fn write_message(out: &mut String, message: &DbcMessage) {
+    let _ = writeln!(
+        out,
+        "BO_ {} {}: {} {}",
+        message.id, message.name, message.length, message.transmitter
+    );
+    for signal in &message.signals {
+        let spec = signal.spec;
+        let byte_order = match spec.byte_order {
+            ByteOrder::LittleEndian => 1,
+            ByteOrder::BigEndian => 0,
+        };
+        let sign = match spec.data_type {
+            SignalDataType::Unsigned => '+',
+            _ => '-',
+        };
+        let (min, max) = physical_range(spec);
+        let _ = writeln!(
+            out,
+            " SG_ {} : {}|{}@{}{} ({},{}) [{}|{}] \"{}\" {}",
+            signal.name,
+            signal.start_bit,
+            spec.length,
+            byte_order,
+            sign,
+            spec.scale,
+            spec.offset,
+            number(spec.min.unwrap_or(min)),
+            number(spec.max.unwrap_or(max)),
+            escape(spec.unit.as_deref().unwrap_or_default()),
+            NO_NODE
+        );
+    }
+    out.push('\n');
+}
+
+fn write_comments(out: &mut String, protocol: &ProtocolSpec, messages: &[DbcMessage]) {
+    if let Some(description) = &protocol.description {
+        let _ = writeln!(out, "CM_ \"{}\";", escape(description));
+    }
+    for message in messages {
+        if let Some(description) = &message.spec.description {
+            let _ = writeln!(out, "CM_ BO_ {} \"{}\";", message.id, escape(description));
+        }
+        for signal in &message.signals {
+            let text: Vec<&str> = [&signal.spec.description, &signal.spec.comment]
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .collect();
+            if !text.is_empty() {
+                let _ = writeln!(
+                    out,
+                    "CM_ SG_ {} {} \"{}\";",
+                    message.id,
+                    signal.name,
+                    escape(&text.join("\n"))
+                );
+            }
+        }
+    }
+}
+
+fn write_attributes(out: &mut String, protocol: &ProtocolSpec, messages: &[DbcMessage]) {
+    let info = &protocol.protocol;
+    let fd = info.fd_enabled || info.protocol_type == ProtocolType::Canfd;
+
+    out.push_str("BA_DEF_  \"BusType\" STRING ;\n");
+    out.push_str("BA_DEF_  \"DBName\" STRING ;\n");
+    out.push_str("BA_DEF_  \"Baudrate\" INT 0 1000000000;\n");
+    if info.data_baudrate.is_some() {
+        out.push_str("BA_DEF_  \"BaudrateCANFD\" INT 0 1000000000;\n");
+    }
+    out.push_str("BA_DEF_ BO_  \"GenMsgCycleTime\" INT 0 65535;\n");
+    if fd {
+        out.push_str(
+            "BA_DEF_ BO_  \"VFrameFormat\" ENUM  \"StandardCAN\",\"ExtendedCAN\",\
+             \"reserved\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\
+             \"reserved\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\
+             \"reserved\",\"reserved\",\"StandardCAN_FD\",\"ExtendedCAN_FD\";\n",
+        );
+    }
+
+    out.push_str("BA_DEF_DEF_  \"BusType\" \"\";\n");
+    out.push_str("BA_DEF_DEF_  \"DBName\" \"\";\n");
+    out.push_str("BA_DEF_DEF_  \"Baudrate\" 0;\n");
+    if info.data_baudrate.is_some() {
+        out.push_str("BA_DEF_DEF_  \"BaudrateCANFD\" 0;\n");
+    }
+    out.push_str("BA_DEF_DEF_  \"GenMsgCycleTime\" 0;\n");
+    if fd {
+        out.push_str("BA_DEF_DEF_  \"VFrameFormat\" \"StandardCAN\";\n");
+    }
+
+    let _ = writeln!(
+        out,
+        "BA_ \"BusType\" \"{}\";",
+        if fd { "CAN FD" } else { "CAN" }
+    );
+    let _ = writeln!(out, "BA_ \"DBName\" \"{}\";", identifier(&protocol.id));
+    let _ = writeln!(out, "BA_ \"Baudrate\" {};", info.baudrate);
+    if let Some(data_baudrate) = info.data_baudrate {
+        let _ = writeln!(out, "BA_ \"BaudrateCANFD\" {};", data_baudrate);
+    }
+    for message in messages {
+        if let Some(interval) = message.spec.interval_ms {
+            let _ = writeln!(
+                out,
+                "BA_ \"GenMsgCycleTime\" BO_ {} {};",
+                message.id,
+                interval.round() as u32
+            );
+        }
+        if fd {
+            let format = if message.id & EXTENDED_ID_FLAG != 0 {
+                FD_EXTENDED_FRAME
+            } else {
+                FD_STANDARD_FRAME
+            };
+            let _ = writeln!(out, "BA_ \"VFrameFormat\" BO_ {} {};", message.id, format);
+        }
+    }
+}
+
+fn write_value_tables(out: &mut String, protocol: &ProtocolSpec, messages: &[DbcMessage]) {
+    let Some(enums) = &protocol.enums else {
+        return;
+    };
+    for message in messages {
+        for signal in &message.signals {
+            let Some(spec) = signal
+                .spec
+                .enum_ref
+                .as_deref()
+                .and_then(|name| enums.iter().find(|e| e.name == name))
+            else {
+                continue;
+            };
+
+            // Labels name physical values; DBC tables are keyed by raw values
+            let mut values: Vec<(i64, &str)> = spec
+                .values
+                .iter()
+                .filter_map(|(value, label)| {
+                    let value = parse_enum_value(value)?;
+                    let raw = (value - signal.spec.offset) / signal.spec.scale;
+                    (raw.fract() == 0.0).then_some((raw as i64, label.as_str()))
+                })
+                .collect();
+            if values.is_empty() {
+                continue;
+            }
+            values.sort_by_key(|(raw, _)| std::cmp::Reverse(*raw));
+
+            let _ = write!(out, "VAL_ {} {}", message.id, signal.name);
+            for (raw, label) in values {
+                let _ = write!(out, " {} \"{}\"", raw, escape(label));
+            }
+            out.push_str(" ;\n");
+        }
+    }
+}
+
+/// Physical range a signal's raw bits can represent, used when the spec
+/// gives no min/max. Float signals have no useful range and write `0|0`
+/// as DBC tools expect.
+fn physical_range(signal: &SignalSpec) -> (f64, f64) {
+    let length = signal.length.clamp(1, 64) as i32;
+    let (raw_min, raw_max) = match signal.data_type {
+        SignalDataType::Unsigned => (0.0, 2f64.powi(length) - 1.0),
+        SignalDataType::Signed => {
+            let half = 2f64.powi(length - 1);
+            (-half, half - 1.0)
+        }
+        SignalDataType::Float | SignalDataType::Double => return (0.0, 0.0),
+    };
+    let a = raw_min * signal.scale + signal.offset;
+    let b = raw_max * signal.scale + signal.offset;
+    (a.min(b), a.max(b))
+}
+
+/// Format a range limit without the noise of scaled floating-point steps
+/// (`6553.5`, not `6553.500000000001`)
+fn number(value: f64) -> String {
+    let text = format!("{:.9}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    match text {
+        "-0" => "0".to_string(),
+        text => text.to_string(),
+    }
+}
+
+/// Enum keys are decimal or `0x` hexadecimal numbers
+fn parse_enum_value(value: &str) -> Option<f64> {
+    let value = value.trim();
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => i64::from_str_radix(hex, 16).ok().map(|v| v as f64),
+        None => value.parse().ok(),
+    }
+}
+
+/// A DBC identifier for a name: letters, digits and underscores, not
+/// starting with a digit
+pub fn identifier(name: &str) -> String {
+    let mut id = String::with_capacity(name.len());
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            id.push(c);
+        } else if !id.ends_with('_') {
+            id.push('_');
+        }
+    }
+    let id = id.trim_end_matches('_').to_string();
+    match id.chars().next() {
+        None => "Unnamed".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("_{}", id),
+        Some(_) => id,
+    }
+}
+
+/// Make `name` unique among `used` by appending a number
+fn unique(name: String, used: &mut HashSet<String>) -> String {
+    if used.insert(name.clone()) {
+        return name;
+    }
+    let mut n = 2;
+    loop {
+        let candidate = format!("{}_{}", name, n);
+        if used.insert(candidate.clone()) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// Escape a string for a quoted DBC value
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// Baud rate assumed when a DBC has no `Baudrate` attribute
+pub const DEFAULT_BAUDRATE: u32 = 500_000;
+
+/// `BO_` identifier DBC editors use to hold signals not assigned to a message
+const INDEPENDENT_SIGNALS_ID: u32 = 0xC000_0000;
+
+/// Largest 29-bit identifier, masking off [`EXTENDED_ID_FLAG`]
+const ID_MASK: u32 = 0x1FFF_FFFF;
+
+static MESSAGE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^BO_\s+(\d+)\s+(\w+)\s*:\s*(\d+)\s+(\w+)").expect("Invalid BO_ regex")
+});
+
+static SIGNAL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"^SG_\s+(\w+)\s*(M|m\d+M?)?\s*:\s*(\d+)\s*\|\s*(\d+)\s*@\s*([01])\s*([+-])\s*\(\s*([^,\s]+)\s*,\s*([^)\s]+)\s*\)\s*\[\s*([^|\s]+)\s*\|\s*([^\]\s]+)\s*\]\s*"((?:[^"\\]|\\.)*)""#,
+    )
+    .expect("Invalid SG_ regex")
+});
+
+/// Errors from reading a DBC file
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum DbcError {
+    /// A message or signal definition couldn't be read
+    #[error("Line {line}: {reason}")]
+    InvalidLine { line: usize, reason: String },
+
+    /// The file defines no messages with signals
+    #[error("The DBC file defines no CAN messages")]
+    NoMessages,
+}
+
+/// Raw values and their labels from a `VAL_` statement
+type ValueTable = Vec<(i64, String)>;
+
+/// A DBC statement: one line, or several when a quoted string spans lines
+struct Statement {
+    /// Line number the statement starts on (1-based)
+    line: usize,
+    text: String,
+}
+
+/// Split DBC text into statements, keeping multi-line quoted strings whole
+fn statements(text: &str) -> Vec<Statement> {
+    let mut statements = Vec::new();
+    let mut current: Option<Statement> = None;
+    let mut in_string = false;
+
+    for (index, line) in text.lines().enumerate() {
+        match &mut current {
+            Some(statement) => {
+                statement.text.push('\n');
+                statement.text.push_str(line);
+            }
+            None => {
+                current = Some(Statement {
+                    line: index + 1,
+                    text: line.trim().to_string(),
+                })
+            }
+        }
+
+        let mut escaped = false;
+        for c in line.chars() {
+            match c {
+                '\\' if in_string => escaped = !escaped,
+                '"' if !escaped => in_string = !in_string,
+                _ => escaped = false,
+            }
+        }
+        if !in_string {
+            statements.extend(current.take().filter(|s| !s.text.is_empty()));
+        }
+    }
+    statements.extend(current);
+    statements
+}
+
+/// A token of a DBC statement: a bare word or a quoted string
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Text(String),
+}
+
+impl Token {
+    fn word(&self) -> Option<&str> {
+        match self {
+            Token::Word(word) => Some(word),
+            Token::Text(_) => None,
+        }
+    }
+}
+
+/// Split a statement into words and quoted strings, dropping the trailing `;`
+fn tokens(statement: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = statement.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || c == ';' {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut text = String::new();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => text.extend(chars.next()),
+                    '"' => break,
+                    c => text.push(c),
+                }
+            }
+            tokens.push(Token::Text(text));
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == ';' || c == '"' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+    tokens
+}
+
+/// Read a DBC file into a protocol spec with the given id
+///
+/// Messages, signals, `VAL_` tables (as enums), comments, broadcast
+/// intervals and the bus baud rate are kept. Multiplexed signals can't be
+/// described by a protocol spec and are left out, as are signals placed in
+/// the independent-signals pseudo message.
+pub fn parse_dbc(text: &str, id: &str) -> Result<ProtocolSpec, DbcError> {
+    let text = text.trim_start_matches('\u{feff}');
+
+    let mut version = String::new();
+    let mut nodes: Vec<String> = Vec::new();
+    let mut description = None;
+    let mut db_name = None;
+    let mut baudrate = None;
+    let mut data_baudrate = None;
+    let mut fd = false;
+    // Messages by their raw `BO_` identifier
+    let mut messages: Vec<(u32, MessageSpec)> = Vec::new();
+    let mut value_types: Vec<(u32, String, SignalDataType)> = Vec::new();
+    let mut value_tables: Vec<(u32, String, ValueTable)> = Vec::new();
+    let mut skipped_multiplexed = 0;
+    let mut in_independent = false;
+
+    for statement in statements(text) {
+        let keyword = statement
+            .text
+            .split(|c: char| c.is_whitespace() || c == ':')
+            .next()
+            .unwrap_or_default();
+        let invalid = |reason: &str| DbcError::InvalidLine {
+            line: statement.line,
+            reason: reason.to_string(),
+        };
+
+        match keyword {
+            "VERSION" => {
+                if let Some(Token::Text(text)) = tokens(&statement.text).get(1) {
+                    version = text.clone();
+                }
+            }
+            "BU_" => {
+                let list = statement.text.split_once(':').map_or("", |(_, list)| list);
+                nodes = list.split_whitespace().map(str::to_string).collect();
+            }
+            "BO_" => {
+                let caps = MESSAGE_REGEX
+                    .captures(&statement.text)
+                    .ok_or_else(|| invalid("malformed message definition"))?;
+                let raw_id: u32 = caps[1]
+                    .parse()
+                    .map_err(|_| invalid("message identifier out of range"))?;
+                in_independent = raw_id == INDEPENDENT_SIGNALS_ID;
+                if in_independent {
+                    continue;
+                }
+                let length: u8 = caps[3]
+                    .parse()
+                    .map_err(|_| invalid("message length out of range"))?;
+                let transmitter = &caps[4];
+                messages.push((
+                    raw_id,
+                    MessageSpec {
+                        id: raw_id & ID_MASK,
+                        name: caps[2].to_string(),
+                        description: None,
+                        length,
+                        part_ids: Vec::new(),
+                        interval_ms: None,
+                        transmitter: (transmitter != NO_NODE).then(|| transmitter.to_string()),
+                        signals: Vec::new(),
+                    },
+                ));
+            }
+            "SG_" => {
+                if in_independent {
+                    continue;
+                }
+                let caps = SIGNAL_REGEX
+                    .captures(&statement.text)
+                    .ok_or_else(|| invalid("malformed signal definition"))?;
+                let Some((_, message)) = messages.last_mut() else {
+                    return Err(invalid("signal defined before any message"));
+                };
+                if caps.get(2).is_some_and(|m| m.as_str().starts_with('m')) {
+                    skipped_multiplexed += 1;
+                    continue;
+                }
+
+                let number = |index: usize, what: &str| -> Result<f64, DbcError> {
+                    caps[index]
+                        .parse::<f64>()
+                        .map_err(|_| invalid(&format!("invalid {}", what)))
+                };
+                let start_bit: u16 = caps[3]
+                    .parse()
+                    .map_err(|_| invalid("start bit out of range"))?;
+                let length: u8 = caps[4]
+                    .parse()
+                    .map_err(|_| invalid("signal length out of range"))?;
+                let byte_order = if &caps[5] == "1" {
+                    ByteOrder::LittleEndian
+                } else {
+                    ByteOrder::BigEndian
+                };
+                let start_bit = match byte_order {
+                    ByteOrder::LittleEndian => start_bit,
+                    ByteOrder::BigEndian => dbc_to_motorola_start(start_bit),
+                };
+                let (min, max) = (number(9, "minimum")?, number(10, "maximum")?);
+                let unit = unescape(&caps[11]);
+
+                message.signals.push(SignalSpec {
+                    name: caps[1].to_string(),
+                    description: None,
+                    start_bit,
+                    length,
+                    byte_order,
+                    data_type: if &caps[6] == "-" {
+                        SignalDataType::Signed
+                    } else {
+                        SignalDataType::Unsigned
+                    },
+                    scale: number(7, "factor")?,
+                    offset: number(8, "offset")?,
+                    unit: (!unit.is_empty()).then_some(unit),
+                    // [0|0] means the range is not given
+                    min: (min != 0.0 || max != 0.0).then_some(min),
+                    max: (min != 0.0 || max != 0.0).then_some(max),
+                    enum_ref: None,
+                    comment: None,
+                });
+            }
+            "CM_" => {
+                let tokens = tokens(&statement.text);
+                match tokens.as_slice() {
+                    [_, Token::Text(text)] => description = Some(text.clone()),
+                    [_, Token::Word(kind), Token::Word(raw_id), Token::Text(text)]
+                        if kind == "BO_" =>
+                    {
+                        if let Some(message) = find_message(&mut messages, raw_id) {
+                            message.description = Some(text.clone());
+                        }
+                    }
+                    [_, Token::Word(kind), Token::Word(raw_id), Token::Word(name), Token::Text(text)]
+                        if kind == "SG_" =>
+                    {
+                        if let Some(signal) = find_message(&mut messages, raw_id)
+                            .and_then(|m| m.signals.iter_mut().find(|s| &s.name == name))
+                        {
+                            signal.description = Some(text.clone());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            "BA_" => {
+                let tokens = tokens(&statement.text);
+                let Some(Token::Text(attribute)) = tokens.get(1) else {
+                    continue;
+                };
+                let words: Vec<Option<&str>> = tokens[2..].iter().map(Token::word).collect();
+                match (attribute.as_str(), words.as_slice()) {
+                    ("Baudrate", [Some(value)]) => baudrate = value.parse().ok(),
+                    ("BaudrateCANFD", [Some(value)]) => data_baudrate = value.parse().ok(),
+                    ("BusType", _) => {
+                        fd |= matches!(tokens.get(2), Some(Token::Text(bus)) if bus.contains("FD"))
+                    }
+                    ("DBName", _) => {
+                        if let Some(Token::Text(name)) = tokens.get(2) {
+                            db_name = Some(name.clone());
+                        }
+                    }
+                    ("GenMsgCycleTime", [Some("BO_"), Some(raw_id), Some(value)]) => {
+                        let interval = value.parse::<f64>().ok().filter(|v| *v > 0.0);
+                        if let Some(message) = find_message(&mut messages, raw_id) {
+                            message.interval_ms = interval;
+                        }
+                    }
+                    ("VFrameFormat", [Some("BO_"), Some(_), Some(value)]) => {
+                        let format: u32 = value.parse().unwrap_or(0);
+                        fd |= format == FD_STANDARD_FRAME || format == FD_EXTENDED_FRAME;
+                    }
+                    _ => {}
+                }
+            }
+            "VAL_" => {
+                let tokens = tokens(&statement.text);
+                let (Some(raw_id), Some(signal)) = (
+                    tokens.get(1).and_then(Token::word),
+                    tokens.get(2).and_then(Token::word),
+                ) else {
+                    continue;
+                };
+                let Ok(raw_id) = raw_id.parse() else {
+                    continue;
+                };
+                let values = tokens[3..]
+                    .chunks(2)
+                    .filter_map(|pair| match pair {
+                        [Token::Word(value), Token::Text(label)] => {
+                            Some((value.parse().ok()?, label.clone()))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                value_tables.push((raw_id, signal.to_string(), values));
+            }
+            "SIG_VALTYPE_" => {
+                let tokens = tokens(&statement.text);
+                let words: Vec<&str> = tokens.iter().filter_map(Token::word).collect();
+                if let [_, raw_id, signal, ":", value_type] = words.as_slice() {
+                    let data_type = match *value_type {
+                        "1" => SignalDataType::Float,
+                        "2" => SignalDataType::Double,
+                        _ => continue,
+                    };
+                    if let Ok(raw_id) = raw_id.parse() {
+                        value_types.push((raw_id, signal.to_string(), data_type));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if skipped_multiplexed > 0 {
+        tracing::warn!(
+            "Left {} multiplexed signals out of the DBC import",
+            skipped_multiplexed
+        );
+    }
+
+    for (raw_id, name, data_type) in value_types {
+        if let Some(signal) = find_message(&mut messages, &raw_id.to_string())
+            .and_then(|m| m.signals.iter_mut().find(|s| s.name == name))
+        {
+            signal.data_type = data_type;
+        }
+    }
+
+    // Value tables become enums named after their message and signal
+    let mut enums = Vec::new();
+    for (raw_id, name, values) in value_tables {
+        let Some((message_name, signal)) = find_message(&mut messages, &raw_id.to_string())
+            .and_then(|m| {
+                let message_name = m.name.clone();
+                m.signals
+                    .iter_mut()
+                    .find(|s| s.name == name)
+                    .map(|s| (message_name, s))
+            })
+        else {
+            continue;
+        };
+        // Enum keys are physical values, like every other spec value
+        let labels: HashMap<String, String> = values
+            .into_iter()
+            .filter_map(|(raw, label): (i64, String)| {
+                let value = raw as f64 * signal.scale + signal.offset;
+                (value.fract() == 0.0).then(|| ((value as i64).to_string(), label))
+            })
+            .collect();
+        if labels.is_empty() {
+            continue;
+        }
+        let enum_name = format!("{}_{}", message_name, signal.name);
+        signal.enum_ref = Some(enum_name.clone());
+        enums.push(EnumSpec {
+            name: enum_name,
+            description: None,
+            values: labels,
+        });
+    }
+
+    messages.retain(|(_, message)| !message.signals.is_empty());
+    if messages.is_empty() {
+        return Err(DbcError::NoMessages);
+    }
+
+    // A spec marks IDs extended protocol-wide; IDs above 0x7FF always are
+    let all_extended = messages
+        .iter()
+        .all(|(raw_id, _)| raw_id & EXTENDED_ID_FLAG != 0);
+    if !all_extended
+        && messages
+            .iter()
+            .any(|(raw_id, m)| raw_id & EXTENDED_ID_FLAG != 0 && m.id <= MAX_STANDARD_ID)
+    {
+        tracing::warn!("Low extended IDs in a mixed DBC are read as standard IDs");
+    }
+
+    let fd = fd
+        || messages
+            .iter()
+            .any(|(_, m)| m.length as usize > CAN_PAYLOAD);
+    Ok(ProtocolSpec {
+        openecualliance: "1.0".to_string(),
+        spec_type: "protocol".to_string(),
+        id: id.to_string(),
+        name: db_name.unwrap_or_else(|| id.to_string()),
+        version: if version.is_empty() {
+            "1.0.0".to_string()
+        } else {
+            version
+        },
+        vendor: nodes
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "Unknown".to_string()),
+        description,
+        website: None,
+        branding: None,
+        protocol: ProtocolInfo {
+            protocol_type: if fd {
+                ProtocolType::Canfd
+            } else {
+                ProtocolType::Can
+            },
+            baudrate: baudrate.unwrap_or(DEFAULT_BAUDRATE),
+            extended_id: all_extended,
+            data_baudrate,
+            fd_enabled: fd,
+            base_id: None,
+            base_id_configurable: false,
+        },
+        messages: messages.into_iter().map(|(_, m)| m).collect(),
+        enums: (!enums.is_empty()).then_some(enums),
+        metadata: None,
+    })
+}
+
+/// Message with a raw `BO_` identifier, as written in `CM_`, `BA_` and
+/// `VAL_` statements
+fn find_message<'a>(
+    messages: &'a mut [(u32, MessageSpec)],
+    raw_id: &str,
+) -> Option<&'a mut MessageSpec> {
+    let raw_id: u32 = raw_id.parse().ok()?;
+    messages
+        .iter_mut()
+        .find(|(id, _)| *id == raw_id)
+        .map(|(_, message)| message)
+}
+
+/// Undo [`escape`] for a quoted value matched by a regex
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.extend(chars.next()),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::can;
+    use crate::adapters::registry::get_protocols;
+
+    const TEST_PROTOCOL: &str = r#"
+openecualliance: "1.0"
+type: protocol
+id: test-broadcast
+name: Test Broadcast
+version: "1.2.0"
+vendor: test
+description: Test "broadcast" protocol
+protocol:
+  type: can
+  baudrate: 500000
+messages:
+  - id: "0x360"
+    name: Engine
+    description: Engine state
+    length: 8
+    interval_ms: 20
+    transmitter: Main ECU
+    signals:
+      - { name: RPM, start_bit: 0, length: 16, byte_order: big_endian, data_type: unsigned, unit: rpm, min: 0, max: 9000 }
+      - { name: Manifold Pressure, start_bit: 16, length: 16, byte_order: big_endian, data_type: unsigned, scale: 0.1, unit: kPa, description: Absolute pressure }
+      - { name: Ignition, start_bit: 36, length: 12, byte_order: big_endian, data_type: signed, scale: 0.1, unit: deg }
+      - { name: Gear, start_bit: 48, length: 4, byte_order: little_endian, data_type: unsigned, enum_ref: gears, comment: Selected gear }
+  - id: "0x361"
+    name: Intel
+    length: 8
+    signals:
+      - { name: Speed, start_bit: 4, length: 16, byte_order: little_endian, data_type: unsigned }
+      - { name: Lambda, start_bit: 32, length: 32, byte_order: little_endian, data_type: float }
+  - id: "0x370"
+    name: Long
+    length: 16
+    part_ids: ["0x371"]
+    signals:
+      - { name: First, start_bit: 0, length: 32, byte_order: big_endian, data_type: signed }
+      - { name: Across, start_bit: 56, length: 16, byte_order: big_endian, data_type: unsigned }
+      - { name: Last, start_bit: 64, length: 64, byte_order: little_endian, data_type: double }
+  - id: "0x1F0A000"
+    name: Extended
+    length: 2
+    signals:
+      - { name: Temp, start_bit: 0, length: 16, byte_order: big_endian, data_type: signed, offset: -40 }
+enums:
+  - name: gears
+    values:
+      "0": Neutral
+      "1": First
+      "2": Second
+"#;
+
+    fn test_protocol() -> ProtocolSpec {
+        serde_yaml::from_str(TEST_PROTOCOL).unwrap()
+    }
+
+    /// Fields of an `SG_` line
+    #[derive(Debug, PartialEq)]
+    struct SgLine {
+        name: String,
+        start_bit: u16,
+        length: u8,
+        little_endian: bool,
+        signed: bool,
+        scale: f64,
+        offset: f64,
+        min: f64,
+        max: f64,
+        unit: String,
+    }
+
+    fn parse_sg(line: &str) -> SgLine {
+        let re = regex::Regex::new(
+            r#"^ SG_ (\w+) : (\d+)\|(\d+)@([01])([+-]) \(([^,]+),([^)]+)\) \[([^|]+)\|([^\]]+)\] "([^"]*)" \w+$"#,
+        )
+        .unwrap();
+        let caps = re
+            .captures(line)
+            .unwrap_or_else(|| panic!("bad SG_: {}", line));
+        SgLine {
+            name: caps[1].to_string(),
+            start_bit: caps[2].parse().unwrap(),
+            length: caps[3].parse().unwrap(),
+            little_endian: &caps[4] == "1",
+            signed: &caps[5] == "-",
+            scale: caps[6].parse().unwrap(),
+            offset: caps[7].parse().unwrap(),
+            min: caps[8].parse().unwrap(),
+            max: caps[9].parse().unwrap(),
+            unit: caps[10].to_string(),
+        }
+    }
+
+    /// `SG_` lines of each `BO_` message, keyed by its header line
+    fn messages(dbc: &str) -> Vec<(String, Vec<SgLine>)> {
+        let mut messages: Vec<(String, Vec<SgLine>)> = Vec::new();
+        for line in dbc.lines() {
+            if line.starts_with("BO_ ") {
+                messages.push((line.to_string(), Vec::new()));
+            } else if line.starts_with(" SG_ ") {
+                messages.last_mut().unwrap().1.push(parse_sg(line));
+            }
+        }
+        messages
+    }
+
+    #[test]
+    fn test_message_lines() {
+        let dbc = write_dbc(&test_protocol());
+        let headers: Vec<String> = messages(&dbc).into_iter().map(|(h, _)| h).collect();
+
+        assert_eq!(
+            headers,
+            vec![
+                "BO_ 864 Engine: 8 Main_ECU",
+                "BO_ 865 Intel: 8 Vector__XXX",
+                "BO_ 880 Long_Part0: 8 Vector__XXX",
+                "BO_ 881 Long_Part1: 8 Vector__XXX",
+                // 0x1F0A000 with the extended flag
+                "BO_ 2180030464 Extended: 2 Vector__XXX",
+            ]
+        );
+        assert!(dbc.contains("\nBU_: Main_ECU\n"));
+        assert!(dbc.starts_with("VERSION \"1.2.0\""));
+    }
+
+    #[test]
+    fn test_signal_notation() {
+        let dbc = write_dbc(&test_protocol());
+        let messages = messages(&dbc);
+        let engine = &messages[0].1;
+
+        // Motorola MSB of byte 0 is DBC bit 7
+        assert_eq!(
+            engine[0],
+            SgLine {
+                name: "RPM".to_string(),
+                start_bit: 7,
+                length: 16,
+                little_endian: false,
+                signed: false,
+                scale: 1.0,
+                offset: 0.0,
+                min: 0.0,
+                max: 9000.0,
+                unit: "rpm".to_string(),
+            }
+        );
+        assert_eq!(engine[1].name, "Manifold_Pressure");
+        assert_eq!(engine[1].start_bit, 23);
+        // Range from the raw bits when the spec has none
+        assert_eq!(engine[1].max, 6553.5);
+        assert!(engine[2].signed);
+        assert_eq!(engine[2].min, -204.8);
+        assert!(engine[3].little_endian);
+        assert_eq!(engine[3].start_bit, 48);
+
+        let intel = &messages[1].1;
+        assert_eq!(intel[1].start_bit, 32);
+        assert!(intel[1].signed, "Floats are written as signed");
+        assert!(dbc.contains("SIG_VALTYPE_ 865 Lambda : 1;"));
+    }
+
+    #[test]
+    fn test_multi_frame_messages() {
+        let dbc = write_dbc(&test_protocol());
+        let messages = messages(&dbc);
+
+        let names =
+            |i: usize| -> Vec<&str> { messages[i].1.iter().map(|s| s.name.as_str()).collect() };
+        // "Across" straddles the two frames and is left out
+        assert_eq!(names(2), vec!["First"]);
+        assert_eq!(names(3), vec!["Last"]);
+        assert_eq!(messages[3].1[0].start_bit, 0);
+        assert!(dbc.contains("SIG_VALTYPE_ 881 Last : 2;"));
+    }
+
+    #[test]
+    fn test_value_tables_comments_and_attributes() {
+        let dbc = write_dbc(&test_protocol());
+
+        assert!(dbc.contains("VAL_ 864 Gear 2 \"Second\" 1 \"First\" 0 \"Neutral\" ;"));
+        assert!(dbc.contains("CM_ \"Test \\\"broadcast\\\" protocol\";"));
+        assert!(dbc.contains("CM_ BO_ 864 \"Engine state\";"));
+        assert!(dbc.contains("CM_ SG_ 864 Manifold_Pressure \"Absolute pressure\";"));
+        assert!(dbc.contains("CM_ SG_ 864 Gear \"Selected gear\";"));
+        assert!(dbc.contains("BA_ \"Baudrate\" 500000;"));
+        assert!(dbc.contains("BA_ \"BusType\" \"CAN\";"));
+        assert!(dbc.contains("BA_ \"GenMsgCycleTime\" BO_ 864 20;"));
+        assert!(!dbc.contains("VFrameFormat"));
+    }
+
+    #[test]
+    fn test_can_fd_attributes() {
+        let mut protocol = test_protocol();
+        protocol.protocol.fd_enabled = true;
+        protocol.protocol.data_baudrate = Some(2_000_000);
+        let dbc = write_dbc(&protocol);
+
+        assert!(dbc.contains("BA_ \"BusType\" \"CAN FD\";"));
+        assert!(dbc.contains("BA_ \"BaudrateCANFD\" 2000000;"));
+        // A 16-byte message fits one FD frame
+        assert!(dbc.contains("BO_ 880 Long: 16 Vector__XXX"));
+        assert!(dbc.contains("BA_ \"VFrameFormat\" BO_ 880 14;"));
+        assert!(dbc.contains("BA_ \"VFrameFormat\" BO_ 2180030464 15;"));
+    }
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(identifier("Manifold Pressure"), "Manifold_Pressure");
+        assert_eq!(identifier("AFR (Bank 1)"), "AFR_Bank_1");
+        assert_eq!(identifier("2 Step"), "_2_Step");
+        assert_eq!(identifier("  "), "Unnamed");
+
+        let mut used = HashSet::new();
+        assert_eq!(unique("RPM".to_string(), &mut used), "RPM");
+        assert_eq!(unique("RPM".to_string(), &mut used), "RPM_2");
+    }
+
+    /// Every signal written to the DBC reads back with the spec's layout
+    fn assert_layout_round_trip(protocol: &ProtocolSpec) {
+        let dbc = write_dbc(protocol);
+        let written = messages(&dbc);
+        let payload = frame_payload(&protocol.protocol);
+
+        let mut index = 0;
+        for message in &protocol.messages {
+            for part in 0..frame_count(&protocol.protocol, message) {
+                let (_, lines) = &written[index];
+                index += 1;
+                for line in lines {
+                    let signal = message
+                        .signals
+                        .iter()
+                        .find(|s| unique_match(&identifier(&s.name), &line.name))
+                        .unwrap_or_else(|| panic!("{} not in {}", line.name, message.name));
+                    let start_bit = if line.little_endian {
+                        line.start_bit
+                    } else {
+                        dbc_to_motorola_start(line.start_bit)
+                    };
+                    assert_eq!(
+                        start_bit as usize + part * payload * 8,
+                        signal.start_bit as usize,
+                        "{}",
+                        signal.name
+                    );
+                    assert_eq!(line.length, signal.length);
+                    assert_eq!(
+                        line.little_endian,
+                        signal.byte_order == ByteOrder::LittleEndian
+                    );
+                    assert_eq!(line.signed, signal.data_type != SignalDataType::Unsigned);
+                    assert_eq!(line.scale, signal.scale);
+                    assert_eq!(line.offset, signal.offset);
+                    assert_eq!(line.unit, signal.unit.clone().unwrap_or_default());
+                    if let Some(min) = signal.min {
+                        assert_eq!(line.min, min);
+                    }
+                    if let Some(max) = signal.max {
+                        assert_eq!(line.max, max);
+                    }
+                }
+            }
+        }
+        assert_eq!(index, written.len());
+    }
+
+    /// Whether a written name is the identifier or a numbered duplicate of it
+    fn unique_match(identifier: &str, written: &str) -> bool {
+        written == identifier
+            || written
+                .strip_prefix(identifier)
+                .and_then(|rest| rest.strip_prefix('_'))
+                .is_some_and(|n| n.parse::<u32>().is_ok())
+    }
+
+    #[test]
+    fn test_layout_round_trip() {
+        assert_layout_round_trip(&test_protocol());
+    }
+
+    const VENDOR_DBC: &str = r#"VERSION "2.1"
+
+NS_ :
+	CM_
+	BA_
+
+BS_:
+
+BU_: PDM Dash
+
+BO_ 1280 PDM_Status: 8 PDM
+ SG_ Battery_Voltage : 0|16@1+ (0.01,0) [0|0] "V" Dash
+ SG_ Output_Current : 23|12@0- (0.1,0) [-204.8|204.7] "A" Dash
+ SG_ Fault : 32|8@1+ (1,0) [0|255] "" Dash
+ SG_ Mode M : 40|4@1+ (1,0) [0|15] "" Dash
+ SG_ Channel_1 m1 : 48|8@1+ (1,0) [0|255] "" Dash
+
+BO_ 2566848741 Throttle: 4 Vector__XXX
+ SG_ Position : 0|32@1- (1,0) [0|100] "%" Dash
+
+BO_ 1296 Empty: 8 PDM
+
+BO_ 3221225472 VECTOR__INDEPENDENT_SIG_MSG: 0 Vector__XXX
+ SG_ Orphan : 0|8@1+ (1,0) [0|0] "" Vector__XXX
+
+CM_ "Vendor \"PDM\" broadcast";
+CM_ BO_ 1280 "Status frame
+sent by the PDM";
+CM_ SG_ 1280 Fault "Latched fault code";
+BA_DEF_ "Baudrate" INT 0 1000000;
+BA_ "Baudrate" 1000000;
+BA_ "DBName" "PDM Broadcast";
+BA_ "GenMsgCycleTime" BO_ 1280 50;
+VAL_ 1280 Fault 2 "Overcurrent" 1 "Open Load" 0 "OK" ;
+SIG_VALTYPE_ 2566848741 Position : 1;
+"#;
+
+    #[test]
+    fn test_parse_dbc() {
+        let spec = parse_dbc(VENDOR_DBC, "pdm").unwrap();
+
+        assert_eq!(spec.id, "pdm");
+        assert_eq!(spec.name, "PDM Broadcast");
+        assert_eq!(spec.version, "2.1");
+        assert_eq!(spec.vendor, "PDM");
+        assert_eq!(
+            spec.description.as_deref(),
+            Some("Vendor \"PDM\" broadcast")
+        );
+        assert_eq!(spec.protocol.baudrate, 1_000_000);
+        assert_eq!(spec.protocol.protocol_type, ProtocolType::Can);
+        assert!(!spec.protocol.extended_id);
+
+        // Messages without signals and the independent-signals message are dropped
+        let names: Vec<&str> = spec.messages.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["PDM_Status", "Throttle"]);
+
+        let status = &spec.messages[0];
+        assert_eq!(status.id, 0x500);
+        assert_eq!(status.interval_ms, Some(50.0));
+        assert_eq!(status.transmitter.as_deref(), Some("PDM"));
+        assert_eq!(
+            status.description.as_deref(),
+            Some("Status frame\nsent by the PDM")
+        );
+        assert_eq!(spec.messages[1].id, 0x18FF_00E5);
+        assert_eq!(spec.messages[1].transmitter, None);
+    }
+
+    #[test]
+    fn test_parse_dbc_signals() {
+        let spec = parse_dbc(VENDOR_DBC, "pdm").unwrap();
+        let status = &spec.messages[0];
+
+        // The multiplexor is kept, multiplexed signals are not
+        let names: Vec<&str> = status.signals.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Battery_Voltage", "Output_Current", "Fault", "Mode"]
+        );
+
+        let voltage = &status.signals[0];
+        assert_eq!(voltage.byte_order, ByteOrder::LittleEndian);
+        assert_eq!(voltage.scale, 0.01);
+        assert_eq!(voltage.unit.as_deref(), Some("V"));
+        assert_eq!((voltage.min, voltage.max), (None, None));
+
+        // Motorola DBC bit 23 is the MSB of byte 2
+        let current = &status.signals[1];
+        assert_eq!(current.byte_order, ByteOrder::BigEndian);
+        assert_eq!(current.start_bit, 16);
+        assert_eq!(current.data_type, SignalDataType::Signed);
+        assert_eq!(current.min, Some(-204.8));
+
+        let fault = &status.signals[2];
+        assert_eq!(fault.description.as_deref(), Some("Latched fault code"));
+        assert_eq!(fault.unit, None);
+
+        let position = &spec.messages[1].signals[0];
+        assert_eq!(position.data_type, SignalDataType::Float);
+    }
+
+    #[test]
+    fn test_parse_dbc_value_tables() {
+        let spec = parse_dbc(VENDOR_DBC, "pdm").unwrap();
+        let fault = &spec.messages[0].signals[2];
+        assert_eq!(fault.enum_ref.as_deref(), Some("PDM_Status_Fault"));
+
+        let enums = spec.enums.as_ref().unwrap();
+        assert_eq!(enums.len(), 1);
+        assert_eq!(enums[0].name, "PDM_Status_Fault");
+        assert_eq!(enums[0].values["1"], "Open Load");
+
+        // Keys are physical values
+        let scaled =
+            VENDOR_DBC.replace("SG_ Fault : 32|8@1+ (1,0)", "SG_ Fault : 32|8@1+ (10,100)");
+        let spec = parse_dbc(&scaled, "pdm").unwrap();
+        let values = &spec.enums.unwrap()[0].values;
+        assert_eq!(values["120"], "Overcurrent");
+        assert_eq!(values["100"], "OK");
+    }
+
+    #[test]
+    fn test_parse_dbc_extended_and_fd() {
+        let dbc = "BO_ 2566848741 A: 8 Vector__XXX\n SG_ X : 0|8@1+ (1,0) [0|0] \"\" Vector__XXX\n\
+                   BO_ 2147484512 B: 64 Vector__XXX\n SG_ Y : 0|8@1+ (1,0) [0|0] \"\" Vector__XXX\n";
+        let spec = parse_dbc(dbc, "fd").unwrap();
+
+        assert!(spec.protocol.extended_id);
+        assert_eq!(spec.messages[1].id, 0x360);
+        // Payloads above 8 bytes only fit CAN FD frames
+        assert!(spec.protocol.fd_enabled);
+        assert_eq!(spec.protocol.protocol_type, ProtocolType::Canfd);
+        assert_eq!(spec.protocol.baudrate, DEFAULT_BAUDRATE);
+        assert_eq!(spec.name, "fd");
+    }
+
+    #[test]
+    fn test_parse_dbc_errors() {
+        assert_eq!(
+            parse_dbc("VERSION \"\"\n\nBU_:\n", "empty").unwrap_err(),
+            DbcError::NoMessages
+        );
+        assert_eq!(
+            parse_dbc("BO_ 1280 Status: 8 PDM\n SG_ Broken : 0|16\n", "bad").unwrap_err(),
+            DbcError::InvalidLine {
+                line: 2,
+                reason: "malformed signal definition".to_string()
+            }
+        );
+        assert!(matches!(
+            parse_dbc(" SG_ X : 0|8@1+ (1,0) [0|0] \"\" Vector__XXX\n", "bad"),
+            Err(DbcError::InvalidLine { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_import_export_round_trip() {
+        let dbc = write_dbc(&test_protocol());
+        let spec = parse_dbc(&dbc, "test-broadcast").unwrap();
+
+        assert_eq!(write_dbc(&spec), dbc);
+    }
+
+    #[test]
+    fn test_imported_protocol_encodes_same_frames() {
+        let protocol = test_protocol();
+        let imported = parse_dbc(&write_dbc(&protocol), "test-broadcast").unwrap();
+
+        for message in protocol.messages.iter().filter(|m| m.length <= 8) {
+            let reimported = imported
+                .messages
+                .iter()
+                .find(|m| m.id == message.id)
+                .unwrap();
+            let values: Vec<f64> = (1..=message.signals.len()).map(|i| i as f64).collect();
+            let frame = can::encode_message(message, &values).unwrap();
+
+            assert_eq!(can::encode_message(reimported, &values).unwrap(), frame);
+            assert_eq!(can::decode_message(reimported, &frame), values);
+        }
+    }
+
+    #[test]
+    fn test_embedded_protocols_round_trip() {
+        for protocol in get_protocols() {
+            assert_layout_round_trip(&protocol);
+        }
+    }
+}
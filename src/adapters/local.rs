@@ -0,0 +1,571 @@
+//! User-supplied adapter and protocol specs loaded from the config directory.
+//!
+//! Teams can drop adapter YAML files they can't publish (in-house or
+//! customer ECUs) into `<config dir>/adapters/`, and CAN databases (`.dbc`)
+//! from ECU and PDM vendors into `<config dir>/protocols/`. Each file is
+//! validated on load; a broken file is reported on its own and never stops
+//! the others from loading. Local specs override embedded, cached or API
+//! specs that share the same `id`; a DBC's id is its file name.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use super::dbc;
+use super::types::{AdapterSpec, ProtocolSpec};
+use crate::settings::UserSettings;
+
+/// Adapters subdirectory within the config directory
+const LOCAL_ADAPTERS_DIR: &str = "adapters";
+
+/// Protocols subdirectory within the config directory
+const LOCAL_PROTOCOLS_DIR: &str = "protocols";
+
+/// File extensions scanned for CAN databases
+const DBC_EXTENSIONS: &[&str] = &["dbc"];
+
+/// File extensions scanned for adapter specs
+const SPEC_EXTENSIONS: &[&str] = &["yaml", "yml"];
+
+/// Spec file format types UltraLog understands
+const FORMAT_TYPES: &[&str] = &["csv", "binary"];
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+/// Errors that can occur loading a single local adapter spec
+#[derive(Debug, Clone, Error)]
+pub enum LocalSpecError {
+    /// Failed to read the spec file
+    #[error("Failed to read file: {0}")]
+    ReadError(String),
+
+    /// File is not a valid adapter YAML document
+    #[error("Invalid YAML: {0}")]
+    ParseError(String),
+
+    /// File parsed but the spec is not usable
+    #[error("Invalid adapter spec: {0}")]
+    ValidationError(String),
+
+    /// File is not a readable CAN database
+    #[error("Invalid DBC: {0}")]
+    DbcError(#[from] dbc::DbcError),
+
+    /// Failed to copy an imported file into the config directory
+    #[error("Failed to save file: {0}")]
+    WriteError(String),
+}
+
+/// A local spec file that failed to load
+#[derive(Debug, Clone)]
+pub struct LocalSpecIssue {
+    /// Path of the spec file
+    pub path: PathBuf,
+    /// Why the file was rejected
+    pub error: LocalSpecError,
+}
+
+/// Result of scanning the local adapters directory
+#[derive(Debug, Clone, Default)]
+pub struct LocalAdapters {
+    /// Valid adapter specs, in file name order
+    pub adapters: Vec<AdapterSpec>,
+    /// Files that failed to load
+    pub issues: Vec<LocalSpecIssue>,
+}
+
+/// Result of scanning the local protocols directory
+#[derive(Debug, Clone, Default)]
+pub struct LocalProtocols {
+    /// Protocol specs read from CAN databases, in file name order
+    pub protocols: Vec<ProtocolSpec>,
+    /// Files that failed to load
+    pub issues: Vec<LocalSpecIssue>,
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+/// Get the local adapters directory path
+/// Returns None if the config directory cannot be determined
+pub fn get_local_adapters_dir() -> Option<PathBuf> {
+    UserSettings::get_config_dir().map(|p| p.join(LOCAL_ADAPTERS_DIR))
+}
+
+/// Load adapter specs from the local adapters directory, if it exists
+pub fn load_local_adapters() -> LocalAdapters {
+    match get_local_adapters_dir() {
+        Some(dir) if dir.is_dir() => load_adapters_from_dir(&dir),
+        _ => LocalAdapters::default(),
+    }
+}
+
+/// Load and validate every adapter YAML file in `dir`
+pub fn load_adapters_from_dir(dir: &Path) -> LocalAdapters {
+    let mut result = LocalAdapters::default();
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::warn!("Failed to read local adapters directory {:?}: {}", dir, e);
+            return result;
+        }
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && is_spec_file(path))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        match load_adapter_file(&path) {
+            Ok(spec) => {
+                tracing::info!("Loaded local adapter '{}' from {:?}", spec.id, path);
+                result.adapters.push(spec);
+            }
+            Err(error) => {
+                tracing::warn!("Skipping local adapter {:?}: {}", path, error);
+                result.issues.push(LocalSpecIssue { path, error });
+            }
+        }
+    }
+
+    result
+}
+
+/// Load and validate a single adapter YAML file
+pub fn load_adapter_file(path: &Path) -> Result<AdapterSpec, LocalSpecError> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| LocalSpecError::ReadError(e.to_string()))?;
+    let spec: AdapterSpec =
+        serde_yaml::from_str(&contents).map_err(|e| LocalSpecError::ParseError(e.to_string()))?;
+    validate_adapter(&spec)?;
+    Ok(spec)
+}
+
+fn is_spec_file(path: &Path) -> bool {
+    has_extension(path, SPEC_EXTENSIONS)
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+}
+
+/// Get the local protocols directory path
+/// Returns None if the config directory cannot be determined
+pub fn get_local_protocols_dir() -> Option<PathBuf> {
+    UserSettings::get_config_dir().map(|p| p.join(LOCAL_PROTOCOLS_DIR))
+}
+
+/// Load protocol specs from the local protocols directory, if it exists
+pub fn load_local_protocols() -> LocalProtocols {
+    match get_local_protocols_dir() {
+        Some(dir) if dir.is_dir() => load_protocols_from_dir(&dir),
+        _ => LocalProtocols::default(),
+    }
+}
+
+/// Load every CAN database in `dir` as a protocol spec
+pub fn load_protocols_from_dir(dir: &Path) -> LocalProtocols {
+    let mut result = LocalProtocols::default();
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::warn!("Failed to read local protocols directory {:?}: {}", dir, e);
+            return result;
+        }
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && has_extension(path, DBC_EXTENSIONS))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        match load_dbc_file(&path) {
+            Ok(spec) => {
+                tracing::info!(
+                    "Loaded local protocol '{}' ({} messages) from {:?}",
+                    spec.id,
+                    spec.messages.len(),
+                    path
+                );
+                result.protocols.push(spec);
+            }
+            Err(error) => {
+                tracing::warn!("Skipping local protocol {:?}: {}", path, error);
+                result.issues.push(LocalSpecIssue { path, error });
+            }
+        }
+    }
+
+    result
+}
+
+/// Read a CAN database as a protocol spec, using the file name as its id
+pub fn load_dbc_file(path: &Path) -> Result<ProtocolSpec, LocalSpecError> {
+    let bytes = fs::read(path).map_err(|e| LocalSpecError::ReadError(e.to_string()))?;
+    // DBC editors commonly save in Windows-1252; keep what decodes
+    let contents = String::from_utf8_lossy(&bytes);
+    Ok(dbc::parse_dbc(&contents, &dbc_protocol_id(path))?)
+}
+
+/// Protocol id for a DBC file: its name without the extension, in the
+/// lowercase, hyphenated style of spec ids
+pub fn dbc_protocol_id(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let mut id = String::with_capacity(stem.len());
+    for c in stem.chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c);
+        } else if !id.is_empty() && !id.ends_with('-') {
+            id.push('-');
+        }
+    }
+    let id = id.trim_end_matches('-');
+    if id.is_empty() {
+        "dbc".to_string()
+    } else {
+        id.to_string()
+    }
+}
+
+/// First of `id`, `id-2`, `id-3`, ... that `taken` doesn't claim
+pub fn unique_protocol_id(id: &str, taken: impl Fn(&str) -> bool) -> String {
+    let mut candidate = id.to_string();
+    let mut n = 1;
+    while taken(&candidate) {
+        n += 1;
+        candidate = format!("{}-{}", id, n);
+    }
+    candidate
+}
+
+/// Read a CAN database and copy it into the local protocols directory so
+/// it loads again on the next start. An id already used by a protocol in
+/// `taken_ids` or by a file in the directory gets a numeric suffix, so an
+/// import never replaces a built-in or previously imported protocol.
+pub fn install_dbc_file(path: &Path, taken_ids: &[&str]) -> Result<ProtocolSpec, LocalSpecError> {
+    let dir = get_local_protocols_dir()
+        .ok_or_else(|| LocalSpecError::WriteError("No config directory".to_string()))?;
+    install_dbc_file_in(path, &dir, taken_ids)
+}
+
+fn install_dbc_file_in(
+    path: &Path,
+    dir: &Path,
+    taken_ids: &[&str],
+) -> Result<ProtocolSpec, LocalSpecError> {
+    let mut spec = load_dbc_file(path)?;
+    // Already installed; it keeps the id it was loaded with
+    if dir.join(format!("{}.dbc", spec.id)) == path {
+        return Ok(spec);
+    }
+
+    spec.id = unique_protocol_id(&spec.id, |id| {
+        taken_ids.contains(&id) || dir.join(format!("{}.dbc", id)).exists()
+    });
+    fs::create_dir_all(dir)
+        .and_then(|_| fs::copy(path, dir.join(format!("{}.dbc", spec.id))))
+        .map_err(|e| LocalSpecError::WriteError(e.to_string()))?;
+    Ok(spec)
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Check that an adapter spec is usable, listing every problem found
+pub fn validate_adapter(spec: &AdapterSpec) -> Result<(), LocalSpecError> {
+    let mut problems = Vec::new();
+
+    if spec.id.trim().is_empty() {
+        problems.push("id is empty".to_string());
+    }
+    if spec.name.trim().is_empty() {
+        problems.push("name is empty".to_string());
+    }
+
+    let format = &spec.file_format;
+    if !FORMAT_TYPES
+        .iter()
+        .any(|t| format.format_type.eq_ignore_ascii_case(t))
+    {
+        problems.push(format!(
+            "file_format.type '{}' is not one of {}",
+            format.format_type,
+            FORMAT_TYPES.join(", ")
+        ));
+    }
+    if format.extensions.is_empty() {
+        problems.push("file_format.extensions is empty".to_string());
+    }
+    for ext in &format.extensions {
+        if !ext.starts_with('.') || ext.len() < 2 {
+            problems.push(format!("extension '{}' must look like \".csv\"", ext));
+        }
+    }
+    if let (Some(header), Some(data)) = (format.header_row, format.data_start_row) {
+        if header < 0 || data <= header {
+            problems.push(format!(
+                "data_start_row ({}) must come after header_row ({})",
+                data, header
+            ));
+        }
+    }
+
+    if spec.channels.is_empty() {
+        problems.push("no channels defined".to_string());
+    }
+    let mut seen_ids = std::collections::HashSet::new();
+    for channel in &spec.channels {
+        if channel.id.trim().is_empty() {
+            problems.push(format!("channel '{}' has an empty id", channel.name));
+        } else if !seen_ids.insert(channel.id.as_str()) {
+            problems.push(format!("duplicate channel id '{}'", channel.id));
+        }
+        if let (Some(min), Some(max)) = (channel.min, channel.max) {
+            if min > max {
+                problems.push(format!("channel '{}' has min > max", channel.id));
+            }
+        }
+        if let Some(formula) = &channel.conversion {
+            let valid = formula
+                .parse::<meval::Expr>()
+                .and_then(|expr| expr.bind("x"))
+                .is_ok();
+            if !valid {
+                problems.push(format!(
+                    "channel '{}' has an invalid conversion '{}'",
+                    channel.id, formula
+                ));
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(LocalSpecError::ValidationError(problems.join("; ")))
+    }
+}
+
+// ============================================================================
+// Merging
+// ============================================================================
+
+/// Overlay local specs onto `base`: a local spec replaces the base spec with
+/// the same id, and new ids are appended
+pub fn merge_adapters(mut base: Vec<AdapterSpec>, local: &[AdapterSpec]) -> Vec<AdapterSpec> {
+    for spec in local {
+        match base.iter_mut().find(|a| a.id == spec.id) {
+            Some(existing) => {
+                tracing::info!("Local adapter '{}' overrides the built-in spec", spec.id);
+                *existing = spec.clone();
+            }
+            None => base.push(spec.clone()),
+        }
+    }
+    base
+}
+
+/// Overlay local protocol specs onto `base`: a local spec replaces the base
+/// spec with the same id, and new ids are appended
+pub fn merge_protocols(mut base: Vec<ProtocolSpec>, local: &[ProtocolSpec]) -> Vec<ProtocolSpec> {
+    for spec in local {
+        match base.iter_mut().find(|p| p.id == spec.id) {
+            Some(existing) => {
+                tracing::info!("Local protocol '{}' overrides the built-in spec", spec.id);
+                *existing = spec.clone();
+            }
+            None => base.push(spec.clone()),
+        }
+    }
+    base
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID_YAML: &str = r#"
+openecualliance: "1.0"
+id: inhouse-logger
+name: In-house Logger
+version: "0.1.0"
+vendor: inhouse
+file_format:
+  type: csv
+  extensions: [".ilg"]
+  header_row: 0
+  data_start_row: 1
+channels:
+  - id: rpm
+    name: Engine RPM
+    category: engine
+    data_type: float
+    unit: rpm
+    source_names: ["RPM"]
+"#;
+
+    fn valid_spec() -> AdapterSpec {
+        serde_yaml::from_str(VALID_YAML).unwrap()
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_spec() {
+        assert!(validate_adapter(&valid_spec()).is_ok());
+    }
+
+    #[test]
+    fn test_validate_reports_all_problems() {
+        let mut spec = valid_spec();
+        spec.file_format.format_type = "xml".to_string();
+        spec.file_format.extensions = vec!["ilg".to_string()];
+        spec.channels[0].conversion = Some("x *".to_string());
+        spec.channels.push(spec.channels[0].clone());
+
+        let Err(LocalSpecError::ValidationError(message)) = validate_adapter(&spec) else {
+            panic!("Expected a validation error");
+        };
+        assert!(message.contains("file_format.type"));
+        assert!(message.contains("extension 'ilg'"));
+        assert!(message.contains("invalid conversion"));
+        assert!(message.contains("duplicate channel id 'rpm'"));
+    }
+
+    #[test]
+    fn test_load_from_dir_reports_per_file() {
+        let dir =
+            std::env::temp_dir().join(format!("ultralog-local-adapters-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a_valid.adapter.yaml"), VALID_YAML).unwrap();
+        fs::write(dir.join("b_broken.yaml"), "id: [unclosed").unwrap();
+        fs::write(
+            dir.join("c_invalid.yml"),
+            VALID_YAML.replace("type: csv", "type: xml"),
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), "not a spec").unwrap();
+
+        let loaded = load_adapters_from_dir(&dir);
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(loaded.adapters.len(), 1);
+        assert_eq!(loaded.adapters[0].id, "inhouse-logger");
+        assert_eq!(loaded.issues.len(), 2);
+        assert!(loaded.issues[0].path.ends_with("b_broken.yaml"));
+        assert!(matches!(
+            loaded.issues[0].error,
+            LocalSpecError::ParseError(_)
+        ));
+        assert!(matches!(
+            loaded.issues[1].error,
+            LocalSpecError::ValidationError(_)
+        ));
+    }
+
+    #[test]
+    fn test_merge_overrides_by_id() {
+        let mut base_spec = valid_spec();
+        base_spec.name = "Published Logger".to_string();
+        let mut other = valid_spec();
+        other.id = "other".to_string();
+
+        let mut local_spec = valid_spec();
+        local_spec.version = "0.2.0".to_string();
+        let mut new_spec = valid_spec();
+        new_spec.id = "customer-ecu".to_string();
+
+        let merged = merge_adapters(vec![base_spec, other], &[local_spec, new_spec]);
+        let ids: Vec<&str> = merged.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["inhouse-logger", "other", "customer-ecu"]);
+        assert_eq!(merged[0].version, "0.2.0");
+        assert_eq!(merged[0].name, "In-house Logger");
+    }
+
+    const VALID_DBC: &str = "BU_: PDM\n\n\
+        BO_ 1280 Status: 8 PDM\n \
+        SG_ Battery_Voltage : 0|16@1+ (0.01,0) [0|0] \"V\" Vector__XXX\n";
+
+    #[test]
+    fn test_load_protocols_from_dir_reports_per_file() {
+        let dir =
+            std::env::temp_dir().join(format!("ultralog-local-protocols-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Acme PDM v2.DBC"), VALID_DBC).unwrap();
+        fs::write(dir.join("b_empty.dbc"), "VERSION \"\"\n").unwrap();
+        fs::write(dir.join("notes.txt"), VALID_DBC).unwrap();
+
+        let loaded = load_protocols_from_dir(&dir);
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(loaded.protocols.len(), 1);
+        assert_eq!(loaded.protocols[0].id, "acme-pdm-v2");
+        assert_eq!(loaded.protocols[0].vendor, "PDM");
+        assert_eq!(loaded.issues.len(), 1);
+        assert!(matches!(
+            loaded.issues[0].error,
+            LocalSpecError::DbcError(dbc::DbcError::NoMessages)
+        ));
+    }
+
+    #[test]
+    fn test_dbc_protocol_id() {
+        assert_eq!(
+            dbc_protocol_id(Path::new("/tmp/Haltech_CAN.dbc")),
+            "haltech-can"
+        );
+        assert_eq!(dbc_protocol_id(Path::new("__ecu__.dbc")), "ecu");
+        assert_eq!(dbc_protocol_id(Path::new("___.dbc")), "dbc");
+    }
+
+    #[test]
+    fn test_install_dbc_suffixes_taken_ids() {
+        let root =
+            std::env::temp_dir().join(format!("ultralog-dbc-install-{}", std::process::id()));
+        let dir = root.join("protocols");
+        fs::create_dir_all(&root).unwrap();
+        let source = root.join("Haltech.dbc");
+        fs::write(&source, VALID_DBC).unwrap();
+
+        // A built-in protocol and an earlier import keep their ids
+        let first = install_dbc_file_in(&source, &dir, &["haltech"]).unwrap();
+        let second = install_dbc_file_in(&source, &dir, &["haltech"]).unwrap();
+        // Installing a file that is already in the directory copies nothing
+        let again = install_dbc_file_in(&dir.join("haltech-2.dbc"), &dir, &["haltech"]).unwrap();
+        let installed = load_protocols_from_dir(&dir);
+        fs::remove_dir_all(&root).ok();
+
+        assert_eq!(first.id, "haltech-2");
+        assert_eq!(second.id, "haltech-3");
+        assert_eq!(again.id, "haltech-2");
+        let mut ids: Vec<&str> = installed.protocols.iter().map(|p| p.id.as_str()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["haltech-2", "haltech-3"]);
+    }
+
+    #[test]
+    fn test_merge_protocols_overrides_by_id() {
+        let base = dbc::parse_dbc(VALID_DBC, "pdm").unwrap();
+        let mut local = base.clone();
+        local.version = "2.0".to_string();
+        let other = dbc::parse_dbc(VALID_DBC, "other").unwrap();
+
+        let merged = merge_protocols(vec![base], &[local, other]);
+        let ids: Vec<&str> = merged.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["pdm", "other"]);
+        assert_eq!(merged[0].version, "2.0");
+    }
+}
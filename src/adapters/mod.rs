@@ -22,16 +22,23 @@
 
 pub mod api;
 pub mod cache;
+pub mod can;
+pub mod dbc;
+pub mod local;
 pub mod registry;
 pub mod types;
 
 // Re-export commonly used types and functions
+pub use can::{CanDecoder, CanError, CanFrame, DecodedMessage};
+pub use dbc::DbcError;
+pub use local::{get_local_adapters_dir, get_local_protocols_dir, LocalSpecError, LocalSpecIssue};
 pub use registry::{
     find_adapters_by_extension, find_protocols_by_vendor, get_adapter_by_id, get_adapters,
     get_adapters_by_vendor, get_all_categories, get_channel_metadata, get_channels_by_category,
-    get_protocol_by_id, get_protocols, get_spec_normalizations, get_spec_source,
-    has_spec_normalization, normalize_from_spec, refresh_specs_from_api, specs_refreshed,
-    ChannelMetadata, RefreshResult,
+    get_local_adapter_issues, get_local_protocol_issues, get_protocol_by_id, get_protocols,
+    get_spec_normalizations, get_spec_source, has_spec_normalization, import_dbc,
+    local_adapter_count, local_protocol_count, normalize_from_spec, refresh_specs_from_api,
+    register_protocol, specs_refreshed, ChannelMetadata, RefreshResult,
 };
 pub use types::{
     AdapterSpec, ByteOrder, ChannelCategory, ChannelSpec, DataType, EnumSpec, FileFormatSpec,
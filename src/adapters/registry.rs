@@ -2,6 +2,9 @@
 //!
 //! This module provides functionality to:
 //! - Load adapter YAML files with fallback chain: API -> cache -> embedded
+//! - Overlay user-supplied adapter specs from the local adapters directory
+//! - Register CAN databases (`.dbc`) from the local protocols directory, or
+//!   imported at runtime, alongside the embedded protocols
 //! - Build normalization maps from channel source_names
 //! - Look up channel metadata by source name
 //! - Support background refresh of specs from the API
@@ -12,6 +15,7 @@ use std::sync::{LazyLock, RwLock};
 
 use super::api;
 use super::cache;
+use super::local::{self, LocalAdapters, LocalProtocols, LocalSpecError, LocalSpecIssue};
 use super::types::{AdapterSpec, ChannelCategory, ChannelSpec, ProtocolSpec};
 
 // Embed adapter YAML files at compile time
@@ -142,15 +146,42 @@ fn load_protocols_with_fallback() -> Vec<ProtocolSpec> {
 /// Tracks whether specs have been refreshed from API
 static SPECS_REFRESHED: AtomicBool = AtomicBool::new(false);
 
+/// User-supplied adapter specs, scanned once from the local adapters directory
+static LOCAL_ADAPTERS: LazyLock<LocalAdapters> = LazyLock::new(local::load_local_adapters);
+
 /// Dynamically updatable adapter specifications
-/// Initial load uses cache/embedded, background refresh updates from API
-static ADAPTER_SPECS: LazyLock<RwLock<Vec<AdapterSpec>>> =
-    LazyLock::new(|| RwLock::new(load_adapters_with_fallback()));
+/// Initial load uses cache/embedded, background refresh updates from API.
+/// Local specs are always layered on top, replacing specs with the same ID.
+static ADAPTER_SPECS: LazyLock<RwLock<Vec<AdapterSpec>>> = LazyLock::new(|| {
+    RwLock::new(local::merge_adapters(
+        load_adapters_with_fallback(),
+        &LOCAL_ADAPTERS.adapters,
+    ))
+});
+
+/// Protocol specs read from CAN databases in the local protocols directory,
+/// plus any imported while the app runs
+static LOCAL_PROTOCOLS: LazyLock<RwLock<LocalProtocols>> =
+    LazyLock::new(|| RwLock::new(local::load_local_protocols()));
 
 /// Dynamically updatable protocol specifications
-/// Initial load uses cache/embedded, background refresh updates from API
-static PROTOCOL_SPECS: LazyLock<RwLock<Vec<ProtocolSpec>>> =
-    LazyLock::new(|| RwLock::new(load_protocols_with_fallback()));
+/// Initial load uses cache/embedded, background refresh updates from API.
+/// Local protocols are always layered on top, replacing specs with the same ID.
+static PROTOCOL_SPECS: LazyLock<RwLock<Vec<ProtocolSpec>>> = LazyLock::new(|| {
+    RwLock::new(local::merge_protocols(
+        load_protocols_with_fallback(),
+        &local_protocols(),
+    ))
+});
+
+/// Snapshot of the local protocol specs
+fn local_protocols() -> Vec<ProtocolSpec> {
+    LOCAL_PROTOCOLS
+        .read()
+        .expect("Failed to read local protocols")
+        .protocols
+        .clone()
+}
 
 /// Channel metadata lookup by source name (lowercase)
 #[derive(Debug, Clone)]
@@ -375,6 +406,44 @@ pub fn find_protocols_by_vendor(vendor: &str) -> Vec<ProtocolSpec> {
         .collect()
 }
 
+/// Import a CAN database as a protocol spec and register it alongside the
+/// loaded protocols. The file is copied into the local protocols directory
+/// so it is registered again on the next start. A file named like a loaded
+/// protocol gets a suffixed id rather than replacing it.
+pub fn import_dbc(path: &std::path::Path) -> Result<ProtocolSpec, LocalSpecError> {
+    let loaded: Vec<String> = get_protocols().into_iter().map(|p| p.id).collect();
+    let taken: Vec<&str> = loaded.iter().map(String::as_str).collect();
+    let spec = match local::install_dbc_file(path, &taken) {
+        Ok(spec) => spec,
+        // Still usable this session when the config directory is read-only
+        Err(LocalSpecError::WriteError(e)) => {
+            tracing::warn!("Failed to save imported DBC {:?}: {}", path, e);
+            let mut spec = local::load_dbc_file(path)?;
+            spec.id = local::unique_protocol_id(&spec.id, |id| taken.contains(&id));
+            spec
+        }
+        Err(e) => return Err(e),
+    };
+    register_protocol(spec.clone());
+    Ok(spec)
+}
+
+/// Register a protocol spec for this session, replacing any with the same id
+pub fn register_protocol(spec: ProtocolSpec) {
+    if let Ok(mut local_lock) = LOCAL_PROTOCOLS.write() {
+        local_lock.protocols = local::merge_protocols(
+            std::mem::take(&mut local_lock.protocols),
+            std::slice::from_ref(&spec),
+        );
+    }
+    if let Ok(mut protocol_lock) = PROTOCOL_SPECS.write() {
+        *protocol_lock = local::merge_protocols(
+            std::mem::take(&mut *protocol_lock),
+            std::slice::from_ref(&spec),
+        );
+    }
+}
+
 // ============================================================================
 // Background Refresh Functions
 // ============================================================================
@@ -422,10 +491,10 @@ pub fn refresh_specs_from_api() -> RefreshResult {
 
             // Update the registry
             if let Ok(mut adapter_lock) = ADAPTER_SPECS.write() {
-                *adapter_lock = adapters;
+                *adapter_lock = local::merge_adapters(adapters, &LOCAL_ADAPTERS.adapters);
             }
             if let Ok(mut protocol_lock) = PROTOCOL_SPECS.write() {
-                *protocol_lock = protocols;
+                *protocol_lock = local::merge_protocols(protocols, &local_protocols());
             }
 
             // Rebuild derived maps
@@ -467,16 +536,47 @@ pub fn specs_refreshed() -> bool {
 }
 
 /// Get the current spec source (for display purposes)
-pub fn get_spec_source() -> &'static str {
-    if SPECS_REFRESHED.load(Ordering::SeqCst) {
+/// Local adapters are noted after the base source, e.g. "Cache + 2 local"
+pub fn get_spec_source() -> String {
+    let base = if SPECS_REFRESHED.load(Ordering::SeqCst) {
         "API (refreshed)"
     } else if !cache::is_cache_stale() && cache::load_cached_adapters().is_some() {
         "Cache"
     } else {
         "Embedded"
+    };
+
+    match LOCAL_ADAPTERS.adapters.len() + local_protocol_count() {
+        0 => base.to_string(),
+        count => format!("{} + {} local", base, count),
     }
 }
 
+/// Get the number of adapter specs loaded from the local adapters directory
+pub fn local_adapter_count() -> usize {
+    LOCAL_ADAPTERS.adapters.len()
+}
+
+/// Get the local adapter spec files that failed to load, one entry per file
+pub fn get_local_adapter_issues() -> Vec<LocalSpecIssue> {
+    LOCAL_ADAPTERS.issues.clone()
+}
+
+/// Get the number of protocol specs loaded from local CAN databases
+pub fn local_protocol_count() -> usize {
+    LOCAL_PROTOCOLS
+        .read()
+        .map_or(0, |local| local.protocols.len())
+}
+
+/// Get the local CAN database files that failed to load, one entry per file
+pub fn get_local_protocol_issues() -> Vec<LocalSpecIssue> {
+    LOCAL_PROTOCOLS
+        .read()
+        .map(|local| local.issues.clone())
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -570,11 +670,18 @@ mod tests {
 
     #[test]
     fn test_get_spec_source() {
-        // Initially should be "Embedded" or "Cache" depending on environment
+        // Initially should be "Embedded" or "Cache" depending on environment,
+        // followed by the local adapter count if any are installed
         let source = get_spec_source();
+        let base = source.split(" + ").next().unwrap();
         assert!(
-            source == "Embedded" || source == "Cache" || source == "API (refreshed)",
+            base == "Embedded" || base == "Cache" || base == "API (refreshed)",
             "Spec source should be valid"
         );
+        assert_eq!(
+            source.contains(" local"),
+            local_adapter_count() > 0,
+            "Local adapters should be noted in the spec source"
+        );
     }
 }
@@ -31,11 +31,21 @@ pub struct AdapterSpec {
     pub file_format: FileFormatSpec,
     /// Channel definitions
     pub channels: Vec<ChannelSpec>,
+    /// Enumeration definitions for discrete channels
+    #[serde(default)]
+    pub enums: Option<Vec<EnumSpec>>,
     /// Additional metadata
     #[serde(default)]
     pub metadata: Option<MetadataSpec>,
 }
 
+impl AdapterSpec {
+    /// Find an enumeration definition by name
+    pub fn find_enum(&self, name: &str) -> Option<&EnumSpec> {
+        self.enums.as_ref()?.iter().find(|e| e.name == name)
+    }
+}
+
 /// Branding assets for the vendor
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct BrandingSpec {
@@ -135,6 +145,9 @@ pub struct ChannelSpec {
     /// Searchable tags
     #[serde(default)]
     pub tags: Option<Vec<String>>,
+    /// Reference to an enum definition for `enum` channels
+    #[serde(default)]
+    pub enum_ref: Option<String>,
 }
 
 /// Channel categories
@@ -322,6 +335,14 @@ pub struct MessageSpec {
     pub description: Option<String>,
     /// Message length in bytes (0-8 for CAN, 0-64 for CAN FD)
     pub length: u8,
+    /// IDs of the frames carrying the rest of a message longer than one
+    /// frame's payload, in order after `id`
+    #[serde(
+        default,
+        deserialize_with = "deserialize_message_ids",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub part_ids: Vec<u32>,
     /// Broadcast interval in milliseconds
     #[serde(default)]
     pub interval_ms: Option<f64>,
@@ -463,6 +484,18 @@ where
     deserializer.deserialize_any(MessageIdVisitor)
 }
 
+/// Custom deserializer for a list of message IDs in either notation
+fn deserialize_message_ids<'de, D>(deserializer: D) -> Result<Vec<u32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct MessageId(#[serde(deserialize_with = "deserialize_message_id")] u32);
+
+    let ids = Vec::<MessageId>::deserialize(deserializer)?;
+    Ok(ids.into_iter().map(|id| id.0).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -486,5 +519,10 @@ mod tests {
         let msg: Result<MessageSpec, _> = serde_json::from_str(json);
         assert!(msg.is_ok());
         assert_eq!(msg.unwrap().id, 864);
+
+        // Part IDs take either notation too
+        let json = r#"{"id": "0x370", "name": "Test", "length": 16, "part_ids": ["0x380", 897], "signals": []}"#;
+        let msg: MessageSpec = serde_json::from_str(json).unwrap();
+        assert_eq!(msg.part_ids, vec![0x380, 897]);
     }
 }
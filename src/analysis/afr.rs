@@ -146,10 +146,10 @@ impl Analyzer for FuelTrimDriftAnalyzer {
 
     fn analyze(&self, log: &Log) -> Result<AnalysisResult, AnalysisError> {
         let data = require_channel(log, &self.channel)?;
-        require_min_length(&data, 100)?;
+        require_min_length(data, 100)?;
 
         let (result_data, computation_time) =
-            timed_analyze(|| cusum_drift_detection(&data, self.k, self.h, self.baseline_pct));
+            timed_analyze(|| cusum_drift_detection(data, self.k, self.h, self.baseline_pct));
 
         // Count drift events for warnings
         let high_drift_samples = result_data.drift_flags.iter().filter(|&&x| x > 0.5).count();
@@ -294,10 +294,10 @@ impl Analyzer for RichLeanZoneAnalyzer {
 
     fn analyze(&self, log: &Log) -> Result<AnalysisResult, AnalysisError> {
         let data = require_channel(log, &self.channel)?;
-        require_min_length(&data, 10)?;
+        require_min_length(data, 10)?;
 
         // Auto-detect unit type
-        let unit = detect_fuel_mixture_unit(&data);
+        let unit = detect_fuel_mixture_unit(data);
 
         // Use configured values or auto-detect based on unit type
         let target = if self.target > 0.0 {
@@ -490,10 +490,10 @@ impl Analyzer for AfrDeviationAnalyzer {
 
     fn analyze(&self, log: &Log) -> Result<AnalysisResult, AnalysisError> {
         let data = require_channel(log, &self.channel)?;
-        require_min_length(&data, 2)?;
+        require_min_length(data, 2)?;
 
         // Auto-detect unit type
-        let unit = detect_fuel_mixture_unit(&data);
+        let unit = detect_fuel_mixture_unit(data);
 
         // Use configured target or auto-detect based on unit type
         let target = if self.target > 0.0 {
@@ -793,7 +793,7 @@ mod tests {
 
     #[test]
     fn test_afr_deviation() {
-        let afr_data = vec![14.7, 15.435, 13.965]; // 0%, +5%, -5%
+        let afr_data = [14.7, 15.435, 13.965]; // 0%, +5%, -5%
         let target = 14.7;
 
         let deviations: Vec<f64> = afr_data
@@ -808,7 +808,7 @@ mod tests {
 
     #[test]
     fn test_lambda_deviation() {
-        let lambda_data = vec![1.0, 1.05, 0.95]; // 0%, +5%, -5%
+        let lambda_data = [1.0, 1.05, 0.95]; // 0%, +5%, -5%
         let target = 1.0;
 
         let deviations: Vec<f64> = lambda_data
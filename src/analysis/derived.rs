@@ -70,7 +70,7 @@ impl Analyzer for VolumetricEfficiencyAnalyzer {
             ));
         }
 
-        require_min_length(&rpm, 2)?;
+        require_min_length(rpm, 2)?;
 
         if self.displacement_l <= 0.0 {
             return Err(AnalysisError::InvalidParameter(
@@ -79,7 +79,7 @@ impl Analyzer for VolumetricEfficiencyAnalyzer {
         }
 
         let (ve_values, computation_time) = timed_analyze(|| {
-            compute_volumetric_efficiency(&rpm, &map, &iat, self.displacement_l, self.is_iat_kelvin)
+            compute_volumetric_efficiency(rpm, map, iat, self.displacement_l, self.is_iat_kelvin)
         });
 
         // Compute statistics
@@ -220,10 +220,9 @@ impl Analyzer for InjectorDutyCycleAnalyzer {
             ));
         }
 
-        require_min_length(&pw, 2)?;
+        require_min_length(pw, 2)?;
 
-        let (idc_values, computation_time) =
-            timed_analyze(|| compute_injector_duty_cycle(&pw, &rpm));
+        let (idc_values, computation_time) = timed_analyze(|| compute_injector_duty_cycle(pw, rpm));
 
         // Compute statistics
         let stats = super::statistics::compute_descriptive_stats(&idc_values);
@@ -347,7 +346,7 @@ impl Analyzer for LambdaCalculator {
 
     fn analyze(&self, log: &Log) -> Result<AnalysisResult, AnalysisError> {
         let afr = require_channel(log, &self.afr_channel)?;
-        require_min_length(&afr, 2)?;
+        require_min_length(afr, 2)?;
 
         if self.stoich_afr <= 0.0 {
             return Err(AnalysisError::InvalidParameter(
@@ -549,8 +548,9 @@ mod tests {
     #[test]
     fn test_lambda_calculation() {
         // Lambda = AFR / 14.7
-        assert!((14.7_f64 / 14.7 - 1.0).abs() < 0.001); // Stoich = lambda 1.0
-        assert!((13.0_f64 / 14.7) < 1.0); // Rich (lambda < 1)
-        assert!((16.0_f64 / 14.7) > 1.0); // Lean (lambda > 1)
+        let lambda = |afr: f64| afr / 14.7;
+        assert!((lambda(14.7) - 1.0).abs() < 0.001); // Stoich = lambda 1.0
+        assert!(lambda(13.0) < 1.0); // Rich (lambda < 1)
+        assert!(lambda(16.0) > 1.0); // Lean (lambda > 1)
     }
 }
@@ -48,9 +48,9 @@ impl Analyzer for MovingAverageAnalyzer {
 
     fn analyze(&self, log: &Log) -> Result<AnalysisResult, AnalysisError> {
         let data = require_channel(log, &self.channel)?;
-        require_min_length(&data, self.window_size)?;
+        require_min_length(data, self.window_size)?;
 
-        let (values, computation_time) = timed_analyze(|| moving_average(&data, self.window_size));
+        let (values, computation_time) = timed_analyze(|| moving_average(data, self.window_size));
 
         Ok(AnalysisResult {
             name: format!("{} (MA{})", self.channel, self.window_size),
@@ -139,7 +139,7 @@ impl Analyzer for ExponentialMovingAverageAnalyzer {
 
     fn analyze(&self, log: &Log) -> Result<AnalysisResult, AnalysisError> {
         let data = require_channel(log, &self.channel)?;
-        require_min_length(&data, 2)?;
+        require_min_length(data, 2)?;
 
         if self.alpha <= 0.0 || self.alpha > 1.0 {
             return Err(AnalysisError::InvalidParameter(
@@ -148,7 +148,7 @@ impl Analyzer for ExponentialMovingAverageAnalyzer {
         }
 
         let (values, computation_time) =
-            timed_analyze(|| exponential_moving_average(&data, self.alpha));
+            timed_analyze(|| exponential_moving_average(data, self.alpha));
 
         Ok(AnalysisResult {
             name: format!("{} (EMA α={:.2})", self.channel, self.alpha),
@@ -236,7 +236,7 @@ impl Analyzer for MedianFilterAnalyzer {
 
     fn analyze(&self, log: &Log) -> Result<AnalysisResult, AnalysisError> {
         let data = require_channel(log, &self.channel)?;
-        require_min_length(&data, self.window_size)?;
+        require_min_length(data, self.window_size)?;
 
         // Ensure odd window size
         #[allow(clippy::manual_is_multiple_of)]
@@ -246,7 +246,7 @@ impl Analyzer for MedianFilterAnalyzer {
             self.window_size
         };
 
-        let (values, computation_time) = timed_analyze(|| median_filter(&data, window));
+        let (values, computation_time) = timed_analyze(|| median_filter(data, window));
 
         let mut warnings = vec![];
         #[allow(clippy::manual_is_multiple_of)]
@@ -426,7 +426,7 @@ impl Analyzer for ButterworthLowpassAnalyzer {
 
     fn analyze(&self, log: &Log) -> Result<AnalysisResult, AnalysisError> {
         let data = require_channel(log, &self.channel)?;
-        require_min_length(&data, 10)?;
+        require_min_length(data, 10)?;
 
         if self.cutoff_normalized <= 0.0 || self.cutoff_normalized >= 0.5 {
             return Err(AnalysisError::InvalidParameter(
@@ -441,7 +441,7 @@ impl Analyzer for ButterworthLowpassAnalyzer {
         }
 
         let (values, computation_time) = timed_analyze(|| {
-            butterworth_lowpass_filtfilt(&data, self.cutoff_normalized, self.order)
+            butterworth_lowpass_filtfilt(data, self.cutoff_normalized, self.order)
         });
 
         let mut warnings = vec![];
@@ -569,7 +569,7 @@ impl Analyzer for ButterworthHighpassAnalyzer {
 
     fn analyze(&self, log: &Log) -> Result<AnalysisResult, AnalysisError> {
         let data = require_channel(log, &self.channel)?;
-        require_min_length(&data, 10)?;
+        require_min_length(data, 10)?;
 
         if self.cutoff_normalized <= 0.0 || self.cutoff_normalized >= 0.5 {
             return Err(AnalysisError::InvalidParameter(
@@ -584,7 +584,7 @@ impl Analyzer for ButterworthHighpassAnalyzer {
         }
 
         let (values, computation_time) = timed_analyze(|| {
-            butterworth_highpass_filtfilt(&data, self.cutoff_normalized, self.order)
+            butterworth_highpass_filtfilt(data, self.cutoff_normalized, self.order)
         });
 
         Ok(AnalysisResult {
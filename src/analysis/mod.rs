@@ -15,7 +15,7 @@ pub mod derived;
 pub mod filters;
 pub mod statistics;
 
-use crate::parsers::types::Log;
+use crate::parsers::types::{is_missing, Log, MISSING};
 use std::collections::HashMap;
 use std::time::Instant;
 
@@ -151,7 +151,7 @@ impl Clone for Box<dyn Analyzer> {
 /// Helper trait for accessing log data by channel name
 pub trait LogDataAccess {
     /// Get channel values by name (case-insensitive)
-    fn get_channel_values(&self, name: &str) -> Option<Vec<f64>>;
+    fn get_channel_values(&self, name: &str) -> Option<&[f64]>;
 
     /// Check if a channel exists
     fn has_channel(&self, name: &str) -> bool;
@@ -164,21 +164,14 @@ pub trait LogDataAccess {
 }
 
 impl LogDataAccess for Log {
-    fn get_channel_values(&self, name: &str) -> Option<Vec<f64>> {
+    fn get_channel_values(&self, name: &str) -> Option<&[f64]> {
         // Find the channel index (case-insensitive)
         let channel_idx = self
             .channels
             .iter()
             .position(|c| c.name().eq_ignore_ascii_case(name))?;
 
-        // Extract values from the data matrix
-        let values: Vec<f64> = self
-            .data
-            .iter()
-            .filter_map(|row| row.get(channel_idx).map(|v| v.as_f64()))
-            .collect();
-
-        Some(values)
+        Some(self.channel_data(channel_idx))
     }
 
     fn has_channel(&self, name: &str) -> bool {
@@ -301,7 +294,7 @@ where
 }
 
 /// Helper to get a required channel or return an error
-pub fn require_channel(log: &Log, name: &str) -> Result<Vec<f64>, AnalysisError> {
+pub fn require_channel<'a>(log: &'a Log, name: &str) -> Result<&'a [f64], AnalysisError> {
     log.get_channel_values(name)
         .ok_or_else(|| AnalysisError::MissingChannel(name.to_string()))
 }
@@ -318,6 +311,65 @@ pub fn require_min_length(data: &[f64], min_len: usize) -> Result<(), AnalysisEr
     }
 }
 
+/// Run an analyzer over only the records where its channels have samples
+///
+/// Analyzers assume every record is a real sample, so gaps in the log would
+/// otherwise be filtered, averaged or differentiated as if they were data.
+/// When any channel the analyzer reads has missing records, it runs on a
+/// copy of the log without those records and the result is spread back out
+/// to full length, with [`MISSING`] in the gaps.
+pub fn analyze_skipping_gaps(
+    analyzer: &dyn Analyzer,
+    log: &Log,
+) -> Result<AnalysisResult, AnalysisError> {
+    let mut names = analyzer.required_channels();
+    names.extend(analyzer.optional_channels());
+    let indices: Vec<usize> = log
+        .channels
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| names.iter().any(|n| c.name().eq_ignore_ascii_case(n)))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    if !indices.iter().any(|&idx| log.has_gaps(idx)) {
+        return analyzer.analyze(log);
+    }
+
+    let rows: Vec<usize> = (0..log.record_count())
+        .filter(|&row| {
+            indices
+                .iter()
+                .all(|&idx| !is_missing(log.channel_data(idx)[row]))
+        })
+        .collect();
+    let compact = Log::new(
+        log.meta.clone(),
+        indices
+            .iter()
+            .map(|&idx| log.channels[idx].clone())
+            .collect(),
+        rows.iter().map(|&row| log.times[row]).collect(),
+        indices
+            .iter()
+            .map(|&idx| {
+                let data = log.channel_data(idx);
+                rows.iter().map(|&row| data[row]).collect()
+            })
+            .collect(),
+    );
+
+    let mut result = analyzer.analyze(&compact)?;
+    if result.values.len() == rows.len() {
+        let mut values = vec![MISSING; log.record_count()];
+        for (&row, &value) in rows.iter().zip(&result.values) {
+            values[row] = value;
+        }
+        result.values = values;
+    }
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -343,4 +395,38 @@ mod tests {
         assert_eq!(result.values.len(), 3);
         assert!(!result.has_warnings());
     }
+
+    #[test]
+    fn test_analyze_skipping_gaps() {
+        use crate::parsers::types::{Channel, ComputedChannelInfo, Meta};
+
+        let channel = |name: &str| {
+            Channel::Computed(ComputedChannelInfo {
+                name: name.to_string(),
+                formula: String::new(),
+                unit: String::new(),
+            })
+        };
+        let log = Log::new(
+            Meta::Empty,
+            vec![channel("RPM"), channel("TPS")],
+            vec![0.0, 1.0, 2.0, 3.0, 4.0],
+            vec![
+                vec![1000.0, MISSING, 3000.0, MISSING, 5000.0],
+                vec![MISSING; 5],
+            ],
+        );
+        let analyzer = filters::MovingAverageAnalyzer {
+            channel: "RPM".to_string(),
+            window_size: 2,
+        };
+
+        let result = analyze_skipping_gaps(&analyzer, &log).unwrap();
+        assert_eq!(result.values.len(), 5);
+        assert_eq!(result.values[0], 1000.0);
+        assert!(is_missing(result.values[1]));
+        assert_eq!(result.values[2], 2000.0);
+        assert!(is_missing(result.values[3]));
+        assert_eq!(result.values[4], 4000.0);
+    }
 }
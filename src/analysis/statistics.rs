@@ -44,9 +44,9 @@ impl Analyzer for DescriptiveStatsAnalyzer {
 
     fn analyze(&self, log: &Log) -> Result<AnalysisResult, AnalysisError> {
         let data = require_channel(log, &self.channel)?;
-        require_min_length(&data, 2)?;
+        require_min_length(data, 2)?;
 
-        let (stats, computation_time) = timed_analyze(|| compute_descriptive_stats(&data));
+        let (stats, computation_time) = timed_analyze(|| compute_descriptive_stats(data));
 
         let mut warnings = vec![];
 
@@ -158,9 +158,9 @@ impl Analyzer for CorrelationAnalyzer {
             ));
         }
 
-        require_min_length(&x, 3)?;
+        require_min_length(x, 3)?;
 
-        let (correlation, computation_time) = timed_analyze(|| pearson_correlation(&x, &y));
+        let (correlation, computation_time) = timed_analyze(|| pearson_correlation(x, y));
 
         let mut warnings = vec![];
         let r = correlation.r;
@@ -186,7 +186,7 @@ impl Analyzer for CorrelationAnalyzer {
         ));
 
         // Create residuals for visualization
-        let residuals = compute_residuals(&x, &y);
+        let residuals = compute_residuals(x, y);
 
         Ok(AnalysisResult {
             name: format!("{} vs {} Residuals", self.channel_x, self.channel_y),
@@ -274,7 +274,7 @@ impl Analyzer for RateOfChangeAnalyzer {
 
     fn analyze(&self, log: &Log) -> Result<AnalysisResult, AnalysisError> {
         let data = require_channel(log, &self.channel)?;
-        require_min_length(&data, 2)?;
+        require_min_length(data, 2)?;
 
         let times = log.times();
         if times.len() != data.len() {
@@ -285,9 +285,9 @@ impl Analyzer for RateOfChangeAnalyzer {
 
         let (derivative, computation_time) = timed_analyze(|| {
             if self.time_based {
-                time_derivative(&data, times)
+                time_derivative(data, times)
             } else {
-                sample_derivative(&data)
+                sample_derivative(data)
             }
         });
 
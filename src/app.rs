@@ -8,21 +8,27 @@ use memmap2::Mmap;
 use rust_i18n::t;
 use std::collections::HashMap;
 use std::fs::{self, File};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::thread;
 
 use crate::adapters;
 use crate::analysis::{AnalysisResult, AnalyzerRegistry};
 use crate::analytics;
+use crate::cache::{self, SourceKey};
 use crate::computed::{ComputedChannel, ComputedChannelLibrary, FormulaEditorState};
+use crate::decompress::{self, Compression};
 use crate::i18n::Language;
-use crate::parsers::{
-    Aim, EcuMaster, EcuType, Emerald, Haltech, Link, Locomotive, Parseable, RomRaider, Speeduino,
-};
+use crate::live::{self, LiveEvent, LiveStream, Recording};
+use crate::parsers::datetime;
+use crate::parsers::detection::{self, Detection, DetectionInput};
+use crate::parsers::streaming::StreamProgress;
+use crate::parsers::types::{is_missing, Annotation, Channel};
+use crate::parsers::{CanStream, CanTrace, EcuType, Parseable};
 use crate::settings::UserSettings;
 use crate::state::{
-    ActivePanel, ActiveTool, CacheKey, FontScale, LoadResult, LoadedFile, LoadingState,
+    ActivePanel, ActiveTool, CacheKey, FontScale, LiveSession, LiveSetup, LoadResult, LoadedFile,
+    LoadingState, ParseFailure, PendingArchive, PendingFormat, PendingProtocol, ProtocolCandidate,
     ScatterPlotConfig, ScatterPlotState, SelectedChannel, Tab, ToastType, CHART_COLORS,
     COLORBLIND_COLORS, MAX_CHANNELS,
 };
@@ -47,6 +53,20 @@ pub struct UltraLogApp {
     load_receiver: Option<Receiver<LoadResult>>,
     /// Current loading state
     pub(crate) loading_state: LoadingState,
+    /// Fraction of the file parsed so far, for formats that report progress
+    pub(crate) loading_progress: Option<f32>,
+    /// Zip archive waiting for the user to choose an entry
+    pub(crate) pending_archive: Option<PendingArchive>,
+    /// File matching several formats, waiting for the user to pick one
+    pub(crate) pending_format: Option<PendingFormat>,
+    /// CAN trace waiting for the user to pick a protocol to decode it with
+    pub(crate) pending_protocol: Option<PendingProtocol>,
+    /// File whose parser failed, shown in the diagnostics dialog
+    pub(crate) parse_failure: Option<ParseFailure>,
+    /// Interface and protocol picked in the live CAN dialog, while it is open
+    pub(crate) live_setup: Option<LiveSetup>,
+    /// Live CAN bus filling one of the loaded files
+    pub(crate) live: Option<LiveSession>,
     /// Cache for downsampled chart data
     pub(crate) downsample_cache: HashMap<CacheKey, Vec<[f64; 2]>>,
     /// Cache for channel min/max values (avoids O(n) scans)
@@ -62,6 +82,8 @@ pub struct UltraLogApp {
     pub(crate) cursor_tracking: bool,
     /// Visible time window width in seconds (for cursor tracking mode)
     pub(crate) view_window_seconds: f64,
+    /// When true, show times as wall-clock time for logs with a known start
+    pub(crate) absolute_time: bool,
     // === Playback ===
     /// Whether playback is active
     pub(crate) is_playing: bool,
@@ -162,6 +184,13 @@ impl Default for UltraLogApp {
             last_drop_time: None,
             load_receiver: None,
             loading_state: LoadingState::Idle,
+            loading_progress: None,
+            pending_archive: None,
+            pending_format: None,
+            pending_protocol: None,
+            parse_failure: None,
+            live_setup: None,
+            live: None,
             downsample_cache: HashMap::new(),
             minmax_cache: HashMap::new(),
             cursor_time: None,
@@ -169,6 +198,7 @@ impl Default for UltraLogApp {
             cursor_record: None,
             cursor_tracking: false,
             view_window_seconds: 30.0, // Default 30 second window
+            absolute_time: false,
             is_playing: false,
             last_frame_time: None,
             playback_speed: 1.0,
@@ -250,11 +280,13 @@ impl UltraLogApp {
         let user_settings = UserSettings::load();
         rust_i18n::set_locale(user_settings.language.locale_code());
 
-        Self {
+        let mut app = Self {
             user_settings: user_settings.clone(),
             language: user_settings.language,
             ..Self::default()
-        }
+        };
+        app.report_local_spec_issues();
+        app
     }
 
     // ========================================================================
@@ -294,21 +326,133 @@ impl UltraLogApp {
             .map(|n| n.to_string_lossy().to_string())
             .unwrap_or_else(|| "Unknown".to_string());
 
-        self.loading_state = LoadingState::Loading(filename.clone());
+        self.spawn_loader(filename, path, None, None, None, false);
+    }
+
+    /// Start loading one entry of a zip archive chosen by the user
+    pub fn start_loading_archive_entry(&mut self, archive: PathBuf, entry: String) {
+        if self.files.iter().any(|f| f.path == archive.join(&entry)) {
+            self.show_toast_warning(&t!("toast.file_already_loaded"));
+            return;
+        }
+
+        let filename = entry.rsplit('/').next().unwrap_or(&entry).to_string();
+        self.spawn_loader(filename, archive, Some(entry), None, None, false);
+    }
+
+    /// Load a file the user has told us the format of, after detection found it ambiguous
+    pub fn start_loading_with_format(&mut self, pending: PendingFormat, format: &'static str) {
+        let filename = match &pending.entry {
+            Some(entry) => entry.rsplit('/').next().unwrap_or(entry).to_string(),
+            None => pending
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Unknown".to_string()),
+        };
+        self.spawn_loader(
+            filename,
+            pending.path,
+            pending.entry,
+            Some(format),
+            None,
+            false,
+        );
+    }
+
+    /// Decode a CAN trace with the protocol spec the user picked
+    pub fn start_loading_with_protocol(&mut self, pending: PendingProtocol, protocol: String) {
+        let filename = match &pending.entry {
+            Some(entry) => entry.rsplit('/').next().unwrap_or(entry).to_string(),
+            None => pending
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Unknown".to_string()),
+        };
+        self.spawn_loader(
+            filename,
+            pending.path,
+            pending.entry,
+            None,
+            Some(protocol),
+            false,
+        );
+    }
+
+    /// Register a vendor CAN database as a protocol spec
+    pub fn import_dbc_file(&mut self, path: &Path) {
+        match adapters::import_dbc(path) {
+            Ok(protocol) => self.show_toast_success(&t!(
+                "toast.dbc_imported",
+                name = protocol.name,
+                messages = protocol.messages.len()
+            )),
+            Err(e) => self.show_toast_error(&t!("toast.dbc_import_failed", error = e.to_string())),
+        }
+    }
+
+    /// Parse a file again in recovery mode after its parser rejected it
+    pub fn start_recovering(&mut self, failure: ParseFailure) {
+        let filename = failure.file_name();
+        self.spawn_loader(
+            filename,
+            failure.path,
+            failure.entry,
+            Some(failure.format_id),
+            None,
+            true,
+        );
+    }
+
+    /// Load a file (or an archive entry) on a background thread.
+    /// `format` forces a registered format instead of detecting one,
+    /// `protocol` names the protocol spec to decode a CAN trace with, and
+    /// `recover` parses it in recovery mode, skipping damaged data.
+    fn spawn_loader(
+        &mut self,
+        filename: String,
+        path: PathBuf,
+        entry: Option<String>,
+        format: Option<&'static str>,
+        protocol: Option<String>,
+        recover: bool,
+    ) {
+        self.loading_state = LoadingState::Loading(filename);
+        self.loading_progress = None;
 
         let (sender, receiver): (Sender<LoadResult>, Receiver<LoadResult>) = channel();
         self.load_receiver = Some(receiver);
 
         // Spawn background thread for loading
         thread::spawn(move || {
-            let result = Self::load_file_sync(path);
+            let progress_sender = sender.clone();
+            let mut progress = move |p: StreamProgress| {
+                let _ = progress_sender.send(LoadResult::Progress(p.fraction()));
+            };
+            let result = Self::load_file_sync(
+                path,
+                entry,
+                format,
+                protocol.as_deref(),
+                recover,
+                &mut progress,
+            );
             let _ = sender.send(result);
         });
     }
 
     /// Synchronously load a file (runs in background thread)
     /// Uses memory-mapped files for large files (>10MB) for better performance.
-    fn load_file_sync(path: PathBuf) -> LoadResult {
+    /// `entry` selects a file inside a zip archive when the archive holds several logs.
+    fn load_file_sync(
+        path: PathBuf,
+        entry: Option<String>,
+        format: Option<&str>,
+        protocol: Option<&str>,
+        recover: bool,
+        progress: &mut dyn FnMut(StreamProgress),
+    ) -> LoadResult {
         // Use memory mapping for large files (>10MB) to reduce memory pressure
         const MMAP_THRESHOLD: u64 = 10 * 1024 * 1024;
 
@@ -321,19 +465,68 @@ impl UltraLogApp {
         // Link .llg files are now supported via the Link parser
         // No need to reject them early - let the parser handle detection
 
+        // A plain open of a large file parsed before reads the cached copy
+        let cache_dir = cache::cache_dir();
+        let cache_key = (format.is_none()
+            && protocol.is_none()
+            && !recover
+            && file_size >= cache::MIN_CACHED_BYTES)
+            .then(|| SourceKey::for_file(&path, entry.as_deref()).ok())
+            .flatten();
+        let cached = cache_dir
+            .as_deref()
+            .zip(cache_key.as_ref())
+            .and_then(|(dir, key)| cache::load(dir, key));
+        let from_cache = cached.is_some();
+
         // Load file data - use mmap for large files, regular read for small files
-        let (log, ecu_type) = if file_size > MMAP_THRESHOLD {
-            // Use memory-mapped file for large files
-            match Self::load_with_mmap(&path) {
-                Ok(result) => result,
-                Err(e) => return e,
+        let result = match cached {
+            Some(cached) => {
+                tracing::info!("Loaded {} from the parsed-log cache", path.display());
+                Ok((cached.log, cached.ecu_type, cached.computed))
             }
-        } else {
-            // Use regular file read for small files
-            match Self::load_with_read(&path) {
-                Ok(result) => result,
-                Err(e) => return e,
+            None if file_size > MMAP_THRESHOLD => {
+                // Use memory-mapped file for large files
+                Self::load_with_mmap(&path, entry.as_deref(), format, protocol, recover, progress)
+                    .map(|(log, ecu_type)| (log, ecu_type, Vec::new()))
+            }
+            None => {
+                // Use regular file read for small files
+                Self::load_with_read(&path, entry.as_deref(), format, protocol, recover, progress)
+                    .map(|(log, ecu_type)| (log, ecu_type, Vec::new()))
+            }
+        };
+        let (mut log, ecu_type, computed) = match result {
+            Ok(result) => result,
+            // Reloading with a chosen format starts again from the file on disk
+            Err(LoadResult::FormatSelection(pending)) => {
+                return LoadResult::FormatSelection(PendingFormat {
+                    path,
+                    entry,
+                    ..pending
+                })
             }
+            Err(LoadResult::ProtocolSelection(pending)) => {
+                return LoadResult::ProtocolSelection(PendingProtocol {
+                    path,
+                    entry,
+                    ..pending
+                })
+            }
+            Err(LoadResult::ParseFailed(failure)) => {
+                return LoadResult::ParseFailed(Box::new(ParseFailure {
+                    path,
+                    entry,
+                    ..*failure
+                }))
+            }
+            Err(e) => return e,
+        };
+
+        // Archive entries are identified by a virtual path inside the archive
+        let path = match entry {
+            Some(entry) => path.join(entry),
+            None => path,
         };
 
         let name = path
@@ -341,11 +534,42 @@ impl UltraLogApp {
             .map(|n| n.to_string_lossy().to_string())
             .unwrap_or_else(|| "Unknown".to_string());
 
-        LoadResult::Success(Box::new(LoadedFile::new(path, name, ecu_type, log)))
+        // Many loggers name files after when logging started
+        if log.start.is_none() {
+            log.start = datetime::from_file_name(&path);
+        }
+
+        // Cache fresh parses of intact files for the next open
+        let mut cached = from_cache;
+        if let (false, None, Some(dir), Some(key)) =
+            (from_cache, &log.recovery, &cache_dir, &cache_key)
+        {
+            match cache::store(dir, key, &log, ecu_type) {
+                Ok(_) => {
+                    cached = true;
+                    if let Err(e) = cache::prune(dir, cache::MAX_CACHE_BYTES) {
+                        tracing::warn!("Failed to prune the parsed-log cache: {}", e);
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to cache parsed log: {}", e),
+            }
+        }
+
+        let mut file = LoadedFile::new(path, name, ecu_type, log);
+        file.cache_key = cache_key.filter(|_| cached);
+        file.cached_computed = computed;
+        LoadResult::Success(Box::new(file))
     }
 
     /// Load file using memory-mapped I/O for better performance with large files
-    fn load_with_mmap(path: &PathBuf) -> Result<(crate::parsers::Log, EcuType), LoadResult> {
+    fn load_with_mmap(
+        path: &Path,
+        entry: Option<&str>,
+        format: Option<&str>,
+        protocol: Option<&str>,
+        recover: bool,
+        progress: &mut dyn FnMut(StreamProgress),
+    ) -> Result<(crate::parsers::Log, EcuType), LoadResult> {
         let file = match File::open(path) {
             Ok(f) => f,
             Err(e) => return Err(LoadResult::Error(format!("Failed to open file: {}", e))),
@@ -363,161 +587,212 @@ impl UltraLogApp {
             }
         };
 
-        Self::parse_binary_data(&mmap, path)
+        Self::decompress_and_parse(&mmap, path, entry, format, protocol, recover, progress)
     }
 
     /// Load file using regular file read (for smaller files)
-    fn load_with_read(path: &PathBuf) -> Result<(crate::parsers::Log, EcuType), LoadResult> {
+    fn load_with_read(
+        path: &Path,
+        entry: Option<&str>,
+        format: Option<&str>,
+        protocol: Option<&str>,
+        recover: bool,
+        progress: &mut dyn FnMut(StreamProgress),
+    ) -> Result<(crate::parsers::Log, EcuType), LoadResult> {
         let binary_data = match fs::read(path) {
             Ok(d) => d,
             Err(e) => return Err(LoadResult::Error(format!("Failed to read file: {}", e))),
         };
 
-        Self::parse_binary_data(&binary_data, path)
+        Self::decompress_and_parse(
+            &binary_data,
+            path,
+            entry,
+            format,
+            protocol,
+            recover,
+            progress,
+        )
     }
 
-    /// Parse binary data and detect file format
-    fn parse_binary_data(
+    /// Unwrap gzip/zip containers before running format detection on the inner file
+    fn decompress_and_parse(
         binary_data: &[u8],
-        path: &PathBuf,
+        path: &Path,
+        entry: Option<&str>,
+        format: Option<&str>,
+        protocol: Option<&str>,
+        recover: bool,
+        progress: &mut dyn FnMut(StreamProgress),
     ) -> Result<(crate::parsers::Log, EcuType), LoadResult> {
-        // Check for Haltech HEPS format (.hlgzip) - proprietary compressed format
-        if binary_data.len() >= 4 && &binary_data[0..4] == b"HEPS" {
-            return Err(LoadResult::Error(
-                "This is a Haltech .hlgzip file which uses proprietary compression.\n\n\
-                To use this log in UltraLog, please export it as CSV from Haltech's ESP or NSP software:\n\
-                1. Open the .hlgzip file in Haltech ESP/NSP\n\
-                2. Go to File → Export → CSV\n\
-                3. Load the exported .csv file in UltraLog"
-                    .to_string(),
-            ));
+        match decompress::detect_compression(binary_data) {
+            Some(Compression::Gzip) => {
+                let inner = decompress::decompress_gzip(binary_data).map_err(LoadResult::Error)?;
+                let inner_path = decompress::gzip_inner_path(path);
+                Self::decompress_and_parse(
+                    &inner,
+                    &inner_path,
+                    None,
+                    format,
+                    protocol,
+                    recover,
+                    progress,
+                )
+            }
+            Some(Compression::Zip) => {
+                let entry = match entry {
+                    Some(entry) => entry.to_string(),
+                    None => {
+                        let mut entries =
+                            decompress::zip_log_entries(binary_data).map_err(LoadResult::Error)?;
+                        match entries.len() {
+                            0 => {
+                                return Err(LoadResult::Error(
+                                    "The zip archive does not contain any files".to_string(),
+                                ))
+                            }
+                            1 => entries.remove(0),
+                            _ => {
+                                return Err(LoadResult::ArchiveSelection(PendingArchive {
+                                    path: path.to_path_buf(),
+                                    entries,
+                                }))
+                            }
+                        }
+                    }
+                };
+
+                let inner = decompress::extract_zip_entry(binary_data, &entry)
+                    .map_err(LoadResult::Error)?;
+                Self::decompress_and_parse(
+                    &inner,
+                    &path.join(&entry),
+                    None,
+                    format,
+                    protocol,
+                    recover,
+                    progress,
+                )
+            }
+            None => Self::parse_binary_data(binary_data, path, format, protocol, recover, progress),
         }
+    }
 
-        // Check for AEM .daq format - proprietary format (starts with "EMERALD")
-        if binary_data.len() >= 7 && &binary_data[0..7] == b"EMERALD" {
-            return Err(LoadResult::Error(
-                "This is an AEM .daq file which uses a proprietary format.\n\n\
-                To use this log in UltraLog, please export it as CSV from AEM's software:\n\
-                1. Open the .daq file in AEMdata or AEM Pro\n\
-                2. Go to File → Export → CSV\n\
-                3. Load the exported .csv file in UltraLog"
-                    .to_string(),
-            ));
+    /// Detect the file format through the format registry and parse the data.
+    /// `format` names a registered format to use instead of detecting one.
+    fn parse_binary_data(
+        binary_data: &[u8],
+        path: &Path,
+        format: Option<&str>,
+        protocol: Option<&str>,
+        recover: bool,
+        progress: &mut dyn FnMut(StreamProgress),
+    ) -> Result<(crate::parsers::Log, EcuType), LoadResult> {
+        // Proprietary containers (Haltech .hlgzip, EMU PRO .emuprolog) need exporting first
+        if let Some(message) = detection::unsupported_format_message(binary_data) {
+            return Err(LoadResult::Error(message.to_string()));
         }
 
-        // Check for AIM XRK format - parse using pure Rust implementation
-        if Aim::detect(binary_data) {
-            match Aim::parse_file(path) {
-                Ok(l) => return Ok((l, EcuType::Aim)),
-                Err(e) => {
-                    return Err(LoadResult::Error(format!(
-                        "Failed to parse AIM XRK file: {}",
-                        e
-                    )))
+        let input = DetectionInput::new(binary_data, path);
+        let detector = match format {
+            Some(id) => detection::find_detector(id)
+                .ok_or_else(|| LoadResult::Error(format!("Unknown log format '{}'", id)))?,
+            None => match detection::detect(&input) {
+                Detection::Match(detector) => detector,
+                Detection::Ambiguous(candidates) => {
+                    return Err(LoadResult::FormatSelection(PendingFormat {
+                        path: path.to_path_buf(),
+                        entry: None,
+                        candidates,
+                    }))
                 }
-            }
-        }
+                Detection::Unknown => {
+                    return Err(LoadResult::Error(
+                        "Unrecognized log format. UltraLog could not match this file to any \
+                        supported ECU or logger format."
+                            .to_string(),
+                    ))
+                }
+            },
+        };
 
-        // Auto-detect file format and parse
-        if Speeduino::detect(binary_data) {
-            // Speeduino/rusEFI MLG format detected (binary)
-            match Speeduino::parse_binary(binary_data) {
-                Ok(l) => Ok((l, EcuType::Speeduino)),
-                Err(e) => Err(LoadResult::Error(format!(
-                    "Failed to parse Speeduino/rusEFI MLG file: {}",
-                    e
-                ))),
-            }
-        } else if Link::detect(binary_data) {
-            // Link ECU LLG format detected (binary)
-            match Link::parse_binary(binary_data) {
-                Ok(l) => Ok((l, EcuType::Link)),
-                Err(e) => Err(LoadResult::Error(format!(
-                    "Failed to parse Link ECU LLG file: {}",
-                    e
-                ))),
-            }
-        } else if Emerald::is_emerald_path(path)
-            && (Emerald::detect(binary_data) || Emerald::detect_lg2(binary_data))
-        {
-            // Emerald ECU LG1/LG2 format detected
-            match Emerald::parse_file(path) {
-                Ok(l) => Ok((l, EcuType::Emerald)),
-                Err(e) => Err(LoadResult::Error(format!(
-                    "Failed to parse Emerald ECU file: {}",
-                    e
-                ))),
-            }
-        } else {
-            // Try parsing as text-based formats
-            // For mmap, we use from_utf8 which doesn't copy the data
-            let contents = match std::str::from_utf8(binary_data) {
-                Ok(c) => c,
-                Err(_) => {
-                    // Fall back to lossy conversion for files with encoding issues
-                    return Self::parse_text_lossy(binary_data, path);
+        // CAN traces decode with a protocol spec the user picks
+        if detector.id == CanTrace::FORMAT_ID {
+            let text = input.text();
+            let parsed = match protocol {
+                Some(id) => {
+                    let spec = adapters::get_protocol_by_id(id)
+                        .ok_or_else(|| LoadResult::Error(format!("Unknown protocol '{}'", id)))?;
+                    tracing::info!("Decoding {:?} with protocol {}", path, spec.name);
+                    CanTrace::new(spec).parse(&text)
+                }
+                None => {
+                    let candidates = CanTrace::rank_protocols(&text, adapters::get_protocols())
+                        .into_iter()
+                        .map(|m| ProtocolCandidate {
+                            id: m.protocol.id.clone(),
+                            name: m.protocol.name.clone(),
+                            matched_ids: m.matched_ids,
+                        })
+                        .collect();
+                    return Err(LoadResult::ProtocolSelection(PendingProtocol {
+                        path: path.to_path_buf(),
+                        entry: None,
+                        trace_ids: CanTrace::trace_ids(&text).len(),
+                        candidates,
+                    }));
                 }
             };
-
-            Self::parse_text_content(contents)
+            return parsed.map(|log| (log, detector.ecu_type)).map_err(|error| {
+                LoadResult::ParseFailed(Box::new(ParseFailure {
+                    path: path.to_path_buf(),
+                    entry: None,
+                    format_id: detector.id,
+                    format: detector.name,
+                    recovery: false,
+                    error,
+                }))
+            });
         }
-    }
 
-    /// Parse text content after UTF-8 validation
-    fn parse_text_content(contents: &str) -> Result<(crate::parsers::Log, EcuType), LoadResult> {
-        if EcuMaster::detect(contents) {
-            // ECUMaster format detected
-            let parser = EcuMaster;
-            match parser.parse(contents) {
-                Ok(l) => Ok((l, EcuType::EcuMaster)),
-                Err(e) => Err(LoadResult::Error(format!(
-                    "Failed to parse ECUMaster file: {}",
-                    e
-                ))),
-            }
-        } else if RomRaider::detect(contents) {
-            // RomRaider format detected
-            let parser = RomRaider;
-            match parser.parse(contents) {
-                Ok(l) => Ok((l, EcuType::RomRaider)),
-                Err(e) => Err(LoadResult::Error(format!(
-                    "Failed to parse RomRaider file: {}",
-                    e
-                ))),
-            }
-        } else if Locomotive::detect(contents) {
-            // Locomotive format detected
-            let parser = Locomotive;
-            match parser.parse(contents) {
-                Ok(l) => Ok((l, EcuType::Locomotive)),
-                Err(e) => Err(LoadResult::Error(format!(
-                    "Failed to parse Locomotive file: {}",
-                    e
-                ))),
-            }
+        tracing::info!("Parsing {:?} as {}", path, detector.name);
+        let recovered = if recover {
+            detector.recover(&input, progress)
         } else {
-            // Default to Haltech format
-            let parser = Haltech;
-            match parser.parse(contents) {
-                Ok(l) => Ok((l, EcuType::Haltech)),
-                Err(e) => Err(LoadResult::Error(format!("Failed to parse file: {}", e))),
-            }
+            None
+        };
+        let parsed = match recovered {
+            Some(result) => result,
+            None => detector.parse_with_progress(&input, progress),
+        };
+        match parsed {
+            Ok(log) => Ok((log, detector.ecu_type)),
+            Err(error) => Err(LoadResult::ParseFailed(Box::new(ParseFailure {
+                path: path.to_path_buf(),
+                entry: None,
+                format_id: detector.id,
+                format: detector.name,
+                recovery: recover,
+                error,
+            }))),
         }
     }
 
-    /// Parse text with lossy UTF-8 conversion for files with encoding issues
-    fn parse_text_lossy(
-        binary_data: &[u8],
-        _path: &PathBuf,
-    ) -> Result<(crate::parsers::Log, EcuType), LoadResult> {
-        let contents = String::from_utf8_lossy(binary_data);
-        Self::parse_text_content(&contents)
-    }
-
     /// Check for completed background loads
     fn check_loading_complete(&mut self) {
         if let Some(receiver) = &self.load_receiver {
-            if let Ok(result) = receiver.try_recv() {
+            // Progress updates queue up ahead of the final result
+            let mut finished = None;
+            while let Ok(result) = receiver.try_recv() {
+                match result {
+                    LoadResult::Progress(fraction) => self.loading_progress = Some(fraction),
+                    result => {
+                        finished = Some(result);
+                        break;
+                    }
+                }
+            }
+            if let Some(result) = finished {
                 match result {
                     LoadResult::Success(file) => {
                         let file_index = self.files.len();
@@ -537,9 +812,12 @@ impl UltraLogApp {
                                 None
                             };
 
-                        self.files.push(*file);
+                        let mut file = *file;
+                        let computed = std::mem::take(&mut file.cached_computed);
+                        self.files.push(file);
                         self.selected_file = Some(file_index);
                         self.update_time_range();
+                        self.restore_computed_channels(file_index, &computed);
 
                         // Create a new tab for this file with its time range
                         let mut tab = Tab::new(file_index, file_name);
@@ -552,7 +830,14 @@ impl UltraLogApp {
                         self.tabs.push(tab);
                         self.active_tab = Some(self.tabs.len() - 1);
 
-                        self.show_toast_success(&t!("toast.file_loaded"));
+                        match &self.files[file_index].log.recovery {
+                            Some(report) => self.show_toast_warning(&t!(
+                                "toast.file_recovered",
+                                records = self.files[file_index].log.record_count(),
+                                skipped = report.skipped_records
+                            )),
+                            None => self.show_toast_success(&t!("toast.file_loaded")),
+                        }
 
                         // Switch to Channels panel so user can select channels
                         self.active_panel = ActivePanel::ToolProperties;
@@ -560,11 +845,227 @@ impl UltraLogApp {
                     LoadResult::Error(e) => {
                         self.show_toast_error(&format!("Error: {}", e));
                     }
+                    LoadResult::ArchiveSelection(archive) => {
+                        self.pending_archive = Some(archive);
+                    }
+                    LoadResult::FormatSelection(pending) => {
+                        self.pending_format = Some(pending);
+                    }
+                    LoadResult::ProtocolSelection(pending) => {
+                        self.pending_protocol = Some(pending);
+                    }
+                    LoadResult::ParseFailed(failure) => {
+                        tracing::warn!(
+                            "Failed to parse {:?} as {}: {}",
+                            failure.path,
+                            failure.format,
+                            failure.error
+                        );
+                        self.parse_failure = Some(*failure);
+                    }
+                    // Handled while draining the channel above
+                    LoadResult::Progress(_) => {}
                 }
                 self.load_receiver = None;
                 self.loading_state = LoadingState::Idle;
+                self.loading_progress = None;
+            }
+        }
+    }
+
+    // ========================================================================
+    // Live Streaming
+    // ========================================================================
+
+    /// Open a SocketCAN interface and stream its frames into a new live tab,
+    /// decoded with the protocol spec `protocol_id`
+    pub fn start_live(&mut self, interface: &str, protocol_id: &str) {
+        let Some(protocol) = adapters::get_protocol_by_id(protocol_id) else {
+            self.show_toast_error(&t!("toast.live_unknown_protocol", protocol = protocol_id));
+            return;
+        };
+        // One bus at a time; the previous live log stays open
+        self.disconnect_live();
+
+        let stream = match LiveStream::open(interface) {
+            Ok(stream) => stream,
+            Err(e) => {
+                self.show_toast_error(&t!("toast.live_connect_failed", error = e.to_string()));
+                return;
+            }
+        };
+        let decoder = CanStream::new(protocol);
+        let log = decoder.empty_log(&format!("SocketCAN {}", interface));
+        let protocol_name = decoder.protocol().name.clone();
+
+        let file_index = self.files.len();
+        let name = t!("live.file_name", interface = interface).to_string();
+        self.files.push(LoadedFile::new(
+            PathBuf::from(interface),
+            name.clone(),
+            EcuType::Unknown,
+            log,
+        ));
+        self.selected_file = Some(file_index);
+        self.live = Some(LiveSession {
+            stream,
+            decoder,
+            file_index,
+        });
+
+        // The tab starts empty and follows the data as it arrives
+        let mut tab = Tab::new(file_index, name);
+        tab.time_range = Some((0.0, 0.0));
+        tab.cursor_time = Some(0.0);
+        tab.follow_live = true;
+        self.tabs.push(tab);
+        self.active_tab = Some(self.tabs.len() - 1);
+        self.active_panel = ActivePanel::ToolProperties;
+
+        self.show_toast_success(&t!(
+            "toast.live_connected",
+            interface = interface,
+            protocol = protocol_name
+        ));
+    }
+
+    /// Stop streaming, finishing any recording. The live log stays open.
+    pub fn disconnect_live(&mut self) {
+        let Some(session) = self.live.take() else {
+            return;
+        };
+        self.finish_live_recording(session.stream.stop_recording());
+        for tab in &mut self.tabs {
+            if tab.file_index == session.file_index {
+                tab.follow_live = false;
+            }
+        }
+    }
+
+    /// Whether the active tab shows the log a live bus is filling
+    pub fn is_live_tab(&self) -> bool {
+        match (&self.live, self.active_tab) {
+            (Some(session), Some(tab_idx)) => self.tabs[tab_idx].file_index == session.file_index,
+            _ => false,
+        }
+    }
+
+    /// Whether frames from the live bus are being recorded
+    pub fn is_live_recording(&self) -> bool {
+        self.live
+            .as_ref()
+            .is_some_and(|session| session.stream.is_recording())
+    }
+
+    /// Record every frame from now on to `path` as a candump log
+    pub fn start_live_recording(&mut self, path: &Path) {
+        let Some(session) = &self.live else {
+            return;
+        };
+        match session.stream.start_recording(path) {
+            Ok(previous) => {
+                self.finish_live_recording(Ok(previous));
+                self.show_toast(&t!(
+                    "toast.live_recording_started",
+                    path = path.display().to_string()
+                ));
+            }
+            Err(e) => {
+                self.show_toast_error(&t!("toast.live_recording_failed", error = e.to_string()))
+            }
+        }
+    }
+
+    /// Finish the recording in progress
+    pub fn stop_live_recording(&mut self) {
+        if let Some(session) = &self.live {
+            let finished = session.stream.stop_recording();
+            self.finish_live_recording(finished);
+        }
+    }
+
+    /// Report a finished recording
+    fn finish_live_recording(&mut self, finished: std::io::Result<Option<Recording>>) {
+        match finished {
+            Ok(Some(recording)) => self.show_toast_success(&t!(
+                "toast.live_recording_saved",
+                frames = recording.frames,
+                path = recording.path.display().to_string()
+            )),
+            Ok(None) => {}
+            Err(e) => {
+                self.show_toast_error(&t!("toast.live_recording_failed", error = e.to_string()))
+            }
+        }
+    }
+
+    /// Decode the frames that arrived since the last frame into the live log
+    fn update_live(&mut self, ctx: &egui::Context) {
+        let Some(session) = &mut self.live else {
+            return;
+        };
+        let file_index = session.file_index;
+        let Some(file) = self.files.get_mut(file_index) else {
+            return;
+        };
+
+        let mut received = false;
+        let mut failure = None;
+        let mut recording_failure = None;
+        for event in session.stream.poll() {
+            match event {
+                LiveEvent::Frame(frame) => {
+                    session.decoder.push(&mut file.log, &frame);
+                    received = true;
+                }
+                LiveEvent::RecordingFailed(e) => recording_failure = Some(e),
+                LiveEvent::Error(e) => failure = Some(e),
             }
         }
+
+        if received {
+            // Channels are only added once their message decodes, so they have data
+            let channel_count = file.log.channels.len();
+            file.channels_with_data.resize(channel_count, true);
+            let end_time = file.log.end_time();
+
+            // Charts of the live log are re-downsampled as it grows
+            self.downsample_cache
+                .retain(|key, _| key.file_index != file_index);
+            self.minmax_cache
+                .retain(|key, _| key.file_index != file_index);
+
+            self.extend_live_time_range(file_index, end_time);
+        }
+
+        if let Some(e) = recording_failure {
+            self.show_toast_error(&t!("toast.live_recording_failed", error = e));
+        }
+        if let Some(e) = failure {
+            self.disconnect_live();
+            self.show_toast_error(&t!("toast.live_disconnected", error = e));
+            return;
+        }
+
+        // Poll again shortly, whether or not anything arrived
+        ctx.request_repaint_after(live::REFRESH_INTERVAL);
+    }
+
+    /// Move the end of the live log's tabs to `end_time`, keeping following
+    /// cursors on the newest record
+    fn extend_live_time_range(&mut self, file_index: usize, end_time: Option<f64>) {
+        let Some(end) = end_time else {
+            return;
+        };
+        let last_record = self.files[file_index].log.record_count().checked_sub(1);
+        for tab in self.tabs.iter_mut().filter(|t| t.file_index == file_index) {
+            tab.time_range = Some((0.0, end));
+            if tab.follow_live {
+                tab.cursor_time = Some(end);
+                tab.cursor_record = last_record;
+            }
+        }
+        self.update_time_range();
     }
 
     // ========================================================================
@@ -600,8 +1101,12 @@ impl UltraLogApp {
 
     /// Find the record index closest to the given time
     pub fn find_record_at_time(&self, time: f64) -> Option<usize> {
-        // Use the first file with data for record indexing
-        if let Some(file) = self.files.first() {
+        // Use the active tab's file for record indexing
+        let file = match self.active_tab {
+            Some(tab_idx) => self.files.get(self.tabs[tab_idx].file_index),
+            None => self.files.first(),
+        };
+        if let Some(file) = file {
             let times = file.log.get_times_as_f64();
             if times.is_empty() {
                 return None;
@@ -628,6 +1133,45 @@ impl UltraLogApp {
         }
     }
 
+    /// Markers recorded in the active tab's log
+    pub fn get_annotations(&self) -> &[Annotation] {
+        self.active_tab
+            .and_then(|idx| self.files.get(self.tabs[idx].file_index))
+            .map_or(&[], |file| file.log.annotations.as_slice())
+    }
+
+    /// Move the cursor to the next (or previous) marker and center the chart on it
+    pub fn jump_to_annotation(&mut self, forward: bool) {
+        // Markers closer than this to the cursor count as the current one
+        const EPSILON: f64 = 1e-6;
+
+        let Some((min_time, _)) = self.get_time_range() else {
+            return;
+        };
+        let current = self.get_cursor_time().unwrap_or(min_time - EPSILON * 2.0);
+        let times = self.get_annotations().iter().map(|a| a.time);
+        let target = if forward {
+            times
+                .filter(|&t| t > current + EPSILON)
+                .min_by(|a, b| a.total_cmp(b))
+        } else {
+            times
+                .filter(|&t| t < current - EPSILON)
+                .max_by(|a, b| a.total_cmp(b))
+        };
+
+        if let Some(time) = target {
+            self.set_cursor_time(Some(time));
+            let record = self.find_record_at_time(time);
+            self.set_cursor_record(record);
+            self.set_jump_to_time(Some(time));
+            // Stop playback, and following a live log, when jumping
+            self.is_playing = false;
+            self.last_frame_time = None;
+            self.set_follow_live(false);
+        }
+    }
+
     /// Get value at a specific record index for a channel (handles computed channels)
     pub fn get_value_at_record(
         &self,
@@ -644,18 +1188,17 @@ impl UltraLogApp {
 
         if channel_index < regular_count {
             // Regular channel
-            if record < file.log.data.len() && channel_index < file.log.data[record].len() {
-                return Some(file.log.data[record][channel_index].as_f64());
-            }
+            return file
+                .log
+                .value(channel_index, record)
+                .filter(|v| !is_missing(*v));
         } else {
             // Computed channel
             let computed_idx = channel_index - regular_count;
             if let Some(computed_channels) = self.file_computed_channels.get(&file_index) {
                 if let Some(computed) = computed_channels.get(computed_idx) {
                     if let Some(cached_data) = &computed.cached_data {
-                        if record < cached_data.len() {
-                            return Some(cached_data[record]);
-                        }
+                        return cached_data.get(record).copied().filter(|v| !is_missing(*v));
                     }
                 }
             }
@@ -664,9 +1207,9 @@ impl UltraLogApp {
     }
 
     /// Get all data for a channel (handles computed channels)
-    pub fn get_channel_data(&self, file_index: usize, channel_index: usize) -> Vec<f64> {
+    pub fn get_channel_data(&self, file_index: usize, channel_index: usize) -> &[f64] {
         if file_index >= self.files.len() {
-            return Vec::new();
+            return &[];
         }
 
         let file = &self.files[file_index];
@@ -674,21 +1217,56 @@ impl UltraLogApp {
 
         if channel_index < regular_count {
             // Regular channel
-            file.log.get_channel_data(channel_index)
+            file.log.channel_data(channel_index)
         } else {
             // Computed channel
             let computed_idx = channel_index - regular_count;
             if let Some(computed_channels) = self.file_computed_channels.get(&file_index) {
                 if let Some(computed) = computed_channels.get(computed_idx) {
                     if let Some(cached_data) = &computed.cached_data {
-                        return cached_data.clone();
+                        return cached_data;
                     }
                 }
             }
-            Vec::new()
+            &[]
         }
     }
 
+    /// Format a channel value for display: enum and bitfield channels show
+    /// their labels, numeric channels the value in the preferred unit
+    pub fn format_channel_value(&self, channel: &Channel, value: f64) -> String {
+        if let Some(label) = channel.value_label(value) {
+            return label;
+        }
+        let (converted_value, display_unit) =
+            self.unit_preferences.convert_value(value, channel.unit());
+        if display_unit.is_empty() {
+            format!("{:.2}", converted_value)
+        } else {
+            format!("{:.2} {}", converted_value, display_unit)
+        }
+    }
+
+    /// Get the present `(time, value)` samples of a channel, skipping gaps
+    /// (handles both regular and computed channels). Regular channels with a
+    /// native timebase are returned at their own sample times.
+    pub fn get_channel_samples(&self, file_index: usize, channel_index: usize) -> Vec<[f64; 2]> {
+        let Some(file) = self.files.get(file_index) else {
+            return Vec::new();
+        };
+        if channel_index < file.log.channels.len() {
+            return file.log.channel_samples(channel_index);
+        }
+
+        file.log
+            .times
+            .iter()
+            .zip(self.get_channel_data(file_index, channel_index))
+            .filter(|(_, v)| !is_missing(**v))
+            .map(|(t, v)| [*t, *v])
+            .collect()
+    }
+
     /// Get min and max values for a channel across all records (cached, handles computed channels)
     pub fn get_channel_min_max(
         &mut self,
@@ -711,11 +1289,11 @@ impl UltraLogApp {
         // Compute min/max (handles both regular and computed channels)
         let data = self.get_channel_data(file_index, channel_index);
 
-        if data.is_empty() {
-            return None;
-        }
+        // Gaps don't count towards the range
+        let mut present = data.iter().copied().filter(|v| v.is_finite()).peekable();
+        present.peek()?;
 
-        let (min_val, max_val) = data.iter().fold((f64::MAX, f64::MIN), |(min, max), &v| {
+        let (min_val, max_val) = present.fold((f64::MAX, f64::MIN), |(min, max), v| {
             (min.min(v), max.max(v))
         });
 
@@ -728,9 +1306,34 @@ impl UltraLogApp {
     // File and Channel Management
     // ========================================================================
 
+    /// Save a file's computed channels with its cache entry so they are
+    /// applied again the next time it is opened
+    fn store_computed_channels(&self, file_index: usize) {
+        let Some(key) = self
+            .files
+            .get(file_index)
+            .and_then(|f| f.cache_key.as_ref())
+        else {
+            return;
+        };
+        let Some(dir) = cache::cache_dir() else {
+            return;
+        };
+        let templates: Vec<_> = self
+            .file_computed_channels
+            .get(&file_index)
+            .map(|channels| channels.iter().map(|c| c.template.clone()).collect())
+            .unwrap_or_default();
+        if let Err(e) = cache::store_computed(&dir, key, &templates) {
+            tracing::warn!("Failed to save computed channels to the cache: {}", e);
+        }
+    }
+
     /// Remove a loaded file
     pub fn remove_file(&mut self, index: usize) {
         if index < self.files.len() {
+            self.store_computed_channels(index);
+
             // Find and close the tab for this file
             if let Some(tab_idx) = self.tabs.iter().position(|t| t.file_index == index) {
                 self.close_tab(tab_idx);
@@ -788,6 +1391,17 @@ impl UltraLogApp {
             }
             self.file_computed_channels = new_computed_channels;
 
+            // A removed live log stops streaming; later ones shift down
+            if let Some(live_index) = self.live.as_ref().map(|session| session.file_index) {
+                if live_index == index {
+                    self.disconnect_live();
+                } else if live_index > index {
+                    if let Some(session) = &mut self.live {
+                        session.file_index -= 1;
+                    }
+                }
+            }
+
             // Update file indices for remaining tabs and their channels
             for tab in &mut self.tabs {
                 if tab.file_index > index {
@@ -1018,6 +1632,21 @@ impl UltraLogApp {
         }
     }
 
+    /// Get whether the active tab follows the newest data of a live log
+    pub fn get_follow_live(&self) -> bool {
+        self.is_live_tab()
+            && self
+                .active_tab
+                .is_some_and(|idx| self.tabs[idx].follow_live)
+    }
+
+    /// Set whether the active tab follows the newest data of a live log
+    pub fn set_follow_live(&mut self, follow: bool) {
+        if let Some(tab_idx) = self.active_tab {
+            self.tabs[tab_idx].follow_live = follow;
+        }
+    }
+
     /// Get the scatter plot state for the active tab
     pub fn get_scatter_plot_state(&self) -> Option<&ScatterPlotState> {
         self.active_tab
@@ -1258,6 +1887,28 @@ impl UltraLogApp {
         });
     }
 
+    /// Warn about user-supplied adapter specs and CAN databases that failed
+    /// to load, naming each file
+    fn report_local_spec_issues(&mut self) {
+        let adapter_issues = adapters::get_local_adapter_issues();
+        if !adapter_issues.is_empty() {
+            self.show_toast_warning(&t!(
+                "toast.local_adapters_failed",
+                count = adapter_issues.len(),
+                files = describe_issues(&adapter_issues)
+            ));
+        }
+
+        let protocol_issues = adapters::get_local_protocol_issues();
+        if !protocol_issues.is_empty() {
+            self.show_toast_warning(&t!(
+                "toast.local_protocols_failed",
+                count = protocol_issues.len(),
+                files = describe_issues(&protocol_issues)
+            ));
+        }
+    }
+
     // ========================================================================
     // Toast Notifications
     // ========================================================================
@@ -1416,6 +2067,14 @@ impl UltraLogApp {
                     }
                 }
             }
+
+            // [ / ] - Jump to the previous/next marker
+            if !cmd && i.key_pressed(egui::Key::OpenBracket) {
+                self.jump_to_annotation(false);
+            }
+            if !cmd && i.key_pressed(egui::Key::CloseBracket) {
+                self.jump_to_annotation(true);
+            }
         });
     }
 }
@@ -1444,6 +2103,9 @@ impl eframe::App for UltraLogApp {
         // Check for completed background loads
         self.check_loading_complete();
 
+        // Append frames from a live bus
+        self.update_live(ctx);
+
         // Handle file drops
         self.handle_dropped_files(ctx);
 
@@ -1472,6 +2134,11 @@ impl eframe::App for UltraLogApp {
         // Modal windows
         self.render_normalization_editor(ctx);
         self.render_update_dialog(ctx);
+        self.render_archive_dialog(ctx);
+        self.render_format_dialog(ctx);
+        self.render_protocol_dialog(ctx);
+        self.render_live_dialog(ctx);
+        self.render_diagnostics_dialog(ctx);
         self.render_computed_channels_manager(ctx);
         self.render_formula_editor(ctx);
         self.render_analysis_panel(ctx);
@@ -1581,4 +2248,29 @@ impl eframe::App for UltraLogApp {
             }
         });
     }
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // Flush a recording in progress to disk
+        self.disconnect_live();
+
+        // Keep each open file's computed channels for the next time it's opened
+        for file_index in 0..self.files.len() {
+            self.store_computed_channels(file_index);
+        }
+    }
+}
+
+/// "file (error)" for each local spec file that failed to load
+fn describe_issues(issues: &[adapters::LocalSpecIssue]) -> String {
+    issues
+        .iter()
+        .map(|issue| {
+            let name = issue
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            format!("{} ({})", name, issue.error)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
 }
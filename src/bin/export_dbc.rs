@@ -0,0 +1,82 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// Import from the library
+use ultralog::adapters::{self, dbc, ProtocolSpec};
+
+const USAGE: &str = "Usage: export_dbc <protocol-id | protocol.yaml> [-o <output.dbc>]\n       \
+                     export_dbc --list";
+
+fn main() {
+    // Get the protocol (a loaded spec id or a YAML file) and optional -o <path> from command line
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut source: Option<&str> = None;
+    let mut output: Option<&str> = None;
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        if arg == "--list" {
+            list_protocols();
+            return;
+        } else if arg == "-o" || arg == "--output" {
+            output = args_iter.next().map(String::as_str);
+        } else {
+            source = Some(arg);
+        }
+    }
+
+    let Some(source) = source else {
+        eprintln!("{}", USAGE);
+        std::process::exit(2);
+    };
+
+    let protocol = load_protocol(source);
+    let text = dbc::write_dbc(&protocol);
+
+    match output {
+        Some(path) => {
+            fs::write(path, text).expect("Failed to write DBC file");
+            println!(
+                "Wrote {} ({} messages) to {}",
+                protocol.name,
+                protocol.messages.len(),
+                path
+            );
+        }
+        None => print!("{}", text),
+    }
+}
+
+/// Read a protocol spec from a YAML file, or look one up by id
+fn load_protocol(source: &str) -> ProtocolSpec {
+    let path = Path::new(source);
+    let is_yaml = matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("yaml" | "yml")
+    );
+    if is_yaml {
+        let yaml = fs::read_to_string(path).expect("Failed to read protocol spec");
+        return serde_yaml::from_str(&yaml).unwrap_or_else(|e| {
+            eprintln!("Invalid protocol spec {}: {}", source, e);
+            std::process::exit(1);
+        });
+    }
+
+    adapters::get_protocol_by_id(source).unwrap_or_else(|| {
+        eprintln!("Unknown protocol '{}'.", source);
+        list_protocols();
+        std::process::exit(1);
+    })
+}
+
+fn list_protocols() {
+    let protocols = adapters::get_protocols();
+    if protocols.is_empty() {
+        eprintln!("No protocol specs are loaded.");
+        return;
+    }
+    eprintln!("Loaded protocols:");
+    for protocol in protocols {
+        eprintln!("  {:<32} {}", protocol.id, protocol.name);
+    }
+}
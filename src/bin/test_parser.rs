@@ -1,109 +1,190 @@
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::PathBuf;
 
 // Import from the library
-use ultralog::parsers::{
-    EcuMaster, EcuType, Emerald, Haltech, Link, Locomotive, Parseable, Speeduino,
-};
+use ultralog::adapters;
+use ultralog::decompress::{self, Compression};
+use ultralog::parsers::detection::{self, Detection, DetectionInput};
+use ultralog::parsers::{CanTrace, LogBuilder, Parseable};
 
 fn main() {
-    // Get file path from command line or use default
-    let args: Vec<String> = env::args().collect();
-    let path = if args.len() > 1 {
-        &args[1]
-    } else {
-        "exampleLogs/haltech/2025-07-18_0215pm_Log1118.csv"
-    };
+    // Get file path (and optional --format <id> / --protocol <id> / --decimate <n> / --recover)
+    // from command line or use default
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut forced_format: Option<&str> = None;
+    let mut protocol: Option<&str> = None;
+    let mut decimation: Option<usize> = None;
+    let mut recover = false;
+    let mut path = "exampleLogs/haltech/2025-07-18_0215pm_Log1118.csv";
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        if arg == "--format" {
+            forced_format = args_iter.next().map(String::as_str);
+        } else if arg == "--protocol" {
+            protocol = args_iter.next().map(String::as_str);
+        } else if arg == "--decimate" {
+            decimation = args_iter.next().and_then(|n| n.parse().ok());
+        } else if arg == "--recover" {
+            recover = true;
+        } else {
+            path = arg;
+        }
+    }
+
+    if decimation.is_some() && (protocol.is_some() || recover) {
+        eprintln!("--decimate cannot be combined with --protocol or --recover");
+        std::process::exit(1);
+    }
 
     println!("Reading file: {}", path);
-    let binary_data = fs::read(path).expect("Failed to read file");
+    let mut binary_data = fs::read(path).expect("Failed to read file");
     println!("File size: {} bytes", binary_data.len());
 
-    // Auto-detect file format - try binary formats first
-    let (ecu_type, log) = if Speeduino::detect(&binary_data) {
-        println!("\nDetected: Speeduino/rusEFI MLG format");
-        println!("Parsing Speeduino/rusEFI log...");
-        match Speeduino::parse_binary(&binary_data) {
-            Ok(log) => (EcuType::Speeduino, log),
-            Err(e) => {
-                eprintln!("Parse error: {}", e);
-                std::process::exit(1);
+    // Unwrap gzip/zip containers (the first log entry is used for zip archives),
+    // tracking the inner file name so extension-based detection sees it
+    let mut inner_path = PathBuf::from(path);
+    while let Some(compression) = decompress::detect_compression(&binary_data) {
+        binary_data = match compression {
+            Compression::Gzip => {
+                inner_path = decompress::gzip_inner_path(&inner_path);
+                decompress::decompress_gzip(&binary_data)
             }
+            Compression::Zip => decompress::zip_log_entries(&binary_data).and_then(|entries| {
+                let entry = entries.first().ok_or("Zip archive contains no files")?;
+                println!("Using zip entry: {}", entry);
+                inner_path = inner_path.join(entry);
+                decompress::extract_zip_entry(&binary_data, entry)
+            }),
         }
-    } else if Link::detect(&binary_data) {
-        println!("\nDetected: Link ECU LLG format");
-        println!("Parsing Link ECU log...");
-        match Link::parse_binary(&binary_data) {
-            Ok(log) => (EcuType::Link, log),
-            Err(e) => {
-                eprintln!("Parse error: {}", e);
+        .expect("Failed to decompress file");
+        println!("Decompressed size: {} bytes", binary_data.len());
+    }
+
+    if let Some(message) = detection::unsupported_format_message(&binary_data) {
+        eprintln!("\n{}", message);
+        std::process::exit(1);
+    }
+
+    // Score every registered format, then use the forced or best one
+    let input = DetectionInput::new(&binary_data, &inner_path);
+    let candidates = detection::rank(&input);
+    println!("\nFormat candidates:");
+    for candidate in &candidates {
+        println!(
+            "  {:<14} {:<28} {:.0}%",
+            candidate.detector.id,
+            candidate.detector.name,
+            candidate.confidence * 100.0
+        );
+    }
+
+    let detector = match forced_format {
+        Some(id) => detection::find_detector(id).unwrap_or_else(|| {
+            eprintln!("Unknown format '{}'. Known formats:", id);
+            for detector in detection::detectors() {
+                eprintln!("  {}", detector.id);
+            }
+            std::process::exit(1);
+        }),
+        None => match detection::detect(&input) {
+            Detection::Match(detector) => detector,
+            Detection::Ambiguous(candidates) => {
+                let ids: Vec<&str> = candidates.iter().map(|c| c.detector.id).collect();
+                eprintln!(
+                    "\nAmbiguous format ({}). Re-run with --format <id>.",
+                    ids.join(", ")
+                );
                 std::process::exit(1);
             }
-        }
-    } else if Emerald::is_emerald_path(Path::new(path)) && Emerald::detect(&binary_data) {
-        println!("\nDetected: Emerald ECU LG1/LG2 format");
-        println!("Parsing Emerald ECU log...");
-        match Emerald::parse_file(Path::new(path)) {
-            Ok(log) => (EcuType::Emerald, log),
-            Err(e) => {
-                eprintln!("Parse error: {}", e);
+            Detection::Unknown => {
+                eprintln!("\nUnrecognized log format");
                 std::process::exit(1);
             }
-        }
-    } else {
-        // Try text-based formats
-        let contents = match std::str::from_utf8(&binary_data) {
-            Ok(c) => c.to_string(),
-            Err(_) => String::from_utf8_lossy(&binary_data).to_string(),
-        };
+        },
+    };
 
-        if EcuMaster::detect(&contents) {
-            println!("\nDetected: ECUMaster format");
-            println!("Parsing ECUMaster log...");
-            let parser = EcuMaster;
-            match parser.parse(&contents) {
-                Ok(log) => (EcuType::EcuMaster, log),
-                Err(e) => {
-                    eprintln!("Parse error: {}", e);
-                    std::process::exit(1);
-                }
-            }
-        } else if Locomotive::detect(&contents) {
-            println!("\nDetected: Locomotive format");
-            println!("Parsing Locomotive log...");
-            let parser = Locomotive;
-            match parser.parse(&contents) {
-                Ok(log) => (EcuType::Locomotive, log),
-                Err(e) => {
-                    eprintln!("Parse error: {}", e);
-                    std::process::exit(1);
+    println!("\nDetected: {}", detector.name);
+    println!("Parsing {} log...", detector.name);
+    let parsed = match (decimation, protocol) {
+        // Decode a CAN trace with a named protocol spec instead of the best match
+        (None, Some(id)) if detector.id == CanTrace::FORMAT_ID => {
+            let Some(spec) = adapters::get_protocol_by_id(id) else {
+                eprintln!("Unknown protocol '{}'. Loaded protocols:", id);
+                for spec in adapters::get_protocols() {
+                    eprintln!("  {}", spec.id);
                 }
+                std::process::exit(1);
+            };
+            println!("Decoding with protocol: {}", spec.name);
+            CanTrace::new(spec).parse(&input.text())
+        }
+        // Keep every intact record of a damaged file
+        (None, _) if recover => match detector.recover(&input, &mut |_| {}) {
+            Some(result) => result,
+            None => {
+                eprintln!("{} has no recovery mode", detector.name);
+                std::process::exit(1);
             }
-        } else {
-            println!("\nDetected: Haltech format");
-            println!("Parsing Haltech log...");
-            let parser = Haltech;
-            match parser.parse(&contents) {
-                Ok(log) => (EcuType::Haltech, log),
-                Err(e) => {
-                    eprintln!("Parse error: {}", e);
+        },
+        // Stream the file, keeping one record in every `n`
+        (Some(n), _) => {
+            let mut builder = LogBuilder::new().with_decimation(n);
+            match detector.parse_stream(&input, &mut builder, &mut |_| {}) {
+                Some(result) => result.map(|()| builder.finish()),
+                None => {
+                    eprintln!("{} has no streaming parser to decimate with", detector.name);
                     std::process::exit(1);
                 }
             }
         }
+        (None, _) => detector.parse(&input),
+    };
+    let (ecu_type, log) = match parsed {
+        Ok(log) => (detector.ecu_type, log),
+        Err(e) => {
+            eprintln!("Parse error ({}): {}", e.kind(), e);
+            std::process::exit(1);
+        }
     };
 
     println!("\n=== Parse Results ===");
     println!("ECU Type: {}", ecu_type.name());
     println!("Channels: {}", log.channels.len());
-    println!("Data points: {}", log.data.len());
+    println!("Data points: {}", log.record_count());
     println!(
         "Time range: {:.3} to {:.3} seconds",
         log.times.first().unwrap_or(&0.0),
         log.times.last().unwrap_or(&0.0)
     );
 
+    if let Some(start) = log.start {
+        println!("Started: {}", start.format("%Y-%m-%d %H:%M:%S%.3f"));
+    }
+
+    if let Some(report) = &log.recovery {
+        println!("\n=== Recovery ===");
+        println!("Skipped records: {}", report.skipped_records);
+        println!("Resyncs: {}", report.resyncs);
+        if let Some(offset) = report.first_error_offset {
+            println!("First damage at byte: {}", offset);
+        }
+    }
+
+    if !log.annotations.is_empty() {
+        println!("\n=== Markers ===");
+        for annotation in &log.annotations {
+            println!("  {:>10.3}s  {}", annotation.time, annotation.label);
+        }
+    }
+
+    let info = log.info();
+    println!("\n=== Log Info ===");
+    println!("  {}", info.summary());
+    for (label, value) in &info.vendor {
+        println!("  {}: {}", label, value);
+    }
+
     println!("\n=== First 15 Channels (with units) ===");
     for (i, channel) in log.channels.iter().take(15).enumerate() {
         let unit = channel.unit();
@@ -140,13 +221,14 @@ fn main() {
     println!("  Time      | {}", header.join(" | "));
     println!("  ----------+-{}", ["-------------"; 8].join("-+-"));
 
-    for (time, row) in log.times.iter().zip(log.data.iter()).take(5) {
-        let values: Vec<String> = row
+    for (record, time) in log.times.iter().enumerate().take(5) {
+        let values: Vec<String> = log
+            .columns()
             .iter()
             .zip(log.channels.iter())
             .take(8)
-            .map(|(v, c)| {
-                let val = v.as_f64();
+            .map(|(column, c)| {
+                let val = column[record];
                 let unit = c.unit();
                 if unit.is_empty() {
                     format!("{:>12.2}", val)
@@ -160,7 +242,7 @@ fn main() {
 
     // Show some key channels with values
     println!("\n=== Key Channel Values (first data point) ===");
-    if let Some(first_row) = log.data.first() {
+    if log.record_count() > 0 {
         for (i, channel) in log.channels.iter().enumerate() {
             let name = channel.name().to_lowercase();
             // Show specific interesting channels
@@ -180,9 +262,9 @@ fn main() {
                 || name.contains("speed")
                 || name.contains("temp")
             {
-                if let Some(value) = first_row.get(i) {
+                if let Some(value) = log.value(i, 0) {
                     let unit = channel.unit();
-                    println!("  {}: {:.2} {}", channel.name(), value.as_f64(), unit);
+                    println!("  {}: {:.2} {}", channel.name(), value, unit);
                 }
             }
         }
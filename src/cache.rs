@@ -0,0 +1,456 @@
+//! On-disk cache of parsed logs.
+//!
+//! Parsing a large CSV or binary log can take seconds. After a successful
+//! parse the loader writes the [`Log`] to a cache file under the config
+//! directory, and the next open of the same file reads that instead.
+//!
+//! A cache file is named after a hash of the source file's contents and
+//! also records its size and modification time, along with the UltraLog
+//! version that parsed it; an entry whose source no longer matches, or that
+//! an older or newer parser wrote, is ignored and overwritten. The layout is columnar so it
+//! can be read straight out of a memory map:
+//!
+//! ```text
+//! magic "ULCACHE\0" | version u32 | 0u32
+//! times, then each channel column, then each native series (f64 LE)
+//! footer: JSON with channels, units, metadata and computed channels
+//! trailer: footer offset u64 | footer length u64 | magic
+//! ```
+//!
+//! The footer sits at the end so the computed-channel definitions can be
+//! rewritten by copying the sample data across unchanged. Entries are only
+//! ever replaced by rename, never modified in place, so another instance
+//! with the old entry mapped keeps reading a complete file.
+
+use memmap2::Mmap;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::computed::ComputedChannelTemplate;
+use crate::parsers::aem::AemChannel;
+use crate::parsers::aim::AimChannel;
+use crate::parsers::bluedriver::BlueDriverChannel;
+use crate::parsers::can_trace::CanSignalChannel;
+use crate::parsers::ecumaster::EcuMasterChannel;
+use crate::parsers::emerald::EmeraldChannel;
+use crate::parsers::haltech::HaltechChannel;
+use crate::parsers::link::LinkChannel;
+use crate::parsers::locomotive::LocomotiveChannel;
+use crate::parsers::maxxecu::MaxxEcuChannel;
+use crate::parsers::megasquirt::MegaSquirtChannel;
+use crate::parsers::motec::MotecChannel;
+use crate::parsers::romraider::RomRaiderChannel;
+use crate::parsers::spec_csv::SpecCsvChannel;
+use crate::parsers::speeduino::SpeeduinoChannel;
+use crate::parsers::types::{
+    Annotation, BitChannel, ComputedChannelInfo, Meta, NativeSeries, RecoveryReport,
+};
+use crate::parsers::{Channel, EcuType, Log};
+use crate::settings::UserSettings;
+
+/// Signature at the start and end of every cache file
+const MAGIC: &[u8; 8] = b"ULCACHE\0";
+
+/// Bumped whenever the layout or any cached type changes shape
+const VERSION: u32 = 2;
+
+/// Version of the parsers that wrote an entry. Parser fixes change what a
+/// file parses to, so entries from any other release are parsed again.
+const PARSER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Bytes before the first column: magic, version and padding
+const PREAMBLE_BYTES: u64 = 16;
+
+/// Bytes after the footer: its offset, its length and the magic
+const TRAILER_BYTES: usize = 24;
+
+/// File extension of cache entries
+const EXTENSION: &str = "ulcache";
+
+/// Files smaller than this parse quickly enough not to be worth caching
+pub const MIN_CACHED_BYTES: u64 = 1024 * 1024;
+
+/// Total size the cache directory is trimmed to, oldest entries first
+pub const MAX_CACHE_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Identifies the source a cache entry was built from
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SourceKey {
+    /// 64-bit FNV-1a hash of the file contents (and archive entry name)
+    pub hash: u64,
+    /// Source file size in bytes
+    pub size: u64,
+    /// Source modification time, nanoseconds since the Unix epoch
+    pub modified: u64,
+}
+
+impl SourceKey {
+    /// Key for a source file's bytes, or for one entry inside an archive
+    pub fn new(data: &[u8], entry: Option<&str>, modified: SystemTime) -> Self {
+        let mut hash = fnv1a(FNV_OFFSET, data);
+        if let Some(entry) = entry {
+            hash = fnv1a(hash, entry.as_bytes());
+        }
+        Self {
+            hash,
+            size: data.len() as u64,
+            modified: modified
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(0),
+        }
+    }
+
+    /// Key for a file on disk, hashing it through a memory map
+    pub fn for_file(path: &Path, entry: Option<&str>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let modified = file.metadata()?.modified()?;
+        // SAFETY: the map is only read while hashing; a file changed under
+        // us gives a key that won't match on the next open
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(Self::new(&mmap, entry, modified))
+    }
+
+    fn file_name(&self) -> String {
+        format!("{:016x}.{}", self.hash, EXTENSION)
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a over 8-byte words (then any tail bytes), which keeps hashing a
+/// large log well under the time it takes to parse it
+fn fnv1a(mut hash: u64, data: &[u8]) -> u64 {
+    let mut words = data.chunks_exact(8);
+    for word in &mut words {
+        hash ^= u64::from_le_bytes(word.try_into().expect("8-byte chunk"));
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    for &byte in words.remainder() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// A log read back from the cache
+pub struct CachedLog {
+    pub log: Log,
+    pub ecu_type: EcuType,
+    /// Computed channels that were applied to the log when it was last open
+    pub computed: Vec<ComputedChannelTemplate>,
+}
+
+/// Directory cache files are kept in
+pub fn cache_dir() -> Option<PathBuf> {
+    UserSettings::get_config_dir().map(|dir| dir.join("cache"))
+}
+
+/// Format-tagged channel, since [`Channel`]'s own serialization is untagged
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "Channel")]
+enum ChannelDef {
+    Aem(AemChannel),
+    Aim(AimChannel),
+    BlueDriver(BlueDriverChannel),
+    CanSignal(CanSignalChannel),
+    Emerald(EmeraldChannel),
+    Haltech(HaltechChannel),
+    EcuMaster(EcuMasterChannel),
+    Link(LinkChannel),
+    Locomotive(LocomotiveChannel),
+    MaxxEcu(MaxxEcuChannel),
+    MegaSquirt(MegaSquirtChannel),
+    Motec(MotecChannel),
+    RomRaider(RomRaiderChannel),
+    SpecCsv(SpecCsvChannel),
+    Speeduino(SpeeduinoChannel),
+    Computed(ComputedChannelInfo),
+    Bit(BitChannel),
+}
+
+#[derive(Deserialize, Serialize)]
+struct StoredChannel(#[serde(with = "ChannelDef")] Channel);
+
+/// Everything in a cache file except the sample data
+#[derive(Deserialize, Serialize)]
+struct Footer {
+    source: SourceKey,
+    parser_version: String,
+    ecu_type: EcuType,
+    meta: Meta,
+    channels: Vec<StoredChannel>,
+    record_count: usize,
+    /// Sample count of each channel's native series, if it has one
+    native: Vec<Option<usize>>,
+    start: Option<chrono::NaiveDateTime>,
+    annotations: Vec<Annotation>,
+    recovery: Option<RecoveryReport>,
+    computed: Vec<ComputedChannelTemplate>,
+}
+
+/// Write `log` to the cache in `dir`, replacing any older entry for the
+/// same source
+pub fn store(dir: &Path, key: &SourceKey, log: &Log, ecu_type: EcuType) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(key.file_name());
+    // Write beside the entry and rename so a reader never sees half a file
+    let partial = path.with_extension("partial");
+    let mut writer = BufWriter::new(File::create(&partial)?);
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&0u32.to_le_bytes())?;
+
+    write_f64s(&mut writer, &log.times)?;
+    for column in log.columns() {
+        write_f64s(&mut writer, column)?;
+    }
+    let mut native = Vec::with_capacity(log.channels.len());
+    for index in 0..log.channels.len() {
+        let series = log.native_series(index);
+        if let Some(series) = series {
+            write_f64s(&mut writer, &series.times)?;
+            write_f64s(&mut writer, &series.values)?;
+        }
+        native.push(series.map(|s| s.times.len()));
+    }
+
+    let footer = Footer {
+        source: key.clone(),
+        parser_version: PARSER_VERSION.to_string(),
+        ecu_type,
+        meta: log.meta.clone(),
+        channels: log.channels.iter().cloned().map(StoredChannel).collect(),
+        record_count: log.record_count(),
+        native,
+        start: log.start,
+        annotations: log.annotations.clone(),
+        recovery: log.recovery.clone(),
+        computed: Vec::new(),
+    };
+    let offset = data_bytes(&footer);
+    write_footer(&mut writer, offset, &footer)?;
+    writer
+        .into_inner()
+        .map_err(|e| e.into_error())?
+        .sync_all()?;
+
+    fs::rename(&partial, &path)?;
+    Ok(path)
+}
+
+/// Read the cached log for `key` from `dir`, if there is a current one
+pub fn load(dir: &Path, key: &SourceKey) -> Option<CachedLog> {
+    let path = dir.join(key.file_name());
+    let file = File::open(&path).ok()?;
+    // SAFETY: cache files are only replaced by rename, never written in place
+    let mmap = unsafe { Mmap::map(&file) }.ok()?;
+
+    match read_cache(&mmap, key) {
+        Ok(cached) => Some(cached),
+        Err(e) => {
+            tracing::debug!("Ignoring cache entry {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Replace the computed-channel definitions stored with a cached log
+pub fn store_computed(
+    dir: &Path,
+    key: &SourceKey,
+    computed: &[ComputedChannelTemplate],
+) -> io::Result<()> {
+    let path = dir.join(key.file_name());
+    let mut file = File::open(&path)?;
+    let file_len = file.metadata()?.len();
+    if file_len < PREAMBLE_BYTES + TRAILER_BYTES as u64 {
+        return Err(invalid("cache file is incomplete"));
+    }
+
+    let mut trailer = [0u8; TRAILER_BYTES];
+    file.seek(SeekFrom::End(-(TRAILER_BYTES as i64)))?;
+    file.read_exact(&mut trailer)?;
+    let (offset, footer_len) = parse_trailer(&trailer, file_len)?;
+
+    let mut json = vec![0u8; footer_len as usize];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut json)?;
+    let mut footer: Footer = serde_json::from_slice(&json)?;
+    if footer.source != *key {
+        return Err(invalid(
+            "cache entry belongs to another version of the file",
+        ));
+    }
+    footer.computed = computed.to_vec();
+
+    // Copy the samples into a new entry and rename it over the old one, so
+    // a reader with the old entry mapped never sees it shrink
+    let partial = path.with_extension("partial");
+    let mut writer = BufWriter::new(File::create(&partial)?);
+    file.seek(SeekFrom::Start(0))?;
+    let copied = io::copy(&mut (&mut file).take(offset), &mut writer)?;
+    if copied != offset {
+        return Err(invalid("cache file is incomplete"));
+    }
+    write_footer(&mut writer, offset, &footer)?;
+    writer
+        .into_inner()
+        .map_err(|e| e.into_error())?
+        .sync_all()?;
+
+    fs::rename(&partial, &path)
+}
+
+/// Delete the oldest cache entries until the directory holds at most
+/// `max_bytes`
+pub fn prune(dir: &Path, max_bytes: u64) -> io::Result<()> {
+    let mut entries: Vec<(SystemTime, u64, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == EXTENSION))
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            Some((meta.modified().ok()?, meta.len(), entry.path()))
+        })
+        .collect();
+
+    let mut total: u64 = entries.iter().map(|(_, len, _)| len).sum();
+    entries.sort_by_key(|(modified, _, _)| *modified);
+    for (_, len, path) in entries {
+        if total <= max_bytes {
+            break;
+        }
+        fs::remove_file(&path)?;
+        total -= len;
+    }
+    Ok(())
+}
+
+fn read_cache(bytes: &[u8], key: &SourceKey) -> io::Result<CachedLog> {
+    if bytes.len() < PREAMBLE_BYTES as usize + TRAILER_BYTES || !bytes.starts_with(MAGIC) {
+        return Err(invalid("not a cache file"));
+    }
+    let version = u32::from_le_bytes(bytes[8..12].try_into().expect("4 bytes"));
+    if version != VERSION {
+        return Err(invalid(format!("cache version {}", version)));
+    }
+
+    let (offset, footer) = read_footer(bytes)?;
+    if footer.source != *key {
+        return Err(invalid("source file has changed"));
+    }
+    if footer.parser_version != PARSER_VERSION {
+        return Err(invalid(format!(
+            "parsed by UltraLog {}",
+            footer.parser_version
+        )));
+    }
+    if data_bytes(&footer) != offset {
+        return Err(invalid("sample data doesn't match the footer"));
+    }
+
+    let mut data = &bytes[PREAMBLE_BYTES as usize..offset as usize];
+    let mut take = |count: usize| {
+        let (head, rest) = data.split_at(count * 8);
+        data = rest;
+        read_f64s(head)
+    };
+
+    let times = take(footer.record_count);
+    let columns: Vec<Vec<f64>> = footer
+        .channels
+        .iter()
+        .map(|_| take(footer.record_count))
+        .collect();
+    let native: Vec<Option<NativeSeries>> = footer
+        .native
+        .iter()
+        .map(|len| {
+            len.map(|len| NativeSeries {
+                times: take(len),
+                values: take(len),
+            })
+        })
+        .collect();
+
+    let channels = footer.channels.into_iter().map(|c| c.0).collect();
+    let mut log = Log::new(footer.meta, channels, times, columns);
+    for (index, series) in native.into_iter().enumerate() {
+        if let Some(series) = series {
+            log.set_native_series(index, series);
+        }
+    }
+    log.start = footer.start;
+    log.annotations = footer.annotations;
+    log.recovery = footer.recovery;
+
+    Ok(CachedLog {
+        log,
+        ecu_type: footer.ecu_type,
+        computed: footer.computed,
+    })
+}
+
+/// Offset of the footer and its contents
+fn read_footer(bytes: &[u8]) -> io::Result<(u64, Footer)> {
+    let len = bytes.len();
+    if len < PREAMBLE_BYTES as usize + TRAILER_BYTES {
+        return Err(invalid("cache file is incomplete"));
+    }
+    let (offset, footer_len) = parse_trailer(&bytes[len - TRAILER_BYTES..], len as u64)?;
+    let footer = serde_json::from_slice(&bytes[offset as usize..(offset + footer_len) as usize])?;
+    Ok((offset, footer))
+}
+
+/// Footer offset and length from the trailer of a file `file_len` bytes long
+fn parse_trailer(trailer: &[u8], file_len: u64) -> io::Result<(u64, u64)> {
+    if !trailer.ends_with(MAGIC) {
+        return Err(invalid("cache file is incomplete"));
+    }
+    let offset = u64::from_le_bytes(trailer[0..8].try_into().expect("8 bytes"));
+    let footer_len = u64::from_le_bytes(trailer[8..16].try_into().expect("8 bytes"));
+    let in_place = offset >= PREAMBLE_BYTES
+        && offset.checked_add(footer_len) == Some(file_len - TRAILER_BYTES as u64);
+    if !in_place {
+        return Err(invalid("cache footer is out of place"));
+    }
+    Ok((offset, footer_len))
+}
+
+fn write_footer<W: Write>(writer: &mut W, offset: u64, footer: &Footer) -> io::Result<()> {
+    let json = serde_json::to_vec(footer)?;
+    writer.write_all(&json)?;
+    writer.write_all(&offset.to_le_bytes())?;
+    writer.write_all(&(json.len() as u64).to_le_bytes())?;
+    writer.write_all(MAGIC)
+}
+
+/// Where the footer starts for the sample data it describes
+fn data_bytes(footer: &Footer) -> u64 {
+    let columns = (1 + footer.channels.len()) * footer.record_count;
+    let native: usize = footer.native.iter().flatten().map(|len| len * 2).sum();
+    PREAMBLE_BYTES + ((columns + native) * 8) as u64
+}
+
+fn write_f64s<W: Write>(writer: &mut W, values: &[f64]) -> io::Result<()> {
+    for value in values {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn read_f64s(bytes: &[u8]) -> Vec<f64> {
+    bytes
+        .chunks_exact(8)
+        .map(|b| f64::from_le_bytes(b.try_into().expect("8-byte chunk")))
+        .collect()
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
@@ -0,0 +1,189 @@
+//! Transparent decompression of compressed log files.
+//!
+//! Some loggers write compressed files directly (ECUMaster PRO `.emuprolog.gz`,
+//! EMU Black `.emublog`), and logs are often shared inside zip archives. This
+//! module unwraps those containers so the normal format detection can run on
+//! the inner file.
+
+use flate2::read::GzDecoder;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use crate::state::SUPPORTED_EXTENSIONS;
+
+/// Container formats recognised by their magic bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zip,
+}
+
+/// Detect a compressed container from its magic bytes
+pub fn detect_compression(data: &[u8]) -> Option<Compression> {
+    if data.starts_with(&[0x1F, 0x8B]) {
+        Some(Compression::Gzip)
+    } else if data.starts_with(b"PK\x03\x04") || data.starts_with(b"PK\x05\x06") {
+        Some(Compression::Zip)
+    } else {
+        None
+    }
+}
+
+/// Decompress a gzip stream into memory
+pub fn decompress_gzip(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decompressed = Vec::new();
+    GzDecoder::new(data)
+        .read_to_end(&mut decompressed)
+        .map_err(|e| format!("Failed to decompress gzip file: {}", e))?;
+    Ok(decompressed)
+}
+
+/// Path of the file inside a gzip stream (e.g. `log.emuprolog.gz` -> `log.emuprolog`)
+pub fn gzip_inner_path(path: &Path) -> PathBuf {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("gz") => path.with_extension(""),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// List the log files inside a zip archive.
+///
+/// Entries with a supported log extension (or a `.gz` wrapper) are preferred.
+/// If none match, every regular file is returned so unusual extensions can
+/// still be opened. macOS resource fork entries are always skipped.
+pub fn zip_log_entries(data: &[u8]) -> Result<Vec<String>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))
+        .map_err(|e| format!("Failed to open zip archive: {}", e))?;
+
+    let mut files = Vec::new();
+    for i in 0..archive.len() {
+        let entry = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read zip entry: {}", e))?;
+        let name = entry.name().to_string();
+        let file_name = name.rsplit('/').next().unwrap_or(&name);
+
+        if entry.is_dir() || name.starts_with("__MACOSX/") || file_name.starts_with("._") {
+            continue;
+        }
+        files.push(name);
+    }
+
+    let logs: Vec<String> = files
+        .iter()
+        .filter(|name| is_log_file_name(name))
+        .cloned()
+        .collect();
+
+    Ok(if logs.is_empty() { files } else { logs })
+}
+
+/// Extract a single named entry from a zip archive
+pub fn extract_zip_entry(data: &[u8], name: &str) -> Result<Vec<u8>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))
+        .map_err(|e| format!("Failed to open zip archive: {}", e))?;
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|e| format!("Failed to find '{}' in zip archive: {}", name, e))?;
+
+    let mut contents = Vec::with_capacity(entry.size() as usize);
+    entry
+        .read_to_end(&mut contents)
+        .map_err(|e| format!("Failed to extract '{}' from zip archive: {}", name, e))?;
+    Ok(contents)
+}
+
+/// Check whether an archive entry name looks like a log file we can open
+fn is_log_file_name(name: &str) -> bool {
+    let path = Path::new(name);
+    let path = gzip_inner_path(path);
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|ext| {
+            let ext = ext.to_lowercase();
+            // Nested archives can't be selected from the picker
+            ext != "zip" && (SUPPORTED_EXTENSIONS.contains(&ext.as_str()) || ext == "emuprolog")
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in entries {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_detect_compression() {
+        assert_eq!(detect_compression(&gzip(b"x")), Some(Compression::Gzip));
+        assert_eq!(
+            detect_compression(&zip(&[("a.csv", b"x")])),
+            Some(Compression::Zip)
+        );
+        assert_eq!(detect_compression(b"TIME;engine/rpm"), None);
+        assert_eq!(detect_compression(&[]), None);
+    }
+
+    #[test]
+    fn test_gzip_round_trip() {
+        let data = b"TIME;engine/rpm\n0.0;1000\n";
+        assert_eq!(decompress_gzip(&gzip(data)).unwrap(), data);
+        assert!(decompress_gzip(&[0x1F, 0x8B, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_gzip_inner_path() {
+        assert_eq!(
+            gzip_inner_path(Path::new("logs/run.emuprolog.gz")),
+            PathBuf::from("logs/run.emuprolog")
+        );
+        assert_eq!(
+            gzip_inner_path(Path::new("run.emublog")),
+            PathBuf::from("run.emublog")
+        );
+    }
+
+    #[test]
+    fn test_zip_log_entries_prefers_logs() {
+        let archive = zip(&[
+            ("readme.pdf", b"x"),
+            ("logs/run1.csv", b"x"),
+            ("__MACOSX/logs/._run1.csv", b"x"),
+            ("logs/run2.mlg", b"x"),
+        ]);
+        assert_eq!(
+            zip_log_entries(&archive).unwrap(),
+            vec!["logs/run1.csv".to_string(), "logs/run2.mlg".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_zip_log_entries_falls_back_to_all_files() {
+        let archive = zip(&[("export.dat", b"x")]);
+        assert_eq!(zip_log_entries(&archive).unwrap(), vec!["export.dat"]);
+    }
+
+    #[test]
+    fn test_extract_zip_entry() {
+        let archive = zip(&[("a.csv", b"first"), ("b.csv", b"second")]);
+        assert_eq!(extract_zip_entry(&archive, "b.csv").unwrap(), b"second");
+        assert!(extract_zip_entry(&archive, "missing.csv").is_err());
+    }
+}
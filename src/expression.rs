@@ -5,7 +5,7 @@
 //! and pre-computed channel statistics for anomaly detection.
 
 use crate::computed::{ChannelReference, TimeShift};
-use crate::parsers::types::Value;
+use crate::parsers::types::{is_missing, MISSING};
 use meval::{Context, Expr};
 use regex::Regex;
 use std::collections::HashMap;
@@ -27,19 +27,14 @@ pub struct ChannelStatistics {
 }
 
 /// Compute statistics for a single channel
-pub fn compute_channel_statistics(
-    channel_idx: usize,
-    log_data: &[Vec<Value>],
-) -> ChannelStatistics {
-    if log_data.is_empty() {
+///
+/// `columns` holds the log's per-channel samples (`columns[channel][record]`).
+pub fn compute_channel_statistics(channel_idx: usize, columns: &[Vec<f64>]) -> ChannelStatistics {
+    let Some(column) = columns.get(channel_idx) else {
         return ChannelStatistics::default();
-    }
+    };
 
-    let values: Vec<f64> = log_data
-        .iter()
-        .filter_map(|row| row.get(channel_idx).map(|v| v.as_f64()))
-        .filter(|v| v.is_finite())
-        .collect();
+    let values: Vec<f64> = column.iter().copied().filter(|v| v.is_finite()).collect();
 
     if values.is_empty() {
         return ChannelStatistics::default();
@@ -67,11 +62,11 @@ pub fn compute_channel_statistics(
 /// Compute statistics for all channels in a log
 pub fn compute_all_channel_statistics(
     channel_names: &[String],
-    log_data: &[Vec<Value>],
+    columns: &[Vec<f64>],
 ) -> HashMap<String, ChannelStatistics> {
     let mut stats = HashMap::new();
     for (idx, name) in channel_names.iter().enumerate() {
-        stats.insert(name.clone(), compute_channel_statistics(idx, log_data));
+        stats.insert(name.clone(), compute_channel_statistics(idx, columns));
     }
     stats
 }
@@ -160,7 +155,7 @@ pub fn extract_channel_references(formula: &str) -> Vec<ChannelReference> {
     }
 
     // Deduplicate by full_match
-    references.sort_by(|a, b| b.full_match.len().cmp(&a.full_match.len())); // Sort by length descending
+    references.sort_by_key(|r| std::cmp::Reverse(r.full_match.len())); // Sort by length descending
     let mut seen = std::collections::HashSet::new();
     references.retain(|r| seen.insert(r.full_match.clone()));
 
@@ -246,7 +241,7 @@ fn prepare_formula_for_meval(formula: &str, refs: &[ChannelReference]) -> String
 
     // Sort refs by length (longest first) to avoid partial replacements
     let mut sorted_refs: Vec<_> = refs.iter().collect();
-    sorted_refs.sort_by(|a, b| b.full_match.len().cmp(&a.full_match.len()));
+    sorted_refs.sort_by_key(|r| std::cmp::Reverse(r.full_match.len()));
 
     for r in sorted_refs {
         let var_name = sanitize_var_name(&r.full_match);
@@ -298,15 +293,18 @@ pub fn build_channel_bindings(
 
 /// Evaluate a formula for all records in the log
 ///
+/// `columns` holds the log's per-channel samples (`columns[channel][record]`)
+/// and `times` has one entry per record.
+///
 /// If `statistics` is provided, injects statistical variables for each channel:
 /// - `_mean_ChannelName`, `_stdev_ChannelName`, `_min_ChannelName`, `_max_ChannelName`, `_range_ChannelName`
 pub fn evaluate_all_records(
     formula: &str,
     bindings: &HashMap<String, usize>,
-    log_data: &[Vec<Value>],
+    columns: &[Vec<f64>],
     times: &[f64],
 ) -> Result<Vec<f64>, String> {
-    evaluate_all_records_with_stats(formula, bindings, log_data, times, None)
+    evaluate_all_records_with_stats(formula, bindings, columns, times, None)
 }
 
 /// Evaluate a formula for all records in the log with optional pre-computed statistics
@@ -316,11 +314,11 @@ pub fn evaluate_all_records(
 pub fn evaluate_all_records_with_stats(
     formula: &str,
     bindings: &HashMap<String, usize>,
-    log_data: &[Vec<Value>],
+    columns: &[Vec<f64>],
     times: &[f64],
     statistics: Option<&HashMap<String, ChannelStatistics>>,
 ) -> Result<Vec<f64>, String> {
-    if log_data.is_empty() {
+    if times.is_empty() {
         return Ok(Vec::new());
     }
 
@@ -332,20 +330,28 @@ pub fn evaluate_all_records_with_stats(
         .parse()
         .map_err(|e| format!("Parse error: {}", e))?;
 
-    let num_records = log_data.len();
+    let num_records = times.len();
     let mut results = Vec::with_capacity(num_records);
 
     for record_idx in 0..num_records {
         let mut ctx = Context::new();
 
         // Set each channel variable to its value at the appropriate record
+        let mut has_gap = false;
         for r in &refs {
             let channel_idx = bindings.get(&r.name).copied().unwrap_or(0);
-            let value = get_shifted_value(record_idx, &r.time_shift, channel_idx, log_data, times);
+            let value = get_shifted_value(record_idx, &r.time_shift, channel_idx, columns, times);
+            has_gap |= is_missing(value);
             let var_name = sanitize_var_name(&r.full_match);
             ctx.var(&var_name, value);
         }
 
+        // A record with a missing input has no computed value either
+        if has_gap {
+            results.push(MISSING);
+            continue;
+        }
+
         // Inject statistics variables if provided
         if let Some(stats) = statistics {
             for (channel_name, channel_stats) in stats {
@@ -381,7 +387,7 @@ fn get_shifted_value(
     record_index: usize,
     time_shift: &TimeShift,
     channel_index: usize,
-    log_data: &[Vec<Value>],
+    columns: &[Vec<f64>],
     times: &[f64],
 ) -> f64 {
     let target_idx = match time_shift {
@@ -389,7 +395,7 @@ fn get_shifted_value(
 
         TimeShift::IndexOffset(offset) => {
             let target = record_index as i64 + *offset as i64;
-            target.clamp(0, log_data.len().saturating_sub(1) as i64) as usize
+            target.clamp(0, times.len().saturating_sub(1) as i64) as usize
         }
 
         TimeShift::TimeOffset(seconds) => {
@@ -399,10 +405,10 @@ fn get_shifted_value(
         }
     };
 
-    log_data
-        .get(target_idx)
-        .and_then(|row| row.get(channel_index))
-        .map(|v| v.as_f64())
+    columns
+        .get(channel_index)
+        .and_then(|column| column.get(target_idx))
+        .copied()
         .unwrap_or(0.0)
 }
 
@@ -448,11 +454,11 @@ fn find_record_at_time(times: &[f64], target_time: f64) -> usize {
 pub fn generate_preview(
     formula: &str,
     bindings: &HashMap<String, usize>,
-    log_data: &[Vec<Value>],
+    columns: &[Vec<f64>],
     times: &[f64],
     count: usize,
 ) -> Result<Vec<f64>, String> {
-    let all_values = evaluate_all_records(formula, bindings, log_data, times)?;
+    let all_values = evaluate_all_records(formula, bindings, columns, times)?;
     Ok(all_values.into_iter().take(count).collect())
 }
 
@@ -529,11 +535,7 @@ mod tests {
 
     #[test]
     fn test_evaluate_simple() {
-        let data = vec![
-            vec![Value::Float(1000.0), Value::Float(10.0)],
-            vec![Value::Float(2000.0), Value::Float(20.0)],
-            vec![Value::Float(3000.0), Value::Float(30.0)],
-        ];
+        let data = vec![vec![1000.0, 2000.0, 3000.0], vec![10.0, 20.0, 30.0]];
         let times = vec![0.0, 0.1, 0.2];
         let mut bindings = HashMap::new();
         bindings.insert("RPM".to_string(), 0);
@@ -546,13 +548,23 @@ mod tests {
         assert_eq!(result[2], 3030.0);
     }
 
+    #[test]
+    fn test_evaluate_propagates_missing() {
+        let data = vec![vec![1000.0, MISSING, 3000.0], vec![10.0, 20.0, MISSING]];
+        let times = vec![0.0, 0.1, 0.2];
+        let mut bindings = HashMap::new();
+        bindings.insert("RPM".to_string(), 0);
+        bindings.insert("Boost".to_string(), 1);
+
+        let result = evaluate_all_records("RPM + Boost", &bindings, &data, &times).unwrap();
+        assert_eq!(result[0], 1010.0);
+        assert!(is_missing(result[1]));
+        assert!(is_missing(result[2]));
+    }
+
     #[test]
     fn test_evaluate_with_index_offset() {
-        let data = vec![
-            vec![Value::Float(1000.0)],
-            vec![Value::Float(2000.0)],
-            vec![Value::Float(3000.0)],
-        ];
+        let data = vec![vec![1000.0, 2000.0, 3000.0]];
         let times = vec![0.0, 0.1, 0.2];
         let mut bindings = HashMap::new();
         bindings.insert("RPM".to_string(), 0);
@@ -7,6 +7,9 @@
 //!
 //! - [`adapters`] - OpenECU Alliance adapter specs for channel normalization
 //! - [`app`] - Main application state and eframe::App implementation
+//! - [`cache`] - On-disk cache of parsed logs for fast re-opening
+//! - [`decompress`] - Transparent gzip/zip unwrapping ahead of format detection
+//! - [`live`] - Live CAN streaming from SocketCAN interfaces
 //! - [`parsers`] - ECU log file parsers (Haltech, etc.)
 //! - [`state`] - Core data types and constants
 //! - [`units`] - Unit preference types and conversion utilities
@@ -36,9 +39,12 @@ pub mod adapters;
 pub mod analysis;
 pub mod analytics;
 pub mod app;
+pub mod cache;
 pub mod computed;
+pub mod decompress;
 pub mod expression;
 pub mod i18n;
+pub mod live;
 pub mod normalize;
 pub mod parsers;
 pub mod settings;
@@ -0,0 +1,264 @@
+//! Live CAN bus streaming.
+//!
+//! A [`LiveStream`] reads frames from a bus on a background thread. The UI
+//! drains them every frame and decodes them into a growing log with
+//! [`CanStream`](crate::parsers::CanStream). While recording, every frame is
+//! also written to disk as a candump log, which opens again later like any
+//! other CAN trace.
+//!
+//! Buses are read through Linux SocketCAN; a virtual interface (`vcan0`)
+//! works for testing without hardware:
+//!
+//! ```text
+//! sudo modprobe vcan
+//! sudo ip link add dev vcan0 type vcan
+//! sudo ip link set up vcan0
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::parsers::can_trace::{candump_line, TraceFrame};
+
+#[cfg(target_os = "linux")]
+pub mod socketcan;
+
+/// Interface offered when the user hasn't picked one
+pub const DEFAULT_INTERFACE: &str = "vcan0";
+
+/// How often the UI drains a live stream while frames are arriving
+pub const REFRESH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long the reader waits for a frame before checking for a stop request
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Errors from opening a live source
+#[derive(Debug, Error)]
+pub enum LiveError {
+    /// The platform has no SocketCAN
+    #[error("Live CAN streaming needs Linux SocketCAN")]
+    Unsupported,
+
+    /// The interface couldn't be opened
+    #[error("Failed to open {interface}: {source}")]
+    Open {
+        interface: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// A source of timestamped frames, read on the stream's background thread
+pub trait FrameSource: Send + 'static {
+    /// Wait up to `timeout` for the next frame, returning `None` if none came
+    fn read_frame(&mut self, timeout: Duration) -> io::Result<Option<TraceFrame>>;
+}
+
+/// Something the reader thread reports
+#[derive(Debug)]
+pub enum LiveEvent {
+    /// A frame arrived; times are seconds since the Unix epoch
+    Frame(TraceFrame),
+    /// Writing the recording failed, so recording stopped
+    RecordingFailed(String),
+    /// Reading the bus failed, so the stream stopped
+    Error(String),
+}
+
+/// A finished recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recording {
+    pub path: PathBuf,
+    /// Frames written
+    pub frames: usize,
+}
+
+/// Writes frames to disk as a candump log
+pub struct Recorder {
+    path: PathBuf,
+    interface: String,
+    out: BufWriter<File>,
+    frames: usize,
+}
+
+impl Recorder {
+    /// Create (or truncate) the file at `path`
+    pub fn create(path: &Path, interface: &str) -> io::Result<Self> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            interface: interface.to_string(),
+            out: BufWriter::new(File::create(path)?),
+            frames: 0,
+        })
+    }
+
+    /// Append a frame
+    pub fn write(&mut self, frame: &TraceFrame) -> io::Result<()> {
+        writeln!(self.out, "{}", candump_line(&self.interface, frame))?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Flush the file and close it
+    pub fn finish(mut self) -> io::Result<Recording> {
+        self.out.flush()?;
+        Ok(Recording {
+            path: self.path,
+            frames: self.frames,
+        })
+    }
+}
+
+/// Frames streaming from a bus interface
+pub struct LiveStream {
+    interface: String,
+    receiver: Receiver<LiveEvent>,
+    stop: Arc<AtomicBool>,
+    recorder: Arc<Mutex<Option<Recorder>>>,
+    reader: Option<JoinHandle<()>>,
+}
+
+impl LiveStream {
+    /// Open a SocketCAN interface (e.g. `can0` or `vcan0`) and start reading
+    #[cfg(target_os = "linux")]
+    pub fn open(interface: &str) -> Result<Self, LiveError> {
+        let socket = socketcan::SocketCan::open(interface).map_err(|source| LiveError::Open {
+            interface: interface.to_string(),
+            source,
+        })?;
+        tracing::info!("Streaming CAN frames from {}", interface);
+        Ok(Self::start(interface, socket))
+    }
+
+    /// Open a SocketCAN interface (e.g. `can0` or `vcan0`) and start reading
+    #[cfg(not(target_os = "linux"))]
+    pub fn open(_interface: &str) -> Result<Self, LiveError> {
+        Err(LiveError::Unsupported)
+    }
+
+    /// Start reading frames from `source` on a background thread
+    pub fn start(interface: &str, source: impl FrameSource) -> Self {
+        let (sender, receiver) = channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let recorder: Arc<Mutex<Option<Recorder>>> = Arc::new(Mutex::new(None));
+
+        let reader = {
+            let stop = Arc::clone(&stop);
+            let recorder = Arc::clone(&recorder);
+            thread::spawn(move || read_frames(source, sender, &stop, &recorder))
+        };
+
+        Self {
+            interface: interface.to_string(),
+            receiver,
+            stop,
+            recorder,
+            reader: Some(reader),
+        }
+    }
+
+    /// Name of the interface frames come from
+    pub fn interface(&self) -> &str {
+        &self.interface
+    }
+
+    /// Events received since the last call, oldest first
+    pub fn poll(&self) -> Vec<LiveEvent> {
+        self.receiver.try_iter().collect()
+    }
+
+    /// Whether the reader is still running
+    pub fn is_running(&self) -> bool {
+        self.reader
+            .as_ref()
+            .is_some_and(|reader| !reader.is_finished())
+    }
+
+    /// Start writing every frame received from now on to `path`, finishing
+    /// any recording in progress
+    pub fn start_recording(&self, path: &Path) -> io::Result<Option<Recording>> {
+        let recorder = Recorder::create(path, &self.interface)?;
+        let previous = self.lock_recorder().replace(recorder);
+        tracing::info!("Recording {} to {:?}", self.interface, path);
+        previous.map(Recorder::finish).transpose()
+    }
+
+    /// Finish the recording in progress, if any
+    pub fn stop_recording(&self) -> io::Result<Option<Recording>> {
+        let recorder = self.lock_recorder().take();
+        recorder.map(Recorder::finish).transpose()
+    }
+
+    /// Whether frames are being recorded
+    pub fn is_recording(&self) -> bool {
+        self.lock_recorder().is_some()
+    }
+
+    /// Stop reading and wait for the reader thread to exit
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(reader) = self.reader.take() {
+            if reader.join().is_err() {
+                tracing::warn!("The {} reader thread panicked", self.interface);
+            }
+        }
+    }
+
+    fn lock_recorder(&self) -> std::sync::MutexGuard<'_, Option<Recorder>> {
+        // A panic mid-write leaves the recorder usable
+        self.recorder
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl Drop for LiveStream {
+    fn drop(&mut self) {
+        self.stop();
+        if let Err(e) = self.stop_recording() {
+            tracing::warn!("Failed to finish the {} recording: {}", self.interface, e);
+        }
+    }
+}
+
+/// Reader thread: forward frames to the UI and the recorder until stopped
+fn read_frames(
+    mut source: impl FrameSource,
+    sender: Sender<LiveEvent>,
+    stop: &AtomicBool,
+    recorder: &Mutex<Option<Recorder>>,
+) {
+    while !stop.load(Ordering::Relaxed) {
+        let frame = match source.read_frame(POLL_INTERVAL) {
+            Ok(Some(frame)) => frame,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::error!("CAN read failed: {}", e);
+                let _ = sender.send(LiveEvent::Error(e.to_string()));
+                return;
+            }
+        };
+
+        let mut recorder = recorder
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(Err(e)) = recorder.as_mut().map(|r| r.write(&frame)) {
+            tracing::error!("Recording failed: {}", e);
+            *recorder = None;
+            let _ = sender.send(LiveEvent::RecordingFailed(e.to_string()));
+        }
+        drop(recorder);
+
+        // The UI closed the stream
+        if sender.send(LiveEvent::Frame(frame)).is_err() {
+            return;
+        }
+    }
+}
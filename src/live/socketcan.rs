@@ -0,0 +1,198 @@
+//! Raw SocketCAN access (Linux).
+//!
+//! Opens a `CAN_RAW` socket bound to one interface, with CAN FD frames
+//! enabled where the kernel supports them. Frames are stamped with the
+//! kernel's receive time.
+
+use std::ffi::CString;
+use std::io;
+use std::mem;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::FrameSource;
+use crate::adapters::can::CanFrame;
+use crate::parsers::can_trace::TraceFrame;
+
+/// `ioctl` reading the receive time of the last frame (`linux/sockios.h`)
+const SIOCGSTAMP: u32 = 0x8906;
+
+/// A raw CAN socket bound to one interface
+pub struct SocketCan {
+    fd: OwnedFd,
+}
+
+impl SocketCan {
+    /// Open and bind a raw socket on `interface` (e.g. `can0` or `vcan0`)
+    pub fn open(interface: &str) -> io::Result<Self> {
+        let name = CString::new(interface)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid interface name"))?;
+        // SAFETY: `name` is a valid NUL-terminated string
+        let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+        if index == 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // SAFETY: plain socket creation; the descriptor is owned right after
+        let fd = unsafe {
+            libc::socket(
+                libc::PF_CAN,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                libc::CAN_RAW,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `fd` is a freshly created descriptor nothing else owns
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // Classic-only kernels refuse CAN FD frames; classic frames still arrive
+        let enable: libc::c_int = 1;
+        // SAFETY: the option value points at a live c_int of the given size
+        unsafe {
+            libc::setsockopt(
+                fd.as_raw_fd(),
+                libc::SOL_CAN_RAW,
+                libc::CAN_RAW_FD_FRAMES,
+                (&enable as *const libc::c_int).cast(),
+                mem::size_of::<libc::c_int>() as libc::socklen_t,
+            );
+        }
+
+        // SAFETY: sockaddr_can is plain data, valid when zeroed
+        let mut address: libc::sockaddr_can = unsafe { mem::zeroed() };
+        address.can_family = libc::AF_CAN as libc::sa_family_t;
+        address.can_ifindex = index as libc::c_int;
+        // SAFETY: the address points at a sockaddr_can of the given size
+        let bound = unsafe {
+            libc::bind(
+                fd.as_raw_fd(),
+                (&address as *const libc::sockaddr_can).cast(),
+                mem::size_of::<libc::sockaddr_can>() as libc::socklen_t,
+            )
+        };
+        if bound < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self { fd })
+    }
+
+    /// Wait up to `timeout` for a data frame. Remote and error frames are
+    /// skipped and read as `None`, like a timeout.
+    pub fn read(&self, timeout: Duration) -> io::Result<Option<TraceFrame>> {
+        let mut poll = libc::pollfd {
+            fd: self.fd.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let timeout_ms = timeout.as_millis().min(libc::c_int::MAX as u128) as libc::c_int;
+        // SAFETY: one valid pollfd
+        match unsafe { libc::poll(&mut poll, 1, timeout_ms) } {
+            0 => return Ok(None),
+            n if n < 0 => {
+                let error = io::Error::last_os_error();
+                return match error.kind() {
+                    io::ErrorKind::Interrupted => Ok(None),
+                    _ => Err(error),
+                };
+            }
+            _ => {}
+        }
+
+        // A CAN FD frame has room for classic frames too
+        // SAFETY: canfd_frame is plain data, valid when zeroed
+        let mut raw: libc::canfd_frame = unsafe { mem::zeroed() };
+        // SAFETY: reads at most CANFD_MTU bytes into `raw`
+        let read = unsafe {
+            libc::read(
+                self.fd.as_raw_fd(),
+                (&mut raw as *mut libc::canfd_frame).cast(),
+                libc::CANFD_MTU,
+            )
+        };
+        if read < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if read as usize != libc::CAN_MTU && read as usize != libc::CANFD_MTU {
+            return Ok(None);
+        }
+        if raw.can_id & (libc::CAN_RTR_FLAG | libc::CAN_ERR_FLAG) != 0 {
+            return Ok(None);
+        }
+
+        let extended = raw.can_id & libc::CAN_EFF_FLAG != 0;
+        let id = if extended {
+            raw.can_id & libc::CAN_EFF_MASK
+        } else {
+            raw.can_id & libc::CAN_SFF_MASK
+        };
+        let length = (raw.len as usize).min(libc::CANFD_MAX_DLEN);
+        Ok(Some(TraceFrame {
+            time: self.receive_time(),
+            frame: CanFrame::new(id, extended, raw.data[..length].to_vec()),
+        }))
+    }
+
+    /// Send a frame on the interface
+    pub fn write(&self, frame: &CanFrame) -> io::Result<()> {
+        if frame.data.len() > libc::CANFD_MAX_DLEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "CAN payload longer than 64 bytes",
+            ));
+        }
+        // SAFETY: canfd_frame is plain data, valid when zeroed
+        let mut raw: libc::canfd_frame = unsafe { mem::zeroed() };
+        raw.can_id = if frame.extended {
+            frame.id | libc::CAN_EFF_FLAG
+        } else {
+            frame.id
+        };
+        raw.len = frame.data.len() as u8;
+        raw.data[..frame.data.len()].copy_from_slice(&frame.data);
+        // Classic frames are sent at their own size
+        let size = if frame.data.len() > libc::CAN_MAX_DLEN {
+            libc::CANFD_MTU
+        } else {
+            libc::CAN_MTU
+        };
+
+        // SAFETY: writes `size` bytes from `raw`, which is at least that long
+        let written = unsafe {
+            libc::write(
+                self.fd.as_raw_fd(),
+                (&raw as *const libc::canfd_frame).cast(),
+                size,
+            )
+        };
+        if written < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Kernel receive time of the last frame read, or now if unavailable
+    fn receive_time(&self) -> f64 {
+        let mut stamp = libc::timeval {
+            tv_sec: 0,
+            tv_usec: 0,
+        };
+        // SAFETY: SIOCGSTAMP writes one timeval
+        let result = unsafe { libc::ioctl(self.fd.as_raw_fd(), SIOCGSTAMP as _, &mut stamp) };
+        if result == 0 {
+            stamp.tv_sec as f64 + stamp.tv_usec as f64 / 1e6
+        } else {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0.0, |elapsed| elapsed.as_secs_f64())
+        }
+    }
+}
+
+impl FrameSource for SocketCan {
+    fn read_frame(&mut self, timeout: Duration) -> io::Result<Option<TraceFrame>> {
+        self.read(timeout)
+    }
+}
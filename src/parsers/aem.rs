@@ -0,0 +1,830 @@
+//! AEM .daq binary log parser.
+//!
+//! `.daq` files are written by AEMdata / AEMPro when an ECU's internal log is
+//! downloaded. The format is undocumented; the layout below was reverse
+//! engineered from sample files.
+//!
+//! - A text header (`EMERALD v1.00 file` + `0x1A`) followed by a short binary
+//!   header, then a sequence of blocks starting at offset `0x50`
+//! - Each block is a little-endian u32 length followed by tagged fields
+//!   (u16 tag + value), terminated by a zero tag. Some blocks carry nested
+//!   child blocks after the terminator
+//! - Raw "source" channels are stored as contiguous big-endian sample arrays.
+//!   Each source belongs to a rate group, and display channels combine a
+//!   source with a scaling (`raw * scale + offset`) or a bitmask
+//!
+//! Units are not stored in the file, so they are inferred from channel names.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::error::ParseError;
+use super::types::{non_empty, Channel, Log, LogInfo, Meta, NativeSeries, MISSING};
+
+/// Text signature at the start of every .daq file
+const MAGIC: &[u8] = b"EMERALD v";
+
+/// Offset of the first block after the fixed-size file header
+const FIRST_BLOCK_OFFSET: usize = 0x50;
+
+/// Sample rate of rate group 1 (the fastest group).
+///
+/// Rates are not stored in the file. 100 Hz is the fastest internal logging
+/// rate of the AEM Series 2 ECUs and gives a plausible duration for the
+/// sample logs.
+const BASE_SAMPLE_RATE_HZ: f64 = 100.0;
+
+/// Slowest rate group accepted (one sample every 100 s); codes beyond it are corrupt
+const MAX_RATE_DIVIDER: usize = 10_000;
+
+// Field tags (u16, little-endian)
+const TAG_END: u16 = 0x0000;
+const TAG_LINK_COUNT: u16 = 0x0070;
+const TAG_LINK_ID: u16 = 0x0400;
+const TAG_MASK: u16 = 0x0418;
+const TAG_RATE_GROUP: u16 = 0x041A;
+const TAG_LINK_TYPE: u16 = 0x041C;
+const TAG_SAMPLES: u16 = 0x0427;
+const TAG_BYTE_COUNT: u16 = 0x042A;
+const TAG_NAME: u16 = 0x03EA;
+const TAG_VERSION: u16 = 0x03EB;
+const TAG_MIN: u16 = 0x03ED;
+const TAG_MAX: u16 = 0x03EE;
+const TAG_SIGNED: u16 = 0x03F1;
+const TAG_WIDTH: u16 = 0x03F2;
+const TAG_SCALE: u16 = 0x03F3;
+const TAG_OFFSET: u16 = 0x03F4;
+const TAG_ADDRESS: u16 = 0x03F7;
+const TAG_DESCRIPTION: u16 = 0x046F;
+const TAG_SERIAL: u16 = 0x07D5;
+
+/// Link type pointing a display channel at its raw source
+const LINK_SOURCE: u32 = 0x01;
+
+/// AEM .daq log metadata
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct AemMeta {
+    /// ECU type string from the file header (e.g., "AEM25")
+    pub ecu_model: String,
+    /// ECU firmware version (e.g., "02v02")
+    pub firmware_version: String,
+    /// Free-text description written by the download software
+    pub description: String,
+    /// ECU serial number
+    pub serial_number: String,
+    /// Sample rate of the log timebase in Hz
+    pub sample_rate_hz: f64,
+    /// Number of channels in the log
+    pub channel_count: usize,
+    /// Number of data points
+    pub data_points: usize,
+}
+
+impl AemMeta {
+    /// ECU details from the AEM file header, as common metadata
+    pub fn info(&self) -> LogInfo {
+        let mut info = LogInfo::new("AEM");
+        info.ecu_model = non_empty(&self.ecu_model);
+        info.ecu_serial = non_empty(&self.serial_number);
+        info.firmware = non_empty(&self.firmware_version);
+        info.push_vendor("Description", &self.description);
+        info.push_vendor("Sample rate", format!("{} Hz", self.sample_rate_hz));
+        info
+    }
+}
+
+/// AEM channel definition
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct AemChannel {
+    /// Display channel name (e.g., "Engine Speed")
+    pub name: String,
+    /// Unit inferred from the channel name
+    pub unit: String,
+    /// Name of the raw source the channel is decoded from (e.g., "Engine Speed_SRC0")
+    pub source: String,
+    /// Minimum value of the channel's scaling
+    pub display_min: Option<f64>,
+    /// Maximum value of the channel's scaling
+    pub display_max: Option<f64>,
+}
+
+impl AemChannel {
+    /// Get the display unit for this channel
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+}
+
+/// Decoded value of a single tagged field
+#[derive(Debug)]
+enum FieldValue<'a> {
+    Text(String),
+    Int(u32),
+    Float(f64),
+    Bytes(&'a [u8]),
+}
+
+/// A block's fields plus any nested child blocks
+#[derive(Debug, Default)]
+struct Record<'a> {
+    fields: Vec<(u16, FieldValue<'a>)>,
+    children: Vec<Record<'a>>,
+}
+
+impl<'a> Record<'a> {
+    fn get(&self, tag: u16) -> Option<&FieldValue<'a>> {
+        self.fields.iter().find(|(t, _)| *t == tag).map(|(_, v)| v)
+    }
+
+    fn has(&self, tag: u16) -> bool {
+        self.get(tag).is_some()
+    }
+
+    fn text(&self, tag: u16) -> Option<&str> {
+        match self.get(tag) {
+            Some(FieldValue::Text(s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn int(&self, tag: u16) -> Option<u32> {
+        match self.get(tag) {
+            Some(FieldValue::Int(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    fn float(&self, tag: u16) -> Option<f64> {
+        match self.get(tag) {
+            Some(FieldValue::Float(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    fn bytes(&self, tag: u16) -> Option<&'a [u8]> {
+        match self.get(tag) {
+            Some(FieldValue::Bytes(b)) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+/// How a display channel converts raw source samples
+#[derive(Clone, Debug)]
+struct Scaling {
+    scale: f64,
+    offset: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+    signed: bool,
+    /// Set for bitmask channels: the value is `(raw & mask) * scale`
+    mask: Option<u32>,
+}
+
+/// A display channel and the names of the blocks it links to
+struct DisplayChannel {
+    name: String,
+    source: String,
+    scaling: String,
+}
+
+/// A fully decoded channel before alignment to the common timebase
+struct DecodedChannel {
+    channel: AemChannel,
+    divider: usize,
+    values: Vec<f64>,
+}
+
+/// AEM .daq binary log parser
+pub struct Aem;
+
+impl Aem {
+    /// Detect if data is an AEM .daq file
+    pub fn detect(data: &[u8]) -> bool {
+        data.starts_with(MAGIC)
+    }
+
+    /// Parse an AEM .daq file from raw bytes
+    pub fn parse_binary(data: &[u8]) -> Result<Log, ParseError> {
+        if !Self::detect(data) {
+            return Err(ParseError::BadMagic { format: "AEM .daq" });
+        }
+        if data.len() < FIRST_BLOCK_OFFSET {
+            return Err(ParseError::truncated(
+                data.len(),
+                "AEM .daq file is too short to contain a header",
+            ));
+        }
+
+        let mut meta = AemMeta::default();
+        let mut sources: HashMap<String, &[u8]> = HashMap::new();
+        let mut formats: HashMap<String, (u32, u32)> = HashMap::new();
+        let mut scalings: HashMap<String, Scaling> = HashMap::new();
+        let mut displays: Vec<DisplayChannel> = Vec::new();
+
+        for payload in Self::read_blocks(data) {
+            let record = match Self::parse_record(payload) {
+                Ok(r) => r,
+                Err(e) => {
+                    tracing::debug!("Skipping unreadable AEM block: {}", e);
+                    continue;
+                }
+            };
+            let name = record.text(TAG_NAME).unwrap_or_default().to_string();
+
+            if let Some(version) = record.text(TAG_VERSION) {
+                meta.ecu_model = name.trim().to_string();
+                meta.firmware_version = version.to_string();
+            } else if let Some(text) = record.text(TAG_DESCRIPTION) {
+                meta.description = text.trim().to_string();
+            } else if let Some(serial) = record.text(TAG_SERIAL) {
+                meta.serial_number = serial.to_string();
+            } else if let Some(samples) = record.bytes(TAG_SAMPLES) {
+                sources.insert(name, samples);
+            } else if record.has(TAG_LINK_COUNT) {
+                if let Some(display) = Self::display_channel(name, &record) {
+                    displays.push(display);
+                }
+            } else if let Some(scale) = record.float(TAG_SCALE) {
+                let mask = record.int(TAG_MASK);
+                scalings.insert(
+                    name,
+                    Scaling {
+                        scale,
+                        offset: record.float(TAG_OFFSET).unwrap_or(0.0),
+                        min: record.float(TAG_MIN),
+                        max: record.float(TAG_MAX),
+                        signed: record.int(TAG_SIGNED).unwrap_or(0) != 0,
+                        mask,
+                    },
+                );
+            } else if let (Some(group), Some(width)) =
+                (record.int(TAG_RATE_GROUP), record.int(TAG_WIDTH))
+            {
+                formats.insert(name, (group, width));
+            }
+        }
+
+        let mut decoded: Vec<DecodedChannel> = Vec::new();
+        for entry in &displays {
+            let (Some(raw), Some(&(group, width)), Some(scaling)) = (
+                sources.get(&entry.source),
+                formats.get(&entry.source),
+                scalings.get(&entry.scaling),
+            ) else {
+                tracing::debug!("Skipping AEM channel '{}': missing source", entry.name);
+                continue;
+            };
+            let Some(divider) = rate_divider(group) else {
+                tracing::debug!(
+                    "Skipping AEM channel '{}': invalid rate group {}",
+                    entry.name,
+                    group
+                );
+                continue;
+            };
+
+            decoded.push(DecodedChannel {
+                channel: AemChannel {
+                    name: entry.name.clone(),
+                    // Bitmask channels are on/off flags
+                    unit: match scaling.mask {
+                        Some(_) => String::new(),
+                        None => infer_unit(&entry.name).to_string(),
+                    },
+                    source: entry.source.clone(),
+                    display_min: scaling.min,
+                    display_max: scaling.max,
+                },
+                divider,
+                values: decode_samples(raw, width, scaling),
+            });
+        }
+
+        decoded.retain(|c| !c.values.is_empty());
+        if decoded.is_empty() {
+            return Err(ParseError::invalid(
+                "AEM .daq file contains no logged channels",
+            ));
+        }
+
+        // Slower rate groups are held at their last value on the fastest
+        // timebase and keep their own timebase as a native series. Rows past
+        // a channel's last sample are missing.
+        let base_divider = decoded.iter().map(|c| c.divider).min().unwrap_or(1);
+        let row_count = decoded
+            .iter()
+            .map(|c| c.values.len() * c.divider / base_divider)
+            .max()
+            .unwrap_or(0);
+        let sample_rate_hz = BASE_SAMPLE_RATE_HZ / base_divider as f64;
+
+        let times: Vec<f64> = (0..row_count)
+            .map(|row| row as f64 / sample_rate_hz)
+            .collect();
+        let columns: Vec<Vec<f64>> = decoded
+            .iter()
+            .map(|c| {
+                (0..row_count)
+                    .map(|row| {
+                        c.values
+                            .get(row * base_divider / c.divider)
+                            .copied()
+                            .unwrap_or(MISSING)
+                    })
+                    .collect()
+            })
+            .collect();
+        let native: Vec<Option<NativeSeries>> = decoded
+            .iter()
+            .map(|c| {
+                (c.divider != base_divider).then(|| NativeSeries {
+                    times: (0..c.values.len())
+                        .map(|i| (i * c.divider) as f64 / BASE_SAMPLE_RATE_HZ)
+                        .collect(),
+                    values: c.values.clone(),
+                })
+            })
+            .collect();
+
+        let channels: Vec<Channel> = decoded
+            .into_iter()
+            .map(|c| Channel::Aem(c.channel))
+            .collect();
+
+        meta.sample_rate_hz = sample_rate_hz;
+        meta.channel_count = channels.len();
+        meta.data_points = row_count;
+
+        tracing::info!(
+            "Parsed AEM .daq log: {} channels, {} data points",
+            channels.len(),
+            row_count
+        );
+
+        let mut log = Log::new(Meta::Aem(meta), channels, times, columns);
+        for (idx, series) in native.into_iter().enumerate() {
+            if let Some(series) = series {
+                log.set_native_series(idx, series);
+            }
+        }
+        Ok(log)
+    }
+
+    /// Split the file into block payloads, stopping at the first truncated block
+    fn read_blocks(data: &[u8]) -> Vec<&[u8]> {
+        let mut blocks = Vec::new();
+        let mut offset = FIRST_BLOCK_OFFSET;
+
+        while offset + 4 <= data.len() {
+            let len = read_u32(data, offset) as usize;
+            let start = offset + 4;
+            if len == 0 || start + len > data.len() {
+                break;
+            }
+            blocks.push(&data[start..start + len]);
+            offset = start + len;
+        }
+
+        blocks
+    }
+
+    /// Parse the tagged fields of a block, followed by any child blocks
+    fn parse_record(payload: &[u8]) -> Result<Record<'_>, String> {
+        let mut record = Record::default();
+        let mut pos = 0;
+
+        loop {
+            let tag = read_u16(payload, pos).ok_or("block ended before terminator")?;
+            pos += 2;
+
+            let (value, size) = match tag {
+                TAG_END => break,
+                TAG_NAME | TAG_SERIAL => {
+                    let len = *payload.get(pos).ok_or("truncated string")? as usize;
+                    let text = read_text(payload.get(pos + 1..pos + 1 + len))?;
+                    (FieldValue::Text(text), 1 + len)
+                }
+                TAG_DESCRIPTION => {
+                    let len = read_u16(payload, pos).ok_or("truncated string")? as usize;
+                    let text = read_text(payload.get(pos + 2..pos + 2 + len))?;
+                    (FieldValue::Text(text), 2 + len)
+                }
+                TAG_VERSION => {
+                    // Unprefixed text; its NUL doubles as the block terminator
+                    let rest = &payload[pos..];
+                    let len = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+                    let text = String::from_utf8_lossy(&rest[..len]).to_string();
+                    (FieldValue::Text(text), len)
+                }
+                TAG_SCALE | TAG_OFFSET | TAG_MIN | TAG_MAX => {
+                    let bytes = payload.get(pos..pos + 8).ok_or("truncated float")?;
+                    let value = f64::from_le_bytes(bytes.try_into().unwrap());
+                    (FieldValue::Float(value), 8)
+                }
+                TAG_RATE_GROUP | TAG_WIDTH | TAG_SIGNED => {
+                    let value = *payload.get(pos).ok_or("truncated byte")?;
+                    (FieldValue::Int(value as u32), 1)
+                }
+                TAG_LINK_COUNT | TAG_LINK_ID | TAG_LINK_TYPE | TAG_MASK | TAG_ADDRESS => {
+                    let value = read_u16(payload, pos).ok_or("truncated word")?;
+                    (FieldValue::Int(value as u32), 2)
+                }
+                TAG_BYTE_COUNT => {
+                    let value = payload
+                        .get(pos..pos + 4)
+                        .map(|_| read_u32(payload, pos))
+                        .ok_or("truncated byte count")?;
+                    (FieldValue::Int(value), 4)
+                }
+                TAG_SAMPLES => {
+                    let len = record
+                        .int(TAG_BYTE_COUNT)
+                        .ok_or("samples without a byte count")?
+                        as usize;
+                    let bytes = payload.get(pos..pos + len).ok_or("truncated samples")?;
+                    (FieldValue::Bytes(bytes), len)
+                }
+                other => return Err(format!("unknown tag 0x{:04X}", other)),
+            };
+
+            record.fields.push((tag, value));
+            pos += size;
+        }
+
+        // Anything after the terminator is a list of nested blocks
+        while pos + 4 <= payload.len() {
+            let len = read_u32(payload, pos) as usize;
+            let child = payload
+                .get(pos + 4..pos + 4 + len)
+                .ok_or("truncated child block")?;
+            record.children.push(Self::parse_record(child)?);
+            pos += 4 + len;
+        }
+
+        Ok(record)
+    }
+
+    /// Resolve the source and scaling links of a display channel block
+    fn display_channel(name: String, record: &Record) -> Option<DisplayChannel> {
+        let mut source = None;
+        let mut scaling = None;
+
+        for link in &record.children {
+            let target = link.text(TAG_NAME)?.to_string();
+            if link.int(TAG_LINK_TYPE)? == LINK_SOURCE {
+                source = Some(target);
+            } else {
+                scaling = Some(target);
+            }
+        }
+
+        Some(DisplayChannel {
+            name,
+            source: source?,
+            scaling: scaling?,
+        })
+    }
+}
+
+/// Sample divider for a rate group code, or `None` past [`MAX_RATE_DIVIDER`].
+///
+/// Codes step through a 1-2-5 sequence: 1 = every sample, 4 = every 10th,
+/// 7 = every 100th.
+fn rate_divider(code: u32) -> Option<usize> {
+    let step = code.max(1) - 1;
+    10usize
+        .checked_pow(step / 3)?
+        .checked_mul([1, 2, 5][(step % 3) as usize])
+        .filter(|&divider| divider <= MAX_RATE_DIVIDER)
+}
+
+/// Decode raw big-endian samples and apply the channel scaling
+fn decode_samples(raw: &[u8], width: u32, scaling: &Scaling) -> Vec<f64> {
+    let raw_values: Vec<i64> = if width == 0 {
+        raw.chunks_exact(2)
+            .map(|b| {
+                let v = u16::from_be_bytes([b[0], b[1]]);
+                if scaling.signed {
+                    v as i16 as i64
+                } else {
+                    v as i64
+                }
+            })
+            .collect()
+    } else {
+        raw.iter()
+            .map(|&v| {
+                if scaling.signed {
+                    v as i8 as i64
+                } else {
+                    v as i64
+                }
+            })
+            .collect()
+    };
+
+    raw_values
+        .into_iter()
+        .map(|v| match scaling.mask {
+            Some(mask) => (v & mask as i64) as f64 * scaling.scale,
+            None => v as f64 * scaling.scale + scaling.offset,
+        })
+        .collect()
+}
+
+/// Infer a display unit from an AEM channel name.
+///
+/// The sample files use imperial scalings (temperatures come out in °F), so
+/// imperial units are assumed throughout.
+fn infer_unit(name: &str) -> &'static str {
+    let name = name.to_lowercase();
+    let has = |s: &str| name.contains(s);
+
+    if has("engine speed") {
+        "rpm"
+    } else if has("volts") || has("battery") || has("adcr") {
+        "V"
+    } else if has("raw") || has("errors") {
+        ""
+    } else if has("temp") {
+        "°F"
+    } else if has("timing") || has("retard") || has("angle") {
+        "°"
+    } else if has("pulse")
+        || has("dwell time")
+        || has("period")
+        || has("fuel map")
+        || has("accel fuel")
+    {
+        "ms"
+    } else if has("pressure") || has("engine load") || has("boost target") {
+        "psi"
+    } else if has("vehicle speed") {
+        "mph"
+    } else if has("o2") && !has("fb") && !has("kill") {
+        "AFR"
+    } else if has("throttle")
+        || has("duty")
+        || has("position")
+        || has("trim")
+        || has("fb value")
+        || has("learned")
+        || has("fuel")
+    {
+        "%"
+    } else {
+        ""
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}
+
+/// Read a length-prefixed string body, which is NUL-terminated with trailing padding
+fn read_text(bytes: Option<&[u8]>) -> Result<String, String> {
+    let bytes = bytes.ok_or("truncated string")?;
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    Ok(String::from_utf8_lossy(&bytes[..len]).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a block: u32 length + tagged fields + terminator + child blocks
+    fn block(fields: &[u8], children: &[Vec<u8>]) -> Vec<u8> {
+        let mut payload = fields.to_vec();
+        payload.extend_from_slice(&TAG_END.to_le_bytes());
+        for child in children {
+            payload.extend_from_slice(child);
+        }
+        let mut out = (payload.len() as u32).to_le_bytes().to_vec();
+        out.extend(payload);
+        out
+    }
+
+    fn name(text: &str) -> Vec<u8> {
+        let mut out = TAG_NAME.to_le_bytes().to_vec();
+        out.push(text.len() as u8 + 2);
+        out.extend_from_slice(text.as_bytes());
+        out.extend_from_slice(&[0x00, 0x03]);
+        out
+    }
+
+    fn tag_u8(tag: u16, value: u8) -> Vec<u8> {
+        let mut out = tag.to_le_bytes().to_vec();
+        out.push(value);
+        out
+    }
+
+    fn tag_u16(tag: u16, value: u16) -> Vec<u8> {
+        let mut out = tag.to_le_bytes().to_vec();
+        out.extend_from_slice(&value.to_le_bytes());
+        out
+    }
+
+    fn tag_f64(tag: u16, value: f64) -> Vec<u8> {
+        let mut out = tag.to_le_bytes().to_vec();
+        out.extend_from_slice(&value.to_le_bytes());
+        out
+    }
+
+    fn samples(source: &str, raw: &[u8]) -> Vec<u8> {
+        let mut fields = name(source);
+        fields.extend_from_slice(&TAG_BYTE_COUNT.to_le_bytes());
+        fields.extend_from_slice(&(raw.len() as u32).to_le_bytes());
+        fields.extend_from_slice(&TAG_SAMPLES.to_le_bytes());
+        fields.extend_from_slice(raw);
+        block(&fields, &[])
+    }
+
+    fn display(channel: &str, group: u8, source: &str, scaling: &str) -> Vec<u8> {
+        let link = |link_type: u16, id: u16, target: &str| {
+            let mut fields = tag_u16(TAG_LINK_TYPE, link_type);
+            fields.extend(tag_u16(TAG_LINK_ID, id));
+            fields.extend(name(target));
+            block(&fields, &[])
+        };
+        let mut fields = tag_u16(TAG_LINK_COUNT, 2);
+        fields.extend(name(channel));
+        fields.extend(tag_u8(TAG_RATE_GROUP, group));
+        block(
+            &fields,
+            &[link(0x01, 0x64, source), link(0x41, 0x65, scaling)],
+        )
+    }
+
+    /// A small two-rate log: RPM (u16, group 1) and coolant temp (signed u8, group 4)
+    fn sample_file() -> Vec<u8> {
+        sample_file_with_clt_group(4)
+    }
+
+    /// The sample log with coolant temp logged at rate group `clt_group`
+    fn sample_file_with_clt_group(clt_group: u8) -> Vec<u8> {
+        let mut data = b"EMERALD v1.00 file\x1a".to_vec();
+        data.resize(FIRST_BLOCK_OFFSET, 0);
+
+        let mut header = name("AEM25   ");
+        header.extend_from_slice(&TAG_VERSION.to_le_bytes());
+        header.extend_from_slice(b"02v02");
+        data.extend(block(&header, &[]));
+
+        let rpm: Vec<u8> = [
+            1000u16, 2000, 3000, 4000, 5000, 6000, 7000, 8000, 9000, 10000,
+        ]
+        .iter()
+        .flat_map(|v| v.to_be_bytes())
+        .collect();
+        data.extend(samples("Engine Speed_SRC0", &rpm));
+        data.extend(samples("Coolant Temp_SRC1", &[0xF6]));
+
+        let mut rpm_format = name("Engine Speed_SRC0");
+        rpm_format.extend(tag_u8(TAG_RATE_GROUP, 1));
+        rpm_format.extend(tag_u8(TAG_WIDTH, 0));
+        data.extend(block(&rpm_format, &[]));
+
+        let mut clt_format = name("Coolant Temp_SRC1");
+        clt_format.extend(tag_u8(TAG_RATE_GROUP, clt_group));
+        clt_format.extend(tag_u8(TAG_WIDTH, 1));
+        data.extend(block(&clt_format, &[]));
+
+        let mut rpm_scale = name("Engine Speed_SC1");
+        rpm_scale.extend(tag_f64(TAG_SCALE, 0.5));
+        rpm_scale.extend(tag_f64(TAG_OFFSET, 0.0));
+        rpm_scale.extend(tag_u8(TAG_SIGNED, 0));
+        data.extend(block(&rpm_scale, &[]));
+
+        let mut clt_scale = name("Coolant Temp_SC2");
+        clt_scale.extend(tag_f64(TAG_SCALE, 1.8));
+        clt_scale.extend(tag_f64(TAG_OFFSET, 32.0));
+        clt_scale.extend(tag_f64(TAG_MIN, -198.4));
+        clt_scale.extend(tag_f64(TAG_MAX, 260.6));
+        clt_scale.extend(tag_u8(TAG_SIGNED, 1));
+        data.extend(block(&clt_scale, &[]));
+
+        data.extend(display(
+            "Engine Speed",
+            1,
+            "Engine Speed_SRC0",
+            "Engine Speed_SC1",
+        ));
+        data.extend(display(
+            "Coolant Temp",
+            4,
+            "Coolant Temp_SRC1",
+            "Coolant Temp_SC2",
+        ));
+        data
+    }
+
+    #[test]
+    fn test_detect() {
+        assert!(Aem::detect(b"EMERALD v1.00 file\x1a"));
+        assert!(!Aem::detect(b"MLVLG\0"));
+        assert!(!Aem::detect(b""));
+    }
+
+    #[test]
+    fn test_rate_divider() {
+        assert_eq!(rate_divider(1), Some(1));
+        assert_eq!(rate_divider(2), Some(2));
+        assert_eq!(rate_divider(3), Some(5));
+        assert_eq!(rate_divider(4), Some(10));
+        assert_eq!(rate_divider(7), Some(100));
+        assert_eq!(rate_divider(13), Some(MAX_RATE_DIVIDER));
+
+        // Corrupt codes are rejected rather than overflowing
+        assert_eq!(rate_divider(14), None);
+        assert_eq!(rate_divider(61), None);
+        assert_eq!(rate_divider(u32::MAX), None);
+    }
+
+    #[test]
+    fn test_parse_skips_invalid_rate_group() {
+        let log = Aem::parse_binary(&sample_file_with_clt_group(200)).unwrap();
+
+        assert_eq!(log.channels.len(), 1);
+        assert_eq!(log.channels[0].name(), "Engine Speed");
+        assert_eq!(log.times.len(), 10);
+    }
+
+    #[test]
+    fn test_infer_unit() {
+        assert_eq!(infer_unit("Engine Speed"), "rpm");
+        assert_eq!(infer_unit("Coolant Temp"), "°F");
+        assert_eq!(infer_unit("Knock 1 Volts"), "V");
+        assert_eq!(infer_unit("O2 #1"), "AFR");
+        assert_eq!(infer_unit("O2 #1 FB Value"), "%");
+        assert_eq!(infer_unit("Fuel Inj 1 Pulse"), "ms");
+        assert_eq!(infer_unit("Status 7"), "");
+        assert_eq!(infer_unit("Timing Errors"), "");
+        assert_eq!(infer_unit("Dwell Time RAW"), "");
+    }
+
+    #[test]
+    fn test_parse_aligns_rate_groups() {
+        let log = Aem::parse_binary(&sample_file()).unwrap();
+
+        assert_eq!(log.channels.len(), 2);
+        assert_eq!(log.channels[0].name(), "Engine Speed");
+        assert_eq!(log.channels[0].unit(), "rpm");
+        assert_eq!(log.channels[1].unit(), "°F");
+
+        // 10 fast samples at 100 Hz
+        assert_eq!(log.times.len(), 10);
+        assert!((log.times[1] - 0.01).abs() < 1e-9);
+
+        let rpm = log.channel_data(0);
+        assert_eq!(rpm[0], 500.0);
+        assert_eq!(rpm[9], 5000.0);
+
+        // One group 4 sample covers all 10 rows; 0xF6 is -10 when signed
+        let clt = log.channel_data(1);
+        assert!(clt.iter().all(|&v| (v - (-10.0 * 1.8 + 32.0)).abs() < 1e-9));
+
+        match &log.meta {
+            Meta::Aem(meta) => {
+                assert_eq!(meta.ecu_model, "AEM25");
+                assert_eq!(meta.firmware_version, "02v02");
+                assert_eq!(meta.sample_rate_hz, 100.0);
+            }
+            _ => panic!("Expected AEM metadata"),
+        }
+    }
+
+    #[test]
+    fn test_decode_bitmask() {
+        let scaling = Scaling {
+            scale: 0.5,
+            offset: 0.0,
+            min: None,
+            max: None,
+            signed: false,
+            mask: Some(0x02),
+        };
+        assert_eq!(
+            decode_samples(&[0x00, 0x02, 0x03, 0x01], 1, &scaling),
+            vec![0.0, 1.0, 1.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_log() {
+        let mut data = b"EMERALD v1.00 file\x1a".to_vec();
+        data.resize(FIRST_BLOCK_OFFSET, 0);
+        assert!(Aem::parse_binary(&data).is_err());
+        assert!(Aem::parse_binary(b"not a daq file").is_err());
+    }
+}
@@ -4,14 +4,14 @@
 //!
 //! Uses a pure Rust implementation to parse XRK binary format.
 
-use serde::Serialize;
-use std::error::Error;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
-use super::types::{Log, Meta, Value};
+use super::error::ParseError;
+use super::types::{non_empty, ColumnBuilder, Log, LogInfo, Meta, RecoveryReport};
 
 /// AIM channel metadata
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct AimChannel {
     pub name: String,
     pub unit: String,
@@ -179,7 +179,7 @@ fn infer_unit_from_name(name: &str) -> &'static str {
 }
 
 /// AIM log file metadata
-#[derive(Clone, Debug, Serialize, Default)]
+#[derive(Clone, Debug, Deserialize, Serialize, Default)]
 pub struct AimMeta {
     pub vehicle: String,
     pub racer: String,
@@ -190,6 +190,21 @@ pub struct AimMeta {
     pub lap_count: usize,
 }
 
+impl AimMeta {
+    /// Session details from the XRK header, as common metadata
+    pub fn info(&self) -> LogInfo {
+        let mut info = LogInfo::new("AiM");
+        info.start_datetime = non_empty(&self.datetime);
+        info.vehicle = non_empty(&self.vehicle);
+        info.driver = non_empty(&self.racer);
+        info.track = non_empty(&self.track);
+        info.lap_count = Some(self.lap_count);
+        info.push_vendor("Championship", &self.championship);
+        info.push_vendor("Venue type", &self.venue_type);
+        info
+    }
+}
+
 /// AIM XRK/DRK parser
 pub struct Aim;
 
@@ -201,15 +216,26 @@ impl Aim {
     }
 
     /// Parse AIM XRK/DRK file from a file path
-    pub fn parse_file(path: &Path) -> Result<Log, Box<dyn Error>> {
+    pub fn parse_file(path: &Path) -> Result<Log, ParseError> {
         let data = std::fs::read(path)?;
         Self::parse_binary(&data)
     }
 
     /// Parse XRK binary data using pure Rust implementation
-    fn parse_binary(data: &[u8]) -> Result<Log, Box<dyn Error>> {
+    pub fn parse_binary(data: &[u8]) -> Result<Log, ParseError> {
+        Self::read_binary(data, false)
+    }
+
+    /// Parse an XRK file that was cut off mid-write. Records are found by
+    /// scanning for their markers, so every whole record is kept; the log's
+    /// `recovery` report counts a record cut short at the end of the file.
+    pub fn recover_binary(data: &[u8]) -> Result<Log, ParseError> {
+        Self::read_binary(data, true)
+    }
+
+    fn read_binary(data: &[u8], recover: bool) -> Result<Log, ParseError> {
         if !Self::detect(data) {
-            return Err("Not a valid AIM XRK file".into());
+            return Err(ParseError::BadMagic { format: "AIM XRK" });
         }
 
         tracing::info!(
@@ -226,28 +252,34 @@ impl Aim {
         tracing::info!("Vehicle: {}, Track: {}", meta.vehicle, meta.track);
 
         // Parse channel data
-        let (times, channel_data) = Self::parse_channel_data(data, channels.len())?;
+        let mut report = RecoveryReport::default();
+        let (times, channel_data) = Self::parse_channel_data(data, channels.len(), &mut report)?;
         tracing::info!("Parsed {} data points", times.len());
 
-        Ok(Log {
-            meta: Meta::Aim(meta),
-            channels: channels
+        let mut log = Log::new(
+            Meta::Aim(meta),
+            channels
                 .into_iter()
                 .map(super::types::Channel::Aim)
                 .collect(),
             times,
-            data: channel_data,
-        })
+            channel_data,
+        );
+        log.recovery = recover.then_some(report);
+        Ok(log)
     }
 
     /// Parse channel definitions from XRK data
-    fn parse_channels(data: &[u8]) -> Result<Vec<AimChannel>, Box<dyn Error>> {
+    fn parse_channels(data: &[u8]) -> Result<Vec<AimChannel>, ParseError> {
         let mut channels = Vec::new();
 
         // Skip past the initial <hCNF> header
         // Format: <hCNF\x00 + 4 bytes length + 2 bytes version
         if data.len() < 12 {
-            return Err("File too short".into());
+            return Err(ParseError::truncated(
+                data.len(),
+                "channel configuration header",
+            ));
         }
         let mut offset = 12; // Skip <hCNF\x00 + length + version
 
@@ -320,7 +352,7 @@ impl Aim {
     }
 
     /// Parse metadata from the XRK file footer
-    fn parse_metadata(data: &[u8]) -> Result<AimMeta, Box<dyn Error>> {
+    fn parse_metadata(data: &[u8]) -> Result<AimMeta, ParseError> {
         let mut meta = AimMeta::default();
 
         // Look for <VEH> (Vehicle) tag near the end of the file
@@ -365,12 +397,13 @@ impl Aim {
     fn parse_channel_data(
         data: &[u8],
         channel_count: usize,
-    ) -> Result<(Vec<f64>, Vec<Vec<Value>>), Box<dyn Error>> {
+        report: &mut RecoveryReport,
+    ) -> Result<(Vec<f64>, Vec<Vec<f64>>), ParseError> {
         let mut times = Vec::new();
-        let mut all_data: Vec<Vec<Value>> = Vec::new();
+        let mut all_data = ColumnBuilder::new(channel_count);
 
         if channel_count == 0 {
-            return Ok((times, all_data));
+            return Ok((times, all_data.into_columns()));
         }
 
         // XRK files store telemetry data in )(G records
@@ -407,11 +440,11 @@ impl Aim {
                                 data[float_offset + 3],
                             ]);
 
-                            // Only include finite values
+                            // Non-finite values are gaps
                             if value.is_finite() {
                                 values.push(value);
                             } else {
-                                values.push(0.0);
+                                values.push(f32::NAN);
                             }
                         }
                     }
@@ -423,20 +456,14 @@ impl Aim {
                         let time_sec = record_count as f64 / sample_rate_hz;
                         times.push(time_sec);
 
-                        // Create data row for all channels
-                        let mut row = Vec::with_capacity(channel_count);
-                        for ch_idx in 0..channel_count {
-                            // Map channel to value - use 0.0 for channels beyond available values
-                            let value = if ch_idx < values.len() {
-                                values[ch_idx] as f64
-                            } else {
-                                0.0
-                            };
-                            row.push(Value::Float(value));
-                        }
-                        all_data.push(row);
+                        // Channels beyond the available values are missing
+                        let row: Vec<f64> = values.iter().map(|&v| v as f64).collect();
+                        all_data.push_record(&row);
                         record_count += 1;
                     }
+                } else if next_pos == data.len() && record_size < 100 {
+                    // The file ends part way through the last record
+                    report.skip(pos as u64, 1);
                 }
 
                 offset = pos + 3;
@@ -464,7 +491,7 @@ impl Aim {
             );
         }
 
-        Ok((times, all_data))
+        Ok((times, all_data.into_columns()))
     }
 
     /// Find a byte pattern in data starting from offset
@@ -521,7 +548,7 @@ mod tests {
         match Aim::parse_file(file_path) {
             Ok(log) => {
                 eprintln!("Parsed {} channels", log.channels.len());
-                eprintln!("Parsed {} data records", log.data.len());
+                eprintln!("Parsed {} data records", log.record_count());
                 if !log.times.is_empty() {
                     eprintln!(
                         "Time range: {:.3}s to {:.3}s",
@@ -656,7 +683,7 @@ mod tests {
             match Aim::parse_file(&path) {
                 Ok(log) => {
                     eprintln!("  Channels: {}", log.channels.len());
-                    eprintln!("  Data records: {}", log.data.len());
+                    eprintln!("  Data records: {}", log.record_count());
 
                     // The pure Rust parser may return incomplete data for some files
                     // Just log warnings instead of asserting
@@ -664,7 +691,7 @@ mod tests {
                         eprintln!("  Warning: No channels parsed");
                         continue;
                     }
-                    if log.data.is_empty() {
+                    if log.is_empty() {
                         eprintln!("  Warning: No data records parsed");
                         continue;
                     }
@@ -681,9 +708,9 @@ mod tests {
 
                     // Check for actual values
                     let has_non_zero = log
-                        .data
+                        .columns()
                         .iter()
-                        .any(|row| row.iter().any(|v| v.as_f64().abs() > 0.0001));
+                        .any(|column| column.iter().any(|v| v.abs() > 0.0001));
                     if !has_non_zero {
                         eprintln!("  Warning: No non-zero values found");
                         continue;
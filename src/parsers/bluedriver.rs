@@ -0,0 +1,302 @@
+//! BlueDriver OBD-II LiveData CSV parser.
+//!
+//! Parses the LiveData CSV exports produced by the BlueDriver Bluetooth
+//! OBD-II scan tool app. The exports differ from a plain CSV in a few ways:
+//!
+//! - They are saved as UTF-16 with a byte order mark (usually little-endian)
+//! - The first two lines are a preamble: a "BlueDriver Data Log" title and
+//!   the capture date (e.g., "Aug 17, 2025")
+//! - The header row uses ", " as separator and puts units in parentheses,
+//!   e.g., "Time(s), O2 Bank 1 - Sensor 2 - Oxygen Sensor Voltage (V)"
+//! - PIDs are polled round-robin, so rows can contain empty cells for
+//!   channels that were not refreshed in that sample
+
+use serde::{Deserialize, Serialize};
+
+use super::error::ParseError;
+use super::types::{non_empty, Channel, ColumnBuilder, Log, LogInfo, Meta, Parseable, MISSING};
+
+/// Title line BlueDriver writes at the top of every LiveData export
+const TITLE_MARKER: &str = "BlueDriver Data Log";
+
+/// BlueDriver LiveData metadata
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BlueDriverMeta {
+    /// Title line from the preamble (normally "BlueDriver Data Log")
+    pub title: String,
+    /// Capture date line from the preamble (e.g., "Aug 17, 2025")
+    pub capture_date: String,
+    /// Number of channels in the log
+    pub channel_count: usize,
+    /// Number of data points
+    pub data_points: usize,
+}
+
+impl BlueDriverMeta {
+    /// Common metadata; the preamble only carries a title and date
+    pub fn info(&self) -> LogInfo {
+        let mut info = LogInfo::new("BlueDriver");
+        info.start_datetime = non_empty(&self.capture_date);
+        info.push_vendor("Title", &self.title);
+        info
+    }
+}
+
+/// BlueDriver channel definition
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BlueDriverChannel {
+    /// PID name without the unit suffix (e.g., "Long Term Secondary Oxygen Sensor Trim Bank 1")
+    pub name: String,
+    /// Unit extracted from parentheses (e.g., "%")
+    pub unit: String,
+}
+
+impl BlueDriverChannel {
+    /// Create a channel from a header column such as "Engine RPM (rpm)".
+    ///
+    /// Only a trailing parenthesised group is treated as the unit, so names
+    /// like "O2 Bank 1 - Sensor 2 (Wide Range) (V)" keep their inner text.
+    pub fn from_header(header: &str) -> Self {
+        let header = header.trim();
+
+        if header.ends_with(')') {
+            if let Some(paren_start) = header.rfind('(') {
+                let unit = header[paren_start + 1..header.len() - 1].trim();
+                let name = header[..paren_start].trim();
+                if !name.is_empty() {
+                    return Self {
+                        name: name.to_string(),
+                        unit: unit.to_string(),
+                    };
+                }
+            }
+        }
+
+        Self {
+            name: header.to_string(),
+            unit: String::new(),
+        }
+    }
+
+    /// Get the display unit for this channel
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+}
+
+/// BlueDriver LiveData CSV parser
+pub struct BlueDriver;
+
+impl BlueDriver {
+    /// Detect if decoded file contents look like a BlueDriver LiveData export
+    pub fn detect(contents: &str) -> bool {
+        contents
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .next()
+            .is_some_and(|line| line.trim().starts_with(TITLE_MARKER))
+    }
+
+    /// Decode UTF-16 text with a byte order mark into a `String`.
+    ///
+    /// Returns `None` if the data has no UTF-16 BOM or is not valid UTF-16,
+    /// in which case callers should fall back to UTF-8 handling.
+    pub fn decode_utf16(data: &[u8]) -> Option<String> {
+        let little_endian = match data {
+            [0xFF, 0xFE, ..] => true,
+            [0xFE, 0xFF, ..] => false,
+            _ => return None,
+        };
+
+        let units: Vec<u16> = data[2..]
+            .chunks_exact(2)
+            .map(|pair| {
+                if little_endian {
+                    u16::from_le_bytes([pair[0], pair[1]])
+                } else {
+                    u16::from_be_bytes([pair[0], pair[1]])
+                }
+            })
+            .collect();
+
+        String::from_utf16(&units).ok()
+    }
+
+    /// Parse raw file bytes, decoding UTF-16 if a BOM is present
+    pub fn parse_binary(data: &[u8]) -> Result<Log, ParseError> {
+        let contents = match Self::decode_utf16(data) {
+            Some(decoded) => decoded,
+            None => String::from_utf8_lossy(data).into_owned(),
+        };
+        Self.parse(&contents)
+    }
+}
+
+impl Parseable for BlueDriver {
+    fn parse(&self, file_contents: &str) -> Result<Log, ParseError> {
+        let mut lines = file_contents.trim_start_matches('\u{feff}').lines();
+
+        // Two-line preamble: title and capture date
+        let title = lines
+            .next()
+            .map(|l| l.trim().to_string())
+            .ok_or_else(|| ParseError::invalid("Empty file: no BlueDriver preamble found"))?;
+        if !title.starts_with(TITLE_MARKER) {
+            return Err(ParseError::bad_header(1, "missing BlueDriver title line"));
+        }
+        let capture_date = lines
+            .next()
+            .map(|l| l.trim().to_string())
+            .ok_or_else(|| ParseError::bad_header(2, "missing capture date line"))?;
+
+        let header = lines
+            .next()
+            .ok_or_else(|| ParseError::bad_header(3, "no column header found"))?;
+        let column_names: Vec<&str> = header.split(',').collect();
+
+        let first_col = column_names[0].trim().to_lowercase();
+        if !first_col.starts_with("time") {
+            return Err(ParseError::bad_header(3, "first column must be Time"));
+        }
+
+        let channels: Vec<Channel> = column_names
+            .iter()
+            .skip(1)
+            .map(|name| Channel::BlueDriver(BlueDriverChannel::from_header(name)))
+            .collect();
+
+        if channels.is_empty() {
+            return Err(ParseError::bad_header(3, "no channels found"));
+        }
+
+        let mut times: Vec<f64> = Vec::new();
+        let mut data = ColumnBuilder::new(channels.len());
+
+        // Channels are polled one after another, so carry the last known value
+        // forward through empty cells instead of dropping to zero
+        let mut last_values: Vec<f64> = vec![MISSING; channels.len()];
+        let mut first_time: Option<f64> = None;
+
+        for line in lines {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut parts = line.split(',');
+            let Some(time_secs) = parts.next().and_then(|t| t.trim().parse::<f64>().ok()) else {
+                continue;
+            };
+
+            let relative_time = match first_time {
+                Some(first) => time_secs - first,
+                None => {
+                    first_time = Some(time_secs);
+                    0.0
+                }
+            };
+
+            for (idx, part) in parts.take(channels.len()).enumerate() {
+                if let Ok(val) = part.trim().parse::<f64>() {
+                    last_values[idx] = val;
+                }
+            }
+
+            times.push(relative_time);
+            data.push_record(&last_values);
+        }
+
+        tracing::info!(
+            "Parsed BlueDriver log: {} channels, {} data points",
+            channels.len(),
+            data.len()
+        );
+
+        Ok(Log::new(
+            Meta::BlueDriver(BlueDriverMeta {
+                title,
+                capture_date,
+                channel_count: channels.len(),
+                data_points: data.len(),
+            }),
+            channels,
+            times,
+            data.into_columns(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "BlueDriver Data Log\n\
+        Nov 9, 2025\n\
+        Time(s), Engine RPM (rpm), Long Term Fuel Trim Bank 1 (%)\n\
+        0.00, 812, 1.6\n\
+        0.40, , 1.5\n\
+        0.80, 845, \n";
+
+    fn encode_utf16le(s: &str) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in s.encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_channel_from_header() {
+        let ch =
+            BlueDriverChannel::from_header(" O2 Bank 1 - Sensor 2 - Oxygen Sensor Voltage (V)");
+        assert_eq!(ch.name, "O2 Bank 1 - Sensor 2 - Oxygen Sensor Voltage");
+        assert_eq!(ch.unit, "V");
+
+        let ch = BlueDriverChannel::from_header("Calculated Load");
+        assert_eq!(ch.name, "Calculated Load");
+        assert_eq!(ch.unit, "");
+    }
+
+    #[test]
+    fn test_detect() {
+        assert!(BlueDriver::detect(SAMPLE));
+        assert!(BlueDriver::detect(&format!("\u{feff}{}", SAMPLE)));
+        assert!(!BlueDriver::detect(
+            "Time (msec),Engine Speed (rpm)\n0,1000"
+        ));
+        assert!(!BlueDriver::detect("%DataLog%\nDataLogVersion : 1.1"));
+    }
+
+    #[test]
+    fn test_decode_utf16() {
+        let decoded = BlueDriver::decode_utf16(&encode_utf16le(SAMPLE)).unwrap();
+        assert_eq!(decoded, SAMPLE);
+
+        let mut be = vec![0xFE, 0xFF];
+        for unit in "BlueDriver".encode_utf16() {
+            be.extend_from_slice(&unit.to_be_bytes());
+        }
+        assert_eq!(BlueDriver::decode_utf16(&be).unwrap(), "BlueDriver");
+
+        assert!(BlueDriver::decode_utf16(SAMPLE.as_bytes()).is_none());
+    }
+
+    #[test]
+    fn test_parse_carries_forward_empty_cells() {
+        let log = BlueDriver::parse_binary(&encode_utf16le(SAMPLE)).unwrap();
+
+        assert_eq!(log.channels.len(), 2);
+        assert_eq!(log.channels[0].name(), "Engine RPM");
+        assert_eq!(log.channels[0].unit(), "rpm");
+        assert_eq!(log.times, vec![0.0, 0.4, 0.8]);
+
+        assert_eq!(log.channel_data(0), vec![812.0, 812.0, 845.0]);
+        assert_eq!(log.channel_data(1), vec![1.6, 1.5, 1.5]);
+
+        if let Meta::BlueDriver(meta) = &log.meta {
+            assert_eq!(meta.capture_date, "Nov 9, 2025");
+        } else {
+            panic!("Expected BlueDriver metadata");
+        }
+    }
+}
@@ -0,0 +1,998 @@
+//! Raw CAN bus traces decoded through a protocol spec.
+//!
+//! Reads frames recorded by common bus loggers and decodes them with a
+//! [`ProtocolSpec`] into an ordinary [`Log`]:
+//!
+//! - **candump** (can-utils): log files written with `-l`
+//!   (`(1436509052.249713) can0 360#0BB8...`) and screen output with
+//!   timestamps (`(1436509052.249713)  can0  360   [8]  0B B8 ...`)
+//! - **Vector ASC**: classic and CAN FD frame lines, hex or decimal
+//!   `base`, absolute or relative timestamps
+//! - **SavvyCAN CSV**: the GVRET layout
+//!   (`Time Stamp,ID,Extended,Dir,Bus,LEN,D1,...`), timestamps in µs
+//!
+//! Every signal of every message seen in the trace becomes a channel named
+//! through field normalization, so analyzers find the channels they expect.
+//! Messages arrive at their own rates, so each signal keeps its exact
+//! samples as a native series and is held onto a shared timebase running
+//! at the fastest message's rate.
+//!
+//! [`CanStream`] decodes frames from a live bus the same way, growing a log
+//! as they arrive.
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::datetime;
+use super::error::ParseError;
+use super::types::{Channel, Log, LogInfo, Meta, NativeSeries, Parseable, ValueKind, MISSING};
+use crate::adapters::can::{CanDecoder, CanFrame, CAN_PAYLOAD};
+use crate::adapters::{get_protocols, ProtocolSpec, SignalDataType};
+use crate::normalize::normalize_channel_name;
+
+/// Finest spacing of the shared timebase, in seconds. Faster messages
+/// still keep every sample in their native series.
+const MIN_RECORD_INTERVAL: f64 = 0.01;
+
+/// Spacing of a live log's shared timebase, in seconds. Message rates
+/// aren't known up front, so live records run at a fixed rate.
+pub const LIVE_RECORD_INTERVAL: f64 = 0.05;
+
+/// Timestamps above this are seconds since the Unix epoch (2001 onwards)
+const EPOCH_SECONDS: f64 = 1.0e9;
+
+/// A silence longer than this between frames, in seconds, sets apart a
+/// cluster of frames that may be stray timestamps
+const OUTLIER_GAP: f64 = 3600.0;
+
+/// Clusters of at most this many frames, set apart from a bigger one, are
+/// dropped, so a stray absolute timestamp among relative ones can't stretch
+/// the timebase over decades. Longer stretches are real logging.
+const OUTLIER_CLUSTER_FRAMES: usize = 8;
+
+/// Most values the shared timebase may hold across all channels. Longer
+/// traces get a coarser timebase; native series keep every sample.
+const MAX_TIMEBASE_VALUES: usize = 20_000_000;
+
+/// Lines checked when sniffing a trace
+const SNIFF_LINES: usize = 5;
+
+/// Recorded trace layouts
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TraceFormat {
+    Candump,
+    VectorAsc,
+    SavvyCan,
+}
+
+impl TraceFormat {
+    /// Display name of the layout
+    pub fn name(&self) -> &'static str {
+        match self {
+            TraceFormat::Candump => "candump",
+            TraceFormat::VectorAsc => "Vector ASC",
+            TraceFormat::SavvyCan => "SavvyCAN CSV",
+        }
+    }
+
+    /// Recognise a trace layout from the start of a file
+    pub fn detect(text: &str) -> Option<Self> {
+        let mut lines = text
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty());
+        let first = lines.next()?;
+
+        if first.to_lowercase().starts_with("time stamp,id,extended") {
+            return Some(TraceFormat::SavvyCan);
+        }
+
+        let head: Vec<&str> = std::iter::once(first).chain(lines.take(20)).collect();
+        if head.iter().any(|line| {
+            let line = line.to_lowercase();
+            line.starts_with("begin triggerblock")
+                || line.starts_with("base hex")
+                || line.starts_with("base dec")
+        }) {
+            return Some(TraceFormat::VectorAsc);
+        }
+
+        head.iter()
+            .take(SNIFF_LINES)
+            .all(|line| parse_candump_line(line).is_some())
+            .then_some(TraceFormat::Candump)
+    }
+}
+
+/// A frame from a trace with its timestamp
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceFrame {
+    /// Time in seconds, as recorded
+    pub time: f64,
+    pub frame: CanFrame,
+}
+
+/// Frames read from a trace file
+#[derive(Clone, Debug, Default)]
+pub struct Trace {
+    /// Frames in time order
+    pub frames: Vec<TraceFrame>,
+    /// Wall-clock time of the first frame, when the trace records it
+    pub start: Option<NaiveDateTime>,
+    /// Lines that looked like frames but couldn't be read
+    pub skipped_lines: usize,
+    /// Frames dropped for timestamps far away from the rest of the trace
+    pub outlier_frames: usize,
+}
+
+/// Read every frame of a trace
+pub fn read_trace(format: TraceFormat, text: &str) -> Trace {
+    let text = text.trim_start_matches('\u{feff}');
+    let mut trace = match format {
+        TraceFormat::Candump => read_candump(text),
+        TraceFormat::VectorAsc => read_asc(text),
+        TraceFormat::SavvyCan => read_savvycan(text),
+    };
+    // Several buses or interfaces can interleave slightly out of order
+    trace.frames.sort_by(|a, b| a.time.total_cmp(&b.time));
+    drop_outliers(&mut trace);
+
+    if trace.start.is_none() {
+        trace.start = trace
+            .frames
+            .first()
+            .and_then(|first| epoch_start(first.time));
+    }
+    trace
+}
+
+/// Drop small clusters of frames set apart from the rest of the trace by
+/// silences longer than [`OUTLIER_GAP`], counting them as outliers
+fn drop_outliers(trace: &mut Trace) {
+    let frames = &trace.frames;
+    let mut clusters = Vec::new();
+    let mut start = 0;
+    for end in 1..=frames.len() {
+        if end == frames.len() || frames[end].time - frames[end - 1].time > OUTLIER_GAP {
+            clusters.push(start..end);
+            start = end;
+        }
+    }
+
+    let largest = clusters.iter().map(|c| c.len()).max().unwrap_or(0);
+    let mut keep = vec![true; frames.len()];
+    for cluster in clusters {
+        if cluster.len() <= OUTLIER_CLUSTER_FRAMES && cluster.len() < largest {
+            keep[cluster].fill(false);
+        }
+    }
+    let mut keep = keep.into_iter();
+    let before = trace.frames.len();
+    trace.frames.retain(|_| keep.next().unwrap_or(true));
+    trace.outlier_frames += before - trace.frames.len();
+}
+
+/// Local wall-clock time for a timestamp in seconds since the Unix epoch
+fn epoch_start(seconds: f64) -> Option<NaiveDateTime> {
+    if seconds < EPOCH_SECONDS {
+        return None;
+    }
+    let whole = seconds.floor();
+    let nanos = ((seconds - whole) * 1e9).round().min(999_999_999.0) as u32;
+    DateTime::from_timestamp(whole as i64, nanos).map(|utc| utc.with_timezone(&Local).naive_local())
+}
+
+fn parse_hex_bytes<'a>(tokens: impl Iterator<Item = &'a str>, radix: u32) -> Option<Vec<u8>> {
+    tokens
+        .map(|token| u8::from_str_radix(token, radix).ok())
+        .collect()
+}
+
+/// Identifier from a trace, with candump's rule that 8-digit IDs are extended
+fn parse_id(text: &str) -> Option<(u32, bool)> {
+    let id = u32::from_str_radix(text, 16).ok()?;
+    Some((id, text.len() > 3))
+}
+
+// ============================================================================
+// candump
+// ============================================================================
+
+/// Parse one line of candump output
+fn parse_candump_line(line: &str) -> Option<TraceFrame> {
+    let mut tokens = line.split_whitespace();
+    let time = tokens
+        .next()?
+        .strip_prefix('(')?
+        .strip_suffix(')')?
+        .parse::<f64>()
+        .ok()?;
+    let _interface = tokens.next()?;
+    let frame_text = tokens.next()?;
+
+    let frame = match frame_text.split_once('#') {
+        // Log format: ID#DATA, or ID##<flags>DATA for CAN FD
+        Some((id, data)) => {
+            let (id, extended) = parse_id(id)?;
+            // Remote frames carry no data
+            if data.starts_with('R') {
+                return None;
+            }
+            let data = match data.strip_prefix('#') {
+                Some(fd) => fd.get(1..)?,
+                None => data,
+            };
+            if data.len() % 2 != 0 {
+                return None;
+            }
+            let bytes = (0..data.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&data[i..i + 2], 16).ok())
+                .collect::<Option<Vec<u8>>>()?;
+            CanFrame::new(id, extended, bytes)
+        }
+        // Screen format: ID [LEN] BYTES
+        None => {
+            let (id, extended) = parse_id(frame_text)?;
+            let length: usize = tokens
+                .next()?
+                .strip_prefix('[')?
+                .strip_suffix(']')?
+                .parse()
+                .ok()?;
+            let bytes = parse_hex_bytes(tokens.take(length), 16)?;
+            if bytes.len() != length {
+                return None;
+            }
+            CanFrame::new(id, extended, bytes)
+        }
+    };
+    Some(TraceFrame { time, frame })
+}
+
+fn read_candump(text: &str) -> Trace {
+    let mut trace = Trace::default();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        match parse_candump_line(line) {
+            Some(frame) => trace.frames.push(frame),
+            None => trace.skipped_lines += 1,
+        }
+    }
+    trace
+}
+
+/// Format a frame as a candump log line (`candump -l`), which reads back
+/// with [`read_trace`]
+pub fn candump_line(interface: &str, trace_frame: &TraceFrame) -> String {
+    let frame = &trace_frame.frame;
+    let id = if frame.extended {
+        format!("{:08X}", frame.id)
+    } else {
+        format!("{:03X}", frame.id)
+    };
+    let data: String = frame.data.iter().map(|b| format!("{:02X}", b)).collect();
+    // Payloads only CAN FD can carry take the `##<flags>` form
+    let separator = if frame.data.len() > CAN_PAYLOAD {
+        "##0"
+    } else {
+        "#"
+    };
+    format!(
+        "({:.6}) {} {}{}{}",
+        trace_frame.time, interface, id, separator, data
+    )
+}
+
+// ============================================================================
+// Vector ASC
+// ============================================================================
+
+/// Parse one frame line of an ASC trace: classic
+/// `time channel id dir d dlc bytes...` or CAN FD
+/// `time CANFD channel dir id [name] brs esi dlc length bytes...`
+fn parse_asc_line(line: &str, radix: u32) -> Option<TraceFrame> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let time = tokens.first()?.parse::<f64>().ok()?;
+
+    let parse_asc_id = |text: &str| -> Option<(u32, bool)> {
+        match text.strip_suffix(['x', 'X']) {
+            Some(id) => Some((u32::from_str_radix(id, radix).ok()?, true)),
+            None => Some((u32::from_str_radix(text, radix).ok()?, false)),
+        }
+    };
+
+    if tokens.get(1)?.eq_ignore_ascii_case("CANFD") {
+        let (id, extended) = parse_asc_id(tokens.get(4)?)?;
+        // An optional symbolic message name precedes the BRS flag
+        let mut rest = &tokens[5..];
+        if rest.first()?.parse::<u8>().is_err() {
+            rest = &rest[1..];
+        }
+        let length: usize = rest.get(3)?.parse().ok()?;
+        let bytes = parse_hex_bytes(rest.get(4..4 + length)?.iter().copied(), radix)?;
+        return Some(TraceFrame {
+            time,
+            frame: CanFrame::new(id, extended, bytes),
+        });
+    }
+
+    // Classic data frame
+    let _channel: u8 = tokens.get(1)?.parse().ok()?;
+    let (id, extended) = parse_asc_id(tokens.get(2)?)?;
+    if !tokens.get(4)?.eq_ignore_ascii_case("d") {
+        return None;
+    }
+    let length = usize::from_str_radix(tokens.get(5)?, 16).ok()?;
+    let bytes = parse_hex_bytes(tokens.get(6..6 + length)?.iter().copied(), radix)?;
+    Some(TraceFrame {
+        time,
+        frame: CanFrame::new(id, extended, bytes),
+    })
+}
+
+fn read_asc(text: &str) -> Trace {
+    let mut trace = Trace::default();
+    let mut radix = 16;
+    let mut relative = false;
+    let mut previous = 0.0;
+
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let lower = line.to_lowercase();
+        if let Some(date) = lower.strip_prefix("date ") {
+            trace.start = datetime::parse_start(date);
+            continue;
+        }
+        if lower.starts_with("base ") {
+            radix = if lower.contains("base dec") { 10 } else { 16 };
+            relative = lower.contains("timestamps relative");
+            continue;
+        }
+        // Everything else that doesn't start with a time is a header or comment
+        if !line.starts_with(|c: char| c.is_ascii_digit()) {
+            continue;
+        }
+
+        match parse_asc_line(line, radix) {
+            Some(mut frame) => {
+                if relative {
+                    frame.time += previous;
+                    previous = frame.time;
+                }
+                trace.frames.push(frame);
+            }
+            // Event lines such as "Start of measurement" and error frames
+            None => trace.skipped_lines += 1,
+        }
+    }
+    trace
+}
+
+// ============================================================================
+// SavvyCAN
+// ============================================================================
+
+fn read_savvycan(text: &str) -> Trace {
+    let mut trace = Trace::default();
+    let mut lines = text.lines();
+    let Some(header) = lines.next() else {
+        return trace;
+    };
+
+    let columns: Vec<String> = header
+        .split(',')
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+    let find = |name: &str| columns.iter().position(|column| column == name);
+    let (Some(time_idx), Some(id_idx), Some(length_idx), Some(data_idx)) =
+        (find("time stamp"), find("id"), find("len"), find("d1"))
+    else {
+        return trace;
+    };
+    let extended_idx = find("extended");
+
+    for line in lines.filter(|line| !line.trim().is_empty()) {
+        let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+        let frame = (|| {
+            let micros: f64 = cells.get(time_idx)?.parse().ok()?;
+            let id_text = cells.get(id_idx)?;
+            let id = u32::from_str_radix(id_text.trim_start_matches("0x"), 16).ok()?;
+            let extended = match extended_idx.and_then(|idx| cells.get(idx)) {
+                Some(flag) => flag.eq_ignore_ascii_case("true"),
+                None => id > crate::adapters::can::MAX_STANDARD_ID,
+            };
+            let length: usize = cells.get(length_idx)?.parse().ok()?;
+            let bytes =
+                parse_hex_bytes(cells.get(data_idx..data_idx + length)?.iter().copied(), 16)?;
+            Some(TraceFrame {
+                time: micros / 1e6,
+                frame: CanFrame::new(id, extended, bytes),
+            })
+        })();
+
+        match frame {
+            Some(frame) => trace.frames.push(frame),
+            None => trace.skipped_lines += 1,
+        }
+    }
+    trace
+}
+
+// ============================================================================
+// Decoding
+// ============================================================================
+
+/// CAN trace log metadata
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CanTraceMeta {
+    /// Trace layout name (e.g., "candump")
+    pub trace_format: String,
+    /// Id of the protocol spec the frames were decoded with
+    pub protocol_id: String,
+    /// Display name of the protocol spec
+    pub protocol_name: String,
+    /// ECU vendor from the protocol spec
+    pub vendor: String,
+    /// Frames read from the trace
+    pub frames: usize,
+    /// Frames the protocol doesn't define
+    pub unmatched_frames: usize,
+    /// Lines that looked like frames but couldn't be read
+    pub skipped_lines: usize,
+    /// Frames dropped for timestamps far away from the rest of the trace
+    pub outlier_frames: usize,
+    /// Spacing of the shared timebase in seconds, when a long trace needed a
+    /// coarser one than its fastest message. Native series keep every sample.
+    pub coarsened_interval: Option<f64>,
+}
+
+impl CanTraceMeta {
+    /// Common metadata, reporting the protocol used
+    pub fn info(&self) -> LogInfo {
+        let mut info = LogInfo::new(&format!("{} CAN trace", self.trace_format));
+        info.push_vendor("Protocol", &self.protocol_name);
+        info.push_vendor("Vendor", &self.vendor);
+        info.push_vendor("Frames", self.frames);
+        if self.unmatched_frames > 0 {
+            info.push_vendor("Unmatched frames", self.unmatched_frames);
+        }
+        if self.skipped_lines > 0 {
+            info.push_vendor("Unreadable lines", self.skipped_lines);
+        }
+        if self.outlier_frames > 0 {
+            info.push_vendor("Outlier frames dropped", self.outlier_frames);
+        }
+        if let Some(interval) = self.coarsened_interval {
+            info.push_vendor("Record interval", format!("{:.3} s", interval));
+        }
+        info
+    }
+}
+
+/// A signal decoded from CAN frames
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CanSignalChannel {
+    /// Display name, normalized from the signal name
+    pub name: String,
+    /// Signal name in the protocol spec
+    pub signal: String,
+    /// Name of the message carrying the signal
+    pub message: String,
+    /// Identifier of the message carrying the signal
+    pub message_id: u32,
+    /// Physical unit from the signal spec
+    pub unit: String,
+    pub display_min: Option<f64>,
+    pub display_max: Option<f64>,
+    /// Enum or boolean states of the signal
+    pub value_kind: ValueKind,
+}
+
+impl CanSignalChannel {
+    /// Get the display unit for this channel
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+}
+
+/// A protocol's fit to a trace
+#[derive(Clone, Debug)]
+pub struct ProtocolMatch {
+    pub protocol: ProtocolSpec,
+    /// Distinct frame identifiers in the trace the protocol defines
+    pub matched_ids: usize,
+}
+
+/// Decoded samples of one message
+struct MessageSamples {
+    times: Vec<f64>,
+    /// `values[signal][sample]`
+    values: Vec<Vec<f64>>,
+}
+
+/// CAN trace parser decoding frames with a protocol spec
+pub struct CanTrace {
+    protocol: ProtocolSpec,
+}
+
+impl CanTrace {
+    /// Registered format id of CAN traces
+    pub const FORMAT_ID: &'static str = "can-trace";
+
+    /// Create a parser decoding frames with `protocol`
+    pub fn new(protocol: ProtocolSpec) -> Self {
+        Self { protocol }
+    }
+
+    /// The protocol spec frames are decoded with
+    pub fn protocol(&self) -> &ProtocolSpec {
+        &self.protocol
+    }
+
+    /// Check whether the file contents are a CAN trace
+    pub fn detect(contents: &str) -> bool {
+        TraceFormat::detect(contents).is_some()
+    }
+
+    /// Distinct frame identifiers in a trace
+    pub fn trace_ids(contents: &str) -> HashSet<(u32, bool)> {
+        TraceFormat::detect(contents)
+            .map(|format| read_trace(format, contents))
+            .unwrap_or_default()
+            .frames
+            .iter()
+            .map(|f| (f.frame.id, f.frame.extended))
+            .collect()
+    }
+
+    /// Score `protocols` by how many of the trace's identifiers each
+    /// defines, best first
+    pub fn rank_protocols(contents: &str, protocols: Vec<ProtocolSpec>) -> Vec<ProtocolMatch> {
+        let ids = Self::trace_ids(contents);
+        let mut matches: Vec<ProtocolMatch> = protocols
+            .into_iter()
+            .map(|protocol| {
+                let decoder = CanDecoder::new(protocol.clone());
+                let matched_ids = ids
+                    .iter()
+                    .filter(|(id, extended)| decoder.defines(*id, *extended))
+                    .count();
+                ProtocolMatch {
+                    protocol,
+                    matched_ids,
+                }
+            })
+            .collect();
+        matches.sort_by_key(|m| std::cmp::Reverse(m.matched_ids));
+        matches
+    }
+
+    /// Parser for the loaded protocol that defines most of the trace's
+    /// identifiers, used when nobody picked one
+    pub fn best_match(contents: &str) -> Option<Self> {
+        Self::rank_protocols(contents, get_protocols())
+            .into_iter()
+            .find(|m| m.matched_ids > 0)
+            .map(|m| Self::new(m.protocol))
+    }
+
+    /// Build the channel for a signal, named through normalization and
+    /// kept unique within the log
+    fn plan_channel(
+        &self,
+        message: usize,
+        signal: usize,
+        used_names: &mut HashSet<String>,
+    ) -> CanSignalChannel {
+        let message = &self.protocol.messages[message];
+        let spec = &message.signals[signal];
+
+        let mut name = normalize_channel_name(&spec.name);
+        if !used_names.insert(name.to_lowercase()) {
+            name = format!("{} ({})", name, message.name);
+            used_names.insert(name.to_lowercase());
+        }
+
+        let enum_spec = spec.enum_ref.as_deref().and_then(|enum_name| {
+            self.protocol
+                .enums
+                .as_ref()?
+                .iter()
+                .find(|e| e.name == enum_name)
+        });
+        let value_kind = match enum_spec {
+            Some(enum_spec) => ValueKind::from_labels(&enum_spec.values),
+            None if spec.length == 1 && spec.data_type == SignalDataType::Unsigned => {
+                ValueKind::boolean()
+            }
+            None => ValueKind::Numeric,
+        };
+
+        CanSignalChannel {
+            name,
+            signal: spec.name.clone(),
+            message: message.name.clone(),
+            message_id: message.id,
+            unit: spec.unit.clone().unwrap_or_default(),
+            display_min: spec.min,
+            display_max: spec.max,
+            value_kind,
+        }
+    }
+
+    /// Decode frames read from a trace into a log
+    pub fn decode(&self, format: TraceFormat, trace: &Trace) -> Result<Log, ParseError> {
+        let Some(first) = trace.frames.first() else {
+            return Err(ParseError::invalid(format!(
+                "No CAN frames found in the {} trace",
+                format.name()
+            )));
+        };
+        let first_time = first.time;
+
+        let mut decoder = CanDecoder::new(self.protocol.clone());
+        let mut messages: BTreeMap<usize, MessageSamples> = BTreeMap::new();
+        let mut unmatched_frames = 0;
+        for trace_frame in &trace.frames {
+            let Some(decoded) = decoder.decode(&trace_frame.frame) else {
+                unmatched_frames += 1;
+                continue;
+            };
+            let samples = messages
+                .entry(decoded.message)
+                .or_insert_with(|| MessageSamples {
+                    times: Vec::new(),
+                    values: vec![Vec::new(); decoded.values.len()],
+                });
+            samples.times.push(trace_frame.time - first_time);
+            for (column, value) in samples.values.iter_mut().zip(decoded.values) {
+                column.push(value);
+            }
+        }
+
+        if messages.is_empty() {
+            return Err(ParseError::invalid(format!(
+                "None of the {} frames in this trace match the {} protocol",
+                trace.frames.len(),
+                self.protocol.name
+            )));
+        }
+
+        // Shared timebase at the fastest message's rate
+        let end_time = trace.frames.last().map_or(0.0, |f| f.time - first_time);
+        let interval = messages
+            .values()
+            .filter_map(|samples| median_period(&samples.times))
+            .fold(f64::INFINITY, f64::min);
+        let interval = if interval.is_finite() {
+            // Epoch timestamps lose precision below a microsecond
+            ((interval * 1e6).round() / 1e6).max(MIN_RECORD_INTERVAL)
+        } else {
+            1.0
+        };
+        // Coarsen very long traces so the dense columns stay affordable
+        let channel_count: usize = messages.values().map(|s| s.values.len()).sum();
+        let coarsened_interval = coarsened_interval(interval, end_time, channel_count);
+        if let Some(coarse) = coarsened_interval {
+            tracing::warn!(
+                "Trace too long for a {} s timebase; records are {} s apart",
+                interval,
+                coarse
+            );
+        }
+        let interval = coarsened_interval.unwrap_or(interval);
+        // Keep the last frame when rounding leaves the end just short of a record
+        let records = (end_time / interval + 1e-6).floor() as usize + 1;
+        let times: Vec<f64> = (0..records).map(|i| i as f64 * interval).collect();
+
+        let mut used_names = HashSet::new();
+        let mut channels = Vec::new();
+        let mut columns = Vec::new();
+        let mut native = Vec::new();
+        for (&message, samples) in &messages {
+            for (signal, values) in samples.values.iter().enumerate() {
+                channels.push(Channel::CanSignal(self.plan_channel(
+                    message,
+                    signal,
+                    &mut used_names,
+                )));
+                columns.push(hold(&samples.times, values, &times));
+                native.push(NativeSeries {
+                    times: samples.times.clone(),
+                    values: values.clone(),
+                });
+            }
+        }
+
+        tracing::info!(
+            "Decoded {} trace with {}: {} channels, {} frames ({} unmatched)",
+            format.name(),
+            self.protocol.name,
+            channels.len(),
+            trace.frames.len(),
+            unmatched_frames
+        );
+        if trace.skipped_lines > 0 {
+            tracing::warn!("Skipped {} unreadable trace lines", trace.skipped_lines);
+        }
+        if trace.outlier_frames > 0 {
+            tracing::warn!(
+                "Dropped {} frames with timestamps far outside the trace",
+                trace.outlier_frames
+            );
+        }
+
+        let mut log = Log::new(
+            Meta::CanTrace(CanTraceMeta {
+                trace_format: format.name().to_string(),
+                protocol_id: self.protocol.id.clone(),
+                protocol_name: self.protocol.name.clone(),
+                vendor: self.protocol.vendor.clone(),
+                frames: trace.frames.len(),
+                unmatched_frames,
+                skipped_lines: trace.skipped_lines,
+                outlier_frames: trace.outlier_frames,
+                coarsened_interval,
+            }),
+            channels,
+            times,
+            columns,
+        );
+        for (idx, series) in native.into_iter().enumerate() {
+            log.set_native_series(idx, series);
+        }
+        log.start = trace.start;
+        Ok(log)
+    }
+}
+
+impl Parseable for CanTrace {
+    fn parse(&self, file_contents: &str) -> Result<Log, ParseError> {
+        let format = TraceFormat::detect(file_contents)
+            .ok_or_else(|| ParseError::invalid("Not a candump, Vector ASC or SavvyCAN trace"))?;
+        self.decode(format, &read_trace(format, file_contents))
+    }
+}
+
+/// Incremental decoder growing a log from frames of a live bus
+///
+/// Channels are added the first time their message arrives. Every decoded
+/// sample goes into its channel's native series, and records are appended
+/// every [`LIVE_RECORD_INTERVAL`] holding each signal's latest value.
+pub struct CanStream {
+    trace: CanTrace,
+    decoder: CanDecoder,
+    /// Log index of the first channel of each message seen so far
+    message_channels: HashMap<usize, usize>,
+    used_names: HashSet<String>,
+    /// Latest value of every channel, held onto new records
+    current: Vec<f64>,
+    /// Timestamp of the first frame, which becomes time zero
+    first_time: Option<f64>,
+    /// Index of the next record to append
+    next_record: usize,
+}
+
+impl CanStream {
+    /// Create a stream decoding frames with `protocol`
+    pub fn new(protocol: ProtocolSpec) -> Self {
+        Self {
+            decoder: CanDecoder::new(protocol.clone()),
+            trace: CanTrace::new(protocol),
+            message_channels: HashMap::new(),
+            used_names: HashSet::new(),
+            current: Vec::new(),
+            first_time: None,
+            next_record: 0,
+        }
+    }
+
+    /// The protocol spec frames are decoded with
+    pub fn protocol(&self) -> &ProtocolSpec {
+        self.trace.protocol()
+    }
+
+    /// An empty log for the stream to grow, naming where frames come from
+    /// (e.g. "SocketCAN vcan0")
+    pub fn empty_log(&self, source: &str) -> Log {
+        let protocol = self.protocol();
+        Log::new(
+            Meta::CanTrace(CanTraceMeta {
+                trace_format: source.to_string(),
+                protocol_id: protocol.id.clone(),
+                protocol_name: protocol.name.clone(),
+                vendor: protocol.vendor.clone(),
+                frames: 0,
+                ..Default::default()
+            }),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    /// Decode a frame into `log`, returning whether the protocol defines it
+    ///
+    /// Frame times are seconds on any origin; the first frame becomes time
+    /// zero, and epoch timestamps give the log its wall-clock start.
+    pub fn push(&mut self, log: &mut Log, trace_frame: &TraceFrame) -> bool {
+        let first_time = *self.first_time.get_or_insert(trace_frame.time);
+        if log.start.is_none() {
+            log.start = epoch_start(first_time);
+        }
+        // Frames can reach the socket slightly out of order across interfaces
+        let time = (trace_frame.time - first_time).max(log.times.last().copied().unwrap_or(0.0));
+        self.fill_records(log, time);
+
+        let decoded = self.decoder.decode(&trace_frame.frame);
+        if let Meta::CanTrace(meta) = &mut log.meta {
+            meta.frames += 1;
+            meta.unmatched_frames += usize::from(decoded.is_none());
+        }
+        let Some(decoded) = decoded else {
+            return false;
+        };
+
+        let first_channel = match self.message_channels.get(&decoded.message) {
+            Some(&index) => index,
+            None => {
+                let index = log.channels.len();
+                for signal in 0..decoded.values.len() {
+                    let channel =
+                        self.trace
+                            .plan_channel(decoded.message, signal, &mut self.used_names);
+                    log.push_channel(Channel::CanSignal(channel));
+                    self.current.push(MISSING);
+                }
+                self.message_channels.insert(decoded.message, index);
+                index
+            }
+        };
+        for (offset, value) in decoded.values.into_iter().enumerate() {
+            self.current[first_channel + offset] = value;
+            log.push_native_sample(first_channel + offset, time, value);
+        }
+        true
+    }
+
+    /// Append the records due before `time`, holding the current values
+    fn fill_records(&mut self, log: &mut Log, time: f64) {
+        // Tolerate rounding in the timebase's multiples of the interval
+        const EPSILON: f64 = 1e-9;
+
+        loop {
+            let record_time = self.next_record as f64 * LIVE_RECORD_INTERVAL;
+            if record_time >= time - EPSILON {
+                break;
+            }
+            log.push_record(record_time, &self.current);
+            self.next_record += 1;
+        }
+    }
+}
+
+/// A wider timebase interval when `interval` would need more than
+/// [`MAX_TIMEBASE_VALUES`] values to cover `end_time` for every channel
+fn coarsened_interval(interval: f64, end_time: f64, channels: usize) -> Option<f64> {
+    let max_records = (MAX_TIMEBASE_VALUES / channels.max(1)).max(2);
+    let coarse = end_time / (max_records - 1) as f64;
+    (coarse > interval).then_some(coarse)
+}
+
+/// Median spacing of sample times, or `None` with fewer than two samples
+fn median_period(times: &[f64]) -> Option<f64> {
+    let mut periods: Vec<f64> = times
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .filter(|period| *period > 0.0)
+        .collect();
+    if periods.is_empty() {
+        return None;
+    }
+    let middle = periods.len() / 2;
+    let (_, median, _) = periods.select_nth_unstable_by(middle, f64::total_cmp);
+    Some(*median)
+}
+
+/// Hold each sample until the next onto the shared timebase
+fn hold(times: &[f64], values: &[f64], timebase: &[f64]) -> Vec<f64> {
+    // Tolerate rounding in the timebase's multiples of the interval
+    const EPSILON: f64 = 1e-9;
+
+    let mut held = Vec::with_capacity(timebase.len());
+    let mut next = 0;
+    let mut current = MISSING;
+    for &time in timebase {
+        while next < times.len() && times[next] <= time + EPSILON {
+            current = values[next];
+            next += 1;
+        }
+        held.push(current);
+    }
+    held
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_layouts() {
+        assert_eq!(
+            TraceFormat::detect("(1436509052.249713) can0 360#0BB8\n"),
+            Some(TraceFormat::Candump)
+        );
+        assert_eq!(
+            TraceFormat::detect(" (0.000000)  can0  360   [2]  0B B8\n"),
+            Some(TraceFormat::Candump)
+        );
+        assert_eq!(
+            TraceFormat::detect("date Wed Jun 14 09:30:12 2023\nbase hex  timestamps absolute\n"),
+            Some(TraceFormat::VectorAsc)
+        );
+        assert_eq!(
+            TraceFormat::detect("Time Stamp,ID,Extended,Dir,Bus,LEN,D1\n"),
+            Some(TraceFormat::SavvyCan)
+        );
+        assert_eq!(TraceFormat::detect("Time,RPM\n0,900\n"), None);
+        assert_eq!(TraceFormat::detect(""), None);
+    }
+
+    #[test]
+    fn test_candump_lines() {
+        let frame = parse_candump_line("(1.5) can0 1F0A000#007D").unwrap();
+        assert_eq!(frame.time, 1.5);
+        assert_eq!(
+            frame.frame,
+            CanFrame::new(0x1F0A000, true, vec![0x00, 0x7D])
+        );
+
+        let fd = parse_candump_line("(2.0) can1 123##1AABB").unwrap();
+        assert_eq!(fd.frame, CanFrame::new(0x123, false, vec![0xAA, 0xBB]));
+
+        assert!(parse_candump_line("(2.0) can0 123#R").is_none());
+        assert!(parse_candump_line("can0 123#00").is_none());
+    }
+
+    #[test]
+    fn test_asc_lines() {
+        let classic = parse_asc_line("0.001234 1  360  Rx   d 2 0B B8", 16).unwrap();
+        assert_eq!(classic.frame, CanFrame::new(0x360, false, vec![0x0B, 0xB8]));
+
+        let extended = parse_asc_line("0.5 2  1F0A000x  Tx   d 1 7D", 16).unwrap();
+        assert!(extended.frame.extended);
+
+        let fd = parse_asc_line(
+            "0.75 CANFD 1 Rx 370 Engine 1 0 9 12 01 02 03 04 05 06 07 08 09 0A 0B 0C",
+            16,
+        )
+        .unwrap();
+        assert_eq!(fd.frame.id, 0x370);
+        assert_eq!(fd.frame.data.len(), 12);
+
+        assert!(parse_asc_line("0.000000 Start of measurement", 16).is_none());
+        assert!(parse_asc_line("0.1 1 ErrorFrame", 16).is_none());
+    }
+
+    #[test]
+    fn test_median_period_and_hold() {
+        assert_eq!(median_period(&[0.0, 0.1, 0.2, 0.5]), Some(0.1));
+        assert_eq!(median_period(&[1.0]), None);
+
+        let held = hold(&[0.05, 0.2], &[1.0, 2.0], &[0.0, 0.1, 0.2, 0.3]);
+        assert!(held[0].is_nan());
+        assert_eq!(&held[1..], &[1.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_coarsened_interval() {
+        // A short trace keeps its fastest message's interval
+        assert_eq!(coarsened_interval(0.01, 600.0, 50), None);
+
+        // A day at 100 Hz over 50 channels would be 432M values
+        let coarse = coarsened_interval(0.01, 86_400.0, 50).unwrap();
+        assert!(86_400.0 / coarse <= (MAX_TIMEBASE_VALUES / 50) as f64);
+        assert!(coarse > 0.2);
+    }
+}
@@ -0,0 +1,205 @@
+//! Wall-clock times for logs.
+//!
+//! Loggers write their start time as free text in whatever shape the vendor
+//! chose, and many name the file after it too. These helpers turn both into
+//! a [`NaiveDateTime`] so the chart can show clock time instead of seconds
+//! from the start. Times are kept in the logger's local time; any zone name
+//! in the text is dropped.
+//!
+//! Formats that stamp each row with the time of day use [`DayRollover`] so a
+//! log that runs past midnight keeps counting forwards.
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Seconds in a day
+pub const DAY_SECONDS: f64 = 86_400.0;
+
+/// Header date/time formats, tried in order
+const HEADER_FORMATS: &[&str] = &[
+    // Link "1/14/2022 6:22:19 PM"
+    "%m/%d/%Y %I:%M:%S %p",
+    // MLG and MegaSquirt "Tue Sep 10 07:28:20 2024" (zone removed)
+    "%a %b %d %H:%M:%S %Y",
+    // Vector ASC "Wed Jun 14 09:30:12.123 am 2023", 12 or 24 hour
+    "%a %b %d %I:%M:%S%.f %p %Y",
+    "%a %b %d %H:%M:%S%.f %Y",
+    // ISO 8601, with or without the `T`
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M:%S",
+    // MoTeC "14/01/2022 18:22:19"
+    "%d/%m/%Y %H:%M:%S",
+    "%d %b %Y %H:%M:%S",
+];
+
+/// Date/time stamps found in log file names, with the chrono format of each
+static FILE_NAME_FORMATS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    [
+        // Haltech "2025-07-18_0215pm_Log1118"
+        (r"\d{4}-\d{2}-\d{2}_\d{4}[ap]m", "%Y-%m-%d_%I%M%p"),
+        // Link "ECU Log 2024-02-8 3;56;20 pm"
+        (
+            r"\d{4}-\d{1,2}-\d{1,2} \d{1,2};\d{2};\d{2} [ap]m",
+            "%Y-%m-%d %I;%M;%S %p",
+        ),
+        // RomRaider "romraiderlog_20251031_170713"
+        (r"\d{8}_\d{6}", "%Y%m%d_%H%M%S"),
+        // ECUMaster "20250529_1248_31"
+        (r"\d{8}_\d{4}_\d{2}", "%Y%m%d_%H%M_%S"),
+        // ECUMaster "2025_1218_1904"
+        (r"\d{4}_\d{4}_\d{4}", "%Y_%m%d_%H%M"),
+        // BlueDriver "Nov_09__2025_04_20_PM"
+        (
+            r"[A-Z][a-z]{2}_\d{2}__\d{4}_\d{2}_\d{2}_[AP]M",
+            "%b_%d__%Y_%I_%M_%p",
+        ),
+    ]
+    .into_iter()
+    .map(|(pattern, format)| {
+        (
+            Regex::new(pattern).expect("Invalid file name date regex"),
+            format,
+        )
+    })
+    .collect()
+});
+
+/// Parse a start date/time written in a log header.
+///
+/// A leading label such as `Capture Date:` and zone names such as `CDT`
+/// are ignored. Returns `None` for date-only values.
+pub fn parse_start(text: &str) -> Option<NaiveDateTime> {
+    let text = match text.split_once(':') {
+        Some((label, rest)) if !label.chars().any(|c| c.is_ascii_digit()) => rest,
+        _ => text,
+    };
+    let cleaned = text
+        .split_whitespace()
+        .filter(|word| !is_zone_name(word))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    HEADER_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(&cleaned, format).ok())
+}
+
+/// Start date/time from a log's file name, for formats that name files
+/// after when logging started
+pub fn from_file_name(path: &Path) -> Option<NaiveDateTime> {
+    let name = path.file_stem()?.to_string_lossy();
+    FILE_NAME_FORMATS.iter().find_map(|(regex, format)| {
+        let found = regex.find(&name)?;
+        NaiveDateTime::parse_from_str(found.as_str(), format).ok()
+    })
+}
+
+/// Combine a date with a time of day given in seconds since midnight
+pub fn at_time_of_day(date: NaiveDate, seconds: f64) -> Option<NaiveDateTime> {
+    let whole = seconds.floor();
+    let nanos = ((seconds - whole) * 1e9).round() as u32;
+    let time = NaiveTime::from_num_seconds_from_midnight_opt(whole as u32, nanos.min(999_999_999))?;
+    Some(date.and_time(time))
+}
+
+/// Zone abbreviations like "CDT" or "AEST" (chrono can't parse them)
+fn is_zone_name(word: &str) -> bool {
+    (3..=5).contains(&word.len()) && word.chars().all(|c| c.is_ascii_uppercase())
+}
+
+/// Turns time-of-day readings into a running time that keeps counting past
+/// midnight
+#[derive(Clone, Debug, Default)]
+pub struct DayRollover {
+    days: u32,
+    previous: Option<f64>,
+}
+
+impl DayRollover {
+    /// Seconds since the first day's midnight for a time of day in seconds.
+    /// A step back of more than half a day is taken as midnight passing;
+    /// smaller steps are left alone so out-of-order rows stay visible.
+    pub fn advance(&mut self, seconds_of_day: f64) -> f64 {
+        if self
+            .previous
+            .is_some_and(|previous| previous - seconds_of_day > DAY_SECONDS / 2.0)
+        {
+            self.days += 1;
+        }
+        self.previous = Some(seconds_of_day);
+        seconds_of_day + self.days as f64 * DAY_SECONDS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(text: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_parse_header_starts() {
+        // Haltech's "Log" header can be on a 12-hour clock with no AM/PM
+        assert_eq!(parse_start("20250718 02:15:46"), None);
+        assert_eq!(
+            parse_start("1/14/2022 6:22:19 PM"),
+            Some(datetime("2022-01-14 18:22:19"))
+        );
+        assert_eq!(
+            parse_start("Capture Date: Tue Sep 10 07:28:20 CDT 2024"),
+            Some(datetime("2024-09-10 07:28:20"))
+        );
+        assert_eq!(
+            parse_start("Wed Jun 14 09:30:12.123 pm 2023"),
+            Some(datetime("2023-06-14 21:30:12") + chrono::Duration::milliseconds(123))
+        );
+        assert_eq!(parse_start("Nov 9, 2025"), None);
+        assert_eq!(parse_start(""), None);
+    }
+
+    #[test]
+    fn test_file_name_starts() {
+        let cases = [
+            ("2025-07-18_0215pm_Log1118.csv", "2025-07-18 14:15:00"),
+            ("ECU Log 2024-02-8 3;56;20 pm.llg5", "2024-02-08 15:56:20"),
+            ("romraiderlog_20251031_170713.csv", "2025-10-31 17:07:13"),
+            ("20250529_1248_31.emuprolog", "2025-05-29 12:48:31"),
+            ("2025_1218_1904.csv", "2025-12-18 19:04:00"),
+            (
+                "SBM22GCA4MW001405_Nov_09__2025_04_20_PM_LiveData.csv",
+                "2025-11-09 16:20:00",
+            ),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(
+                from_file_name(Path::new(name)),
+                Some(datetime(expected)),
+                "{}",
+                name
+            );
+        }
+        assert_eq!(from_file_name(Path::new("linklog.llg")), None);
+    }
+
+    #[test]
+    fn test_day_rollover() {
+        let mut rollover = DayRollover::default();
+        assert_eq!(rollover.advance(86_398.0), 86_398.0);
+        assert_eq!(rollover.advance(86_399.5), 86_399.5);
+        assert_eq!(rollover.advance(0.5), DAY_SECONDS + 0.5);
+        // A small step back is not a new day
+        assert_eq!(rollover.advance(0.25), DAY_SECONDS + 0.25);
+    }
+
+    #[test]
+    fn test_at_time_of_day() {
+        let date = NaiveDate::from_ymd_opt(2025, 7, 18).unwrap();
+        let start = at_time_of_day(date, 51_346.5).unwrap();
+        assert_eq!(start.format("%H:%M:%S%.3f").to_string(), "14:15:46.500");
+        assert_eq!(at_time_of_day(date, DAY_SECONDS + 1.0), None);
+    }
+}
@@ -0,0 +1,574 @@
+//! Format detection registry.
+//!
+//! Every supported log format registers a [`FormatDetector`]: a sniff
+//! function that scores how confident it is that a file is in its format,
+//! and a parse function. Detection runs every sniffer and picks the best
+//! score instead of taking the first hit of an ordered if-chain, so:
+//!
+//! - Specific signatures (magic bytes, title lines) outrank generic CSV
+//!   headers that several formats share
+//! - Files two formats score about equally on are reported as ambiguous,
+//!   letting the user choose rather than guessing
+//! - Files nothing recognises are reported as unknown instead of being
+//!   handed to a default parser
+//!
+//! The desktop app and `test_parser` both detect through this registry.
+
+use std::borrow::Cow;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use super::can_trace::TraceFormat;
+use super::error::ParseError;
+use super::streaming::{LogBuilder, RecordSink, StreamProgress, StreamingParseable};
+use super::types::{EcuType, Log, Parseable};
+use super::{
+    Aem, Aim, BlueDriver, CanTrace, EcuMaster, Emerald, EmuBlog, Haltech, Link, Locomotive,
+    MaxxEcu, MegaSquirt, Motec, RomRaider, SpecCsv, Speeduino,
+};
+
+/// Confidence for a format-specific magic number or title line
+pub const CONFIDENCE_CERTAIN: f32 = 1.0;
+/// Confidence for a strong but not unique signature
+pub const CONFIDENCE_HIGH: f32 = 0.9;
+/// Confidence for a distinctive header layout
+pub const CONFIDENCE_MEDIUM: f32 = 0.75;
+/// Confidence for a generic header that other CSV formats could share
+pub const CONFIDENCE_LOW: f32 = 0.5;
+
+/// Candidates scoring within this margin of the best match make a file ambiguous
+pub const AMBIGUITY_MARGIN: f32 = 0.15;
+
+/// Number of bytes decoded as text for sniffing
+const SNIFF_LEN: usize = 64 * 1024;
+
+/// File contents and path handed to sniff and parse functions
+pub struct DetectionInput<'a> {
+    data: &'a [u8],
+    path: &'a Path,
+    sniff_text: OnceLock<String>,
+}
+
+impl<'a> DetectionInput<'a> {
+    /// Wrap raw (already decompressed) file data and its path
+    pub fn new(data: &'a [u8], path: &'a Path) -> Self {
+        Self {
+            data,
+            path,
+            sniff_text: OnceLock::new(),
+        }
+    }
+
+    /// Raw file bytes
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Path of the file (or of the entry inside an archive)
+    pub fn path(&self) -> &'a Path {
+        self.path
+    }
+
+    /// Lowercase file extension without the dot
+    pub fn extension(&self) -> Option<String> {
+        self.path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase)
+    }
+
+    /// The start of the file decoded as text, for sniffing text formats
+    /// without decoding a whole (possibly binary) file
+    pub fn sniff_text(&self) -> &str {
+        self.sniff_text.get_or_init(|| {
+            let head = &self.data[..self.data.len().min(SNIFF_LEN)];
+            decode_text(head).into_owned()
+        })
+    }
+
+    /// The whole file decoded as text: UTF-16 with a BOM, UTF-8, or lossy UTF-8
+    pub fn text(&self) -> Cow<'a, str> {
+        decode_text(self.data)
+    }
+}
+
+/// Decode text, handling UTF-16 exports and invalid UTF-8
+fn decode_text(data: &[u8]) -> Cow<'_, str> {
+    if let Some(decoded) = BlueDriver::decode_utf16(data) {
+        return Cow::Owned(decoded);
+    }
+    match std::str::from_utf8(data) {
+        Ok(text) => Cow::Borrowed(text),
+        // A sniff window can cut a multi-byte character in half
+        Err(e) if e.error_len().is_none() => {
+            Cow::Borrowed(std::str::from_utf8(&data[..e.valid_up_to()]).unwrap_or_default())
+        }
+        Err(_) => String::from_utf8_lossy(data),
+    }
+}
+
+/// A registered log format
+pub struct FormatDetector {
+    /// Stable identifier (e.g., "haltech", used by `test_parser --format`)
+    pub id: &'static str,
+    /// Display name shown to the user
+    pub name: &'static str,
+    /// ECU type recorded for files parsed by this format
+    pub ecu_type: EcuType,
+    sniff: fn(&DetectionInput) -> f32,
+    parse: fn(&DetectionInput) -> Result<Log, ParseError>,
+    /// Chunked parse for formats with a [`StreamingParseable`] parser
+    stream: Option<StreamFn>,
+    /// Tolerant parse for binary formats that can skip damaged data
+    recover: Option<RecoverFn>,
+}
+
+/// Streaming parse entry point of a registered format
+type StreamFn = fn(
+    &DetectionInput,
+    &mut dyn RecordSink,
+    &mut dyn FnMut(StreamProgress),
+) -> Result<(), ParseError>;
+
+/// Recovery-mode parse entry point of a registered format
+type RecoverFn = fn(&DetectionInput, &mut dyn FnMut(StreamProgress)) -> Result<Log, ParseError>;
+
+impl FormatDetector {
+    /// Score how likely the input is in this format, from 0.0 (no) to 1.0 (certain)
+    pub fn sniff(&self, input: &DetectionInput) -> f32 {
+        (self.sniff)(input)
+    }
+
+    /// Parse the input with this format's parser
+    pub fn parse(&self, input: &DetectionInput) -> Result<Log, ParseError> {
+        (self.parse)(input)
+    }
+
+    /// Whether this format can be parsed in chunks
+    pub fn supports_streaming(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Parse the input in chunks into `sink`, or `None` if the format has
+    /// no streaming parser
+    pub fn parse_stream(
+        &self,
+        input: &DetectionInput,
+        sink: &mut dyn RecordSink,
+        progress: &mut dyn FnMut(StreamProgress),
+    ) -> Option<Result<(), ParseError>> {
+        self.stream.map(|stream| stream(input, sink, progress))
+    }
+
+    /// Whether this format can recover a damaged file
+    pub fn supports_recovery(&self) -> bool {
+        self.recover.is_some()
+    }
+
+    /// Parse a damaged file, keeping every intact record, or `None` if the
+    /// format has no recovery mode. The log's `recovery` report says what
+    /// was skipped.
+    pub fn recover(
+        &self,
+        input: &DetectionInput,
+        progress: &mut dyn FnMut(StreamProgress),
+    ) -> Option<Result<Log, ParseError>> {
+        self.recover.map(|recover| recover(input, progress))
+    }
+
+    /// Parse the input, reporting progress for formats that stream.
+    /// Formats without a streaming parser report once, when done.
+    pub fn parse_with_progress(
+        &self,
+        input: &DetectionInput,
+        progress: &mut dyn FnMut(StreamProgress),
+    ) -> Result<Log, ParseError> {
+        let mut builder = LogBuilder::new();
+        match self.parse_stream(input, &mut builder, progress) {
+            Some(result) => result.map(|()| builder.finish()),
+            None => {
+                let log = self.parse(input)?;
+                let total_bytes = input.data().len() as u64;
+                progress(StreamProgress {
+                    bytes_read: total_bytes,
+                    total_bytes,
+                    records: log.record_count(),
+                });
+                Ok(log)
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for FormatDetector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FormatDetector")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+/// A format that recognised the input, with its confidence
+#[derive(Clone, Copy, Debug)]
+pub struct Candidate {
+    pub detector: &'static FormatDetector,
+    pub confidence: f32,
+}
+
+/// Outcome of format detection
+#[derive(Debug)]
+pub enum Detection {
+    /// One format clearly scored best
+    Match(&'static FormatDetector),
+    /// Several formats scored about equally, best first
+    Ambiguous(Vec<Candidate>),
+    /// No format recognised the file
+    Unknown,
+}
+
+/// Convert a detect function's verdict into a fixed confidence
+fn score(matched: bool, confidence: f32) -> f32 {
+    if matched {
+        confidence
+    } else {
+        0.0
+    }
+}
+
+/// Parse the whole file as text with a text-based parser
+fn parse_text<P: Parseable>(parser: P, input: &DetectionInput) -> Result<Log, ParseError> {
+    parser.parse(&input.text())
+}
+
+/// Find an adapter spec registered for the file's extension whose header matches
+fn spec_csv_parser(input: &DetectionInput) -> Option<SpecCsv> {
+    SpecCsv::from_extension(&input.extension()?, input.sniff_text())
+}
+
+/// All registered formats
+static DETECTORS: &[FormatDetector] = &[
+    FormatDetector {
+        id: "aem",
+        name: "AEM .daq",
+        ecu_type: EcuType::Aem,
+        sniff: |input| score(Aem::detect(input.data()), CONFIDENCE_CERTAIN),
+        parse: |input| Aem::parse_binary(input.data()),
+        stream: None,
+        recover: None,
+    },
+    FormatDetector {
+        id: "aim",
+        name: "AiM XRK/DRK",
+        ecu_type: EcuType::Aim,
+        sniff: |input| score(Aim::detect(input.data()), CONFIDENCE_CERTAIN),
+        parse: |input| Aim::parse_binary(input.data()),
+        stream: None,
+        recover: Some(|input, _| Aim::recover_binary(input.data())),
+    },
+    FormatDetector {
+        id: "speeduino",
+        name: "Speeduino/rusEFI MLG",
+        ecu_type: EcuType::Speeduino,
+        sniff: |input| score(Speeduino::detect(input.data()), CONFIDENCE_CERTAIN),
+        parse: |input| Speeduino::parse_binary(input.data()),
+        stream: Some(|input, sink, progress| {
+            Speeduino.parse_stream(Cursor::new(input.data()), sink, progress)
+        }),
+        recover: Some(|input, progress| {
+            let mut builder = LogBuilder::new();
+            Speeduino::recover_stream(Cursor::new(input.data()), &mut builder, progress)?;
+            Ok(builder.finish())
+        }),
+    },
+    FormatDetector {
+        id: "link",
+        name: "Link ECU LLG",
+        ecu_type: EcuType::Link,
+        sniff: |input| score(Link::detect(input.data()), CONFIDENCE_CERTAIN),
+        parse: |input| Link::parse_binary(input.data()),
+        stream: None,
+        recover: Some(|input, _| Link::recover_binary(input.data())),
+    },
+    FormatDetector {
+        id: "emublog",
+        name: "ECUMaster .emublog",
+        ecu_type: EcuType::EcuMaster,
+        sniff: |input| score(EmuBlog::detect(input.data()), CONFIDENCE_CERTAIN),
+        parse: |input| EmuBlog::parse_binary(input.data()),
+        stream: None,
+        recover: None,
+    },
+    FormatDetector {
+        id: "motec",
+        name: "MoTeC .ld",
+        ecu_type: EcuType::MotEc,
+        // The 0x40 marker is short; the channel list pointer check backs it up
+        sniff: |input| score(Motec::detect(input.data()), CONFIDENCE_HIGH),
+        parse: |input| Motec::parse_binary(input.data()),
+        stream: None,
+        recover: None,
+    },
+    FormatDetector {
+        id: "emerald",
+        name: "Emerald ECU LG1/LG2",
+        ecu_type: EcuType::Emerald,
+        sniff: |input| {
+            let data = input.data();
+            score(
+                Emerald::is_emerald_path(input.path())
+                    && (Emerald::detect(data) || Emerald::detect_lg2(data)),
+                CONFIDENCE_HIGH,
+            )
+        },
+        parse: |input| Emerald::parse_file(input.path()),
+        stream: None,
+        recover: None,
+    },
+    FormatDetector {
+        id: "haltech",
+        name: "Haltech NSP CSV",
+        ecu_type: EcuType::Haltech,
+        sniff: |input| {
+            let text = input.sniff_text();
+            if Haltech::detect(text) {
+                CONFIDENCE_CERTAIN
+            } else {
+                // Exports that lost the marker still carry channel definitions
+                score(
+                    text.lines()
+                        .take(20)
+                        .any(|line| line.trim_start().starts_with("DataLogVersion")),
+                    CONFIDENCE_LOW,
+                )
+            }
+        },
+        parse: |input| parse_text(Haltech, input),
+        // UTF-16 exports are decoded up front; everything else streams from the raw bytes
+        stream: Some(
+            |input, sink, progress| match BlueDriver::decode_utf16(input.data()) {
+                Some(text) => Haltech.parse_stream(Cursor::new(text.as_bytes()), sink, progress),
+                None => Haltech.parse_stream(Cursor::new(input.data()), sink, progress),
+            },
+        ),
+        recover: None,
+    },
+    FormatDetector {
+        id: "bluedriver",
+        name: "BlueDriver LiveData",
+        ecu_type: EcuType::BlueDriver,
+        sniff: |input| score(BlueDriver::detect(input.sniff_text()), CONFIDENCE_CERTAIN),
+        parse: |input| parse_text(BlueDriver, input),
+        stream: None,
+        recover: None,
+    },
+    FormatDetector {
+        id: CanTrace::FORMAT_ID,
+        name: "CAN bus trace",
+        ecu_type: EcuType::Unknown,
+        sniff: |input| match TraceFormat::detect(input.sniff_text()) {
+            Some(TraceFormat::Candump) => CONFIDENCE_HIGH,
+            Some(_) => CONFIDENCE_CERTAIN,
+            None => 0.0,
+        },
+        // The app asks which protocol to decode with; elsewhere the loaded
+        // protocol defining most of the trace's IDs is used
+        parse: |input| {
+            let text = input.text();
+            match CanTrace::best_match(&text) {
+                Some(parser) => parser.parse(&text),
+                None => Err(ParseError::invalid(
+                    "No loaded CAN protocol defines the frames in this trace",
+                )),
+            }
+        },
+        stream: None,
+        recover: None,
+    },
+    FormatDetector {
+        id: "locomotive",
+        name: "Locomotive",
+        ecu_type: EcuType::Locomotive,
+        sniff: |input| score(Locomotive::detect(input.sniff_text()), CONFIDENCE_HIGH),
+        parse: |input| parse_text(Locomotive, input),
+        stream: None,
+        recover: None,
+    },
+    FormatDetector {
+        id: "ecumaster",
+        name: "ECUMaster CSV",
+        ecu_type: EcuType::EcuMaster,
+        sniff: |input| score(EcuMaster::detect(input.sniff_text()), CONFIDENCE_MEDIUM),
+        parse: |input| parse_text(EcuMaster, input),
+        stream: None,
+        recover: None,
+    },
+    FormatDetector {
+        id: "megasquirt",
+        name: "MegaSquirt/TunerStudio .msl",
+        ecu_type: EcuType::MegaSquirt,
+        sniff: |input| score(MegaSquirt::detect(input.sniff_text()), CONFIDENCE_MEDIUM),
+        parse: |input| parse_text(MegaSquirt, input),
+        stream: None,
+        recover: None,
+    },
+    FormatDetector {
+        id: "maxxecu",
+        name: "MaxxECU MTune CSV",
+        ecu_type: EcuType::MaxxEcu,
+        sniff: |input| score(MaxxEcu::detect(input.sniff_text()), CONFIDENCE_MEDIUM),
+        parse: |input| parse_text(MaxxEcu, input),
+        stream: None,
+        recover: None,
+    },
+    FormatDetector {
+        id: "romraider",
+        name: "RomRaider CSV",
+        ecu_type: EcuType::RomRaider,
+        // Any CSV with a leading Time column passes, so rank below specific formats
+        sniff: |input| score(RomRaider::detect(input.sniff_text()), CONFIDENCE_LOW),
+        parse: |input| parse_text(RomRaider, input),
+        stream: None,
+        recover: None,
+    },
+    FormatDetector {
+        id: "spec-csv",
+        name: "Adapter spec CSV",
+        ecu_type: EcuType::Unknown,
+        sniff: |input| score(spec_csv_parser(input).is_some(), CONFIDENCE_LOW),
+        parse: |input| match spec_csv_parser(input) {
+            Some(parser) => parser.parse(&input.text()),
+            None => Err(ParseError::invalid("No adapter spec describes this file")),
+        },
+        stream: None,
+        recover: None,
+    },
+];
+
+/// All registered formats
+pub fn detectors() -> &'static [FormatDetector] {
+    DETECTORS
+}
+
+/// Look up a registered format by id
+pub fn find_detector(id: &str) -> Option<&'static FormatDetector> {
+    DETECTORS.iter().find(|d| d.id.eq_ignore_ascii_case(id))
+}
+
+/// Score the input against every format, best first, dropping formats that
+/// don't recognise it at all
+pub fn rank(input: &DetectionInput) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = DETECTORS
+        .iter()
+        .map(|detector| Candidate {
+            detector,
+            confidence: detector.sniff(input),
+        })
+        .filter(|c| c.confidence > 0.0)
+        .collect();
+    // Stable sort keeps registration order between equal scores
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    candidates
+}
+
+/// Pick the format for the input
+pub fn detect(input: &DetectionInput) -> Detection {
+    let candidates = rank(input);
+    let Some(best) = candidates.first() else {
+        return Detection::Unknown;
+    };
+
+    let close: Vec<Candidate> = candidates
+        .iter()
+        .copied()
+        .filter(|c| best.confidence - c.confidence < AMBIGUITY_MARGIN)
+        .collect();
+    if close.len() > 1 {
+        Detection::Ambiguous(close)
+    } else {
+        Detection::Match(best.detector)
+    }
+}
+
+/// Explain how to convert proprietary container formats UltraLog can't read
+pub fn unsupported_format_message(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"HEPS") {
+        // Haltech .hlgzip uses proprietary compression
+        Some(
+            "This is a Haltech .hlgzip file which uses proprietary compression.\n\n\
+            To use this log in UltraLog, please export it as CSV from Haltech's ESP or NSP software:\n\
+            1. Open the .hlgzip file in Haltech ESP/NSP\n\
+            2. Go to File → Export → CSV\n\
+            3. Load the exported .csv file in UltraLog",
+        )
+    } else if data.starts_with(b"\xEFeml") {
+        // ECUMaster EMU PRO .emuprolog is a proprietary container
+        Some(
+            "This is an ECUMaster EMU PRO .emuprolog file which uses a proprietary format.\n\n\
+            To use this log in UltraLog, please export it as CSV from EMU PRO Client:\n\
+            1. Open the log in EMU PRO Client\n\
+            2. Go to File → Export → CSV\n\
+            3. Load the exported .csv file in UltraLog",
+        )
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect_str(contents: &str, path: &str) -> Detection {
+        detect(&DetectionInput::new(contents.as_bytes(), Path::new(path)))
+    }
+
+    #[test]
+    fn test_detector_ids_unique() {
+        for (i, detector) in DETECTORS.iter().enumerate() {
+            assert!(
+                DETECTORS[i + 1..].iter().all(|d| d.id != detector.id),
+                "Duplicate detector id '{}'",
+                detector.id
+            );
+        }
+        assert!(find_detector("MaxxECU").is_some());
+        assert!(find_detector("nope").is_none());
+    }
+
+    #[test]
+    fn test_specific_format_outranks_generic_csv() {
+        // RomRaider also accepts this header, but MaxxECU's bracketed units win
+        let Detection::Match(detector) = detect_str("Time [s],RPM [rpm]\n0.0,900\n", "log.csv")
+        else {
+            panic!("Expected a single match");
+        };
+        assert_eq!(detector.id, "maxxecu");
+    }
+
+    #[test]
+    fn test_unrecognised_text_is_unknown() {
+        assert!(matches!(
+            detect_str("hello,world\n1,2\n", "notes.csv"),
+            Detection::Unknown
+        ));
+        assert!(matches!(detect_str("", "empty.csv"), Detection::Unknown));
+    }
+
+    #[test]
+    fn test_sniff_text_cuts_at_char_boundary() {
+        let mut data = vec![b'a'; SNIFF_LEN - 1];
+        data.extend_from_slice("°C".as_bytes());
+        let input = DetectionInput::new(&data, Path::new("x.csv"));
+        assert_eq!(input.sniff_text().len(), SNIFF_LEN - 1);
+        assert!(input.text().ends_with("°C"));
+    }
+
+    #[test]
+    fn test_unsupported_format_message() {
+        assert!(unsupported_format_message(b"HEPS\x00\x01").is_some());
+        assert!(unsupported_format_message(b"\xEFeml\x00").is_some());
+        assert!(unsupported_format_message(b"%DataLog%").is_none());
+    }
+}
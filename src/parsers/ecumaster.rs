@@ -3,13 +3,13 @@
 //! Parses CSV log files exported from ECUMaster EMU Pro ECUs.
 //! Format: Semicolon-delimited CSV with hierarchical channel names.
 
-use serde::Serialize;
-use std::error::Error;
+use serde::{Deserialize, Serialize};
 
-use super::types::{Channel, Log, Meta, Parseable, Value};
+use super::error::ParseError;
+use super::types::{Channel, ColumnBuilder, Log, Meta, Parseable, MISSING};
 
 /// ECUMaster log file metadata
-#[derive(Clone, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct EcuMasterMeta {
     /// Number of channels in the log
     pub channel_count: usize,
@@ -18,7 +18,7 @@ pub struct EcuMasterMeta {
 }
 
 /// ECUMaster channel definition
-#[derive(Clone, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct EcuMasterChannel {
     /// Full channel path (e.g., "engine/rpm")
     pub path: String,
@@ -177,19 +177,20 @@ impl EcuMaster {
 }
 
 impl Parseable for EcuMaster {
-    fn parse(&self, file_contents: &str) -> Result<Log, Box<dyn Error>> {
+    fn parse(&self, file_contents: &str) -> Result<Log, ParseError> {
         // Pre-allocate based on estimated row count (first line is header)
         let line_count = file_contents.lines().count();
         let estimated_data_rows = line_count.saturating_sub(1);
 
         let mut channels: Vec<Channel> = Vec::with_capacity(50);
         let mut times: Vec<f64> = Vec::with_capacity(estimated_data_rows);
-        let mut data: Vec<Vec<Value>> = Vec::with_capacity(estimated_data_rows);
 
         let mut lines = file_contents.lines();
 
         // Parse header line to get channel names
-        let header = lines.next().ok_or("Empty file: no header found")?;
+        let header = lines
+            .next()
+            .ok_or_else(|| ParseError::invalid("Empty file: no header found"))?;
 
         // Determine delimiter (semicolon or tab)
         let delimiter = if header.contains(';') { ';' } else { '\t' };
@@ -197,7 +198,7 @@ impl Parseable for EcuMaster {
         let column_names: Vec<&str> = header.split(delimiter).collect();
 
         if column_names.is_empty() || column_names[0].to_uppercase() != "TIME" {
-            return Err("Invalid ECUMaster log: first column must be TIME".into());
+            return Err(ParseError::bad_header(1, "first column must be TIME"));
         }
 
         // Create channels from header (skip TIME column)
@@ -206,6 +207,8 @@ impl Parseable for EcuMaster {
             channels.push(Channel::EcuMaster(channel));
         }
 
+        let mut data = ColumnBuilder::with_capacity(channels.len(), estimated_data_rows);
+
         // Track last known values for sparse data interpolation
         let mut last_values: Vec<Option<f64>> = vec![None; channels.len()];
 
@@ -227,36 +230,36 @@ impl Parseable for EcuMaster {
                 times.push(time_val);
 
                 // Parse remaining values (may be sparse/empty)
-                let mut row_values: Vec<Value> = Vec::with_capacity(channels.len());
+                let mut row_values: Vec<f64> = Vec::with_capacity(channels.len());
 
                 for (idx, part) in parts.iter().skip(1).enumerate() {
                     let part = part.trim();
 
                     if part.is_empty() {
-                        // Empty value - use last known value or 0
-                        let value = last_values.get(idx).and_then(|v| *v).unwrap_or(0.0);
-                        row_values.push(Value::Float(value));
+                        // Empty value - hold the last known value, missing before the first
+                        let value = last_values.get(idx).and_then(|v| *v).unwrap_or(MISSING);
+                        row_values.push(value);
                     } else if let Ok(val) = part.parse::<f64>() {
                         // Valid numeric value
                         if idx < last_values.len() {
                             last_values[idx] = Some(val);
                         }
-                        row_values.push(Value::Float(val));
+                        row_values.push(val);
                     } else {
-                        // Non-numeric value - use last known or 0
-                        let value = last_values.get(idx).and_then(|v| *v).unwrap_or(0.0);
-                        row_values.push(Value::Float(value));
+                        // Non-numeric value - hold the last known value
+                        let value = last_values.get(idx).and_then(|v| *v).unwrap_or(MISSING);
+                        row_values.push(value);
                     }
                 }
 
                 // Pad row to match channel count if needed
                 while row_values.len() < channels.len() {
                     let idx = row_values.len();
-                    let value = last_values.get(idx).and_then(|v| *v).unwrap_or(0.0);
-                    row_values.push(Value::Float(value));
+                    let value = last_values.get(idx).and_then(|v| *v).unwrap_or(MISSING);
+                    row_values.push(value);
                 }
 
-                data.push(row_values);
+                data.push_record(&row_values);
             }
         }
 
@@ -266,15 +269,15 @@ impl Parseable for EcuMaster {
             data.len()
         );
 
-        Ok(Log {
-            meta: Meta::EcuMaster(EcuMasterMeta {
+        Ok(Log::new(
+            Meta::EcuMaster(EcuMasterMeta {
                 channel_count: channels.len(),
                 data_points: data.len(),
             }),
             channels,
             times,
-            data,
-        })
+            data.into_columns(),
+        ))
     }
 }
 
@@ -326,17 +329,17 @@ mod tests {
         assert_eq!(log.channels[2].name(), "angle");
 
         assert_eq!(log.times.len(), 3);
-        assert_eq!(log.data.len(), 3);
+        assert_eq!(log.record_count(), 3);
 
         // Check first row
-        assert_eq!(log.data[0][0].as_f64(), 1000.0);
-        assert_eq!(log.data[0][1].as_f64(), 10.5);
-        assert_eq!(log.data[0][2].as_f64(), 15.0);
+        assert_eq!(log.channel_data(0)[0], 1000.0);
+        assert_eq!(log.channel_data(1)[0], 10.5);
+        assert_eq!(log.channel_data(2)[0], 15.0);
 
         // Check sparse data handling (empty values use previous)
-        assert_eq!(log.data[1][0].as_f64(), 1050.0);
-        assert_eq!(log.data[1][1].as_f64(), 10.5); // Previous value
-        assert_eq!(log.data[1][2].as_f64(), 15.5);
+        assert_eq!(log.channel_data(0)[1], 1050.0);
+        assert_eq!(log.channel_data(1)[1], 10.5); // Previous value
+        assert_eq!(log.channel_data(2)[1], 15.5);
 
         // Check units
         assert_eq!(log.channels[0].unit(), "RPM");
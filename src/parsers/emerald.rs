@@ -10,11 +10,12 @@
 //!
 //! The channel IDs map to specific ECU parameters (RPM, TPS, temperatures, etc.)
 
-use serde::Serialize;
-use std::error::Error;
+use serde::{Deserialize, Serialize};
+use std::io;
 use std::path::Path;
 
-use super::types::{Channel, Log, Meta, Value};
+use super::error::ParseError;
+use super::types::{Channel, Log, LogInfo, Meta};
 
 /// Known Emerald ECU channel IDs and their metadata
 /// These are reverse-engineered from observed data patterns
@@ -272,7 +273,7 @@ fn get_channel_definition(id: u8) -> ChannelDefinition {
 }
 
 /// Emerald ECU channel metadata
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EmeraldChannel {
     pub name: String,
     pub unit: String,
@@ -293,7 +294,7 @@ impl EmeraldChannel {
 }
 
 /// Emerald ECU log metadata
-#[derive(Clone, Debug, Serialize, Default)]
+#[derive(Clone, Debug, Deserialize, Serialize, Default)]
 pub struct EmeraldMeta {
     /// Source file name (without extension)
     pub source_file: String,
@@ -305,6 +306,16 @@ pub struct EmeraldMeta {
     pub sample_rate_hz: f64,
 }
 
+impl EmeraldMeta {
+    /// Common metadata; Emerald logs carry no header, only what was derived on load
+    pub fn info(&self) -> LogInfo {
+        let mut info = LogInfo::new("Emerald");
+        info.push_vendor("Source file", &self.source_file);
+        info.push_vendor("Sample rate", format!("{:.1} Hz", self.sample_rate_hz));
+        info
+    }
+}
+
 /// Emerald ECU log file parser
 pub struct Emerald;
 
@@ -400,7 +411,7 @@ impl Emerald {
     }
 
     /// Parse the LG2 channel definition file
-    fn parse_lg2(contents: &str) -> Result<Vec<(u8, u8)>, Box<dyn Error>> {
+    fn parse_lg2(contents: &str) -> Result<Vec<(u8, u8)>, ParseError> {
         let mut channels: Vec<(u8, u8)> = Vec::new();
 
         let lines: Vec<&str> = contents.lines().collect();
@@ -429,7 +440,9 @@ impl Emerald {
         }
 
         if channels.is_empty() {
-            return Err("No channel definitions found in LG2 file".into());
+            return Err(ParseError::invalid(
+                "No channel definitions found in LG2 file",
+            ));
         }
 
         // Sort by slot number to ensure correct order
@@ -439,17 +452,20 @@ impl Emerald {
     }
 
     /// Parse Emerald log files (requires both .lg1 and .lg2)
-    pub fn parse_file(path: &Path) -> Result<Log, Box<dyn Error>> {
+    pub fn parse_file(path: &Path) -> Result<Log, ParseError> {
         // Determine the base path (without extension)
         let base_path = path.with_extension("");
 
         // Read LG2 file (channel definitions)
         let lg2_path = base_path.with_extension("lg2");
         let lg2_contents = std::fs::read_to_string(&lg2_path).map_err(|e| {
-            format!(
-                "Cannot read LG2 file '{}': {}. Both .lg1 and .lg2 files are required.",
-                lg2_path.display(),
-                e
+            io::Error::new(
+                e.kind(),
+                format!(
+                    "Cannot read LG2 file '{}': {}. Both .lg1 and .lg2 files are required.",
+                    lg2_path.display(),
+                    e
+                ),
             )
         })?;
 
@@ -459,10 +475,13 @@ impl Emerald {
         // Read LG1 file (binary data)
         let lg1_path = base_path.with_extension("lg1");
         let lg1_data = std::fs::read(&lg1_path).map_err(|e| {
-            format!(
-                "Cannot read LG1 file '{}': {}. Both .lg1 and .lg2 files are required.",
-                lg1_path.display(),
-                e
+            io::Error::new(
+                e.kind(),
+                format!(
+                    "Cannot read LG1 file '{}': {}. Both .lg1 and .lg2 files are required.",
+                    lg1_path.display(),
+                    e
+                ),
             )
         })?;
 
@@ -474,16 +493,21 @@ impl Emerald {
         data: &[u8],
         channel_defs: &[(u8, u8)],
         source_path: &Path,
-    ) -> Result<Log, Box<dyn Error>> {
+    ) -> Result<Log, ParseError> {
         if !Self::detect(data) {
-            return Err("Invalid LG1 file - not recognized as Emerald format".into());
+            return Err(ParseError::BadMagic {
+                format: "Emerald LG1",
+            });
         }
 
         const RECORD_SIZE: usize = 24;
         let num_records = data.len() / RECORD_SIZE;
 
         if num_records == 0 {
-            return Err("LG1 file contains no data records".into());
+            return Err(ParseError::truncated(
+                data.len(),
+                "LG1 file contains no data records",
+            ));
         }
 
         // Build channel metadata
@@ -507,7 +531,9 @@ impl Emerald {
 
         // Parse binary data
         let mut times: Vec<f64> = Vec::with_capacity(num_records);
-        let mut data_matrix: Vec<Vec<Value>> = Vec::with_capacity(num_records);
+        let mut columns: Vec<Vec<f64>> = (0..channels.len())
+            .map(|_| Vec::with_capacity(num_records))
+            .collect();
 
         let mut first_timestamp: Option<f64> = None;
 
@@ -532,18 +558,15 @@ impl Emerald {
             times.push(time_seconds);
 
             // Read 8 channel values (16 bytes, 8 x u16)
-            let mut row: Vec<Value> = Vec::with_capacity(channels.len());
-            for (ch_idx, channel) in channels.iter().enumerate() {
+            for (ch_idx, (channel, column)) in channels.iter().zip(&mut columns).enumerate() {
                 let value_offset = offset + 8 + (ch_idx * 2);
                 let raw_value =
                     u16::from_le_bytes([data[value_offset], data[value_offset + 1]]) as f64;
 
                 // Apply scaling and offset
                 let scaled_value = raw_value * channel.scale + channel.offset;
-                row.push(Value::Float(scaled_value));
+                column.push(scaled_value);
             }
-
-            data_matrix.push(row);
         }
 
         // Calculate metadata
@@ -574,12 +597,12 @@ impl Emerald {
             sample_rate
         );
 
-        Ok(Log {
-            meta: Meta::Emerald(meta),
-            channels: channels.into_iter().map(Channel::Emerald).collect(),
+        Ok(Log::new(
+            Meta::Emerald(meta),
+            channels.into_iter().map(Channel::Emerald).collect(),
             times,
-            data: data_matrix,
-        })
+            columns,
+        ))
     }
 }
 
@@ -701,7 +724,7 @@ mod tests {
         // Verify structure
         assert_eq!(log.channels.len(), 8, "Should have 8 channels");
         assert!(!log.times.is_empty(), "Should have time data");
-        assert!(!log.data.is_empty(), "Should have data records");
+        assert!(!log.is_empty(), "Should have data records");
 
         // Verify channel names
         for channel in &log.channels {
@@ -718,6 +741,6 @@ mod tests {
             eprintln!("Sample rate: {:.1} Hz", meta.sample_rate_hz);
         }
 
-        eprintln!("Parsed {} data records", log.data.len());
+        eprintln!("Parsed {} data records", log.record_count());
     }
 }
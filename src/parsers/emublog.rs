@@ -0,0 +1,363 @@
+//! ECUMaster EMU Black binary log (.emublog) parser.
+//!
+//! `.emublog` files are written by the EMU Black internal logger. On disk they
+//! are gzip-compressed; the decompressed stream has a small header followed by
+//! fixed-size records:
+//!
+//! - Header (12 bytes, little-endian): magic `0x22446082`, format version,
+//!   record count
+//! - Records (308 bytes each for version 5): u16 sample counter followed by
+//!   the ECU's live data block
+//!
+//! The record layout is not published. Offsets below were inferred from sample
+//! logs by correlating them with known engine behaviour, so only the channels
+//! we are confident about are decoded. Channels use the same paths as the
+//! EMU Pro CSV export so normalization and spec metadata apply unchanged.
+//!
+//! Time comes from the sample counter alone. The counter keeps running while
+//! logging is paused, so a jump of thousands is a pause of that length: time
+//! advances by the full jump, a record with every channel missing marks the
+//! gap so charts don't draw across it, and an annotation marks the restart.
+
+use flate2::read::GzDecoder;
+use std::io::Read;
+
+use super::ecumaster::{EcuMasterChannel, EcuMasterMeta};
+use super::error::ParseError;
+use super::types::{Annotation, Channel, Log, Meta, MISSING};
+
+/// Magic number at the start of a decompressed .emublog stream
+const EMUBLOG_MAGIC: u32 = 0x2244_6082;
+
+/// Size of the decompressed file header
+const HEADER_SIZE: usize = 12;
+
+/// Record size for format version 5 (the only version seen so far)
+const RECORD_SIZE_V5: usize = 308;
+
+/// Assumed rate at which the sample counter advances.
+///
+/// The counter is the only time reference in the record, and its rate is not
+/// stored in the file. 20 Hz is an assumption that hasn't been checked
+/// against a log of known duration, so logs recorded at another rate will be
+/// stretched or squeezed.
+const SAMPLE_RATE_HZ: f64 = 20.0;
+
+/// Largest counter step read as consecutive logging (one second at
+/// [`SAMPLE_RATE_HZ`]). Bigger jumps are pauses in logging.
+const MAX_COUNTER_STEP: u64 = 20;
+
+/// Raw storage type of a decoded field
+#[derive(Clone, Copy, Debug)]
+enum FieldKind {
+    U8,
+    I8,
+    U16,
+}
+
+/// Location and scaling of a channel inside a record
+struct FieldDef {
+    path: &'static str,
+    offset: usize,
+    kind: FieldKind,
+    scale: f64,
+}
+
+/// Channels decoded from each version 5 record
+const FIELDS_V5: &[FieldDef] = &[
+    FieldDef {
+        path: "engine/rpm",
+        offset: 2,
+        kind: FieldKind::U16,
+        scale: 1.0,
+    },
+    FieldDef {
+        path: "sensors/map",
+        offset: 4,
+        kind: FieldKind::U16,
+        scale: 1.0,
+    },
+    FieldDef {
+        path: "sensors/tps1",
+        offset: 6,
+        kind: FieldKind::U8,
+        scale: 1.0,
+    },
+    FieldDef {
+        path: "sensors/intakeAirTemp",
+        offset: 7,
+        kind: FieldKind::I8,
+        scale: 1.0,
+    },
+    FieldDef {
+        path: "sensors/batteryVoltage",
+        offset: 8,
+        kind: FieldKind::U16,
+        scale: 1.0 / 37.0,
+    },
+    FieldDef {
+        path: "ignition/angle",
+        offset: 13,
+        kind: FieldKind::U8,
+        scale: 0.5,
+    },
+    FieldDef {
+        path: "sensors/coolantTemp",
+        offset: 21,
+        kind: FieldKind::I8,
+        scale: 1.0,
+    },
+    FieldDef {
+        path: "sensors/lambda1",
+        offset: 43,
+        kind: FieldKind::U8,
+        scale: 0.01,
+    },
+];
+
+impl FieldDef {
+    fn read(&self, record: &[u8]) -> f64 {
+        let raw = match self.kind {
+            FieldKind::U8 => record[self.offset] as f64,
+            FieldKind::I8 => record[self.offset] as i8 as f64,
+            FieldKind::U16 => {
+                u16::from_le_bytes([record[self.offset], record[self.offset + 1]]) as f64
+            }
+        };
+        raw * self.scale
+    }
+}
+
+/// ECUMaster EMU Black binary log parser
+pub struct EmuBlog;
+
+impl EmuBlog {
+    /// Detect if data is an EMU Black .emublog file (gzip-compressed or raw)
+    pub fn detect(data: &[u8]) -> bool {
+        if Self::has_magic(data) {
+            return true;
+        }
+
+        // Compressed file: inflate just enough to check the header magic
+        if data.len() >= 2 && data[0] == 0x1F && data[1] == 0x8B {
+            let mut header = [0u8; 4];
+            return GzDecoder::new(data).read_exact(&mut header).is_ok()
+                && Self::has_magic(&header);
+        }
+
+        false
+    }
+
+    fn has_magic(data: &[u8]) -> bool {
+        data.len() >= 4 && u32::from_le_bytes([data[0], data[1], data[2], data[3]]) == EMUBLOG_MAGIC
+    }
+
+    /// Parse .emublog data, decompressing it first if needed
+    pub fn parse_binary(data: &[u8]) -> Result<Log, ParseError> {
+        if Self::has_magic(data) {
+            return Self::parse_decompressed(data);
+        }
+
+        let mut decompressed = Vec::new();
+        GzDecoder::new(data)
+            .read_to_end(&mut decompressed)
+            .map_err(|e| {
+                ParseError::EncodingError(format!("Failed to decompress .emublog file: {}", e))
+            })?;
+        Self::parse_decompressed(&decompressed)
+    }
+
+    fn parse_decompressed(data: &[u8]) -> Result<Log, ParseError> {
+        if !Self::has_magic(data) {
+            return Err(ParseError::BadMagic { format: ".emublog" });
+        }
+        if data.len() < HEADER_SIZE {
+            return Err(ParseError::truncated(data.len(), ".emublog header"));
+        }
+
+        let version = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+        let record_count = u32::from_le_bytes([data[8], data[9], data[10], data[11]]) as usize;
+
+        let (record_size, fields) = match version {
+            5 => (RECORD_SIZE_V5, FIELDS_V5),
+            _ => {
+                return Err(ParseError::UnsupportedVersion {
+                    format: ".emublog",
+                    version: version.to_string(),
+                })
+            }
+        };
+
+        // Trust the data over the header if the logger was cut off mid-write
+        let available = (data.len() - HEADER_SIZE) / record_size;
+        if available < record_count {
+            tracing::warn!(
+                ".emublog header declares {} records but only {} are present",
+                record_count,
+                available
+            );
+        }
+        let record_count = record_count.min(available);
+
+        let channels: Vec<Channel> = fields
+            .iter()
+            .map(|f| Channel::EcuMaster(EcuMasterChannel::from_path(f.path)))
+            .collect();
+
+        let mut times: Vec<f64> = Vec::with_capacity(record_count);
+        let mut columns: Vec<Vec<f64>> = (0..fields.len())
+            .map(|_| Vec::with_capacity(record_count))
+            .collect();
+
+        // Sample counter is a u16 that wraps; track wraps to keep time monotonic
+        let mut prev_counter: Option<u64> = None;
+        let mut prev_raw: u16 = 0;
+        let mut wrap_count: u64 = 0;
+        let mut elapsed: u64 = 0;
+        let mut annotations = Vec::new();
+
+        for record in data[HEADER_SIZE..]
+            .chunks_exact(record_size)
+            .take(record_count)
+        {
+            let raw_counter = u16::from_le_bytes([record[0], record[1]]);
+            if prev_counter.is_some() && raw_counter < prev_raw {
+                wrap_count += 1;
+            }
+            prev_raw = raw_counter;
+
+            let counter = wrap_count * 65536 + raw_counter as u64;
+            if let Some(prev) = prev_counter {
+                let step = counter - prev;
+                if step > MAX_COUNTER_STEP {
+                    // Nothing was logged from the next sample on
+                    times.push((elapsed + 1) as f64 / SAMPLE_RATE_HZ);
+                    for column in &mut columns {
+                        column.push(MISSING);
+                    }
+                    annotations.push(Annotation {
+                        time: (elapsed + step) as f64 / SAMPLE_RATE_HZ,
+                        label: format!("Logging resumed ({} samples skipped)", step - 1),
+                    });
+                }
+                elapsed += step;
+            }
+            prev_counter = Some(counter);
+
+            times.push(elapsed as f64 / SAMPLE_RATE_HZ);
+            for (column, field) in columns.iter_mut().zip(fields) {
+                column.push(field.read(record));
+            }
+        }
+
+        tracing::info!(
+            "Parsed ECUMaster .emublog (v{}): {} channels, {} data points, {} logging pauses",
+            version,
+            channels.len(),
+            times.len(),
+            annotations.len()
+        );
+
+        let mut log = Log::new(
+            Meta::EcuMaster(EcuMasterMeta {
+                channel_count: channels.len(),
+                data_points: times.len(),
+            }),
+            channels,
+            times,
+            columns,
+        );
+        log.annotations = annotations;
+        Ok(log)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_log(records: &[(u16, u16)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&EMUBLOG_MAGIC.to_le_bytes());
+        data.extend_from_slice(&5u32.to_le_bytes());
+        data.extend_from_slice(&(records.len() as u32).to_le_bytes());
+        for &(counter, rpm) in records {
+            let mut record = vec![0u8; RECORD_SIZE_V5];
+            record[0..2].copy_from_slice(&counter.to_le_bytes());
+            record[2..4].copy_from_slice(&rpm.to_le_bytes());
+            record[7] = (-5i8) as u8;
+            record[8..10].copy_from_slice(&518u16.to_le_bytes());
+            data.extend_from_slice(&record);
+        }
+        data
+    }
+
+    #[test]
+    fn test_detect() {
+        assert!(EmuBlog::detect(&build_log(&[(0, 1000)])));
+        assert!(!EmuBlog::detect(b"TIME;engine/rpm\n"));
+        assert!(!EmuBlog::detect(&[0x1F, 0x8B]));
+    }
+
+    #[test]
+    fn test_parse_raw_records() {
+        let log =
+            EmuBlog::parse_binary(&build_log(&[(100, 900), (101, 950), (103, 1000)])).unwrap();
+
+        assert_eq!(log.channels.len(), FIELDS_V5.len());
+        assert_eq!(log.channels[0].name(), "rpm");
+        assert_eq!(log.channels[0].unit(), "RPM");
+        assert_eq!(log.times, vec![0.0, 0.05, 0.15]);
+        assert_eq!(log.channel_data(0), vec![900.0, 950.0, 1000.0]);
+
+        // Signed temperature and scaled voltage
+        assert_eq!(log.channel_data(3)[0], -5.0);
+        assert!((log.channel_data(4)[0] - 14.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_counter_wraparound() {
+        let log =
+            EmuBlog::parse_binary(&build_log(&[(65534, 900), (65535, 900), (0, 900)])).unwrap();
+        assert_eq!(log.times, vec![0.0, 0.05, 0.1]);
+    }
+
+    #[test]
+    fn test_counter_gaps_are_pauses() {
+        let log = EmuBlog::parse_binary(&build_log(&[
+            (10, 900),
+            (11, 900),
+            (1898, 950),
+            (1899, 950),
+        ]))
+        .unwrap();
+
+        // The jump keeps its full 94 seconds, with a gap where nothing was logged
+        assert_eq!(log.times, vec![0.0, 0.05, 0.1, 94.40, 94.45]);
+        assert_eq!(log.channel_data(0)[..2], [900.0, 900.0]);
+        assert!(log.channel_data(0)[2].is_nan());
+        assert_eq!(log.channel_data(0)[3..], [950.0, 950.0]);
+        assert_eq!(log.gap_times(), vec![0.1]);
+        assert_eq!(log.annotations.len(), 1);
+        assert_eq!(log.annotations[0].time, 94.40);
+        assert_eq!(
+            log.annotations[0].label,
+            "Logging resumed (1886 samples skipped)"
+        );
+    }
+
+    #[test]
+    fn test_truncated_records_are_dropped() {
+        let mut data = build_log(&[(0, 900), (1, 900)]);
+        data.truncate(data.len() - 10);
+        let log = EmuBlog::parse_binary(&data).unwrap();
+        assert_eq!(log.record_count(), 1);
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let mut data = build_log(&[(0, 900)]);
+        data[4] = 9;
+        assert!(EmuBlog::parse_binary(&data).is_err());
+    }
+}
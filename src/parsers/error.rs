@@ -0,0 +1,134 @@
+//! Structured errors returned by the log parsers.
+//!
+//! Each variant says what kind of problem was found and, where the parser
+//! knows it, where: a byte offset for binary formats or a 1-based line
+//! number for text headers. The loader keeps the error intact so the
+//! diagnostics dialog can show the location rather than a flattened string.
+
+use std::io;
+use thiserror::Error;
+
+/// Errors that can occur parsing a log file
+#[derive(Debug, Error)]
+pub enum ParseError {
+    /// The data doesn't start with the format's signature
+    #[error("Invalid {format} file header")]
+    BadMagic { format: &'static str },
+
+    /// The data ends before a structure it declares
+    #[error("File is truncated at byte {offset}: {context}")]
+    Truncated { offset: u64, context: String },
+
+    /// A header line is missing or malformed
+    #[error("Bad header at line {line}: {message}")]
+    BadHeader { line: usize, message: String },
+
+    /// The file declares a format version the parser can't read
+    #[error("Unsupported {format} version: {version}")]
+    UnsupportedVersion {
+        format: &'static str,
+        version: String,
+    },
+
+    /// Text that can't be decoded
+    #[error("Encoding error: {0}")]
+    EncodingError(String),
+
+    /// A binary structure holds an impossible value
+    #[error("Corrupt data at byte {offset}: {message}")]
+    Corrupt { offset: u64, message: String },
+
+    /// The file is well formed but holds nothing to load, or was handed to
+    /// the wrong kind of parser
+    #[error("{0}")]
+    Invalid(String),
+
+    /// Reading the input failed
+    #[error("Read error: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl ParseError {
+    pub fn truncated(offset: usize, context: impl Into<String>) -> Self {
+        Self::Truncated {
+            offset: offset as u64,
+            context: context.into(),
+        }
+    }
+
+    pub fn corrupt(offset: usize, message: impl Into<String>) -> Self {
+        Self::Corrupt {
+            offset: offset as u64,
+            message: message.into(),
+        }
+    }
+
+    pub fn bad_header(line: usize, message: impl Into<String>) -> Self {
+        Self::BadHeader {
+            line,
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::Invalid(message.into())
+    }
+
+    /// Short name of the kind of problem, for display
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::BadMagic { .. } => "Bad magic",
+            Self::Truncated { .. } => "Truncated",
+            Self::BadHeader { .. } => "Bad header",
+            Self::UnsupportedVersion { .. } => "Unsupported version",
+            Self::EncodingError(_) => "Encoding error",
+            Self::Corrupt { .. } => "Corrupt data",
+            Self::Invalid(_) => "Invalid log",
+            Self::Io(_) => "Read error",
+        }
+    }
+
+    /// Byte offset of the problem, for binary formats
+    pub fn offset(&self) -> Option<u64> {
+        match self {
+            Self::Truncated { offset, .. } | Self::Corrupt { offset, .. } => Some(*offset),
+            _ => None,
+        }
+    }
+
+    /// 1-based line number of the problem, for text formats
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Self::BadHeader { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location_accessors() {
+        let truncated = ParseError::truncated(120, "channel table");
+        assert_eq!(truncated.offset(), Some(120));
+        assert_eq!(truncated.line(), None);
+        assert_eq!(
+            truncated.to_string(),
+            "File is truncated at byte 120: channel table"
+        );
+
+        let header = ParseError::bad_header(3, "first column must be Time");
+        assert_eq!(header.line(), Some(3));
+        assert_eq!(header.offset(), None);
+        assert_eq!(header.kind(), "Bad header");
+    }
+
+    #[test]
+    fn test_io_errors_convert() {
+        let error: ParseError = io::Error::from(io::ErrorKind::UnexpectedEof).into();
+        assert!(matches!(error, ParseError::Io(_)));
+        assert_eq!(error.kind(), "Read error");
+    }
+}
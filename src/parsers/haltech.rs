@@ -1,7 +1,8 @@
+use chrono::NaiveDate;
 use rayon::prelude::*;
 use regex::Regex;
-use serde::Serialize;
-use std::error::Error;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Cursor, Read, Seek};
 use std::str::FromStr;
 use std::sync::LazyLock;
 use strum::{AsRefStr, EnumString};
@@ -10,10 +11,16 @@ use strum::{AsRefStr, EnumString};
 static TIMESTAMP_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^\d{1,2}:\d{2}:\d{2}").expect("Invalid timestamp regex"));
 
-use super::types::{Channel, Log, Meta, Parseable, Value};
+use super::datetime::{self, DayRollover};
+use super::error::ParseError;
+use super::streaming::{
+    stream_len, ChunkedOutput, LogBuilder, RecordSink, StreamHeader, StreamProgress,
+    StreamingParseable, CHUNK_RECORDS,
+};
+use super::types::{non_empty, Annotation, Channel, Log, LogInfo, Meta, Parseable, MISSING};
 
 /// Haltech channel types - comprehensive list from actual log files
-#[derive(AsRefStr, Clone, Debug, EnumString, Serialize, Default)]
+#[derive(AsRefStr, Clone, Debug, EnumString, Deserialize, Serialize, Default)]
 pub enum ChannelType {
     AFR,
     AbsPressure,
@@ -231,7 +238,7 @@ impl ChannelType {
 }
 
 /// Haltech log file metadata
-#[derive(Clone, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct HaltechMeta {
     pub data_log_version: String,
     pub software: String,
@@ -242,8 +249,23 @@ pub struct HaltechMeta {
     pub log_date_time: String,
 }
 
+impl HaltechMeta {
+    /// Common metadata from the `%DataLog%` header block
+    pub fn info(&self) -> LogInfo {
+        let mut info = LogInfo::new("Haltech");
+        info.start_datetime = non_empty(&self.log_date_time);
+        info.push_vendor("Data log version", &self.data_log_version);
+        info.push_vendor("Software", &self.software);
+        info.push_vendor("Software version", &self.software_version);
+        info.push_vendor("Downloaded", &self.download_date_time);
+        info.push_vendor("Log source", &self.log_source);
+        info.push_vendor("Log number", &self.log_number);
+        info
+    }
+}
+
 /// Haltech channel definition
-#[derive(Clone, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct HaltechChannel {
     pub name: String,
     pub id: String,
@@ -263,7 +285,15 @@ impl HaltechChannel {
 pub struct Haltech;
 
 impl Haltech {
-    /// Parse timestamp from HH:MM:SS.mmm format to seconds
+    /// Detect if file contents start with the Haltech NSP `%DataLog%` marker
+    pub fn detect(contents: &str) -> bool {
+        contents
+            .trim_start_matches('\u{feff}')
+            .trim_start()
+            .starts_with("%DataLog%")
+    }
+
+    /// Parse a time of day from HH:MM:SS.mmm format to seconds since midnight
     fn parse_timestamp(timestamp: &str) -> Option<f64> {
         // Format: "HH:MM:SS.mmm" e.g., "14:15:46.000"
         let parts: Vec<&str> = timestamp.split(':').collect();
@@ -280,6 +310,19 @@ impl Haltech {
         Some(hours * 3600.0 + minutes * 60.0 + seconds)
     }
 
+    /// Whether a channel records driver-pressed log markers (e.g., "Data Log Marker").
+    /// NSP exports have no marker lines; markers only show up as such a channel.
+    fn is_marker_channel(name: &str) -> bool {
+        name.to_ascii_lowercase().contains("marker")
+    }
+
+    /// Date from the "Log" header line (e.g., "20250718 02:15:46"). Its time
+    /// is not used: it can be on a 12-hour clock while rows are on 24.
+    fn log_date(meta: &HaltechMeta) -> Option<NaiveDate> {
+        let date = meta.log_date_time.split_whitespace().next()?;
+        NaiveDate::parse_from_str(date, "%Y%m%d").ok()
+    }
+
     /// Check if a line looks like a data row (starts with timestamp)
     fn is_data_row(line: &str) -> bool {
         // Data rows start with HH:MM:SS pattern - uses pre-compiled static regex
@@ -287,8 +330,55 @@ impl Haltech {
     }
 }
 
-impl Parseable for Haltech {
-    fn parse(&self, file_contents: &str) -> Result<Log, Box<dyn Error>> {
+impl Haltech {
+    /// Parse one data row into its absolute timestamp and converted values.
+    /// Unparsable cells are missing so later columns stay aligned.
+    fn parse_row(line: &str, types: &[ChannelType]) -> Option<(f64, Vec<f64>)> {
+        let mut parts = line.split(',');
+
+        // First column is timestamp
+        let timestamp_secs = Self::parse_timestamp(parts.next()?.trim())?;
+
+        // Parse remaining values and apply unit conversions
+        let values: Vec<f64> = parts
+            .enumerate()
+            .map(|(idx, v)| {
+                let Ok(raw_value) = v.trim().parse::<f64>() else {
+                    return MISSING;
+                };
+
+                // Apply conversion based on channel type if available
+                match types.get(idx) {
+                    Some(channel_type) => channel_type.convert_value(raw_value),
+                    None => raw_value,
+                }
+            })
+            .collect();
+
+        if values.is_empty() {
+            None
+        } else {
+            Some((timestamp_secs, values))
+        }
+    }
+
+    /// Read the next line, decoding invalid UTF-8 lossily. Returns false at end of input.
+    fn read_line<R: BufRead>(reader: &mut R, buf: &mut Vec<u8>) -> std::io::Result<bool> {
+        buf.clear();
+        Ok(reader.read_until(b'\n', buf)? > 0)
+    }
+}
+
+impl StreamingParseable for Haltech {
+    fn parse_stream<R: Read + Seek>(
+        &self,
+        reader: R,
+        sink: &mut dyn RecordSink,
+        progress: &mut dyn FnMut(StreamProgress),
+    ) -> Result<(), ParseError> {
+        let mut reader = BufReader::new(reader);
+        let total_bytes = stream_len(&mut reader)?;
+
         let mut meta = HaltechMeta::default();
         let mut channels: Vec<Channel> = Vec::with_capacity(50); // Typical log has ~20-50 channels
 
@@ -297,157 +387,171 @@ impl Parseable for Haltech {
             Regex::new(r"^(?<name>[^:]+?)\s*:\s*(?<value>.+)$").expect("Failed to compile regex");
 
         let mut current_channel = HaltechChannel::default();
-        let mut data_lines: Vec<&str> = Vec::new();
+        let mut buf = Vec::new();
+        let mut data_lines: Vec<String> = Vec::with_capacity(CHUNK_RECORDS);
 
-        // Phase 1: Parse metadata and channels, collect data lines
-        for line in file_contents.lines() {
-            let line = line.trim();
+        // Phase 1: Parse metadata and channel definitions up to the first data row
+        while Self::read_line(&mut reader, &mut buf)? {
+            let text = String::from_utf8_lossy(&buf);
+            let line = text.trim().trim_start_matches('\u{feff}');
 
             // Skip empty lines and header marker
             if line.is_empty() || line == "%DataLog%" {
                 continue;
             }
 
-            // Check if this is a data row
             if Self::is_data_row(line) {
-                // Push any pending channel before collecting data lines
-                if !current_channel.name.is_empty() {
-                    channels.push(Channel::Haltech(current_channel));
-                    current_channel = HaltechChannel::default();
-                }
-
-                // Collect data line for parallel processing
-                data_lines.push(line);
-                continue;
+                data_lines.push(line.to_string());
+                break;
             }
 
-            // Not in data section yet - parse metadata and channel definitions
-            if data_lines.is_empty() {
-                if let Some(captures) = kv_regex.captures(line) {
-                    let name = captures["name"].trim();
-                    let value = captures["value"].trim().to_string();
-
-                    match name {
-                        "DataLogVersion" => meta.data_log_version = value,
-                        "Software" => meta.software = value,
-                        "SoftwareVersion" => meta.software_version = value,
-                        "DownloadDateTime" | "DownloadDate/Time" => meta.download_date_time = value,
-                        "Log Source" => meta.log_source = value,
-                        "Log Number" => meta.log_number = value,
-                        "Log" => meta.log_date_time = value,
-                        // "Channel" key indicates start of a new channel definition
-                        "Channel" => {
-                            if !current_channel.name.is_empty() {
-                                channels.push(Channel::Haltech(current_channel));
-                            }
-                            current_channel = HaltechChannel::default();
-                            current_channel.name = value;
+            if let Some(captures) = kv_regex.captures(line) {
+                let name = captures["name"].trim();
+                let value = captures["value"].trim().to_string();
+
+                match name {
+                    "DataLogVersion" => meta.data_log_version = value,
+                    "Software" => meta.software = value,
+                    "SoftwareVersion" => meta.software_version = value,
+                    "DownloadDateTime" | "DownloadDate/Time" => meta.download_date_time = value,
+                    "Log Source" => meta.log_source = value,
+                    "Log Number" => meta.log_number = value,
+                    "Log" => meta.log_date_time = value,
+                    // "Channel" key indicates start of a new channel definition
+                    "Channel" => {
+                        if !current_channel.name.is_empty() {
+                            channels.push(Channel::Haltech(current_channel));
                         }
-                        "ID" => current_channel.id = value,
-                        "Type" => {
-                            if let Ok(channel_type) = ChannelType::from_str(&value) {
-                                current_channel.r#type = channel_type;
-                            } else {
-                                tracing::warn!("Unknown channel type: {}", value);
-                                current_channel.r#type = ChannelType::Raw;
-                            }
+                        current_channel = HaltechChannel::default();
+                        current_channel.name = value;
+                    }
+                    "ID" => current_channel.id = value,
+                    "Type" => {
+                        if let Ok(channel_type) = ChannelType::from_str(&value) {
+                            current_channel.r#type = channel_type;
+                        } else {
+                            tracing::warn!("Unknown channel type: {}", value);
+                            current_channel.r#type = ChannelType::Raw;
                         }
-                        "DisplayMaxMin" => {
-                            let values: Vec<&str> = value.split(',').collect();
-                            if values.len() >= 2 {
-                                current_channel.display_max = values[0].trim().parse().ok();
-                                current_channel.display_min = values[1].trim().parse().ok();
-                            }
+                    }
+                    "DisplayMaxMin" => {
+                        let values: Vec<&str> = value.split(',').collect();
+                        if values.len() >= 2 {
+                            current_channel.display_max = values[0].trim().parse().ok();
+                            current_channel.display_min = values[1].trim().parse().ok();
                         }
-                        _ => {}
                     }
+                    _ => {}
                 }
             }
         }
+        if !current_channel.name.is_empty() {
+            channels.push(Channel::Haltech(current_channel));
+        }
 
-        // Phase 2: Parse data rows in parallel
-        // Each row is parsed independently, returning (timestamp, values)
-        let parsed_rows: Vec<(f64, Vec<Value>)> = data_lines
-            .par_iter()
-            .filter_map(|line| {
-                let parts: Vec<&str> = line.split(',').collect();
-                if parts.is_empty() {
-                    return None;
-                }
-
-                // First column is timestamp
-                let timestamp_str = parts[0].trim();
-                let timestamp_secs = Self::parse_timestamp(timestamp_str)?;
-
-                // Parse remaining values and apply unit conversions
-                let values: Vec<Value> = parts[1..]
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(idx, v)| {
-                        let v = v.trim();
-                        let raw_value: f64 = v.parse().ok()?;
-
-                        // Apply conversion based on channel type if available
-                        let converted = if let Some(Channel::Haltech(ch)) = channels.get(idx) {
-                            ch.r#type.convert_value(raw_value)
-                        } else {
-                            raw_value
-                        };
-                        Some(Value::Float(converted))
-                    })
-                    .collect();
-
-                if values.is_empty() {
-                    None
-                } else {
-                    Some((timestamp_secs, values))
-                }
+        let channel_count = channels.len();
+        let types: Vec<ChannelType> = channels
+            .iter()
+            .map(|c| match c {
+                Channel::Haltech(h) => h.r#type.clone(),
+                _ => ChannelType::Raw,
             })
             .collect();
 
-        // Phase 3: Post-process results (sequential for ordering)
-        let data_count = parsed_rows.len();
-        let mut times: Vec<f64> = Vec::with_capacity(data_count);
-        let mut data: Vec<Vec<Value>> = Vec::with_capacity(data_count);
+        // Marker channels, with the value each held in the previous row
+        let mut markers: Vec<(usize, String, f64)> = channels
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| Self::is_marker_channel(&c.name()))
+            .map(|(idx, c)| (idx, c.name(), 0.0))
+            .collect();
 
-        if !parsed_rows.is_empty() {
-            // First timestamp is the base for relative times
-            let first_timestamp = parsed_rows[0].0;
+        // Rows carry the time of day, so the first one dates the log
+        let first_time_of_day = data_lines
+            .first()
+            .and_then(|line| Self::parse_timestamp(line.split(',').next()?.trim()));
+        let start = Self::log_date(&meta)
+            .zip(first_time_of_day)
+            .and_then(|(date, seconds)| datetime::at_time_of_day(date, seconds));
 
-            for (timestamp, values) in parsed_rows {
-                times.push(timestamp - first_timestamp);
-                data.push(values);
+        let header = StreamHeader {
+            meta: Meta::Haltech(meta),
+            channels,
+            estimated_records: 0,
+            start,
+        };
+        let mut output = ChunkedOutput::begin(sink, progress, header, total_bytes)?;
+
+        // Phase 2: Parse data rows a chunk at a time, each chunk in parallel
+        let mut first_timestamp: Option<f64> = None;
+        let mut rollover = DayRollover::default();
+        loop {
+            while data_lines.len() < CHUNK_RECORDS && Self::read_line(&mut reader, &mut buf)? {
+                let text = String::from_utf8_lossy(&buf);
+                let line = text.trim();
+                if Self::is_data_row(line) {
+                    data_lines.push(line.to_string());
+                }
+            }
+            if data_lines.is_empty() {
+                break;
             }
-        }
 
-        // Verify data integrity
-        let channel_count = channels.len();
-        if channel_count > 0 {
-            // Filter out data rows that don't match channel count
-            let mut filtered_times = Vec::with_capacity(times.len());
-            let mut filtered_data = Vec::with_capacity(data.len());
-            for (time, row) in times.into_iter().zip(data.into_iter()) {
-                if row.len() >= channel_count {
-                    filtered_times.push(time);
-                    filtered_data.push(row);
+            let parsed_rows: Vec<(f64, Vec<f64>)> = data_lines
+                .par_iter()
+                .filter_map(|line| Self::parse_row(line, &types))
+                .collect();
+            data_lines.clear();
+
+            for (time_of_day, values) in parsed_rows {
+                // Keep counting forwards when the log runs past midnight
+                let timestamp = rollover.advance(time_of_day);
+                // First timestamp is the base for relative times
+                let base = *first_timestamp.get_or_insert(timestamp);
+                // Verify data integrity: skip rows that don't cover every channel
+                if values.len() < channel_count {
+                    continue;
+                }
+                // A marker channel turning on marks that row
+                for (idx, name, previous) in &mut markers {
+                    let value = values[*idx];
+                    if value != 0.0 && !value.is_nan() && *previous == 0.0 {
+                        output.annotate(Annotation {
+                            time: timestamp - base,
+                            label: if value == 1.0 {
+                                name.clone()
+                            } else {
+                                format!("{} {}", name, value)
+                            },
+                        });
+                    }
+                    if !value.is_nan() {
+                        *previous = value;
+                    }
                 }
+                output.push(timestamp - base, &values)?;
             }
-            times = filtered_times;
-            data = filtered_data;
+            output.report(reader.stream_position()?);
         }
 
         tracing::info!(
             "Parsed Haltech log: {} channels, {} data points",
-            channels.len(),
-            data.len()
+            channel_count,
+            output.records()
         );
+        output.finish()
+    }
+}
 
-        Ok(Log {
-            meta: Meta::Haltech(meta),
-            channels,
-            times,
-            data,
-        })
+impl Parseable for Haltech {
+    fn parse(&self, file_contents: &str) -> Result<Log, ParseError> {
+        let mut builder = LogBuilder::new();
+        self.parse_stream(
+            Cursor::new(file_contents.as_bytes()),
+            &mut builder,
+            &mut |_| {},
+        )?;
+        Ok(builder.finish())
     }
 }
 
@@ -494,7 +598,7 @@ Log : 20250718 02:15:46
         assert_eq!(log.channels[0].name(), "RPM");
         assert_eq!(log.channels[1].name(), "Manifold Pressure");
         assert_eq!(log.times.len(), 3);
-        assert_eq!(log.data.len(), 3);
+        assert_eq!(log.record_count(), 3);
 
         // Check relative timestamps (now stored as f64)
         assert!((log.times[0] - 0.0).abs() < 0.001);
@@ -503,10 +607,10 @@ Log : 20250718 02:15:46
 
         // Check unit conversions are applied
         // RPM: y = x (no conversion) - raw 5000 -> 5000 RPM
-        assert_eq!(log.data[0][0].as_f64(), 5000.0);
+        assert_eq!(log.channel_data(0)[0], 5000.0);
 
         // Pressure: y = x/10 - 101.3 (gauge kPa) - raw 1013 -> 0.0 kPa
-        let pressure_value = log.data[0][1].as_f64();
+        let pressure_value = log.channel_data(1)[0];
         assert!(
             (pressure_value - 0.0).abs() < 0.01,
             "Expected ~0.0, got {}",
@@ -518,6 +622,67 @@ Log : 20250718 02:15:46
         assert_eq!(log.channels[1].unit(), "kPa");
     }
 
+    #[test]
+    fn test_midnight_rollover() {
+        let sample = r#"%DataLog%
+DataLogVersion : 1.1
+Channel : RPM
+ID : 384
+Type : EngineSpeed
+DisplayMaxMin : 20000,0
+Log : 20250718 11:59:59
+23:59:59.500,5000
+23:59:59.900,5100
+00:00:00.300,5200
+"#;
+
+        let log = Haltech.parse(sample).unwrap();
+
+        assert!((log.times[1] - 0.4).abs() < 0.001);
+        assert!((log.times[2] - 0.8).abs() < 0.001);
+
+        // The start takes its date from the header and time from the first row
+        let start = log.start.unwrap();
+        assert_eq!(
+            start.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            "2025-07-18 23:59:59.500"
+        );
+        let last = log.wall_clock(log.times[2]).unwrap();
+        assert_eq!(
+            last.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            "2025-07-19 00:00:00.300"
+        );
+    }
+
+    #[test]
+    fn test_marker_channel_annotations() {
+        let sample = r#"%DataLog%
+DataLogVersion : 1.1
+Channel : RPM
+ID : 384
+Type : EngineSpeed
+DisplayMaxMin : 20000,0
+Channel : Data Log Marker
+ID : 900
+Type : Raw
+DisplayMaxMin : 255,0
+14:15:46.000,5000,0
+14:15:46.020,5100,1
+14:15:46.040,5200,1
+14:15:46.060,5300,0
+14:15:46.080,5400,2
+"#;
+
+        let log = Haltech.parse(sample).unwrap();
+
+        // Only rows where the marker turns on are annotated
+        assert_eq!(log.annotations.len(), 2);
+        assert!((log.annotations[0].time - 0.020).abs() < 0.001);
+        assert_eq!(log.annotations[0].label, "Data Log Marker");
+        assert!((log.annotations[1].time - 0.080).abs() < 0.001);
+        assert_eq!(log.annotations[1].label, "Data Log Marker 2");
+    }
+
     #[test]
     fn test_channel_type_conversions() {
         // RPM: no conversion
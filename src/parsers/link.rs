@@ -7,13 +7,16 @@
 //! - Channel blocks with name, unit, and time-series data
 //! - Data stored as f32 (value, time) pairs
 
-use serde::Serialize;
-use std::error::Error;
+use serde::{Deserialize, Serialize};
 
-use super::types::{Channel, Log, Meta, Parseable, Value};
+use super::error::ParseError;
+use super::types::{
+    non_empty, Channel, ColumnBuilder, Log, LogInfo, Meta, NativeSeries, Parseable, RecoveryReport,
+    MISSING,
+};
 
 /// Link ECU channel metadata
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LinkChannel {
     pub name: String,
     pub unit: String,
@@ -28,7 +31,7 @@ impl LinkChannel {
 }
 
 /// Link ECU log metadata
-#[derive(Clone, Debug, Serialize, Default)]
+#[derive(Clone, Debug, Deserialize, Serialize, Default)]
 pub struct LinkMeta {
     pub ecu_model: String,
     pub log_date: String,
@@ -37,6 +40,18 @@ pub struct LinkMeta {
     pub source: String,
 }
 
+impl LinkMeta {
+    /// Common metadata from the LLG header
+    pub fn info(&self) -> LogInfo {
+        let mut info = LogInfo::new("Link");
+        info.start_datetime = non_empty(&format!("{} {}", self.log_date, self.log_time));
+        info.ecu_model = non_empty(&self.ecu_model);
+        info.push_vendor("Software version", &self.software_version);
+        info.push_vendor("Source", &self.source);
+        info
+    }
+}
+
 /// Link ECU log file parser
 pub struct Link;
 
@@ -100,21 +115,38 @@ impl Link {
     }
 
     /// Parse the LLG binary format
-    pub fn parse_binary(data: &[u8]) -> Result<Log, Box<dyn Error>> {
+    pub fn parse_binary(data: &[u8]) -> Result<Log, ParseError> {
+        Self::read_binary(data, false)
+    }
+
+    /// Parse an LLG file that was cut off mid-write. Channel blocks are
+    /// found by scanning, so everything before the cut is kept; the log's
+    /// `recovery` report notes where the file ends.
+    pub fn recover_binary(data: &[u8]) -> Result<Log, ParseError> {
+        Self::read_binary(data, true)
+    }
+
+    fn read_binary(data: &[u8], recover: bool) -> Result<Log, ParseError> {
         // Validate header
         if !Self::detect(data) {
-            return Err("Invalid LLG file header - expected 'lf3' magic".into());
+            return Err(ParseError::BadMagic { format: "Link LLG" });
         }
 
+        let mut report = RecoveryReport::default();
+
         // Read header size (first 4 bytes)
         let header_size = Self::read_u32(data, 0) as usize;
-        if header_size > data.len() {
-            return Err(format!(
-                "Header size {} exceeds file size {}",
-                header_size,
-                data.len()
-            )
-            .into());
+        if header_size > data.len() && recover {
+            report.skip(data.len() as u64, 0);
+        } else if header_size > data.len() {
+            return Err(ParseError::corrupt(
+                0,
+                format!(
+                    "Header size {} exceeds file size {}",
+                    header_size,
+                    data.len()
+                ),
+            ));
         }
 
         // Parse metadata
@@ -169,6 +201,19 @@ impl Link {
             offset += 1;
         }
 
+        // A channel header too close to the end for its name and unit means
+        // the file was cut off while that channel was being written
+        for tail in offset..data.len().saturating_sub(8) {
+            if tail + 408 > data.len()
+                && data[tail..tail + 4] == [0, 0, 0, 0]
+                && (1..10000).contains(&Self::read_u32(data, tail + 4))
+                && Self::read_utf16_string(data, tail + 8, 100).len() >= 2
+            {
+                report.skip(tail as u64, 1);
+                break;
+            }
+        }
+
         // Update channel_offsets with next channel starts
         for i in 0..channel_offsets.len() {
             if i + 1 < channel_offsets.len() {
@@ -241,28 +286,33 @@ impl Link {
         if all_times.is_empty() {
             tracing::warn!("No valid time-series data found in LLG file");
             // Return empty log with channel definitions
-            return Ok(Log {
-                meta: Meta::Link(meta),
-                channels: channels.into_iter().map(Channel::Link).collect(),
-                times: Vec::new(),
-                data: Vec::new(),
-            });
+            let columns = vec![Vec::new(); channels.len()];
+            let mut log = Log::new(
+                Meta::Link(meta),
+                channels.into_iter().map(Channel::Link).collect(),
+                Vec::new(),
+                columns,
+            );
+            log.recovery = recover.then_some(report);
+            return Ok(log);
         }
 
         // Convert to f64 times (seconds, relative to first timestamp)
         let first_time = *all_times.first().unwrap_or(&0.0);
         let times: Vec<f64> = all_times.iter().map(|t| (*t - first_time) as f64).collect();
 
-        // Build data matrix: for each timestamp, interpolate/hold values for each channel
-        let mut data_matrix: Vec<Vec<Value>> = Vec::with_capacity(times.len());
+        // Build data matrix: for each timestamp, hold each channel's last value.
+        // Before a channel's first sample there is nothing to hold, so the
+        // record is left missing rather than backfilled.
+        let mut data_matrix = ColumnBuilder::with_capacity(channels.len(), times.len());
 
         for (time_idx, &time) in all_times.iter().enumerate() {
-            let mut row: Vec<Value> = Vec::with_capacity(channels.len());
+            let mut row: Vec<f64> = Vec::with_capacity(channels.len());
 
             for ch_data in &channel_data {
                 // Find the value at or before this timestamp
                 let value = if ch_data.is_empty() {
-                    0.0
+                    MISSING
                 } else {
                     // Binary search for the closest time <= current time
                     match ch_data.binary_search_by(|probe| {
@@ -271,21 +321,16 @@ impl Link {
                             .partial_cmp(&time)
                             .unwrap_or(std::cmp::Ordering::Equal)
                     }) {
-                        Ok(idx) => ch_data[idx].1,
-                        Err(idx) => {
-                            if idx == 0 {
-                                ch_data[0].1
-                            } else {
-                                ch_data[idx - 1].1
-                            }
-                        }
+                        Ok(idx) => ch_data[idx].1 as f64,
+                        Err(0) => MISSING,
+                        Err(idx) => ch_data[idx - 1].1 as f64,
                     }
                 };
 
-                row.push(Value::Float(value as f64));
+                row.push(value);
             }
 
-            data_matrix.push(row);
+            data_matrix.push_record(&row);
 
             // Limit output to reasonable size
             if time_idx > 50000 {
@@ -301,20 +346,40 @@ impl Link {
             meta.ecu_model
         );
 
-        Ok(Log {
-            meta: Meta::Link(meta),
-            channels: channels.into_iter().map(Channel::Link).collect(),
-            times: times[..data_matrix.len()].to_vec(),
-            data: data_matrix,
-        })
+        let times = times[..data_matrix.len()].to_vec();
+        let end_time = times.last().copied().unwrap_or(0.0);
+        let mut log = Log::new(
+            Meta::Link(meta),
+            channels.into_iter().map(Channel::Link).collect(),
+            times,
+            data_matrix.into_columns(),
+        );
+
+        // Channels that weren't sampled at every timestamp keep their own timebase
+        for (idx, points) in channel_data.iter().enumerate() {
+            if points.is_empty() || points.len() == log.record_count() {
+                continue;
+            }
+            let (times, values) = points
+                .iter()
+                .map(|&(time, value)| ((time - first_time) as f64, value as f64))
+                .filter(|&(time, _)| time <= end_time)
+                .unzip();
+            log.set_native_series(idx, NativeSeries { times, values });
+        }
+
+        log.recovery = recover.then_some(report);
+        Ok(log)
     }
 }
 
 impl Parseable for Link {
-    fn parse(&self, _data: &str) -> Result<Log, Box<dyn Error>> {
+    fn parse(&self, _data: &str) -> Result<Log, ParseError> {
         // This method is for text-based parsing
         // Link ECU uses binary LLG format, so this will return an error
-        Err("Link ECU LLG files are binary format. Use parse_binary() instead.".into())
+        Err(ParseError::invalid(
+            "Link ECU LLG files are binary format. Use parse_binary() instead.",
+        ))
     }
 }
 
@@ -403,7 +468,7 @@ mod tests {
         }
 
         eprintln!("Parsed {} channels from Link ECU log", log.channels.len());
-        eprintln!("Parsed {} data records", log.data.len());
+        eprintln!("Parsed {} data records", log.record_count());
 
         // Check metadata
         if let Meta::Link(meta) = &log.meta {
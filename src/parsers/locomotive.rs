@@ -1,12 +1,12 @@
 use chrono::NaiveDateTime;
 use rayon::prelude::*;
-use serde::Serialize;
-use std::error::Error;
+use serde::{Deserialize, Serialize};
 
-use super::types::{Channel, Log, Meta, Parseable, Value};
+use super::error::ParseError;
+use super::types::{non_empty, Channel, ColumnBuilder, Log, LogInfo, Meta, Parseable};
 
 /// Locomotive log file metadata
-#[derive(Clone, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct LocomotiveMeta {
     pub timestamp: String,
     pub customer: String,
@@ -15,8 +15,21 @@ pub struct LocomotiveMeta {
     pub software_version: String,
 }
 
+impl LocomotiveMeta {
+    /// Common metadata from the key/value preamble; the unit number is the vehicle
+    pub fn info(&self) -> LogInfo {
+        let mut info = LogInfo::new("Locomotive");
+        info.start_datetime = non_empty(&self.timestamp);
+        info.vehicle = non_empty(&self.unit_number);
+        info.firmware = non_empty(&self.software_version);
+        info.push_vendor("Customer", &self.customer);
+        info.push_vendor("Software part number", &self.software_part_number);
+        info
+    }
+}
+
 /// Locomotive channel definition - simple name and unit storage
-#[derive(Clone, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct LocomotiveChannel {
     pub name: String,
 }
@@ -72,7 +85,7 @@ impl Locomotive {
 }
 
 impl Parseable for Locomotive {
-    fn parse(&self, file_contents: &str) -> Result<Log, Box<dyn Error>> {
+    fn parse(&self, file_contents: &str) -> Result<Log, ParseError> {
         let mut meta = LocomotiveMeta::default();
         let mut channels: Vec<Channel> = Vec::new();
         let mut data_lines: Vec<&str> = Vec::new();
@@ -128,7 +141,7 @@ impl Parseable for Locomotive {
         }
 
         // Phase 2: Parse data rows in parallel
-        let parsed_rows: Vec<(f64, Vec<Value>)> = data_lines
+        let parsed_rows: Vec<(f64, Vec<f64>)> = data_lines
             .par_iter()
             .filter_map(|line| {
                 let parts: Vec<&str> = line.split(',').collect();
@@ -141,14 +154,14 @@ impl Parseable for Locomotive {
                 let timestamp_secs = Self::parse_timestamp(timestamp_str)?;
 
                 // Parse remaining values as f64
-                let values: Vec<Value> = parts[1..]
+                let values: Vec<f64> = parts[1..]
                     .iter()
                     .filter_map(|v| {
                         let v = v.trim();
                         if v.is_empty() {
                             None
                         } else {
-                            v.parse::<f64>().ok().map(Value::Float)
+                            v.parse::<f64>().ok()
                         }
                     })
                     .collect();
@@ -163,32 +176,20 @@ impl Parseable for Locomotive {
 
         // Phase 3: Post-process results (sequential for ordering)
         let data_count = parsed_rows.len();
+        let channel_count = channels.len();
         let mut times: Vec<f64> = Vec::with_capacity(data_count);
-        let mut data: Vec<Vec<Value>> = Vec::with_capacity(data_count);
-
-        if !parsed_rows.is_empty() {
-            // First timestamp is the base for relative times
-            let first_timestamp = parsed_rows[0].0;
+        let mut data = ColumnBuilder::with_capacity(channel_count, data_count);
 
-            for (timestamp, values) in parsed_rows {
-                times.push(timestamp - first_timestamp);
-                data.push(values);
-            }
-        }
+        // First timestamp is the base for relative times
+        let first_timestamp = parsed_rows.first().map(|(t, _)| *t).unwrap_or(0.0);
 
-        // Verify data integrity - filter out rows that don't match channel count
-        let channel_count = channels.len();
-        if channel_count > 0 {
-            let mut filtered_times = Vec::with_capacity(times.len());
-            let mut filtered_data = Vec::with_capacity(data.len());
-            for (time, row) in times.into_iter().zip(data.into_iter()) {
-                if row.len() >= channel_count {
-                    filtered_times.push(time);
-                    filtered_data.push(row);
-                }
+        for (timestamp, values) in parsed_rows {
+            // Verify data integrity - skip rows that don't match channel count
+            if values.len() < channel_count {
+                continue;
             }
-            times = filtered_times;
-            data = filtered_data;
+            times.push(timestamp - first_timestamp);
+            data.push_record(&values);
         }
 
         tracing::info!(
@@ -197,12 +198,12 @@ impl Parseable for Locomotive {
             data.len()
         );
 
-        Ok(Log {
-            meta: Meta::Locomotive(meta),
+        Ok(Log::new(
+            Meta::Locomotive(meta),
             channels,
             times,
-            data,
-        })
+            data.into_columns(),
+        ))
     }
 }
 
@@ -270,7 +271,7 @@ Sat Nov 15 19:00:22 2025, 1, 1, 1
         assert_eq!(log.channels[1].name(), "Rc_tfnd");
         assert_eq!(log.channels[2].name(), "AB Mode");
         assert_eq!(log.times.len(), 3);
-        assert_eq!(log.data.len(), 3);
+        assert_eq!(log.record_count(), 3);
 
         // Check relative timestamps
         assert!((log.times[0] - 0.0).abs() < 0.001);
@@ -278,8 +279,8 @@ Sat Nov 15 19:00:22 2025, 1, 1, 1
         assert!((log.times[2] - 19.0).abs() < 1.0); // ~19 seconds later
 
         // Check data values
-        assert_eq!(log.data[0][0].as_f64(), 1.0);
-        assert_eq!(log.data[0][1].as_f64(), 1.0);
-        assert_eq!(log.data[0][2].as_f64(), 1.0);
+        assert_eq!(log.channel_data(0)[0], 1.0);
+        assert_eq!(log.channel_data(1)[0], 1.0);
+        assert_eq!(log.channel_data(2)[0], 1.0);
     }
 }
@@ -0,0 +1,341 @@
+//! MaxxECU MTune CSV log parser.
+//!
+//! MTune exports logs as a single header row followed by data rows:
+//!
+//! - Every header column is `Name [unit]`, starting with `Time [s]` (or
+//!   `Time [ms]` on some exports). The square brackets tell these files
+//!   apart from RomRaider logs, which put units in parentheses
+//! - The delimiter follows the PC's locale: comma, or semicolon with a
+//!   decimal comma, and occasionally tab
+//! - Disabled channels are written as empty cells
+//!
+//! Column names are matched against the signals in the `maxxecu-default`
+//! protocol spec so CSV and CAN logs share the same channel names, which the
+//! normalization tables then map to UltraLog's standard names.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::error::ParseError;
+use super::types::{Channel, ColumnBuilder, Log, LogInfo, Meta, Parseable, MISSING};
+use crate::adapters::{get_protocol_by_id, SignalSpec};
+
+/// OpenECU Alliance protocol spec holding MaxxECU's channel naming
+pub const MAXXECU_PROTOCOL_ID: &str = "maxxecu-default";
+
+/// Name of the time column in the header row
+const TIME_COLUMN: &str = "Time";
+
+/// MaxxECU log metadata
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MaxxEcuMeta {
+    /// Column delimiter used by the export
+    pub delimiter: char,
+    /// Unit of the time column as written in the header ("s" or "ms")
+    pub time_unit: String,
+    /// Number of columns renamed to match the MaxxECU protocol spec
+    pub spec_matched_channels: usize,
+    /// Number of channels in the log
+    pub channel_count: usize,
+    /// Number of data points
+    pub data_points: usize,
+}
+
+impl MaxxEcuMeta {
+    /// Common metadata; MaxxECU exports have no header beyond the column names
+    pub fn info(&self) -> LogInfo {
+        let mut info = LogInfo::new("MaxxECU");
+        info.push_vendor("Time unit", &self.time_unit);
+        info.push_vendor("Channels matched to spec", self.spec_matched_channels);
+        info
+    }
+}
+
+/// MaxxECU channel definition
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MaxxEcuChannel {
+    /// Channel name, using the protocol spec's signal name when one matches
+    pub name: String,
+    /// Header column name without the unit (e.g., "Coolant temp")
+    pub source_name: String,
+    /// Unit from the square brackets, or from the protocol spec if missing
+    pub unit: String,
+    /// Minimum value from the protocol spec
+    pub display_min: Option<f64>,
+    /// Maximum value from the protocol spec
+    pub display_max: Option<f64>,
+}
+
+impl MaxxEcuChannel {
+    /// Create a channel from a header column such as "Manifold pressure [kPa]"
+    pub fn from_header(header: &str) -> Self {
+        let (name, unit) = split_header(header);
+        Self {
+            name: name.to_string(),
+            source_name: name.to_string(),
+            unit: unit.unwrap_or_default().to_string(),
+            display_min: None,
+            display_max: None,
+        }
+    }
+
+    /// Adopt the naming, unit and range of a matching protocol spec signal
+    fn apply_signal(&mut self, signal: &SignalSpec) {
+        self.name = signal.name.clone();
+        if self.unit.is_empty() {
+            self.unit = signal.unit.clone().unwrap_or_default();
+        }
+        self.display_min = signal.min;
+        self.display_max = signal.max;
+    }
+
+    /// Get the display unit for this channel
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+}
+
+/// MaxxECU MTune CSV parser
+pub struct MaxxEcu;
+
+impl MaxxEcu {
+    /// Detect if file contents look like an MTune CSV export
+    pub fn detect(contents: &str) -> bool {
+        let Some(header) = contents.trim_start_matches('\u{feff}').lines().next() else {
+            return false;
+        };
+        let delimiter = detect_delimiter(header);
+        let mut columns = header.split(delimiter);
+
+        let time_column = columns.next().map(split_header);
+        let Some((name, Some(_))) = time_column else {
+            return false;
+        };
+
+        name.eq_ignore_ascii_case(TIME_COLUMN)
+            && columns.any(|column| split_header(column).1.is_some())
+    }
+
+    /// Map compacted signal names from the MaxxECU protocol spec to their signals
+    fn spec_signals() -> HashMap<String, SignalSpec> {
+        get_protocol_by_id(MAXXECU_PROTOCOL_ID)
+            .map(|protocol| {
+                protocol
+                    .messages
+                    .into_iter()
+                    .flat_map(|message| message.signals)
+                    .map(|signal| (compact_name(&signal.name), signal))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Parseable for MaxxEcu {
+    fn parse(&self, file_contents: &str) -> Result<Log, ParseError> {
+        let mut lines = file_contents.trim_start_matches('\u{feff}').lines();
+
+        let header = lines
+            .next()
+            .ok_or_else(|| ParseError::invalid("Empty file: no MaxxECU header found"))?;
+        let delimiter = detect_delimiter(header);
+        let mut columns = header.split(delimiter);
+
+        let (time_name, time_unit) = split_header(columns.next().unwrap_or_default());
+        if !time_name.eq_ignore_ascii_case(TIME_COLUMN) {
+            return Err(ParseError::bad_header(1, "first column must be Time"));
+        }
+        let time_unit = time_unit.unwrap_or("s").to_string();
+        let time_divisor = if time_unit.eq_ignore_ascii_case("ms") {
+            1000.0
+        } else {
+            1.0
+        };
+
+        let signals = Self::spec_signals();
+        let mut spec_matched_channels = 0;
+        let channels: Vec<Channel> = columns
+            .map(|column| {
+                let mut channel = MaxxEcuChannel::from_header(column);
+                if let Some(signal) = signals.get(&compact_name(&channel.source_name)) {
+                    channel.apply_signal(signal);
+                    spec_matched_channels += 1;
+                }
+                Channel::MaxxEcu(channel)
+            })
+            .collect();
+
+        if channels.is_empty() {
+            return Err(ParseError::bad_header(1, "no channels found"));
+        }
+
+        // Semicolon and tab exports come from locales that use a decimal comma
+        let decimal_comma = delimiter != ',';
+        let parse_number = |cell: &str| -> Option<f64> {
+            let cell = cell.trim();
+            if decimal_comma {
+                cell.replace(',', ".").parse().ok()
+            } else {
+                cell.parse().ok()
+            }
+        };
+
+        let mut times: Vec<f64> = Vec::new();
+        let mut data = ColumnBuilder::new(channels.len());
+
+        // Disabled channels leave empty cells; carry the last value through them
+        let mut last_values: Vec<f64> = vec![MISSING; channels.len()];
+        let mut first_time: Option<f64> = None;
+
+        for line in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut cells = line.split(delimiter);
+            let Some(time) = cells.next().and_then(parse_number) else {
+                continue;
+            };
+            let time_secs = time / time_divisor;
+
+            let relative_time = match first_time {
+                Some(first) => time_secs - first,
+                None => {
+                    first_time = Some(time_secs);
+                    0.0
+                }
+            };
+
+            for (slot, cell) in last_values.iter_mut().zip(cells) {
+                if let Some(val) = parse_number(cell) {
+                    *slot = val;
+                }
+            }
+
+            times.push(relative_time);
+            data.push_record(&last_values);
+        }
+
+        tracing::info!(
+            "Parsed MaxxECU log: {} channels ({} matched to spec), {} data points",
+            channels.len(),
+            spec_matched_channels,
+            data.len()
+        );
+
+        Ok(Log::new(
+            Meta::MaxxEcu(MaxxEcuMeta {
+                delimiter,
+                time_unit,
+                spec_matched_channels,
+                channel_count: channels.len(),
+                data_points: data.len(),
+            }),
+            channels,
+            times,
+            data.into_columns(),
+        ))
+    }
+}
+
+/// Pick the most frequent of comma, semicolon and tab in the header row
+fn detect_delimiter(header: &str) -> char {
+    [',', ';', '\t']
+        .into_iter()
+        .max_by_key(|&d| header.matches(d).count())
+        .unwrap_or(',')
+}
+
+/// Split "Name [unit]" into its name and unit
+fn split_header(column: &str) -> (&str, Option<&str>) {
+    let column = column.trim().trim_matches('"').trim();
+    if column.ends_with(']') {
+        if let Some(start) = column.rfind('[') {
+            let name = column[..start].trim();
+            if !name.is_empty() {
+                return (name, Some(column[start + 1..column.len() - 1].trim()));
+            }
+        }
+    }
+    (column, None)
+}
+
+/// Lowercase a name and drop everything but letters and digits, so
+/// "Coolant_Temp" and "Coolant temp" compare equal
+fn compact_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_header() {
+        assert_eq!(
+            split_header(" Manifold pressure [kPa]"),
+            ("Manifold pressure", Some("kPa"))
+        );
+        assert_eq!(split_header("\"Lambda A [λ]\""), ("Lambda A", Some("λ")));
+        assert_eq!(split_header("Gear"), ("Gear", None));
+        assert_eq!(split_header("[s]"), ("[s]", None));
+    }
+
+    #[test]
+    fn test_detect_delimiter() {
+        assert_eq!(detect_delimiter("Time [s],RPM [rpm]"), ',');
+        assert_eq!(detect_delimiter("Time [s];RPM [rpm];TPS [%]"), ';');
+        assert_eq!(detect_delimiter("Time [s]\tRPM [rpm]"), '\t');
+    }
+
+    #[test]
+    fn test_compact_name() {
+        assert_eq!(compact_name("Coolant_Temp"), compact_name("Coolant temp"));
+        assert_eq!(compact_name("Lambda A"), "lambdaa");
+    }
+
+    #[test]
+    fn test_detect() {
+        assert!(MaxxEcu::detect("Time [s],RPM [rpm]\n0.00,900\n"));
+        assert!(MaxxEcu::detect("TIME [ms];RPM [rpm]\n0;900\n"));
+        assert!(!MaxxEcu::detect("Time (msec),Engine Speed (rpm)\n0,900\n"));
+        assert!(!MaxxEcu::detect("Time [s]\n0.00\n"));
+        assert!(!MaxxEcu::detect(""));
+    }
+
+    #[test]
+    fn test_parse_semicolon_decimal_comma() {
+        let log = MaxxEcu
+            .parse("Time [ms];RPM [rpm];Lambda A [λ]\n1000;900;0,98\n1050;;0,95\n")
+            .unwrap();
+
+        assert_eq!(log.channels.len(), 2);
+        assert_eq!(log.channels[1].unit(), "λ");
+        assert!((log.times[1] - 0.05).abs() < 1e-9);
+        assert_eq!(log.channel_data(0)[1], 900.0);
+        assert!((log.channel_data(1)[1] - 0.95).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_apply_signal_keeps_file_unit() {
+        let signal: SignalSpec = serde_yaml::from_str(
+            "name: Coolant Temp\nstart_bit: 0\nlength: 16\nbyte_order: little_endian\n\
+             data_type: signed\nunit: C\nmin: -40\nmax: 150\n",
+        )
+        .unwrap();
+
+        let mut channel = MaxxEcuChannel::from_header("Coolant_temp [°C]");
+        channel.apply_signal(&signal);
+        assert_eq!(channel.name, "Coolant Temp");
+        assert_eq!(channel.source_name, "Coolant_temp");
+        assert_eq!(channel.unit, "°C");
+        assert_eq!(channel.display_max, Some(150.0));
+
+        let mut channel = MaxxEcuChannel::from_header("Coolant temp");
+        channel.apply_signal(&signal);
+        assert_eq!(channel.unit, "C");
+    }
+}
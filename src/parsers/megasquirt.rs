@@ -0,0 +1,352 @@
+//! MegaSquirt / TunerStudio ASCII log parser (.msl).
+//!
+//! TunerStudio and MegaLogViewer write tab-separated text logs for MS2/MS3
+//! and for Speeduino/rusEFI on older firmware. The layout is:
+//!
+//! - An optional preamble of quoted lines: the firmware signature
+//!   (e.g., `"MS3 Format 0262.09 "`) and a `"Capture Date: ..."` line
+//! - A tab-separated header row of channel names, which includes `Time`
+//! - A tab-separated units row (e.g., `s`, `RPM`, `kPa`)
+//! - Tab-separated data rows, interleaved with `MARK nnn ...` lines where
+//!   the user pressed the marker key while logging
+
+use serde::{Deserialize, Serialize};
+
+use super::error::ParseError;
+use super::types::{
+    non_empty, Annotation, Channel, ColumnBuilder, Log, LogInfo, Meta, Parseable, MISSING,
+};
+
+/// Prefix of the marker lines TunerStudio writes between data rows
+const MARK_PREFIX: &str = "MARK";
+
+/// Prefix of the capture date line in the quoted preamble
+const CAPTURE_DATE_PREFIX: &str = "Capture Date:";
+
+/// Name of the time column in the header row
+const TIME_COLUMN: &str = "Time";
+
+/// A "MARK" line recorded while logging
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MegaSquirtMarker {
+    /// Time of the preceding data row in seconds (relative to log start)
+    pub time: f64,
+    /// Marker text as written in the log (e.g., "MARK 000 - Manual")
+    pub label: String,
+}
+
+/// MegaSquirt log metadata
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MegaSquirtMeta {
+    /// Firmware signature from the first preamble line (e.g., "MS3 Format 0262.09")
+    pub signature: String,
+    /// Capture date from the preamble (e.g., "Sun Jan 31 16:17:01 EST 2021")
+    pub capture_date: String,
+    /// Marker lines found between data rows
+    pub markers: Vec<MegaSquirtMarker>,
+    /// Number of channels in the log
+    pub channel_count: usize,
+    /// Number of data points
+    pub data_points: usize,
+}
+
+impl MegaSquirtMeta {
+    /// Common metadata from the `.msl` preamble; the signature names the firmware
+    pub fn info(&self) -> LogInfo {
+        let mut info = LogInfo::new("MegaSquirt");
+        info.start_datetime = non_empty(&self.capture_date);
+        info.firmware = non_empty(&self.signature);
+        if !self.markers.is_empty() {
+            info.push_vendor("Markers", self.markers.len());
+        }
+        info
+    }
+}
+
+/// MegaSquirt channel definition
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MegaSquirtChannel {
+    /// Channel name from the header row (e.g., "RPM", "MAP")
+    pub name: String,
+    /// Unit from the units row (e.g., "kPa"), empty if the log has none
+    pub unit: String,
+}
+
+impl MegaSquirtChannel {
+    /// Get the display unit for this channel
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+}
+
+/// MegaSquirt / TunerStudio .msl parser
+pub struct MegaSquirt;
+
+impl MegaSquirt {
+    /// Detect if file contents look like a TunerStudio/MegaLogViewer ASCII log
+    pub fn detect(contents: &str) -> bool {
+        let mut saw_preamble = false;
+
+        for line in contents.trim_start_matches('\u{feff}').lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if Self::is_preamble_line(line) {
+                saw_preamble = true;
+                continue;
+            }
+            if !line.contains('\t') {
+                return false;
+            }
+
+            // With a preamble any column may be Time; without one, require the
+            // exact "Time" first column so ECUMaster's "TIME\t" is not claimed
+            return if saw_preamble {
+                Self::find_time_column(line).is_some()
+            } else {
+                line.split('\t').next().map(str::trim) == Some(TIME_COLUMN)
+            };
+        }
+
+        false
+    }
+
+    /// Quoted preamble lines hold the signature and capture date, never tabs
+    fn is_preamble_line(line: &str) -> bool {
+        line.trim_start().starts_with('"') && !line.contains('\t')
+    }
+
+    /// Find the index of the Time column in a header row
+    fn find_time_column(header: &str) -> Option<usize> {
+        header
+            .split('\t')
+            .position(|name| name.trim().eq_ignore_ascii_case(TIME_COLUMN))
+    }
+
+    /// Marker lines are written between data rows when the user hits the mark key
+    fn is_marker_line(line: &str) -> bool {
+        line.trim_start().starts_with(MARK_PREFIX)
+    }
+
+    /// A data row has a numeric value in the Time column
+    fn is_data_row(row: &str, time_idx: usize) -> bool {
+        row.split('\t')
+            .nth(time_idx)
+            .is_some_and(|t| t.trim().parse::<f64>().is_ok())
+    }
+}
+
+impl Parseable for MegaSquirt {
+    fn parse(&self, file_contents: &str) -> Result<Log, ParseError> {
+        let mut lines = file_contents
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .peekable();
+
+        // Quoted preamble: first line is the firmware signature
+        let mut signature = String::new();
+        let mut capture_date = String::new();
+        // Line number of the column header, not counting blank lines
+        let mut header_line = 1;
+        while let Some(line) = lines.next_if(|l| Self::is_preamble_line(l)) {
+            header_line += 1;
+            let text = line.trim().trim_matches('"').trim();
+            if let Some(date) = text.strip_prefix(CAPTURE_DATE_PREFIX) {
+                capture_date = date.trim().to_string();
+            } else if signature.is_empty() {
+                signature = text.to_string();
+            }
+        }
+
+        let header = lines
+            .next()
+            .ok_or_else(|| ParseError::bad_header(header_line, "no column header found"))?;
+        let time_idx = Self::find_time_column(header)
+            .ok_or_else(|| ParseError::bad_header(header_line, "header has no Time column"))?;
+        let column_names: Vec<&str> = header.split('\t').map(str::trim).collect();
+
+        // The units row is optional; it's present when its Time cell isn't a number
+        let units: Vec<&str> = match lines.peek() {
+            Some(row) if !Self::is_data_row(row, time_idx) && !Self::is_marker_line(row) => {
+                lines.next().map(|r| r.split('\t').map(str::trim).collect())
+            }
+            _ => None,
+        }
+        .unwrap_or_default();
+
+        let channels: Vec<Channel> = column_names
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| *idx != time_idx)
+            .map(|(idx, name)| {
+                Channel::MegaSquirt(MegaSquirtChannel {
+                    name: name.to_string(),
+                    unit: units.get(idx).copied().unwrap_or_default().to_string(),
+                })
+            })
+            .collect();
+
+        if channels.is_empty() {
+            return Err(ParseError::bad_header(header_line, "no channels found"));
+        }
+
+        let mut times: Vec<f64> = Vec::new();
+        let mut data = ColumnBuilder::new(channels.len());
+        let mut markers: Vec<MegaSquirtMarker> = Vec::new();
+
+        // Carry the last good value through cells that fail to parse
+        let mut last_values: Vec<f64> = vec![MISSING; channels.len()];
+        let mut first_time: Option<f64> = None;
+
+        for line in lines {
+            if Self::is_marker_line(line) {
+                markers.push(MegaSquirtMarker {
+                    time: times.last().copied().unwrap_or(0.0),
+                    label: line.trim().to_string(),
+                });
+                continue;
+            }
+
+            let parts: Vec<&str> = line.split('\t').collect();
+            let Some(time_secs) = parts
+                .get(time_idx)
+                .and_then(|t| t.trim().parse::<f64>().ok())
+            else {
+                continue;
+            };
+
+            let relative_time = match first_time {
+                Some(first) => time_secs - first,
+                None => {
+                    first_time = Some(time_secs);
+                    0.0
+                }
+            };
+
+            let values = parts
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| *idx != time_idx)
+                .map(|(_, part)| part);
+            for (slot, part) in last_values.iter_mut().zip(values) {
+                if let Ok(val) = part.trim().parse::<f64>() {
+                    *slot = val;
+                }
+            }
+
+            times.push(relative_time);
+            data.push_record(&last_values);
+        }
+
+        tracing::info!(
+            "Parsed MegaSquirt log: {} channels, {} data points, {} markers",
+            channels.len(),
+            data.len(),
+            markers.len()
+        );
+
+        let annotations = markers
+            .iter()
+            .map(|marker| Annotation {
+                time: marker.time,
+                label: marker.label.clone(),
+            })
+            .collect();
+        let mut log = Log::new(
+            Meta::MegaSquirt(MegaSquirtMeta {
+                signature,
+                capture_date,
+                markers,
+                channel_count: channels.len(),
+                data_points: data.len(),
+            }),
+            channels,
+            times,
+            data.into_columns(),
+        );
+        log.annotations = annotations;
+        Ok(log)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\"MS3 Format 0262.09 \"\n\
+        \"Capture Date: Sun Jan 31 16:17:01 EST 2021\"\n\
+        Time\tSecL\tRPM\tMAP\tAFR\n\
+        s\ts\tRPM\tkPa\tAFR\n\
+        12.500\t12\t850\t35.2\t14.7\n\
+        12.600\t12\t900\t36.0\t14.5\n\
+        MARK 000 - Manual - Sun Jan 31 16:17:14 EST 2021\n\
+        12.700\t12\t1250\t\t13.9\n";
+
+    #[test]
+    fn test_detect() {
+        assert!(MegaSquirt::detect(SAMPLE));
+        assert!(MegaSquirt::detect("Time\tRPM\n0.0\t800\n"));
+        assert!(!MegaSquirt::detect("TIME\tRPM\n0.0\t800\n"));
+        assert!(!MegaSquirt::detect("Time,RPM\n0.0,800\n"));
+        assert!(!MegaSquirt::detect("\"Time\",\"RPM\"\n0.0,800\n"));
+        assert!(!MegaSquirt::detect(""));
+    }
+
+    #[test]
+    fn test_parse_preamble_and_units() {
+        let log = MegaSquirt.parse(SAMPLE).unwrap();
+
+        let Meta::MegaSquirt(meta) = &log.meta else {
+            panic!("Expected MegaSquirt metadata");
+        };
+        assert_eq!(meta.signature, "MS3 Format 0262.09");
+        assert_eq!(meta.capture_date, "Sun Jan 31 16:17:01 EST 2021");
+
+        assert_eq!(log.channels.len(), 4);
+        assert_eq!(log.channels[0].name(), "SecL");
+        assert_eq!(log.channels[2].name(), "MAP");
+        assert_eq!(log.channels[2].unit(), "kPa");
+    }
+
+    #[test]
+    fn test_parse_times_and_carry_forward() {
+        let log = MegaSquirt.parse(SAMPLE).unwrap();
+
+        assert_eq!(log.record_count(), 3);
+        assert!((log.times[0]).abs() < 1e-9);
+        assert!((log.times[2] - 0.2).abs() < 1e-9);
+
+        // Empty MAP cell keeps the previous value
+        assert_eq!(log.channel_data(1)[2], 1250.0);
+        assert_eq!(log.channel_data(2)[2], 36.0);
+    }
+
+    #[test]
+    fn test_parse_markers() {
+        let log = MegaSquirt.parse(SAMPLE).unwrap();
+
+        let Meta::MegaSquirt(meta) = &log.meta else {
+            panic!("Expected MegaSquirt metadata");
+        };
+        assert_eq!(meta.markers.len(), 1);
+        assert!((meta.markers[0].time - 0.1).abs() < 1e-9);
+        assert!(meta.markers[0].label.starts_with("MARK 000"));
+        assert_eq!(log.annotations.len(), 1);
+        assert_eq!(log.annotations[0].label, meta.markers[0].label);
+    }
+
+    #[test]
+    fn test_parse_without_units_row() {
+        let log = MegaSquirt.parse("Time\tRPM\n0.0\t800\n0.1\t820\n").unwrap();
+        assert_eq!(log.channels.len(), 1);
+        assert_eq!(log.channels[0].unit(), "");
+        assert_eq!(log.record_count(), 2);
+    }
+
+    #[test]
+    fn test_parse_missing_time_column() {
+        assert!(MegaSquirt.parse("\"MS2\"\nRPM\tMAP\n800\t35\n").is_err());
+    }
+}
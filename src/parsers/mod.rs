@@ -1,19 +1,42 @@
+pub mod aem;
 pub mod aim;
+pub mod bluedriver;
+pub mod can_trace;
+pub mod datetime;
+pub mod detection;
 pub mod ecumaster;
 pub mod emerald;
+pub mod emublog;
+pub mod error;
 pub mod haltech;
 pub mod link;
 pub mod locomotive;
+pub mod maxxecu;
+pub mod megasquirt;
+pub mod motec;
 pub mod romraider;
+pub mod spec_csv;
 pub mod speeduino;
+pub mod streaming;
 pub mod types;
 
+pub use aem::Aem;
 pub use aim::Aim;
+pub use bluedriver::BlueDriver;
+pub use can_trace::{CanStream, CanTrace};
+pub use detection::{Detection, DetectionInput, FormatDetector};
 pub use ecumaster::EcuMaster;
 pub use emerald::Emerald;
+pub use emublog::EmuBlog;
+pub use error::ParseError;
 pub use haltech::Haltech;
 pub use link::Link;
 pub use locomotive::Locomotive;
+pub use maxxecu::MaxxEcu;
+pub use megasquirt::MegaSquirt;
+pub use motec::Motec;
 pub use romraider::RomRaider;
+pub use spec_csv::SpecCsv;
 pub use speeduino::Speeduino;
+pub use streaming::{LogBuilder, StreamingParseable};
 pub use types::{Channel, EcuType, Log, Parseable, Value};
@@ -0,0 +1,646 @@
+//! MoTeC i2 .ld binary log parser.
+//!
+//! `.ld` files are written by MoTeC loggers and ECUs (ADL, C1xx, M1 series)
+//! and opened in i2 Standard/Pro. All integers are little-endian.
+//!
+//! - A fixed 1762-byte file header holding pointers to the channel list and
+//!   the event block, plus the driver, vehicle, venue, date and time strings
+//! - An optional event block (event name, session, comment) that points to a
+//!   venue block, which in turn points to a vehicle block
+//! - A doubly linked list of 124-byte channel descriptors. Each descriptor
+//!   holds a pointer to the channel's samples, its sample count and rate,
+//!   the data type and the `shift`/`multiplier`/`divisor`/decimal places
+//!   scaling, plus the channel name, short name and unit
+//!
+//! Every channel has its own sample rate. Channels are aligned onto the rate
+//! of the fastest channel, holding slower channels at their last value.
+
+use serde::{Deserialize, Serialize};
+
+use super::error::ParseError;
+use super::types::{non_empty, Channel, Log, LogInfo, Meta, NativeSeries, MISSING};
+
+/// Marker in the first four bytes of every .ld file
+const LD_MARKER: u32 = 0x40;
+
+/// Size of the fixed file header
+const HEADER_SIZE: usize = 1762;
+
+/// Size of a channel descriptor in the channel list
+const CHANNEL_SIZE: usize = 124;
+
+// File header offsets
+const HEADER_CHANNEL_META_PTR: usize = 8;
+const HEADER_CHANNEL_DATA_PTR: usize = 12;
+const HEADER_EVENT_PTR: usize = 36;
+const HEADER_DEVICE_SERIAL: usize = 70;
+const HEADER_DEVICE_TYPE: usize = 74;
+const HEADER_DEVICE_VERSION: usize = 82;
+const HEADER_DATE: usize = 94;
+const HEADER_TIME: usize = 126;
+const HEADER_DRIVER: usize = 158;
+const HEADER_VEHICLE: usize = 222;
+const HEADER_VENUE: usize = 350;
+const HEADER_SHORT_COMMENT: usize = 1572;
+
+// Event, venue and vehicle block offsets (relative to the block start)
+const EVENT_SESSION: usize = 64;
+const EVENT_COMMENT: usize = 128;
+const EVENT_VENUE_PTR: usize = 1152;
+const VENUE_VEHICLE_PTR: usize = 1098;
+const VEHICLE_TYPE: usize = 196;
+
+// Channel descriptor offsets (relative to the descriptor start)
+const CHANNEL_NEXT_PTR: usize = 4;
+const CHANNEL_DATA_PTR: usize = 8;
+const CHANNEL_SAMPLE_COUNT: usize = 12;
+const CHANNEL_DATA_KIND: usize = 18;
+const CHANNEL_DATA_SIZE: usize = 20;
+const CHANNEL_SAMPLE_RATE: usize = 22;
+const CHANNEL_SHIFT: usize = 24;
+const CHANNEL_MULTIPLIER: usize = 26;
+const CHANNEL_DIVISOR: usize = 28;
+const CHANNEL_DECIMAL_PLACES: usize = 30;
+const CHANNEL_NAME: usize = 32;
+const CHANNEL_SHORT_NAME: usize = 64;
+const CHANNEL_UNIT: usize = 72;
+
+/// Data kind marking IEEE floating point samples; other kinds are integers
+const DATA_KIND_FLOAT: u16 = 0x07;
+
+/// MoTeC .ld log metadata
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MotecMeta {
+    /// Event name (e.g., "Club Day 3")
+    pub event: String,
+    /// Session name (e.g., "Race 1")
+    pub session: String,
+    /// Event comment
+    pub comment: String,
+    /// Short comment from the file header
+    pub short_comment: String,
+    /// Venue (track) name
+    pub venue: String,
+    /// Vehicle id
+    pub vehicle: String,
+    /// Vehicle type from the vehicle block (e.g., "Sports Car")
+    pub vehicle_type: String,
+    /// Driver name
+    pub driver: String,
+    /// Log date as written by the logger (e.g., "23/11/2024")
+    pub date: String,
+    /// Log start time as written by the logger (e.g., "14:02:37")
+    pub time: String,
+    /// Logging device type (e.g., "ADL")
+    pub device_type: String,
+    /// Logging device serial number
+    pub device_serial: u32,
+    /// Logging device firmware version
+    pub device_version: u16,
+    /// Sample rate of the log timebase in Hz (the fastest channel's rate)
+    pub sample_rate_hz: f64,
+    /// Number of channels in the log
+    pub channel_count: usize,
+    /// Number of data points
+    pub data_points: usize,
+}
+
+impl MotecMeta {
+    /// Common metadata from the `.ld` header and event/vehicle blocks
+    pub fn info(&self) -> LogInfo {
+        let mut info = LogInfo::new("MoTeC");
+        info.start_datetime = non_empty(&format!("{} {}", self.date, self.time));
+        info.ecu_model = non_empty(&self.device_type);
+        if self.device_serial != 0 {
+            info.ecu_serial = Some(self.device_serial.to_string());
+        }
+        if self.device_version != 0 {
+            info.firmware = Some(self.device_version.to_string());
+        }
+        info.vehicle = non_empty(&self.vehicle);
+        info.driver = non_empty(&self.driver);
+        info.track = non_empty(&self.venue);
+        info.push_vendor("Vehicle type", &self.vehicle_type);
+        info.push_vendor("Event", &self.event);
+        info.push_vendor("Session", &self.session);
+        info.push_vendor("Comment", &self.comment);
+        info.push_vendor("Short comment", &self.short_comment);
+        info.push_vendor("Sample rate", format!("{} Hz", self.sample_rate_hz));
+        info
+    }
+}
+
+/// MoTeC channel definition
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MotecChannel {
+    /// Channel name (e.g., "Engine Speed")
+    pub name: String,
+    /// Abbreviated channel name (e.g., "RPM")
+    pub short_name: String,
+    /// Channel unit (e.g., "rpm")
+    pub unit: String,
+    /// Logged sample rate in Hz
+    pub sample_rate_hz: u16,
+    /// Offset added after dividing the raw value
+    pub shift: i16,
+    /// Multiplier applied last
+    pub multiplier: i16,
+    /// Divisor applied to the raw value
+    pub divisor: i16,
+    /// Decimal places the raw value is stored with
+    pub decimal_places: i16,
+}
+
+impl MotecChannel {
+    /// Get the display unit for this channel
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// Convert a raw sample to engineering units:
+    /// `(raw / divisor * 10^-decimal_places + shift) * multiplier`
+    pub fn scale(&self, raw: f64) -> f64 {
+        let divisor = if self.divisor == 0 {
+            1.0
+        } else {
+            self.divisor as f64
+        };
+        (raw / divisor * 10f64.powi(-(self.decimal_places as i32)) + self.shift as f64)
+            * self.multiplier as f64
+    }
+}
+
+/// A channel with its samples decoded into engineering units
+struct DecodedChannel {
+    channel: MotecChannel,
+    values: Vec<f64>,
+}
+
+/// MoTeC .ld parser
+pub struct Motec;
+
+impl Motec {
+    /// Detect if data is a MoTeC .ld file
+    pub fn detect(data: &[u8]) -> bool {
+        if data.len() < HEADER_SIZE || read_u32(data, 0) != Some(LD_MARKER) {
+            return false;
+        }
+
+        // The marker alone is weak; the channel list must also point inside the file
+        match (
+            read_u32(data, HEADER_CHANNEL_META_PTR),
+            read_u32(data, HEADER_CHANNEL_DATA_PTR),
+        ) {
+            (Some(meta_ptr), Some(data_ptr)) => {
+                let meta_ptr = meta_ptr as usize;
+                meta_ptr >= HEADER_SIZE
+                    && meta_ptr + CHANNEL_SIZE <= data.len()
+                    && data_ptr as usize <= data.len()
+            }
+            _ => false,
+        }
+    }
+
+    /// Parse a MoTeC .ld file from raw bytes
+    pub fn parse_binary(data: &[u8]) -> Result<Log, ParseError> {
+        if !Self::detect(data) {
+            return Err(ParseError::BadMagic {
+                format: "MoTeC .ld",
+            });
+        }
+
+        let mut meta = Self::read_header(data);
+
+        let mut decoded: Vec<DecodedChannel> = Vec::new();
+        let mut ptr = read_u32(data, HEADER_CHANNEL_META_PTR).unwrap_or(0) as usize;
+        // Bound the walk so a corrupt next pointer can't loop forever
+        let max_channels = data.len() / CHANNEL_SIZE;
+        let mut visited = 0;
+
+        while ptr != 0 && visited < max_channels {
+            visited += 1;
+            let Some(descriptor) = data.get(ptr..ptr + CHANNEL_SIZE) else {
+                tracing::debug!("MoTeC channel list points past end of file at {}", ptr);
+                break;
+            };
+
+            match Self::read_channel(data, descriptor) {
+                Ok(channel) => decoded.push(channel),
+                Err(e) => tracing::debug!("Skipping MoTeC channel at {}: {}", ptr, e),
+            }
+
+            ptr = read_u32(descriptor, CHANNEL_NEXT_PTR).unwrap_or(0) as usize;
+        }
+
+        decoded.retain(|c| !c.values.is_empty());
+        if decoded.is_empty() {
+            return Err(ParseError::invalid(
+                "MoTeC .ld file contains no logged channels",
+            ));
+        }
+
+        // Slower channels are held at their last value on the fastest
+        // timebase and keep their own timebase as a native series. Rows past
+        // a channel's last sample are missing.
+        let base_rate = decoded
+            .iter()
+            .map(|c| c.channel.sample_rate_hz as usize)
+            .max()
+            .unwrap_or(1);
+        let row_count = decoded
+            .iter()
+            .map(|c| {
+                let rate = c.channel.sample_rate_hz as usize;
+                (c.values.len() * base_rate).div_ceil(rate)
+            })
+            .max()
+            .unwrap_or(0);
+
+        let times: Vec<f64> = (0..row_count)
+            .map(|row| row as f64 / base_rate as f64)
+            .collect();
+        let columns: Vec<Vec<f64>> = decoded
+            .iter()
+            .map(|c| {
+                let rate = c.channel.sample_rate_hz as usize;
+                (0..row_count)
+                    .map(|row| {
+                        c.values
+                            .get(row * rate / base_rate)
+                            .copied()
+                            .unwrap_or(MISSING)
+                    })
+                    .collect()
+            })
+            .collect();
+        let native: Vec<Option<NativeSeries>> = decoded
+            .iter()
+            .map(|c| {
+                let rate = c.channel.sample_rate_hz as usize;
+                (rate != base_rate).then(|| NativeSeries {
+                    times: (0..c.values.len())
+                        .map(|i| i as f64 / rate as f64)
+                        .collect(),
+                    values: c.values.clone(),
+                })
+            })
+            .collect();
+
+        let channels: Vec<Channel> = decoded
+            .into_iter()
+            .map(|c| Channel::Motec(c.channel))
+            .collect();
+
+        meta.sample_rate_hz = base_rate as f64;
+        meta.channel_count = channels.len();
+        meta.data_points = row_count;
+
+        tracing::info!(
+            "Parsed MoTeC .ld log: {} channels, {} data points",
+            channels.len(),
+            row_count
+        );
+
+        let mut log = Log::new(Meta::Motec(meta), channels, times, columns);
+        for (idx, series) in native.into_iter().enumerate() {
+            if let Some(series) = series {
+                log.set_native_series(idx, series);
+            }
+        }
+        Ok(log)
+    }
+
+    /// Read the file header and the event/venue/vehicle chain it points to
+    fn read_header(data: &[u8]) -> MotecMeta {
+        let mut meta = MotecMeta {
+            device_serial: read_u32(data, HEADER_DEVICE_SERIAL).unwrap_or(0),
+            device_type: read_string(data, HEADER_DEVICE_TYPE, 8),
+            device_version: read_u16(data, HEADER_DEVICE_VERSION).unwrap_or(0),
+            date: read_string(data, HEADER_DATE, 16),
+            time: read_string(data, HEADER_TIME, 16),
+            driver: read_string(data, HEADER_DRIVER, 64),
+            vehicle: read_string(data, HEADER_VEHICLE, 64),
+            venue: read_string(data, HEADER_VENUE, 64),
+            short_comment: read_string(data, HEADER_SHORT_COMMENT, 64),
+            ..Default::default()
+        };
+
+        let event_ptr = read_u32(data, HEADER_EVENT_PTR).unwrap_or(0) as usize;
+        if event_ptr == 0 {
+            return meta;
+        }
+        meta.event = read_string(data, event_ptr, 64);
+        meta.session = read_string(data, event_ptr + EVENT_SESSION, 64);
+        meta.comment = read_string(data, event_ptr + EVENT_COMMENT, 1024);
+
+        // The event block's venue and vehicle take precedence over the header copies
+        let venue_ptr = read_u16(data, event_ptr + EVENT_VENUE_PTR).unwrap_or(0) as usize;
+        if venue_ptr == 0 {
+            return meta;
+        }
+        let venue = read_string(data, venue_ptr, 64);
+        if !venue.is_empty() {
+            meta.venue = venue;
+        }
+
+        let vehicle_ptr = read_u16(data, venue_ptr + VENUE_VEHICLE_PTR).unwrap_or(0) as usize;
+        if vehicle_ptr == 0 {
+            return meta;
+        }
+        let vehicle = read_string(data, vehicle_ptr, 64);
+        if !vehicle.is_empty() {
+            meta.vehicle = vehicle;
+        }
+        meta.vehicle_type = read_string(data, vehicle_ptr + VEHICLE_TYPE, 32);
+
+        meta
+    }
+
+    /// Read a channel descriptor and decode its samples
+    fn read_channel(data: &[u8], descriptor: &[u8]) -> Result<DecodedChannel, String> {
+        let field = |offset| read_u16(descriptor, offset).unwrap_or(0);
+        let signed = |offset| field(offset) as i16;
+
+        let channel = MotecChannel {
+            name: read_string(descriptor, CHANNEL_NAME, 32),
+            short_name: read_string(descriptor, CHANNEL_SHORT_NAME, 8),
+            unit: read_string(descriptor, CHANNEL_UNIT, 12),
+            sample_rate_hz: field(CHANNEL_SAMPLE_RATE),
+            shift: signed(CHANNEL_SHIFT),
+            multiplier: signed(CHANNEL_MULTIPLIER),
+            divisor: signed(CHANNEL_DIVISOR),
+            decimal_places: signed(CHANNEL_DECIMAL_PLACES),
+        };
+
+        if channel.sample_rate_hz == 0 {
+            return Err(format!("'{}' has a zero sample rate", channel.name));
+        }
+
+        let kind = field(CHANNEL_DATA_KIND);
+        let size = field(CHANNEL_DATA_SIZE) as usize;
+        let decode: fn(&[u8]) -> f64 = match (kind == DATA_KIND_FLOAT, size) {
+            (true, 2) => |b| f16_to_f64(u16::from_le_bytes([b[0], b[1]])),
+            (true, 4) => |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            (false, 2) => |b| i16::from_le_bytes([b[0], b[1]]) as f64,
+            (false, 4) => |b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            _ => {
+                return Err(format!(
+                    "'{}' has unsupported data type {:#x}/{}",
+                    channel.name, kind, size
+                ))
+            }
+        };
+
+        let data_ptr = read_u32(descriptor, CHANNEL_DATA_PTR).unwrap_or(0) as usize;
+        let count = read_u32(descriptor, CHANNEL_SAMPLE_COUNT).unwrap_or(0) as usize;
+        let raw = data
+            .get(data_ptr..data_ptr.saturating_add(count.saturating_mul(size)))
+            .ok_or_else(|| format!("'{}' samples run past end of file", channel.name))?;
+
+        let values = raw
+            .chunks_exact(size)
+            .map(|b| channel.scale(decode(b)))
+            .collect();
+
+        Ok(DecodedChannel { channel, values })
+    }
+}
+
+/// Convert an IEEE 754 half-precision float to f64
+fn f16_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1F) as i32;
+    let mantissa = (bits & 0x3FF) as f64;
+
+    match exponent {
+        0 => sign * mantissa * 2f64.powi(-24),
+        0x1F if mantissa == 0.0 => sign * f64::INFINITY,
+        0x1F => f64::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f64.powi(exponent - 15),
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Read a fixed-width, NUL-padded string field
+fn read_string(data: &[u8], offset: usize, len: usize) -> String {
+    let Some(bytes) = data.get(offset..offset + len) else {
+        return String::new();
+    };
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(len);
+    String::from_utf8_lossy(&bytes[..end]).trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::is_missing;
+
+    /// Test channel: (name, unit, rate, kind, samples as i16)
+    type TestChannel<'a> = (&'a str, &'a str, u16, u16, &'a [i16]);
+
+    fn put(buf: &mut [u8], offset: usize, bytes: &[u8]) {
+        buf[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+
+    /// Build a minimal .ld file with an event/venue/vehicle chain and i16 channels
+    fn ld_file(channels: &[TestChannel]) -> Vec<u8> {
+        let event_ptr = HEADER_SIZE;
+        let venue_ptr = event_ptr + 1154;
+        let vehicle_ptr = venue_ptr + 1100;
+        let meta_ptr = vehicle_ptr + 260;
+        let data_ptr = meta_ptr + channels.len() * CHANNEL_SIZE;
+
+        let mut buf = vec![0u8; data_ptr];
+        put(&mut buf, 0, &LD_MARKER.to_le_bytes());
+        put(
+            &mut buf,
+            HEADER_CHANNEL_META_PTR,
+            &(meta_ptr as u32).to_le_bytes(),
+        );
+        put(
+            &mut buf,
+            HEADER_CHANNEL_DATA_PTR,
+            &(data_ptr as u32).to_le_bytes(),
+        );
+        put(
+            &mut buf,
+            HEADER_EVENT_PTR,
+            &(event_ptr as u32).to_le_bytes(),
+        );
+        put(&mut buf, HEADER_DEVICE_SERIAL, &12345u32.to_le_bytes());
+        put(&mut buf, HEADER_DEVICE_TYPE, b"ADL");
+        put(&mut buf, HEADER_DATE, b"23/11/2024");
+        put(&mut buf, HEADER_TIME, b"14:02:37");
+        put(&mut buf, HEADER_DRIVER, b"J. Smith");
+        put(&mut buf, HEADER_VEHICLE, b"Header Car");
+
+        put(&mut buf, event_ptr, b"Club Day");
+        put(&mut buf, event_ptr + EVENT_SESSION, b"Race 1");
+        put(
+            &mut buf,
+            event_ptr + EVENT_VENUE_PTR,
+            &(venue_ptr as u16).to_le_bytes(),
+        );
+        put(&mut buf, venue_ptr, b"Brands Hatch");
+        put(
+            &mut buf,
+            venue_ptr + VENUE_VEHICLE_PTR,
+            &(vehicle_ptr as u16).to_le_bytes(),
+        );
+        put(&mut buf, vehicle_ptr, b"Mini 1275");
+        put(&mut buf, vehicle_ptr + VEHICLE_TYPE, b"Saloon");
+
+        let mut sample_ptr = data_ptr;
+        for (i, (name, unit, rate, kind, samples)) in channels.iter().enumerate() {
+            let desc = meta_ptr + i * CHANNEL_SIZE;
+            let next = if i + 1 < channels.len() {
+                desc + CHANNEL_SIZE
+            } else {
+                0
+            };
+            put(
+                &mut buf,
+                desc + CHANNEL_NEXT_PTR,
+                &(next as u32).to_le_bytes(),
+            );
+            put(
+                &mut buf,
+                desc + CHANNEL_DATA_PTR,
+                &(sample_ptr as u32).to_le_bytes(),
+            );
+            put(
+                &mut buf,
+                desc + CHANNEL_SAMPLE_COUNT,
+                &(samples.len() as u32).to_le_bytes(),
+            );
+            put(&mut buf, desc + CHANNEL_DATA_KIND, &kind.to_le_bytes());
+            put(&mut buf, desc + CHANNEL_DATA_SIZE, &2u16.to_le_bytes());
+            put(&mut buf, desc + CHANNEL_SAMPLE_RATE, &rate.to_le_bytes());
+            put(&mut buf, desc + CHANNEL_SHIFT, &0i16.to_le_bytes());
+            put(&mut buf, desc + CHANNEL_MULTIPLIER, &1i16.to_le_bytes());
+            put(&mut buf, desc + CHANNEL_DIVISOR, &1i16.to_le_bytes());
+            put(&mut buf, desc + CHANNEL_DECIMAL_PLACES, &1i16.to_le_bytes());
+            put(&mut buf, desc + CHANNEL_NAME, name.as_bytes());
+            put(&mut buf, desc + CHANNEL_UNIT, unit.as_bytes());
+
+            for s in samples.iter() {
+                buf.extend_from_slice(&s.to_le_bytes());
+            }
+            sample_ptr += samples.len() * 2;
+        }
+        buf
+    }
+
+    #[test]
+    fn test_detect() {
+        let file = ld_file(&[("Engine Speed", "rpm", 10, 3, &[8000])]);
+        assert!(Motec::detect(&file));
+        assert!(!Motec::detect(&file[..HEADER_SIZE - 1]));
+        assert!(!Motec::detect(b"MLVLG\0"));
+        assert!(!Motec::detect(&vec![0u8; HEADER_SIZE + CHANNEL_SIZE]));
+    }
+
+    #[test]
+    fn test_channel_scale() {
+        let channel = MotecChannel {
+            shift: 10,
+            multiplier: 2,
+            divisor: 4,
+            decimal_places: 1,
+            ..Default::default()
+        };
+        // (400 / 4 * 0.1 + 10) * 2
+        assert!((channel.scale(400.0) - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_f16_to_f64() {
+        assert_eq!(f16_to_f64(0x3C00), 1.0);
+        assert_eq!(f16_to_f64(0xC000), -2.0);
+        assert_eq!(f16_to_f64(0x3555), 0.333251953125);
+        assert_eq!(f16_to_f64(0x0000), 0.0);
+        assert!(f16_to_f64(0x7E00).is_nan());
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let file = ld_file(&[("Engine Speed", "rpm", 10, 3, &[8000])]);
+        let log = Motec::parse_binary(&file).unwrap();
+
+        let Meta::Motec(meta) = &log.meta else {
+            panic!("Expected MoTeC metadata");
+        };
+        assert_eq!(meta.event, "Club Day");
+        assert_eq!(meta.session, "Race 1");
+        assert_eq!(meta.venue, "Brands Hatch");
+        assert_eq!(meta.vehicle, "Mini 1275");
+        assert_eq!(meta.vehicle_type, "Saloon");
+        assert_eq!(meta.driver, "J. Smith");
+        assert_eq!(meta.device_type, "ADL");
+        assert_eq!(meta.device_serial, 12345);
+    }
+
+    #[test]
+    fn test_parse_aligns_sample_rates() {
+        let file = ld_file(&[
+            ("Engine Speed", "rpm", 20, 3, &[10000, 11000, 12000, 13000]),
+            ("Coolant Temp", "C", 10, 3, &[850, 860]),
+            ("Lambda", "LA", 5, 0, &[9]),
+        ]);
+        let log = Motec::parse_binary(&file).unwrap();
+
+        assert_eq!(log.channels.len(), 3);
+        assert_eq!(log.record_count(), 4);
+        assert!((log.times[1] - 0.05).abs() < 1e-9);
+
+        // Slower channels are held between their own samples
+        assert_eq!(log.channel_data(1), &[85.0, 85.0, 86.0, 86.0]);
+        assert_eq!(log.channel_data(2), &[0.9; 4]);
+        assert_eq!(log.channel_data(0)[3], 1300.0);
+
+        // ...and keep their own timebase
+        assert!(log.native_series(0).is_none());
+        let coolant = log.native_series(1).unwrap();
+        assert_eq!(coolant.times, vec![0.0, 0.1]);
+        assert_eq!(coolant.values, vec![85.0, 86.0]);
+    }
+
+    #[test]
+    fn test_parse_marks_rows_past_last_sample_missing() {
+        let file = ld_file(&[
+            ("Engine Speed", "rpm", 20, 3, &[10000, 11000, 12000, 13000]),
+            ("Coolant Temp", "C", 10, 3, &[850]),
+        ]);
+        let log = Motec::parse_binary(&file).unwrap();
+
+        let coolant = log.channel_data(1);
+        assert_eq!(&coolant[..2], &[85.0, 85.0]);
+        assert!(coolant[2..].iter().all(|v| is_missing(*v)));
+    }
+
+    #[test]
+    fn test_parse_skips_unsupported_channels() {
+        let file = ld_file(&[
+            ("Engine Speed", "rpm", 10, 3, &[8000]),
+            ("Broken", "", 0, 3, &[1]),
+        ]);
+        let log = Motec::parse_binary(&file).unwrap();
+        assert_eq!(log.channels.len(), 1);
+        assert_eq!(log.channels[0].unit(), "rpm");
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_log() {
+        let file = ld_file(&[("Broken", "", 0, 3, &[1])]);
+        assert!(Motec::parse_binary(&file).is_err());
+    }
+}
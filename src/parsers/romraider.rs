@@ -9,13 +9,13 @@
 //!
 //! Reference: <https://github.com/RomRaider/RomRaider>
 
-use serde::Serialize;
-use std::error::Error;
+use serde::{Deserialize, Serialize};
 
-use super::types::{Channel, Log, Meta, Parseable, Value};
+use super::error::ParseError;
+use super::types::{Channel, ColumnBuilder, Log, Meta, Parseable, MISSING};
 
 /// RomRaider log file metadata
-#[derive(Clone, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct RomRaiderMeta {
     /// Number of channels in the log
     pub channel_count: usize,
@@ -24,7 +24,7 @@ pub struct RomRaiderMeta {
 }
 
 /// RomRaider channel definition
-#[derive(Clone, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct RomRaiderChannel {
     /// Full column name from header (e.g., "Engine Speed (rpm)")
     pub name: String,
@@ -234,19 +234,20 @@ impl RomRaider {
 }
 
 impl Parseable for RomRaider {
-    fn parse(&self, file_contents: &str) -> Result<Log, Box<dyn Error>> {
+    fn parse(&self, file_contents: &str) -> Result<Log, ParseError> {
         // Pre-allocate based on estimated row count (first line is header)
         let line_count = file_contents.lines().count();
         let estimated_data_rows = line_count.saturating_sub(1);
 
         let mut channels: Vec<Channel> = Vec::with_capacity(50);
         let mut times: Vec<f64> = Vec::with_capacity(estimated_data_rows);
-        let mut data: Vec<Vec<Value>> = Vec::with_capacity(estimated_data_rows);
 
         let mut lines = file_contents.lines();
 
         // Parse header line to get channel names
-        let header = lines.next().ok_or("Empty file: no header found")?;
+        let header = lines
+            .next()
+            .ok_or_else(|| ParseError::invalid("Empty file: no header found"))?;
 
         // Detect delimiter (comma for US locale, semicolon for European locale)
         let delimiter = Self::detect_delimiter(header);
@@ -258,13 +259,13 @@ impl Parseable for RomRaider {
         let column_names: Vec<&str> = header.split(delimiter_char).collect();
 
         if column_names.is_empty() {
-            return Err("Invalid RomRaider log: no columns found".into());
+            return Err(ParseError::bad_header(1, "no columns found"));
         }
 
         // First column should be Time
         let first_col = column_names[0].trim().to_lowercase();
         if !first_col.starts_with("time") {
-            return Err("Invalid RomRaider log: first column must be Time".into());
+            return Err(ParseError::bad_header(1, "first column must be Time"));
         }
 
         // Create channels from header (skip Time column)
@@ -273,6 +274,8 @@ impl Parseable for RomRaider {
             channels.push(Channel::RomRaider(channel));
         }
 
+        let mut data = ColumnBuilder::with_capacity(channels.len(), estimated_data_rows);
+
         // Track first timestamp for relative time calculation
         let mut first_time: Option<f64> = None;
 
@@ -302,23 +305,18 @@ impl Parseable for RomRaider {
                 times.push(relative_time);
 
                 // Parse remaining values
-                let mut row_values: Vec<Value> = Vec::with_capacity(channels.len());
+                let mut row_values: Vec<f64> = Vec::with_capacity(channels.len());
 
                 for part in parts.iter().skip(1) {
                     if let Some(val) = Self::parse_european_number(part, delimiter) {
-                        row_values.push(Value::Float(val));
+                        row_values.push(val);
                     } else {
-                        // Empty or non-numeric value - use 0
-                        row_values.push(Value::Float(0.0));
+                        // Empty or non-numeric value - no sample
+                        row_values.push(MISSING);
                     }
                 }
 
-                // Pad row to match channel count if needed
-                while row_values.len() < channels.len() {
-                    row_values.push(Value::Float(0.0));
-                }
-
-                data.push(row_values);
+                data.push_record(&row_values);
             }
         }
 
@@ -329,21 +327,22 @@ impl Parseable for RomRaider {
             data.len()
         );
 
-        Ok(Log {
-            meta: Meta::RomRaider(RomRaiderMeta {
+        Ok(Log::new(
+            Meta::RomRaider(RomRaiderMeta {
                 channel_count: channels.len(),
                 data_points: data.len(),
             }),
             channels,
             times,
-            data,
-        })
+            data.into_columns(),
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parsers::types::is_missing;
 
     #[test]
     fn test_channel_from_header() {
@@ -455,7 +454,7 @@ mod tests {
         assert_eq!(log.channels[2].name(), "A/F Correction #1");
 
         assert_eq!(log.times.len(), 3);
-        assert_eq!(log.data.len(), 3);
+        assert_eq!(log.record_count(), 3);
 
         // Check relative timestamps (converted from ms to seconds)
         assert!((log.times[0] - 0.0).abs() < 0.001);
@@ -463,9 +462,9 @@ mod tests {
         assert!((log.times[2] - 0.040).abs() < 0.001);
 
         // Check first row values
-        assert_eq!(log.data[0][0].as_f64(), 1000.0);
-        assert_eq!(log.data[0][1].as_f64(), 10.5);
-        assert_eq!(log.data[0][2].as_f64(), 1.5);
+        assert_eq!(log.channel_data(0)[0], 1000.0);
+        assert_eq!(log.channel_data(1)[0], 10.5);
+        assert_eq!(log.channel_data(2)[0], 1.5);
 
         // Check units
         assert_eq!(log.channels[0].unit(), "rpm");
@@ -490,7 +489,7 @@ mod tests {
         assert_eq!(log.channels[2].name(), "PLX Manifold Absolute Pressure");
 
         assert_eq!(log.times.len(), 3);
-        assert_eq!(log.data.len(), 3);
+        assert_eq!(log.record_count(), 3);
 
         // Check relative timestamps (converted from ms to seconds)
         assert!((log.times[0] - 0.0).abs() < 0.001);
@@ -498,9 +497,9 @@ mod tests {
         assert!((log.times[2] - 0.396).abs() < 0.001);
 
         // Check first row values (European decimals converted)
-        assert_eq!(log.data[0][0].as_f64(), 14.0);
-        assert_eq!(log.data[0][1].as_f64(), 860.0);
-        assert!((log.data[0][2].as_f64() - 0.38).abs() < 0.001);
+        assert_eq!(log.channel_data(0)[0], 14.0);
+        assert_eq!(log.channel_data(1)[0], 860.0);
+        assert!((log.channel_data(2)[0] - 0.38).abs() < 0.001);
 
         // Check units (extracted from parentheses in header)
         assert_eq!(log.channels[0].unit(), "degrees");
@@ -540,10 +539,11 @@ mod tests {
         let parser = RomRaider;
         let log = parser.parse(sample).unwrap();
 
-        assert_eq!(log.data.len(), 3);
+        assert_eq!(log.record_count(), 3);
 
-        // Empty values should be 0
-        assert_eq!(log.data[1][1].as_f64(), 0.0); // Missing Load
-        assert_eq!(log.data[2][0].as_f64(), 0.0); // Missing RPM
+        // Empty values are missing, not zero
+        assert!(is_missing(log.channel_data(1)[1])); // Missing Load
+        assert!(is_missing(log.channel_data(0)[2])); // Missing RPM
+        assert_eq!(log.channel_data(1)[2], 60.0);
     }
 }
@@ -0,0 +1,502 @@
+//! Generic CSV parser driven by OpenECU Alliance adapter specs.
+//!
+//! Adapter specs describe a CSV layout in their `file_format` section
+//! (`delimiter`, `header_row`, `data_start_row`, `timestamp_column`,
+//! `timestamp_unit`, `encoding`) and list the channels a log may contain,
+//! along with the column names each vendor uses for them. This parser reads
+//! any CSV an adapter describes, so a new format only needs a spec.
+//!
+//! Columns that match a channel's `source_names` take their unit from the
+//! spec. When the spec gives a `conversion` formula, values are converted
+//! from `source_unit` into the canonical unit.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::bluedriver::BlueDriver;
+use super::error::ParseError;
+use super::types::{Channel, ColumnBuilder, Log, LogInfo, Meta, Parseable, ValueKind, MISSING};
+use crate::adapters::{find_adapters_by_extension, AdapterSpec, ChannelSpec, DataType};
+
+/// `file_format.type` of adapters this parser can read
+const CSV_FORMAT_TYPE: &str = "csv";
+
+/// Spec-driven CSV log metadata
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SpecCsvMeta {
+    /// Adapter spec id (e.g., "haltech-nsp")
+    pub adapter_id: String,
+    /// Adapter display name
+    pub adapter_name: String,
+    /// ECU vendor from the adapter spec
+    pub vendor: String,
+    /// Number of channels in the log
+    pub channel_count: usize,
+    /// Number of data points
+    pub data_points: usize,
+}
+
+impl SpecCsvMeta {
+    /// Common metadata, reporting the adapter as the format
+    pub fn info(&self) -> LogInfo {
+        let mut info = LogInfo::new(&self.adapter_name);
+        info.push_vendor("Vendor", &self.vendor);
+        info.push_vendor("Adapter", &self.adapter_id);
+        info
+    }
+}
+
+/// Spec-driven CSV channel definition
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SpecCsvChannel {
+    /// Column name from the header row
+    pub name: String,
+    /// Unit from the matching channel spec (empty if the column has no spec)
+    pub unit: String,
+    /// Canonical channel id of the matching channel spec (e.g., "coolant_temp")
+    pub channel_id: Option<String>,
+    /// Enum or boolean states from the channel spec
+    pub value_kind: ValueKind,
+}
+
+impl SpecCsvChannel {
+    /// Get the display unit for this channel
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+}
+
+/// How to turn a column's cells into values
+struct ColumnPlan {
+    channel: SpecCsvChannel,
+    conversion: Option<Box<dyn Fn(f64) -> f64>>,
+}
+
+/// CSV parser configured by an adapter spec
+pub struct SpecCsv {
+    adapter: AdapterSpec,
+}
+
+impl SpecCsv {
+    /// Create a parser for the CSV layout described by `adapter`
+    pub fn new(adapter: AdapterSpec) -> Self {
+        Self { adapter }
+    }
+
+    /// The adapter spec driving this parser
+    pub fn adapter(&self) -> &AdapterSpec {
+        &self.adapter
+    }
+
+    /// Find a CSV adapter registered for `extension` whose layout matches the
+    /// file contents. Used as the last step of format detection.
+    pub fn from_extension(extension: &str, contents: &str) -> Option<Self> {
+        find_adapters_by_extension(extension)
+            .into_iter()
+            .map(Self::new)
+            .find(|parser| parser.detect(contents))
+    }
+
+    /// Check whether the file contents match this adapter's CSV layout: the
+    /// header row has the timestamp column and at least one known channel
+    pub fn detect(&self, contents: &str) -> bool {
+        if !self
+            .adapter
+            .file_format
+            .format_type
+            .eq_ignore_ascii_case(CSV_FORMAT_TYPE)
+        {
+            return false;
+        }
+
+        let Some(header) = self.header_line(contents) else {
+            return false;
+        };
+        let columns = self.split_row(header);
+        if columns.len() < 2 || self.find_time_column(&columns).is_none() {
+            return false;
+        }
+
+        let sources = self.source_names();
+        columns
+            .iter()
+            .any(|column| Self::lookup(&sources, column).is_some())
+    }
+
+    /// Parse raw file bytes, decoding them with the spec's `encoding`
+    pub fn parse_binary(&self, data: &[u8]) -> Result<Log, ParseError> {
+        let encoding = self
+            .adapter
+            .file_format
+            .encoding
+            .as_deref()
+            .unwrap_or("utf-8")
+            .to_lowercase();
+
+        let contents = match encoding.as_str() {
+            "latin1" | "latin-1" | "iso-8859-1" | "windows-1252" | "cp1252" => {
+                data.iter().map(|&b| b as char).collect()
+            }
+            _ => match BlueDriver::decode_utf16(data) {
+                Some(decoded) => decoded,
+                None => String::from_utf8_lossy(data).into_owned(),
+            },
+        };
+        self.parse(&contents)
+    }
+
+    /// Column delimiter from the spec, defaulting to a comma
+    fn delimiter(&self) -> char {
+        match self.adapter.file_format.delimiter.as_deref() {
+            Some("\t") | Some("\\t") | Some("tab") => '\t',
+            Some(d) => d.chars().next().unwrap_or(','),
+            None => ',',
+        }
+    }
+
+    /// Zero-based index of the header row
+    fn header_row(&self) -> usize {
+        self.adapter.file_format.header_row.unwrap_or(0).max(0) as usize
+    }
+
+    /// Zero-based index of the first data row, defaulting to the row after the header
+    fn data_start_row(&self) -> usize {
+        self.adapter
+            .file_format
+            .data_start_row
+            .map(|row| row.max(0) as usize)
+            .unwrap_or(self.header_row() + 1)
+    }
+
+    /// Divisor converting the timestamp column to seconds
+    fn time_divisor(&self) -> f64 {
+        match self
+            .adapter
+            .file_format
+            .timestamp_unit
+            .as_deref()
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("ms") | Some("msec") | Some("milliseconds") => 1_000.0,
+            Some("us") | Some("µs") | Some("microseconds") => 1_000_000.0,
+            _ => 1.0,
+        }
+    }
+
+    fn header_line<'a>(&self, contents: &'a str) -> Option<&'a str> {
+        contents
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .nth(self.header_row())
+    }
+
+    fn split_row<'a>(&self, row: &'a str) -> Vec<&'a str> {
+        row.split(self.delimiter())
+            .map(|cell| cell.trim().trim_matches('"').trim())
+            .collect()
+    }
+
+    /// Locate the timestamp column named by the spec, or the first column
+    fn find_time_column(&self, columns: &[&str]) -> Option<usize> {
+        match &self.adapter.file_format.timestamp_column {
+            Some(name) => columns.iter().position(|column| {
+                column.eq_ignore_ascii_case(name) || strip_unit(column).eq_ignore_ascii_case(name)
+            }),
+            None => Some(0),
+        }
+    }
+
+    /// Map each lowercase source name to its channel spec
+    fn source_names(&self) -> HashMap<String, &ChannelSpec> {
+        self.adapter
+            .channels
+            .iter()
+            .flat_map(|spec| {
+                spec.source_names
+                    .iter()
+                    .map(move |source| (source.to_lowercase(), spec))
+            })
+            .collect()
+    }
+
+    /// Find the channel spec for a header column, with or without its unit suffix
+    fn lookup<'a>(
+        sources: &HashMap<String, &'a ChannelSpec>,
+        column: &str,
+    ) -> Option<&'a ChannelSpec> {
+        sources
+            .get(&column.to_lowercase())
+            .or_else(|| sources.get(&strip_unit(column).to_lowercase()))
+            .copied()
+    }
+
+    /// Build the channel and value conversion for a header column
+    fn plan_column(&self, column: &str, spec: Option<&ChannelSpec>) -> ColumnPlan {
+        let Some(spec) = spec else {
+            return ColumnPlan {
+                channel: SpecCsvChannel {
+                    name: column.to_string(),
+                    ..Default::default()
+                },
+                conversion: None,
+            };
+        };
+
+        // Convert into the canonical unit when the spec says how; otherwise
+        // the values stay in the source unit
+        let conversion = spec.conversion.as_deref().and_then(|formula| {
+            match formula
+                .parse::<meval::Expr>()
+                .and_then(|expr| expr.bind("x"))
+            {
+                Ok(f) => Some(Box::new(f) as Box<dyn Fn(f64) -> f64>),
+                Err(e) => {
+                    tracing::warn!("Ignoring conversion '{}' for {}: {}", formula, spec.id, e);
+                    None
+                }
+            }
+        });
+        let unit = match (&conversion, &spec.source_unit) {
+            (None, Some(source_unit)) => source_unit.clone(),
+            _ => spec.unit.clone(),
+        };
+
+        let value_kind = match spec.data_type {
+            DataType::Bool => ValueKind::boolean(),
+            DataType::Enum => match spec
+                .enum_ref
+                .as_deref()
+                .and_then(|name| self.adapter.find_enum(name))
+            {
+                Some(enum_spec) => ValueKind::from_labels(&enum_spec.values),
+                None => {
+                    tracing::warn!("Enum channel {} has no matching enum definition", spec.id);
+                    ValueKind::Enum(Vec::new())
+                }
+            },
+            _ => ValueKind::Numeric,
+        };
+
+        ColumnPlan {
+            channel: SpecCsvChannel {
+                name: column.to_string(),
+                unit,
+                channel_id: Some(spec.id.clone()),
+                value_kind,
+            },
+            conversion,
+        }
+    }
+}
+
+impl Parseable for SpecCsv {
+    fn parse(&self, file_contents: &str) -> Result<Log, ParseError> {
+        let adapter_name = &self.adapter.name;
+        let header_line = self.header_row() + 1;
+        let header = self.header_line(file_contents).ok_or_else(|| {
+            ParseError::invalid(format!("{} log has no header row", adapter_name))
+        })?;
+        let columns = self.split_row(header);
+        let time_idx = self
+            .find_time_column(&columns)
+            .ok_or_else(|| ParseError::bad_header(header_line, "no timestamp column"))?;
+
+        let sources = self.source_names();
+        let plans: Vec<(usize, ColumnPlan)> = columns
+            .iter()
+            .enumerate()
+            .filter(|(idx, column)| *idx != time_idx && !column.is_empty())
+            .map(|(idx, column)| {
+                (
+                    idx,
+                    self.plan_column(column, Self::lookup(&sources, column)),
+                )
+            })
+            .collect();
+
+        if plans.is_empty() {
+            return Err(ParseError::bad_header(header_line, "no channels found"));
+        }
+
+        let time_divisor = self.time_divisor();
+        let mut times: Vec<f64> = Vec::new();
+        let mut data = ColumnBuilder::new(plans.len());
+
+        // Carry the last good value through empty or unparsable cells
+        let mut last_values: Vec<f64> = vec![MISSING; plans.len()];
+        let mut first_time: Option<f64> = None;
+
+        let rows = file_contents
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .skip(self.data_start_row());
+        for row in rows {
+            if row.trim().is_empty() {
+                continue;
+            }
+
+            let cells = self.split_row(row);
+            let Some(time) = cells.get(time_idx).and_then(|t| t.parse::<f64>().ok()) else {
+                continue;
+            };
+            let time_secs = time / time_divisor;
+
+            let relative_time = match first_time {
+                Some(first) => time_secs - first,
+                None => {
+                    first_time = Some(time_secs);
+                    0.0
+                }
+            };
+
+            for (slot, (idx, plan)) in last_values.iter_mut().zip(&plans) {
+                if let Some(val) = cells.get(*idx).and_then(|c| c.parse::<f64>().ok()) {
+                    *slot = match &plan.conversion {
+                        Some(convert) => convert(val),
+                        None => val,
+                    };
+                }
+            }
+
+            times.push(relative_time);
+            data.push_record(&last_values);
+        }
+
+        let channels: Vec<Channel> = plans
+            .into_iter()
+            .map(|(_, plan)| Channel::SpecCsv(plan.channel))
+            .collect();
+
+        tracing::info!(
+            "Parsed {} log via adapter spec: {} channels, {} data points",
+            adapter_name,
+            channels.len(),
+            data.len()
+        );
+
+        Ok(Log::new(
+            Meta::SpecCsv(SpecCsvMeta {
+                adapter_id: self.adapter.id.clone(),
+                adapter_name: adapter_name.clone(),
+                vendor: self.adapter.vendor.clone(),
+                channel_count: channels.len(),
+                data_points: data.len(),
+            }),
+            channels,
+            times,
+            data.into_columns(),
+        ))
+    }
+}
+
+/// Drop a trailing "(unit)" or "[unit]" from a header column
+fn strip_unit(column: &str) -> &str {
+    let column = column.trim();
+    for (open, close) in [('(', ')'), ('[', ']')] {
+        if column.ends_with(close) {
+            if let Some(start) = column.rfind(open) {
+                let name = column[..start].trim();
+                if !name.is_empty() {
+                    return name;
+                }
+            }
+        }
+    }
+    column
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADAPTER_YAML: &str = r#"
+openecualliance: "1.0"
+id: test-csv
+name: Test CSV Logger
+version: "1.0.0"
+vendor: test
+file_format:
+  type: csv
+  extensions: [".csv"]
+  delimiter: ";"
+  header_row: 1
+  data_start_row: 3
+  timestamp_column: Time
+  timestamp_unit: ms
+channels:
+  - id: rpm
+    name: Engine RPM
+    category: engine
+    data_type: float
+    unit: rpm
+    source_names: ["RPM", "Engine Speed"]
+  - id: coolant_temp
+    name: Coolant Temperature
+    category: temperature
+    data_type: float
+    unit: °C
+    source_unit: °F
+    conversion: "(x - 32) * 5 / 9"
+    source_names: ["CLT"]
+"#;
+
+    fn adapter() -> AdapterSpec {
+        serde_yaml::from_str(ADAPTER_YAML).unwrap()
+    }
+
+    const SAMPLE: &str = "Test Logger v2\n\
+        Time (ms);RPM;CLT;Gear\n\
+        ms;rpm;F;\n\
+        1000;850;176;0\n\
+        1100;;180;1\n";
+
+    #[test]
+    fn test_strip_unit() {
+        assert_eq!(strip_unit("Time (ms)"), "Time");
+        assert_eq!(strip_unit("MAP [kPa]"), "MAP");
+        assert_eq!(strip_unit("RPM"), "RPM");
+        assert_eq!(strip_unit("(ms)"), "(ms)");
+    }
+
+    #[test]
+    fn test_detect() {
+        let parser = SpecCsv::new(adapter());
+        assert!(parser.detect(SAMPLE));
+        assert!(!parser.detect("Time (ms);Foo;Bar\n"));
+        assert!(!parser.detect("Time,RPM\n0,800\n"));
+        assert!(!parser.detect(""));
+    }
+
+    #[test]
+    fn test_parse_layout_and_units() {
+        let log = SpecCsv::new(adapter()).parse(SAMPLE).unwrap();
+
+        assert_eq!(log.channels.len(), 3);
+        assert_eq!(log.channels[0].unit(), "rpm");
+        assert_eq!(log.channels[1].unit(), "°C");
+        assert_eq!(log.channels[2].unit(), "");
+        assert_eq!(log.record_count(), 2);
+        assert!((log.times[1] - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_applies_conversion_and_carry_forward() {
+        let log = SpecCsv::new(adapter()).parse(SAMPLE).unwrap();
+
+        assert!((log.channel_data(1)[0] - 80.0).abs() < 1e-9);
+        assert_eq!(log.channel_data(0)[1], 850.0);
+        assert_eq!(log.channel_data(2)[1], 1.0);
+    }
+
+    #[test]
+    fn test_parse_binary_latin1() {
+        let mut spec = adapter();
+        spec.file_format.encoding = Some("iso-8859-1".to_string());
+        let bytes = b"Logger\nTime (ms);RPM;CLT (\xB0F)\nms;rpm;F\n0;900;176\n".to_vec();
+
+        let log = SpecCsv::new(spec).parse_binary(&bytes).unwrap();
+        assert_eq!(log.channels[1].name(), "CLT (°F)");
+        assert_eq!(log.channels[1].unit(), "°C");
+    }
+}
@@ -7,10 +7,27 @@
 //! - Field definitions (55 bytes for v1, 89 bytes for v2)
 //! - Binary data records (block type + timestamp + field values)
 
-use serde::Serialize;
-use std::error::Error;
-
-use super::types::{Log, Parseable, Value};
+use serde::{Deserialize, Serialize};
+use std::io::{BufReader, Cursor, ErrorKind, Read, Seek, SeekFrom};
+
+use super::error::ParseError;
+use super::streaming::{
+    stream_len, ChunkedOutput, LogBuilder, RecordSink, StreamHeader, StreamProgress,
+    StreamingParseable, CHUNK_RECORDS,
+};
+use super::types::{
+    non_empty, Annotation, Channel, Log, LogInfo, Meta, Parseable, RecoveryReport, ValueKind,
+};
+
+/// Block type, counter and timestamp at the start of every block
+const BLOCK_HEADER_BYTES: usize = 4;
+/// Message length of a marker block
+const MARKER_BYTES: usize = 50;
+/// Consecutive data blocks that must check out before recovery resumes
+/// parsing after damaged data
+const RESYNC_DATA_BLOCKS: usize = 3;
+/// Blocks (data or marker) examined when confirming a resync point
+const RESYNC_MAX_BLOCKS: usize = 8;
 
 /// MLG field data types (from mlg-converter)
 #[derive(Clone, Copy, Debug)]
@@ -47,6 +64,31 @@ impl FieldType {
         }
     }
 
+    fn is_bitfield(&self) -> bool {
+        matches!(
+            self,
+            Self::U08Bitfield | Self::U16Bitfield | Self::U32Bitfield
+        )
+    }
+
+    /// Read a raw big-endian value of this type from the start of `bytes`
+    fn read(&self, bytes: &[u8]) -> f64 {
+        match self {
+            Self::U08 | Self::U08Bitfield => bytes[0] as f64,
+            Self::S08 => bytes[0] as i8 as f64,
+            Self::U16 | Self::U16Bitfield => u16::from_be_bytes([bytes[0], bytes[1]]) as f64,
+            Self::S16 => i16::from_be_bytes([bytes[0], bytes[1]]) as f64,
+            Self::U32 | Self::U32Bitfield => {
+                u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64
+            }
+            Self::S32 => i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+            Self::F32 => f32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+            Self::S64 => i64::from_be_bytes([
+                bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+            ]) as f64,
+        }
+    }
+
     fn byte_size(&self) -> usize {
         match self {
             Self::U08 | Self::S08 | Self::U08Bitfield => 1,
@@ -58,28 +100,51 @@ impl FieldType {
 }
 
 /// Speeduino field metadata
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SpeeduinoChannel {
     pub name: String,
     pub unit: String,
     pub scale: f32,
     pub transform: f32,
     pub field_type: u8,
+    /// Flag names for bitfield fields, least significant bit first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bit_names: Vec<String>,
 }
 
 impl SpeeduinoChannel {
     pub fn unit(&self) -> &str {
         &self.unit
     }
+
+    /// Bitfield fields hold packed flags; everything else is numeric
+    pub fn value_kind(&self) -> ValueKind {
+        match FieldType::from_u8(self.field_type) {
+            Some(field_type) if field_type.is_bitfield() => {
+                ValueKind::Bitfield(self.bit_names.clone())
+            }
+            _ => ValueKind::Numeric,
+        }
+    }
 }
 
 /// Speeduino log metadata
-#[derive(Clone, Debug, Serialize, Default)]
+#[derive(Clone, Debug, Deserialize, Serialize, Default)]
 pub struct SpeeduinoMeta {
     pub version: String,
     pub capture_date: String,
 }
 
+impl SpeeduinoMeta {
+    /// Common metadata from the MLG header; the info string names the firmware
+    pub fn info(&self) -> LogInfo {
+        let mut info = LogInfo::new("Speeduino");
+        info.start_datetime = non_empty(&self.capture_date);
+        info.firmware = non_empty(&self.version);
+        info
+    }
+}
+
 /// Speeduino parser for MegaLogViewer binary format
 pub struct Speeduino;
 
@@ -90,89 +155,55 @@ impl Speeduino {
     }
 
     /// Parse MegaLogViewer binary format (based on mlg-converter reference)
-    pub fn parse_binary(data: &[u8]) -> Result<Log, Box<dyn Error>> {
-        let mut offset = 0;
+    pub fn parse_binary(data: &[u8]) -> Result<Log, ParseError> {
+        let mut builder = LogBuilder::new();
+        Speeduino.parse_stream(Cursor::new(data), &mut builder, &mut |_| {})?;
+        Ok(builder.finish())
+    }
 
-        // Read file format (6 bytes: "MLVLG" + 1 extra byte)
-        if data.len() < 6 || &data[0..5] != b"MLVLG" {
-            return Err("Invalid MLG file header".into());
-        }
-        offset += 6;
+    /// Parse a damaged MLG file, keeping every intact record. The log's
+    /// `recovery` report says how many records were skipped.
+    pub fn recover_binary(data: &[u8]) -> Result<Log, ParseError> {
+        let mut builder = LogBuilder::new();
+        Self::recover_stream(Cursor::new(data), &mut builder, &mut |_| {})?;
+        Ok(builder.finish())
+    }
 
-        // Read format version (int16, big-endian like DataView default)
-        let format_version = i16::from_be_bytes([data[offset], data[offset + 1]]);
-        offset += 2;
+    /// Streaming form of [`Speeduino::recover_binary`]
+    pub fn recover_stream<R: Read + Seek>(
+        reader: R,
+        sink: &mut dyn RecordSink,
+        progress: &mut dyn FnMut(StreamProgress),
+    ) -> Result<(), ParseError> {
+        Self::read_stream(reader, sink, progress, true)
+    }
 
-        let is_v2 = format_version == 2;
+    /// Parse the field definitions and info data. `data` holds the file
+    /// from its start up to the first data block; `offset` points just past
+    /// the fixed header fields.
+    fn parse_fields(
+        data: &[u8],
+        mut offset: usize,
+        num_fields: usize,
+        is_v2: bool,
+        info_data_start: usize,
+    ) -> Result<(SpeeduinoMeta, Vec<SpeeduinoChannel>), ParseError> {
         let field_length = if is_v2 { 89 } else { 55 };
-
-        eprintln!(
-            "DEBUG: MLG format version: {}, field_length: {}",
-            format_version, field_length
-        );
-
-        // Read timestamp (int32, big-endian)
-        offset += 4;
-
-        // Read info_data_start (int16 for v1, int32 for v2, big-endian)
-        let info_data_start = if is_v2 {
-            u32::from_be_bytes([
-                data[offset],
-                data[offset + 1],
-                data[offset + 2],
-                data[offset + 3],
-            ]) as usize
-        } else {
-            u16::from_be_bytes([data[offset], data[offset + 1]]) as usize
-        };
-        offset += if is_v2 { 4 } else { 2 };
-
-        // Read data_begin_index (int32, big-endian)
-        let data_begin_index = u32::from_be_bytes([
-            data[offset],
-            data[offset + 1],
-            data[offset + 2],
-            data[offset + 3],
-        ]) as usize;
-        offset += 4;
-
-        // Read record_length (int16, big-endian)
-        offset += 2;
-
-        // Read num_logger_fields (int16, big-endian)
-        let num_fields = u16::from_be_bytes([data[offset], data[offset + 1]]) as usize;
-        offset += 2;
-
-        eprintln!(
-            "DEBUG: num_fields: {}, data_begin_index: {}",
-            num_fields, data_begin_index
-        );
-
-        // Validate bounds before parsing
-        if num_fields > 1000 {
-            return Err(format!("Unreasonable field count: {}", num_fields).into());
-        }
-        if data_begin_index > data.len() {
-            return Err(format!(
-                "data_begin_index {} exceeds file size {}",
-                data_begin_index,
-                data.len()
-            )
-            .into());
-        }
+        let info_data = data.get(info_data_start..).unwrap_or(&[]);
 
         // Parse field definitions
         let mut channels = Vec::new();
         for i in 0..num_fields {
             if offset + field_length > data.len() {
-                return Err(format!(
-                    "Not enough data for field {} at offset {} (need {}, have {})",
-                    i,
+                return Err(ParseError::truncated(
                     offset,
-                    field_length,
-                    data.len() - offset
-                )
-                .into());
+                    format!(
+                        "field {} needs {} bytes, {} available",
+                        i,
+                        field_length,
+                        data.len().saturating_sub(offset)
+                    ),
+                ));
             }
             // Read type (1 byte)
             let field_type = data[offset];
@@ -197,6 +228,7 @@ impl Speeduino {
             // Read display_style (1 byte)
             offset += 1;
 
+            let mut bit_names = Vec::new();
             let (scale, transform) = if field_type < 10 {
                 // Scalar field
                 let scale = f32::from_be_bytes([
@@ -225,7 +257,21 @@ impl Speeduino {
 
                 (scale, transform)
             } else {
-                // Bitfield - skip remaining bytes
+                // Bitfield: index of the bit names in the info data, then bit count
+                let names_index = i32::from_be_bytes([
+                    data[offset],
+                    data[offset + 1],
+                    data[offset + 2],
+                    data[offset + 3],
+                ]);
+                let bits = data[offset + 4] as usize;
+                let width = FieldType::from_u8(field_type).map_or(0, |t| t.byte_size() * 8);
+                let bits = if bits == 0 || bits > width {
+                    width
+                } else {
+                    bits
+                };
+                bit_names = Self::read_bit_names(info_data, names_index, bits);
                 offset += field_length - 46; // Already read 46 bytes
                 (1.0, 0.0)
             };
@@ -236,22 +282,14 @@ impl Speeduino {
                 scale,
                 transform,
                 field_type,
+                bit_names,
             });
         }
 
-        eprintln!("DEBUG: Parsed {} channels", channels.len());
-        for (idx, ch) in channels.iter().enumerate() {
-            eprintln!(
-                "  [{}] {} ({}) type={} scale={} transform={}",
-                idx, ch.name, ch.unit, ch.field_type, ch.scale, ch.transform
-            );
-        }
-
         // Extract metadata from info section
         let mut meta = SpeeduinoMeta::default();
-        if info_data_start < data_begin_index && data_begin_index < data.len() {
-            let info_bytes = &data[info_data_start..data_begin_index];
-            let info_str = String::from_utf8_lossy(info_bytes);
+        if !info_data.is_empty() {
+            let info_str = String::from_utf8_lossy(info_data);
 
             if let Some(version_start) = info_str.find("speeduino") {
                 if let Some(version_end) = info_str[version_start..].find('"') {
@@ -265,19 +303,190 @@ impl Speeduino {
             }
         }
 
-        // Parse data blocks
-        offset = data_begin_index;
-        // Estimate record count: remaining data / approximate record size (timestamp + data + CRC)
-        // Each record is roughly: 1 (block type) + 2 (timestamp) + num_fields * ~4 bytes + 1 (CRC)
-        let remaining_data = data.len().saturating_sub(data_begin_index);
-        let estimated_record_size = 4 + channels.len() * 4;
-        let estimated_records = if estimated_record_size > 0 {
-            remaining_data / estimated_record_size
+        Ok((meta, channels))
+    }
+
+    /// Fill `buf` from the reader, returning false if the input ends first
+    fn read_block<R: Read>(reader: &mut R, buf: &mut [u8]) -> std::io::Result<bool> {
+        match reader.read_exact(buf) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Read the null-separated bit names stored in the info data, falling
+    /// back to "Bit N" for any name that can't be read
+    fn read_bit_names(info_data: &[u8], names_index: i32, bits: usize) -> Vec<String> {
+        let mut names: Vec<String> = usize::try_from(names_index)
+            .ok()
+            .and_then(|index| info_data.get(index..))
+            .map(|bytes| {
+                bytes
+                    .split(|&b| b == 0)
+                    .take(bits)
+                    .map(|name| String::from_utf8_lossy(name).trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.resize(bits, String::new());
+
+        names
+            .into_iter()
+            .enumerate()
+            .map(|(bit, name)| {
+                // Names share the 34-byte limit of field names
+                let printable = !name.is_empty()
+                    && name.len() <= 34
+                    && name.chars().all(|c| !c.is_control() && c != '\u{fffd}');
+                if printable {
+                    name
+                } else {
+                    format!("Bit {}", bit)
+                }
+            })
+            .collect()
+    }
+}
+
+impl StreamingParseable for Speeduino {
+    fn parse_stream<R: Read + Seek>(
+        &self,
+        reader: R,
+        sink: &mut dyn RecordSink,
+        progress: &mut dyn FnMut(StreamProgress),
+    ) -> Result<(), ParseError> {
+        Self::read_stream(reader, sink, progress, false)
+    }
+}
+
+impl Speeduino {
+    /// Parse an MLG stream. With `recover` set, data blocks that are cut
+    /// short or fail their checksum are skipped instead of ending the log,
+    /// and parsing resumes at the next valid block.
+    fn read_stream<R: Read + Seek>(
+        reader: R,
+        sink: &mut dyn RecordSink,
+        progress: &mut dyn FnMut(StreamProgress),
+        recover: bool,
+    ) -> Result<(), ParseError> {
+        let mut reader = BufReader::new(reader);
+        let total_bytes = stream_len(&mut reader)?;
+
+        // Read file format (6 bytes: "MLVLG" + 1 extra byte) and format version
+        // (int16, big-endian like DataView default)
+        let mut header = vec![0u8; 8];
+        if !Self::read_block(&mut reader, &mut header)? || &header[0..5] != b"MLVLG" {
+            return Err(ParseError::BadMagic { format: "MLG" });
+        }
+        let format_version = i16::from_be_bytes([header[6], header[7]]);
+        let is_v2 = format_version == 2;
+
+        // Timestamp (int32), info_data_start (int16 for v1, int32 for v2),
+        // data_begin_index (int32), record_length (int16), num_logger_fields (int16)
+        let fixed_length = if is_v2 { 24 } else { 22 };
+        header.resize(fixed_length, 0);
+        if !Self::read_block(&mut reader, &mut header[8..])? {
+            return Err(ParseError::truncated(8, "MLG file header"));
+        }
+        let mut offset = 12;
+
+        let info_data_start = if is_v2 {
+            u32::from_be_bytes([
+                header[offset],
+                header[offset + 1],
+                header[offset + 2],
+                header[offset + 3],
+            ]) as usize
         } else {
-            1000 // Fallback estimate
+            u16::from_be_bytes([header[offset], header[offset + 1]]) as usize
+        };
+        offset += if is_v2 { 4 } else { 2 };
+
+        let data_begin_offset = offset;
+        let data_begin_index = u32::from_be_bytes([
+            header[offset],
+            header[offset + 1],
+            header[offset + 2],
+            header[offset + 3],
+        ]) as usize;
+        offset += 4;
+
+        // Skip record_length
+        offset += 2;
+
+        let num_fields_offset = offset;
+        let num_fields = u16::from_be_bytes([header[offset], header[offset + 1]]) as usize;
+        offset += 2;
+
+        tracing::debug!(
+            "MLG format version {}, {} fields, data begins at {}",
+            format_version,
+            num_fields,
+            data_begin_index
+        );
+
+        // Validate bounds before parsing
+        if num_fields > 1000 {
+            return Err(ParseError::corrupt(
+                num_fields_offset,
+                format!("Unreasonable field count: {}", num_fields),
+            ));
+        }
+        if data_begin_index as u64 > total_bytes {
+            return Err(ParseError::corrupt(
+                data_begin_offset,
+                format!(
+                    "data_begin_index {} exceeds file size {}",
+                    data_begin_index, total_bytes
+                ),
+            ));
+        }
+
+        // Field definitions and info data sit between the fixed header and the data blocks
+        if data_begin_index > fixed_length {
+            header.resize(data_begin_index, 0);
+            reader.read_exact(&mut header[fixed_length..])?;
+        }
+        let (meta, channels) =
+            Self::parse_fields(&header, offset, num_fields, is_v2, info_data_start)?;
+
+        let field_length = if is_v2 { 89 } else { 55 };
+        let field_types = channels
+            .iter()
+            .enumerate()
+            .map(|(i, channel)| {
+                FieldType::from_u8(channel.field_type).ok_or_else(|| {
+                    ParseError::corrupt(
+                        offset + i * field_length,
+                        format!("Unknown field type: {}", channel.field_type),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let record_bytes: usize = field_types.iter().map(FieldType::byte_size).sum();
+
+        // Each data block is 4 header bytes, the field values and a CRC byte
+        let estimated_records =
+            (total_bytes as usize).saturating_sub(data_begin_index) / (record_bytes + 5);
+        let stream_header = StreamHeader {
+            meta: Meta::Speeduino(meta),
+            channels: channels.iter().cloned().map(Channel::Speeduino).collect(),
+            estimated_records,
+            start: None,
         };
-        let mut times: Vec<f64> = Vec::with_capacity(estimated_records);
-        let mut data_records: Vec<Vec<Value>> = Vec::with_capacity(estimated_records);
+        let mut output = ChunkedOutput::begin(sink, progress, stream_header, total_bytes)?;
+
+        // Parse data blocks
+        reader.seek(SeekFrom::Start(data_begin_index as u64))?;
+        let mut position = data_begin_index as u64;
+        let mut report = RecoveryReport::default();
+        let data_block_bytes = BLOCK_HEADER_BYTES + record_bytes + 1;
+        let mut block_header = [0u8; BLOCK_HEADER_BYTES];
+        // Field values plus the trailing CRC byte
+        let mut block = vec![0u8; record_bytes + 1];
+        let mut marker = [0u8; MARKER_BYTES];
+        let mut record = Vec::with_capacity(channels.len());
 
         // Track timestamp wraparound (u16 wraps at 65535ms = 65.535 seconds)
         let mut prev_raw_timestamp: u16 = 0;
@@ -286,20 +495,64 @@ impl Speeduino {
         // (actual wraparounds show ~58.7s drop when going from ~65s to ~6s)
         const WRAP_THRESHOLD: u16 = 30000;
 
-        while offset + 4 <= data.len() {
-            // Read block type (1 byte)
-            let block_type = data[offset];
-            offset += 1;
+        // Block type (1 byte), counter (1 byte), timestamp (uint16, big-endian)
+        while Self::read_block(&mut reader, &mut block_header)? {
+            let block_type = block_header[0];
 
-            // Skip counter (1 byte)
-            offset += 1;
+            let damaged = match block_type {
+                // Data record - a truncated final record is dropped
+                0 => {
+                    if !Self::read_block(&mut reader, &mut block)? {
+                        tracing::debug!("MLG log ends inside a data record");
+                        break;
+                    }
+                    recover && !Self::checksum_matches(&block)
+                }
+                // Marker record - 50-byte message, kept as an annotation
+                1 => {
+                    if !Self::read_block(&mut reader, &mut marker)? {
+                        tracing::debug!("MLG log ends inside a marker block");
+                        break;
+                    }
+                    false
+                }
+                _ => {
+                    tracing::debug!(
+                        "Unknown MLG block type {} at offset {}",
+                        block_type,
+                        position
+                    );
+                    if !recover {
+                        break;
+                    }
+                    true
+                }
+            };
 
-            // Read timestamp (uint16, big-endian)
-            if offset + 2 > data.len() {
-                break;
+            if damaged {
+                let resume = Self::resync(&mut reader, position + 1, record_bytes)?;
+                let next = resume.unwrap_or(total_bytes);
+                report.skip(
+                    position,
+                    ((next - position) as usize).div_ceil(data_block_bytes),
+                );
+                tracing::debug!(
+                    "Damaged MLG block at offset {}, resuming at {}",
+                    position,
+                    next
+                );
+                position = next;
+                match resume {
+                    Some(next) => {
+                        report.resyncs += 1;
+                        reader.seek(SeekFrom::Start(next))?;
+                        continue;
+                    }
+                    None => break,
+                }
             }
-            let raw_timestamp = u16::from_be_bytes([data[offset], data[offset + 1]]);
-            offset += 2;
+
+            let raw_timestamp = u16::from_be_bytes([block_header[2], block_header[3]]);
 
             // Detect wraparound: if current timestamp is much smaller than previous, it wrapped
             if raw_timestamp < prev_raw_timestamp
@@ -312,239 +565,176 @@ impl Speeduino {
             // Calculate actual timestamp with wraparound compensation
             let timestamp = (raw_timestamp as f64 / 1000.0) + (wrap_count as f64 * 65.536);
 
-            if block_type == 0 {
-                // Data record - calculate required bytes for all channels
-                let mut required_bytes = 0;
-                for channel in &channels {
-                    if let Some(field_type) = FieldType::from_u8(channel.field_type) {
-                        required_bytes += field_type.byte_size();
-                    }
-                }
-                required_bytes += 1; // Add CRC byte
-
-                // Check if we have enough data for this record BEFORE adding timestamp
-                if offset + required_bytes > data.len() {
-                    eprintln!(
-                        "DEBUG: Not enough data for complete record at offset {} (need {}, have {})",
-                        offset,
-                        required_bytes,
-                        data.len() - offset
-                    );
-                    break;
-                }
-
-                // Now it's safe to add the timestamp and read the record
-                let mut record = Vec::new();
-
-                for channel in &channels {
-                    if let Some(field_type) = FieldType::from_u8(channel.field_type) {
-                        let value = match field_type {
-                            FieldType::U08 => {
-                                let v = data[offset] as f64;
-                                offset += 1;
-                                // Formula: (value + transform) * scale
-                                Value::Float((v + channel.transform as f64) * channel.scale as f64)
-                            }
-                            FieldType::S08 => {
-                                let v = data[offset] as i8 as f64;
-                                offset += 1;
-                                Value::Float((v + channel.transform as f64) * channel.scale as f64)
-                            }
-                            FieldType::U16 => {
-                                let v = u16::from_be_bytes([data[offset], data[offset + 1]]) as f64;
-                                offset += 2;
-                                Value::Float((v + channel.transform as f64) * channel.scale as f64)
-                            }
-                            FieldType::S16 => {
-                                let v = i16::from_be_bytes([data[offset], data[offset + 1]]) as f64;
-                                offset += 2;
-                                Value::Float((v + channel.transform as f64) * channel.scale as f64)
-                            }
-                            FieldType::U32 => {
-                                let v = u32::from_be_bytes([
-                                    data[offset],
-                                    data[offset + 1],
-                                    data[offset + 2],
-                                    data[offset + 3],
-                                ]) as f64;
-                                offset += 4;
-                                Value::Float((v + channel.transform as f64) * channel.scale as f64)
-                            }
-                            FieldType::S32 => {
-                                let v = i32::from_be_bytes([
-                                    data[offset],
-                                    data[offset + 1],
-                                    data[offset + 2],
-                                    data[offset + 3],
-                                ]) as f64;
-                                offset += 4;
-                                Value::Float((v + channel.transform as f64) * channel.scale as f64)
-                            }
-                            FieldType::F32 => {
-                                let v = f32::from_be_bytes([
-                                    data[offset],
-                                    data[offset + 1],
-                                    data[offset + 2],
-                                    data[offset + 3],
-                                ]) as f64;
-                                offset += 4;
-                                Value::Float((v + channel.transform as f64) * channel.scale as f64)
-                            }
-                            FieldType::S64 => {
-                                let v = i64::from_be_bytes([
-                                    data[offset],
-                                    data[offset + 1],
-                                    data[offset + 2],
-                                    data[offset + 3],
-                                    data[offset + 4],
-                                    data[offset + 5],
-                                    data[offset + 6],
-                                    data[offset + 7],
-                                ]) as f64;
-                                offset += 8;
-                                Value::Float((v + channel.transform as f64) * channel.scale as f64)
-                            }
-                            FieldType::U08Bitfield
-                            | FieldType::U16Bitfield
-                            | FieldType::U32Bitfield => {
-                                offset += field_type.byte_size();
-                                Value::Float(0.0) // Bitfields not fully supported yet
-                            }
-                        };
-                        record.push(value);
-                    } else {
-                        return Err(format!("Unknown field type: {}", channel.field_type).into());
-                    }
-                }
-
-                // Only add the timestamp and record together to ensure they stay in sync
-                times.push(timestamp);
-                data_records.push(record);
+            if block_type == 1 {
+                position += (BLOCK_HEADER_BYTES + MARKER_BYTES) as u64;
+                output.annotate(Annotation {
+                    time: timestamp,
+                    label: Self::marker_label(&marker),
+                });
+                continue;
+            }
+            position += data_block_bytes as u64;
+
+            record.clear();
+            let mut field_offset = 0;
+            for (channel, field_type) in channels.iter().zip(&field_types) {
+                let raw = field_type.read(&block[field_offset..]);
+                field_offset += field_type.byte_size();
+                // Bitfields keep their raw flags; scalars use (value + transform) * scale
+                record.push(if field_type.is_bitfield() {
+                    raw
+                } else {
+                    (raw + channel.transform as f64) * channel.scale as f64
+                });
+            }
+            output.push(timestamp, &record)?;
 
-                // Skip CRC (1 byte)
-                offset += 1;
-            } else if block_type == 1 {
-                // Marker record - skip marker message (50 bytes)
-                if offset + 50 > data.len() {
-                    eprintln!(
-                        "DEBUG: Not enough data for marker block at offset {} (need 50, have {})",
-                        offset,
-                        data.len() - offset
-                    );
-                    break;
-                }
-                offset += 50;
-            } else {
-                eprintln!(
-                    "DEBUG: Unknown block type {} at offset {}",
-                    block_type,
-                    offset - 3
-                );
-                break; // Unknown block type
+            if output.records().is_multiple_of(CHUNK_RECORDS) {
+                output.report(position);
             }
         }
 
-        eprintln!("DEBUG: Parsed {} data records", data_records.len());
-        eprintln!("DEBUG: Times vector length: {}", times.len());
-
-        // Debug: Check if timestamps are monotonically increasing
-        if times.len() > 1 {
-            eprintln!("DEBUG: Timestamp analysis:");
-            let mut non_monotonic_count = 0;
-            let mut prev_time: f64 = 0.0;
-            for (i, &t) in times.iter().enumerate() {
-                if i > 0 && t < prev_time {
-                    non_monotonic_count += 1;
-                    if non_monotonic_count <= 5 {
-                        eprintln!(
-                            "  Non-monotonic at index {}: {} -> {} (delta: {:.3})",
-                            i,
-                            prev_time,
-                            t,
-                            t - prev_time
-                        );
-                    }
-                }
-                prev_time = t;
-            }
-            if non_monotonic_count > 0 {
-                eprintln!("  Total non-monotonic jumps: {}", non_monotonic_count);
-            } else {
-                eprintln!("  All timestamps are monotonically increasing");
+        if recover {
+            // Whatever is left after the last whole block was cut off mid-write
+            if position < total_bytes {
+                report.skip(position, 1);
             }
+            tracing::info!(
+                "Recovered MLG log: {} records kept, {} skipped, {} resyncs",
+                output.records(),
+                report.skipped_records,
+                report.resyncs
+            );
+            output.recovered(report);
+        }
+
+        tracing::info!(
+            "Parsed MLG log: {} channels, {} data points",
+            channels.len(),
+            output.records()
+        );
+        output.finish()
+    }
+
+    /// Text of a marker block's NUL-padded message
+    fn marker_label(message: &[u8]) -> String {
+        let end = message
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(message.len());
+        String::from_utf8_lossy(&message[..end]).trim().to_string()
+    }
+
+    /// Whether a data block's CRC byte matches its field bytes. The CRC is
+    /// the low byte of the sum of the field bytes.
+    fn checksum_matches(block: &[u8]) -> bool {
+        let Some((&crc, fields)) = block.split_last() else {
+            return false;
+        };
+        fields.iter().fold(0u8, |sum, &b| sum.wrapping_add(b)) == crc
+    }
 
-            // Print first 10 and last 5 timestamps
-            eprintln!("DEBUG: First 10 timestamps:");
-            for (i, t) in times.iter().take(10).enumerate() {
-                eprintln!("  [{}] {}", i, t);
+    /// Length of the well-formed block at the start of `bytes`, if one
+    /// starts there. Data blocks must also pass their checksum.
+    fn valid_block(bytes: &[u8], record_bytes: usize) -> Option<usize> {
+        match *bytes.first()? {
+            0 => {
+                let len = BLOCK_HEADER_BYTES + record_bytes + 1;
+                let block = bytes.get(BLOCK_HEADER_BYTES..len)?;
+                Self::checksum_matches(block).then_some(len)
             }
-            if times.len() > 15 {
-                eprintln!("DEBUG: Last 5 timestamps:");
-                for (i, t) in times.iter().skip(times.len() - 5).enumerate() {
-                    eprintln!("  [{}] {}", times.len() - 5 + i, t);
-                }
+            1 => {
+                let len = BLOCK_HEADER_BYTES + MARKER_BYTES;
+                (bytes.len() >= len).then_some(len)
             }
+            _ => None,
         }
+    }
 
-        // Debug: Show first few records to verify data structure
-        if !data_records.is_empty() {
-            eprintln!("DEBUG: First record (time={}):", times[0]);
-            for (idx, val) in data_records[0].iter().enumerate() {
-                if idx < channels.len() {
-                    eprintln!("  [{}] {} = {:.3}", idx, channels[idx].name, val.as_f64());
-                }
+    /// Find the first offset at or after `start` where a run of valid
+    /// blocks begins, so a stray 0 or 1 byte in damaged data isn't taken
+    /// for a block header. See [`Speeduino::confirms_resync`].
+    fn resync<R: Read + Seek>(
+        reader: &mut R,
+        start: u64,
+        record_bytes: usize,
+    ) -> std::io::Result<Option<u64>> {
+        const WINDOW: usize = 64 * 1024;
+        // Room for a whole run of blocks after the last candidate in a window
+        let overlap = RESYNC_MAX_BLOCKS * (BLOCK_HEADER_BYTES + record_bytes.max(MARKER_BYTES) + 1);
+        let mut buf = Vec::with_capacity(WINDOW + overlap);
+        let mut window_start = start;
+
+        loop {
+            reader.seek(SeekFrom::Start(window_start))?;
+            buf.clear();
+            reader
+                .by_ref()
+                .take((WINDOW + overlap) as u64)
+                .read_to_end(&mut buf)?;
+            let at_end = buf.len() < WINDOW + overlap;
+            let candidates = if at_end { buf.len() } else { WINDOW };
+
+            if let Some(i) =
+                (0..candidates).find(|&i| Self::confirms_resync(&buf[i..], record_bytes, at_end))
+            {
+                return Ok(Some(window_start + i as u64));
             }
-            if data_records.len() > 1 {
-                eprintln!("DEBUG: Second record (time={}):", times[1]);
-                for (idx, val) in data_records[1].iter().enumerate() {
-                    if idx < channels.len() {
-                        eprintln!("  [{}] {} = {:.3}", idx, channels[idx].name, val.as_f64());
-                    }
-                }
+            if at_end {
+                return Ok(None);
             }
+            window_start += WINDOW as u64;
         }
+    }
 
-        // Validate that times and data match
-        if times.len() != data_records.len() {
-            return Err(format!(
-                "Data integrity error: {} timestamps but {} data records",
-                times.len(),
-                data_records.len()
-            )
-            .into());
-        }
+    /// Whether `bytes` starts with [`RESYNC_DATA_BLOCKS`] valid data blocks
+    /// with consecutive counters, optionally separated by marker blocks.
+    /// Reaching the end of the file part way through also counts once the
+    /// first block is whole.
+    fn confirms_resync(bytes: &[u8], record_bytes: usize, at_end: bool) -> bool {
+        let mut pos = 0;
+        let mut data_blocks = 0;
+        let mut prev_counter: Option<u8> = None;
+
+        for _ in 0..RESYNC_MAX_BLOCKS {
+            let rest = &bytes[pos..];
+            let Some(len) = Self::valid_block(rest, record_bytes) else {
+                // A block cut short by the end of the file can't be checked
+                let cut_short = match rest.first() {
+                    Some(0) => rest.len() < BLOCK_HEADER_BYTES + record_bytes + 1,
+                    Some(1) => rest.len() < BLOCK_HEADER_BYTES + MARKER_BYTES,
+                    Some(_) => false,
+                    None => true,
+                };
+                return pos > 0 && at_end && cut_short;
+            };
 
-        // Validate that all data records have the correct number of values
-        let channel_count = channels.len();
-        for (i, record) in data_records.iter().enumerate() {
-            if record.len() != channel_count {
-                return Err(format!(
-                    "Data integrity error: record {} has {} values but {} channels expected",
-                    i,
-                    record.len(),
-                    channel_count
-                )
-                .into());
+            if rest[0] == 0 {
+                if prev_counter.is_some_and(|counter| rest[1] != counter.wrapping_add(1)) {
+                    return false;
+                }
+                prev_counter = Some(rest[1]);
+                data_blocks += 1;
+                if data_blocks == RESYNC_DATA_BLOCKS {
+                    return true;
+                }
+            } else {
+                // Marker blocks may or may not advance the counter
+                prev_counter = None;
             }
+            pos += len;
         }
-
-        Ok(Log {
-            meta: super::types::Meta::Speeduino(meta),
-            channels: channels
-                .into_iter()
-                .map(super::types::Channel::Speeduino)
-                .collect(),
-            times,
-            data: data_records,
-        })
+        false
     }
 }
 
 impl Parseable for Speeduino {
-    fn parse(&self, _data: &str) -> Result<Log, Box<dyn Error>> {
+    fn parse(&self, _data: &str) -> Result<Log, ParseError> {
         // This method is for text-based parsing
         // Speeduino/rusEFI uses binary MLG format, so this will return an error
-        Err("Speeduino/rusEFI MLG files are binary format. Use parse_binary() instead.".into())
+        Err(ParseError::invalid(
+            "Speeduino/rusEFI MLG files are binary format. Use parse_binary() instead.",
+        ))
     }
 }
 
@@ -641,6 +831,7 @@ mod tests {
             scale: 1.0,
             transform: 0.0,
             field_type: 2,
+            bit_names: Vec::new(),
         };
         assert_eq!(channel.unit(), "rpm");
     }
@@ -688,6 +879,80 @@ mod tests {
             .contains("Unreasonable field count"));
     }
 
+    /// Fixed-width, null-padded MLG string field
+    fn padded(text: &str, len: usize) -> Vec<u8> {
+        let mut bytes = text.as_bytes().to_vec();
+        bytes.resize(len, 0);
+        bytes
+    }
+
+    #[test]
+    fn test_parse_binary_bitfield() {
+        let info = b"running\0cranking\0ase\0";
+        let info_start = 22 + 2 * 55;
+        let data_begin = info_start + info.len();
+
+        let mut data = Vec::new();
+        data.extend_from_slice(b"MLVLG\x00");
+        data.extend_from_slice(&1_i16.to_be_bytes()); // Format version
+        data.extend_from_slice(&0_i32.to_be_bytes()); // Timestamp
+        data.extend_from_slice(&(info_start as u16).to_be_bytes());
+        data.extend_from_slice(&(data_begin as u32).to_be_bytes());
+        data.extend_from_slice(&3_u16.to_be_bytes()); // record_length
+        data.extend_from_slice(&2_u16.to_be_bytes()); // num_fields
+
+        // Scalar U16 field
+        data.push(2);
+        data.extend(padded("RPM", 34));
+        data.extend(padded("rpm", 10));
+        data.push(0); // display_style
+        data.extend_from_slice(&1.0_f32.to_be_bytes()); // scale
+        data.extend_from_slice(&0.0_f32.to_be_bytes()); // transform
+        data.push(0); // digits
+
+        // U08 bitfield with three named bits
+        data.push(10);
+        data.extend(padded("Status", 34));
+        data.extend(padded("", 10));
+        data.push(0); // display_style
+        data.extend_from_slice(&0_i32.to_be_bytes()); // bit names index
+        data.push(3); // bits
+        data.extend_from_slice(&[0; 4]);
+
+        data.extend_from_slice(info);
+        for (timestamp, rpm, status) in [(0_u16, 900_u16, 0b010_u8), (100, 1500, 0b101)] {
+            data.extend_from_slice(&[0, 0]); // block type, counter
+            data.extend_from_slice(&timestamp.to_be_bytes());
+            data.extend_from_slice(&rpm.to_be_bytes());
+            data.push(status);
+            data.push(0); // CRC
+        }
+
+        let log = Speeduino::parse_binary(&data).unwrap();
+
+        assert_eq!(log.record_count(), 2);
+        assert_eq!(log.channel_data(1), &[2.0, 5.0]);
+        assert_eq!(
+            log.channels[1].value_label(5.0).as_deref(),
+            Some("running, ase")
+        );
+
+        // Every bit that is set somewhere becomes its own channel
+        let names: Vec<String> = log.channels.iter().map(|c| c.name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "RPM",
+                "Status",
+                "Status [running]",
+                "Status [cranking]",
+                "Status [ase]"
+            ]
+        );
+        assert_eq!(log.channel_data(3), &[1.0, 0.0]);
+        assert_eq!(log.channel_data(4), &[0.0, 1.0]);
+    }
+
     // ============================================
     // Text Parser Error Test
     // ============================================
@@ -726,24 +991,25 @@ mod tests {
         // Verify basic structure
         assert!(!log.channels.is_empty(), "Should have channels");
         assert!(!log.times.is_empty(), "Should have timestamps");
-        assert!(!log.data.is_empty(), "Should have data records");
+        assert!(!log.is_empty(), "Should have data records");
 
         // Verify data integrity
         assert_eq!(
             log.times.len(),
-            log.data.len(),
+            log.record_count(),
             "Times and data should have same length"
         );
 
         // Verify all records have correct channel count
         let channel_count = log.channels.len();
-        for (i, record) in log.data.iter().enumerate() {
+        assert_eq!(log.columns().len(), channel_count);
+        for (i, column) in log.columns().iter().enumerate() {
             assert_eq!(
-                record.len(),
-                channel_count,
-                "Record {} should have {} values",
+                column.len(),
+                log.times.len(),
+                "Channel {} should have {} values",
                 i,
-                channel_count
+                log.times.len()
             );
         }
 
@@ -757,7 +1023,7 @@ mod tests {
 
         // Print some debug info
         eprintln!("Parsed {} channels", log.channels.len());
-        eprintln!("Parsed {} data records", log.data.len());
+        eprintln!("Parsed {} data records", log.record_count());
         if !log.times.is_empty() {
             eprintln!(
                 "Time range: {:.3}s to {:.3}s",
@@ -789,12 +1055,12 @@ mod tests {
         // Verify basic structure
         assert!(!log.channels.is_empty(), "Should have channels");
         assert!(!log.times.is_empty(), "Should have timestamps");
-        assert!(!log.data.is_empty(), "Should have data records");
+        assert!(!log.is_empty(), "Should have data records");
 
         // Verify data integrity
         assert_eq!(
             log.times.len(),
-            log.data.len(),
+            log.record_count(),
             "Times and data should have same length"
         );
 
@@ -811,6 +1077,6 @@ mod tests {
         }
 
         eprintln!("Parsed {} channels from rusEFI log", log.channels.len());
-        eprintln!("Parsed {} data records", log.data.len());
+        eprintln!("Parsed {} data records", log.record_count());
     }
 }
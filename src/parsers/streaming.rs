@@ -0,0 +1,417 @@
+//! Streaming parsers for logs too large to load in one piece.
+//!
+//! [`Parseable`](super::types::Parseable) takes the whole file as a string
+//! and the binary parsers take it as a byte slice, so a multi-gigabyte
+//! endurance log has to be held in memory next to the parsed columns.
+//! A [`StreamingParseable`] instead reads from any `Read + Seek` source and
+//! hands decoded records to a [`RecordSink`] a chunk at a time, reporting
+//! progress as it goes.
+//!
+//! [`LogBuilder`] is the sink that assembles a [`Log`]; it can keep only
+//! every Nth record to decimate very long logs while they load.
+
+use chrono::NaiveDateTime;
+use std::io::{self, Read, Seek, SeekFrom};
+
+use super::error::ParseError;
+use super::types::{Annotation, Channel, ColumnBuilder, Log, Meta, RecoveryReport, MISSING};
+
+/// Number of records collected before a chunk is handed to the sink
+pub const CHUNK_RECORDS: usize = 4096;
+
+/// Channel layout and metadata, known before the first record is read
+#[derive(Clone, Debug, Default)]
+pub struct StreamHeader {
+    pub meta: Meta,
+    pub channels: Vec<Channel>,
+    /// Rough number of records in the stream, 0 if unknown
+    pub estimated_records: usize,
+    /// Wall-clock time of the first record, when the parser knows it better
+    /// than the metadata's start text does
+    pub start: Option<NaiveDateTime>,
+}
+
+/// A run of consecutive records, stored row by row
+#[derive(Clone, Debug, Default)]
+pub struct RecordChunk {
+    /// Record times in seconds
+    pub times: Vec<f64>,
+    /// `values[record * channel_count + channel]`
+    values: Vec<f64>,
+    channel_count: usize,
+}
+
+impl RecordChunk {
+    /// Create an empty chunk for `channel_count` channels
+    pub fn new(channel_count: usize) -> Self {
+        Self {
+            times: Vec::new(),
+            values: Vec::new(),
+            channel_count,
+        }
+    }
+
+    /// Append one record. Missing trailing values are stored as
+    /// [`MISSING`] and values beyond the channel count are ignored.
+    pub fn push(&mut self, time: f64, values: &[f64]) {
+        self.times.push(time);
+        self.values
+            .extend((0..self.channel_count).map(|idx| values.get(idx).copied().unwrap_or(MISSING)));
+    }
+
+    /// Values of record `index`, one per channel
+    pub fn record(&self, index: usize) -> &[f64] {
+        let start = index * self.channel_count;
+        &self.values[start..start + self.channel_count]
+    }
+
+    /// Number of records in the chunk
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    /// Whether the chunk holds no records
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    /// Remove all records, keeping the allocation
+    pub fn clear(&mut self) {
+        self.times.clear();
+        self.values.clear();
+    }
+}
+
+/// How far a streaming parse has got
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StreamProgress {
+    /// Bytes consumed from the source
+    pub bytes_read: u64,
+    /// Total size of the source in bytes
+    pub total_bytes: u64,
+    /// Records decoded so far
+    pub records: usize,
+}
+
+impl StreamProgress {
+    /// Fraction of the source consumed, from 0.0 to 1.0
+    pub fn fraction(&self) -> f32 {
+        if self.total_bytes == 0 {
+            1.0
+        } else {
+            (self.bytes_read as f64 / self.total_bytes as f64).min(1.0) as f32
+        }
+    }
+}
+
+/// Receives the output of a streaming parser
+pub trait RecordSink {
+    /// Called once with the channel layout, before any records
+    fn begin(&mut self, header: StreamHeader) -> Result<(), ParseError>;
+
+    /// Called with each chunk of records, in file order
+    fn push_chunk(&mut self, chunk: &RecordChunk) -> Result<(), ParseError>;
+
+    /// Called after the last chunk of a recovering parse with what was skipped
+    fn recovered(&mut self, _report: RecoveryReport) {}
+
+    /// Called with each marker found in the log, in file order
+    fn annotate(&mut self, _annotation: Annotation) {}
+}
+
+/// Trait for parsers that can read a log incrementally
+pub trait StreamingParseable {
+    /// Parse `reader` from its start, feeding records to `sink` in chunks
+    /// and calling `progress` after each one
+    fn parse_stream<R: Read + Seek>(
+        &self,
+        reader: R,
+        sink: &mut dyn RecordSink,
+        progress: &mut dyn FnMut(StreamProgress),
+    ) -> Result<(), ParseError>;
+}
+
+/// Size of a seekable source in bytes, leaving its position unchanged
+pub fn stream_len<S: Seek>(source: &mut S) -> io::Result<u64> {
+    let position = source.stream_position()?;
+    let len = source.seek(SeekFrom::End(0))?;
+    if position != len {
+        source.seek(SeekFrom::Start(position))?;
+    }
+    Ok(len)
+}
+
+/// Batches records into chunks for a sink and tracks progress.
+/// Streaming parsers use this so each doesn't manage its own chunk buffer.
+pub struct ChunkedOutput<'a> {
+    sink: &'a mut dyn RecordSink,
+    progress: &'a mut dyn FnMut(StreamProgress),
+    chunk: RecordChunk,
+    state: StreamProgress,
+}
+
+impl<'a> ChunkedOutput<'a> {
+    /// Send the header to `sink` and start collecting records
+    pub fn begin(
+        sink: &'a mut dyn RecordSink,
+        progress: &'a mut dyn FnMut(StreamProgress),
+        header: StreamHeader,
+        total_bytes: u64,
+    ) -> Result<Self, ParseError> {
+        let chunk = RecordChunk::new(header.channels.len());
+        sink.begin(header)?;
+        Ok(Self {
+            sink,
+            progress,
+            chunk,
+            state: StreamProgress {
+                total_bytes,
+                ..StreamProgress::default()
+            },
+        })
+    }
+
+    /// Add a record, passing a full chunk on to the sink
+    pub fn push(&mut self, time: f64, values: &[f64]) -> Result<(), ParseError> {
+        self.chunk.push(time, values);
+        self.state.records += 1;
+        if self.chunk.len() >= CHUNK_RECORDS {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Number of records pushed so far
+    pub fn records(&self) -> usize {
+        self.state.records
+    }
+
+    /// Report that `bytes_read` bytes of the source have been consumed
+    pub fn report(&mut self, bytes_read: u64) {
+        self.state.bytes_read = bytes_read;
+        (self.progress)(self.state);
+    }
+
+    /// Pass a recovering parse's report on to the sink
+    pub fn recovered(&mut self, report: RecoveryReport) {
+        self.sink.recovered(report);
+    }
+
+    /// Pass a marker straight to the sink
+    pub fn annotate(&mut self, annotation: Annotation) {
+        self.sink.annotate(annotation);
+    }
+
+    /// Send any remaining records and report the parse as complete
+    pub fn finish(mut self) -> Result<(), ParseError> {
+        self.flush()?;
+        let total = self.state.total_bytes;
+        self.report(total);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), ParseError> {
+        if !self.chunk.is_empty() {
+            self.sink.push_chunk(&self.chunk)?;
+            self.chunk.clear();
+        }
+        Ok(())
+    }
+}
+
+/// Sink that assembles the streamed records into a [`Log`]
+#[derive(Debug)]
+pub struct LogBuilder {
+    meta: Meta,
+    channels: Vec<Channel>,
+    times: Vec<f64>,
+    data: ColumnBuilder,
+    /// Keep one record in every `decimation`
+    decimation: usize,
+    /// Records received, including ones dropped by decimation
+    received: usize,
+    recovery: Option<RecoveryReport>,
+    /// Markers are kept whatever the decimation
+    annotations: Vec<Annotation>,
+    start: Option<NaiveDateTime>,
+}
+
+impl Default for LogBuilder {
+    fn default() -> Self {
+        Self {
+            meta: Meta::Empty,
+            channels: Vec::new(),
+            times: Vec::new(),
+            data: ColumnBuilder::new(0),
+            decimation: 1,
+            received: 0,
+            recovery: None,
+            annotations: Vec::new(),
+            start: None,
+        }
+    }
+}
+
+impl LogBuilder {
+    /// Create a builder that keeps every record
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep only the first of every `every` records (1 keeps everything)
+    pub fn with_decimation(mut self, every: usize) -> Self {
+        self.decimation = every.max(1);
+        self
+    }
+
+    /// Number of records kept so far
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    /// Whether no records have been kept
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    /// Finish building. Bitfield channels are split into per-bit channels,
+    /// see [`Log::expand_bitfields`].
+    pub fn finish(self) -> Log {
+        let mut log = Log::new(
+            self.meta,
+            self.channels,
+            self.times,
+            self.data.into_columns(),
+        );
+        log.recovery = self.recovery;
+        log.annotations = self.annotations;
+        if self.start.is_some() {
+            log.start = self.start;
+        }
+        log.expand_bitfields();
+        log
+    }
+}
+
+impl RecordSink for LogBuilder {
+    fn begin(&mut self, header: StreamHeader) -> Result<(), ParseError> {
+        let capacity = header.estimated_records / self.decimation;
+        self.times = Vec::with_capacity(capacity);
+        self.data = ColumnBuilder::with_capacity(header.channels.len(), capacity);
+        self.meta = header.meta;
+        self.channels = header.channels;
+        self.start = header.start;
+        Ok(())
+    }
+
+    fn push_chunk(&mut self, chunk: &RecordChunk) -> Result<(), ParseError> {
+        for (index, &time) in chunk.times.iter().enumerate() {
+            if self.received.is_multiple_of(self.decimation) {
+                self.times.push(time);
+                self.data.push_record(chunk.record(index));
+            }
+            self.received += 1;
+        }
+        Ok(())
+    }
+
+    fn recovered(&mut self, report: RecoveryReport) {
+        self.recovery = Some(report);
+    }
+
+    fn annotate(&mut self, annotation: Annotation) {
+        self.annotations.push(annotation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::ComputedChannelInfo;
+
+    fn channel(name: &str) -> Channel {
+        Channel::Computed(ComputedChannelInfo {
+            name: name.to_string(),
+            formula: String::new(),
+            unit: String::new(),
+        })
+    }
+
+    fn header() -> StreamHeader {
+        StreamHeader {
+            channels: vec![channel("A"), channel("B")],
+            ..StreamHeader::default()
+        }
+    }
+
+    #[test]
+    fn test_record_chunk_pads_missing() {
+        let mut chunk = RecordChunk::new(2);
+        chunk.push(0.0, &[1.0, 2.0, 3.0]);
+        chunk.push(0.1, &[4.0]);
+
+        assert_eq!(chunk.len(), 2);
+        assert_eq!(chunk.record(0), &[1.0, 2.0]);
+        assert_eq!(chunk.record(1)[0], 4.0);
+        assert!(chunk.record(1)[1].is_nan());
+    }
+
+    #[test]
+    fn test_chunked_output_to_log_builder() {
+        let mut builder = LogBuilder::new();
+        let mut reports = Vec::new();
+        let mut progress = |p: StreamProgress| reports.push(p);
+
+        let mut output = ChunkedOutput::begin(&mut builder, &mut progress, header(), 100).unwrap();
+        for i in 0..CHUNK_RECORDS + 10 {
+            output.push(i as f64, &[i as f64, 0.0]).unwrap();
+        }
+        output.report(50);
+        output.finish().unwrap();
+
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].fraction(), 0.5);
+        assert_eq!(reports[1].records, CHUNK_RECORDS + 10);
+        assert_eq!(reports[1].fraction(), 1.0);
+
+        let log = builder.finish();
+        assert_eq!(log.channels.len(), 2);
+        assert_eq!(log.record_count(), CHUNK_RECORDS + 10);
+        assert_eq!(log.channel_data(0)[CHUNK_RECORDS], CHUNK_RECORDS as f64);
+    }
+
+    #[test]
+    fn test_log_builder_decimation() {
+        let mut builder = LogBuilder::new().with_decimation(3);
+        builder.begin(header()).unwrap();
+
+        // Decimation carries across chunk boundaries
+        for start in [0, 4] {
+            let mut chunk = RecordChunk::new(2);
+            for i in start..start + 4 {
+                chunk.push(i as f64, &[i as f64, 0.0]);
+            }
+            builder.push_chunk(&chunk).unwrap();
+        }
+
+        // Markers survive decimation
+        builder.annotate(Annotation {
+            time: 5.0,
+            label: "Lap".to_string(),
+        });
+
+        let log = builder.finish();
+        assert_eq!(log.times, vec![0.0, 3.0, 6.0]);
+        assert_eq!(log.channel_data(0), &[0.0, 3.0, 6.0]);
+        assert_eq!(log.annotations.len(), 1);
+        assert_eq!(log.annotations[0].time, 5.0);
+    }
+
+    #[test]
+    fn test_stream_len_keeps_position() {
+        let mut cursor = io::Cursor::new(vec![0u8; 10]);
+        cursor.seek(SeekFrom::Start(4)).unwrap();
+        assert_eq!(stream_len(&mut cursor).unwrap(), 10);
+        assert_eq!(cursor.stream_position().unwrap(), 4);
+    }
+}
@@ -1,33 +1,152 @@
-use serde::Serialize;
-use std::error::Error;
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+use super::aem::{AemChannel, AemMeta};
 use super::aim::{AimChannel, AimMeta};
+use super::bluedriver::{BlueDriverChannel, BlueDriverMeta};
+use super::can_trace::{CanSignalChannel, CanTraceMeta};
+use super::datetime;
 use super::ecumaster::{EcuMasterChannel, EcuMasterMeta};
 use super::emerald::{EmeraldChannel, EmeraldMeta};
+use super::error::ParseError;
 use super::haltech::{HaltechChannel, HaltechMeta};
 use super::link::{LinkChannel, LinkMeta};
 use super::locomotive::{LocomotiveChannel, LocomotiveMeta};
+use super::maxxecu::{MaxxEcuChannel, MaxxEcuMeta};
+use super::megasquirt::{MegaSquirtChannel, MegaSquirtMeta};
+use super::motec::{MotecChannel, MotecMeta};
 use super::romraider::{RomRaiderChannel, RomRaiderMeta};
+use super::spec_csv::{SpecCsvChannel, SpecCsvMeta};
 use super::speeduino::{SpeeduinoChannel, SpeeduinoMeta};
 use crate::adapters::{get_channel_metadata, ChannelCategory, ChannelMetadata};
 
 /// Metadata enum supporting different ECU formats
-#[derive(Clone, Debug, Serialize, Default)]
+#[derive(Clone, Debug, Deserialize, Serialize, Default)]
 pub enum Meta {
+    Aem(AemMeta),
     Aim(AimMeta),
+    BlueDriver(BlueDriverMeta),
+    CanTrace(CanTraceMeta),
     Emerald(EmeraldMeta),
     Haltech(HaltechMeta),
     EcuMaster(EcuMasterMeta),
     Link(LinkMeta),
     Locomotive(LocomotiveMeta),
+    MaxxEcu(MaxxEcuMeta),
+    MegaSquirt(MegaSquirtMeta),
+    Motec(MotecMeta),
     RomRaider(RomRaiderMeta),
+    SpecCsv(SpecCsvMeta),
     Speeduino(SpeeduinoMeta),
     #[default]
     Empty,
 }
 
+impl Meta {
+    /// Format-independent view of the vendor metadata
+    pub fn info(&self) -> LogInfo {
+        match self {
+            Meta::Aem(m) => m.info(),
+            Meta::Aim(m) => m.info(),
+            Meta::BlueDriver(m) => m.info(),
+            Meta::CanTrace(m) => m.info(),
+            Meta::Emerald(m) => m.info(),
+            Meta::Haltech(m) => m.info(),
+            Meta::EcuMaster(_) => LogInfo::new("ECUMaster"),
+            Meta::Link(m) => m.info(),
+            Meta::Locomotive(m) => m.info(),
+            Meta::MaxxEcu(m) => m.info(),
+            Meta::MegaSquirt(m) => m.info(),
+            Meta::Motec(m) => m.info(),
+            Meta::RomRaider(_) => LogInfo::new("RomRaider"),
+            Meta::SpecCsv(m) => m.info(),
+            Meta::Speeduino(m) => m.info(),
+            Meta::Empty => LogInfo::default(),
+        }
+    }
+}
+
+/// Log metadata common to every format
+///
+/// Text fields hold what the logger wrote, unparsed, and are `None` when the
+/// format doesn't record them or the file left them blank. Anything without a
+/// common field lands in `vendor` as a label/value pair.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct LogInfo {
+    /// Log format name (e.g., "MoTeC")
+    pub format: String,
+    /// Date and time the log started
+    pub start_datetime: Option<String>,
+    pub ecu_model: Option<String>,
+    pub ecu_serial: Option<String>,
+    pub firmware: Option<String>,
+    pub vehicle: Option<String>,
+    pub driver: Option<String>,
+    pub track: Option<String>,
+    pub lap_count: Option<usize>,
+    /// Number of channels, filled in by [`Log::info`]
+    pub channel_count: usize,
+    /// Number of records, filled in by [`Log::info`]
+    pub record_count: usize,
+    /// Log duration in seconds, filled in by [`Log::info`]
+    pub duration_seconds: f64,
+    /// Vendor-specific fields in file order
+    pub vendor: Vec<(String, String)>,
+}
+
+impl LogInfo {
+    pub fn new(format: &str) -> Self {
+        Self {
+            format: format.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Add a vendor field, skipping blank values
+    pub fn push_vendor(&mut self, label: &str, value: impl ToString) {
+        let value = value.to_string();
+        if !value.trim().is_empty() {
+            self.vendor
+                .push((label.to_string(), value.trim().to_string()));
+        }
+    }
+
+    /// One-line summary of the common fields for export headers
+    pub fn summary(&self) -> String {
+        let text_fields = [
+            ("Start", &self.start_datetime),
+            ("ECU", &self.ecu_model),
+            ("Serial", &self.ecu_serial),
+            ("Firmware", &self.firmware),
+            ("Vehicle", &self.vehicle),
+            ("Driver", &self.driver),
+            ("Track", &self.track),
+        ];
+        let mut parts: Vec<String> = Vec::new();
+        if !self.format.is_empty() {
+            parts.push(self.format.clone());
+        }
+        parts.extend(
+            text_fields
+                .into_iter()
+                .filter_map(|(label, value)| value.as_ref().map(|v| format!("{}: {}", label, v))),
+        );
+        if let Some(laps) = self.lap_count {
+            parts.push(format!("Laps: {}", laps));
+        }
+        parts.join(" | ")
+    }
+}
+
+/// Trimmed copy of `text`, or `None` if it is blank
+pub fn non_empty(text: &str) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
 /// Information for a computed channel
-#[derive(Clone, Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ComputedChannelInfo {
     /// Display name for the channel
     pub name: String,
@@ -37,19 +156,120 @@ pub struct ComputedChannelInfo {
     pub unit: String,
 }
 
+/// A single flag split out of a bitfield channel
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct BitChannel {
+    /// Display name, e.g. "Engine Status [running]"
+    pub name: String,
+    /// Name of the bitfield channel the flag was taken from
+    pub parent: String,
+    /// Bit position, 0 = least significant
+    pub bit: u8,
+}
+
+/// How a channel's values are meant to be read
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub enum ValueKind {
+    /// A plain measurement
+    #[default]
+    Numeric,
+    /// Discrete states, each raw value mapped to a label
+    Enum(Vec<(i64, String)>),
+    /// Packed on/off flags, one name per bit from the least significant
+    Bitfield(Vec<String>),
+}
+
+impl ValueKind {
+    /// Off/On states for single-bit channels
+    pub fn boolean() -> Self {
+        Self::Enum(vec![(0, "Off".to_string()), (1, "On".to_string())])
+    }
+
+    /// Build an enum kind from a raw value -> label map. Keys may be decimal
+    /// or `0x` hex; keys that are neither are skipped.
+    pub fn from_labels(labels: &HashMap<String, String>) -> Self {
+        let mut states: Vec<(i64, String)> = labels
+            .iter()
+            .filter_map(|(raw, label)| {
+                let raw = raw.trim();
+                let value = match raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")) {
+                    Some(hex) => i64::from_str_radix(hex, 16).ok()?,
+                    None => raw.parse().ok()?,
+                };
+                Some((value, label.clone()))
+            })
+            .collect();
+        states.sort_by_key(|(value, _)| *value);
+        Self::Enum(states)
+    }
+
+    /// Whether values are states or flags rather than measurements
+    pub fn is_discrete(&self) -> bool {
+        !matches!(self, Self::Numeric)
+    }
+
+    /// Text to show for a value, or None for numeric channels and gaps
+    ///
+    /// Enum values without a label show the raw number; bitfields list the
+    /// names of the set bits.
+    pub fn label(&self, value: f64) -> Option<String> {
+        if is_missing(value) {
+            return None;
+        }
+        match self {
+            Self::Numeric => None,
+            Self::Enum(states) => {
+                let raw = value.round() as i64;
+                Some(
+                    states
+                        .iter()
+                        .find(|(state, _)| *state == raw)
+                        .map(|(_, label)| label.clone())
+                        .unwrap_or_else(|| raw.to_string()),
+                )
+            }
+            Self::Bitfield(names) => {
+                let raw = value as u64;
+                let set: Vec<&str> = names
+                    .iter()
+                    .enumerate()
+                    .filter(|(bit, _)| (raw >> bit) & 1 == 1)
+                    .map(|(_, name)| name.as_str())
+                    .collect();
+                Some(if set.is_empty() {
+                    "none".to_string()
+                } else {
+                    set.join(", ")
+                })
+            }
+        }
+    }
+}
+
 /// Channel enum supporting different ECU formats
 #[derive(Clone, Debug)]
 pub enum Channel {
+    Aem(AemChannel),
     Aim(AimChannel),
+    BlueDriver(BlueDriverChannel),
+    /// A signal decoded from a CAN trace with a protocol spec
+    CanSignal(CanSignalChannel),
     Emerald(EmeraldChannel),
     Haltech(HaltechChannel),
     EcuMaster(EcuMasterChannel),
     Link(LinkChannel),
     Locomotive(LocomotiveChannel),
+    MaxxEcu(MaxxEcuChannel),
+    MegaSquirt(MegaSquirtChannel),
+    Motec(MotecChannel),
     RomRaider(RomRaiderChannel),
+    /// A channel from a CSV parsed with an adapter spec
+    SpecCsv(SpecCsvChannel),
     Speeduino(SpeeduinoChannel),
     /// A computed/virtual channel derived from a formula
     Computed(ComputedChannelInfo),
+    /// One flag of a bitfield channel
+    Bit(BitChannel),
 }
 
 impl Serialize for Channel {
@@ -58,15 +278,23 @@ impl Serialize for Channel {
         S: serde::Serializer,
     {
         match self {
+            Channel::Aem(a) => a.serialize(serializer),
             Channel::Aim(a) => a.serialize(serializer),
+            Channel::BlueDriver(b) => b.serialize(serializer),
+            Channel::CanSignal(c) => c.serialize(serializer),
             Channel::Emerald(e) => e.serialize(serializer),
             Channel::Haltech(h) => h.serialize(serializer),
             Channel::EcuMaster(e) => e.serialize(serializer),
             Channel::Link(l) => l.serialize(serializer),
             Channel::Locomotive(l) => l.serialize(serializer),
+            Channel::MaxxEcu(m) => m.serialize(serializer),
+            Channel::MegaSquirt(m) => m.serialize(serializer),
+            Channel::Motec(m) => m.serialize(serializer),
             Channel::RomRaider(r) => r.serialize(serializer),
+            Channel::SpecCsv(c) => c.serialize(serializer),
             Channel::Speeduino(s) => s.serialize(serializer),
             Channel::Computed(c) => c.serialize(serializer),
+            Channel::Bit(b) => b.serialize(serializer),
         }
     }
 }
@@ -74,44 +302,68 @@ impl Serialize for Channel {
 impl Channel {
     pub fn name(&self) -> String {
         match self {
+            Channel::Aem(a) => a.name.clone(),
             Channel::Aim(a) => a.name.clone(),
+            Channel::BlueDriver(b) => b.name.clone(),
+            Channel::CanSignal(c) => c.name.clone(),
             Channel::Emerald(e) => e.name.clone(),
             Channel::Haltech(h) => h.name.clone(),
             Channel::EcuMaster(e) => e.name.clone(),
             Channel::Link(l) => l.name.clone(),
             Channel::Locomotive(l) => l.name.clone(),
+            Channel::MaxxEcu(m) => m.name.clone(),
+            Channel::MegaSquirt(m) => m.name.clone(),
+            Channel::Motec(m) => m.name.clone(),
             Channel::RomRaider(r) => r.name.clone(),
+            Channel::SpecCsv(c) => c.name.clone(),
             Channel::Speeduino(s) => s.name.clone(),
             Channel::Computed(c) => c.name.clone(),
+            Channel::Bit(b) => b.name.clone(),
         }
     }
 
     #[allow(dead_code)]
     pub fn id(&self) -> String {
         match self {
+            Channel::Aem(a) => a.source.clone(),
             Channel::Aim(a) => a.name.clone(),
+            Channel::BlueDriver(b) => b.name.clone(),
+            Channel::CanSignal(c) => format!("{:X}.{}", c.message_id, c.signal),
             Channel::Emerald(e) => e.channel_id.to_string(),
             Channel::Haltech(h) => h.id.clone(),
             Channel::EcuMaster(e) => e.path.clone(),
             Channel::Link(l) => l.channel_id.to_string(),
             Channel::Locomotive(l) => l.name.clone(),
+            Channel::MaxxEcu(m) => m.source_name.clone(),
+            Channel::MegaSquirt(m) => m.name.clone(),
+            Channel::Motec(m) => m.name.clone(),
             Channel::RomRaider(r) => r.name.clone(),
+            Channel::SpecCsv(c) => c.channel_id.clone().unwrap_or_else(|| c.name.clone()),
             Channel::Speeduino(s) => s.name.clone(),
             Channel::Computed(c) => format!("computed_{}", c.name),
+            Channel::Bit(b) => format!("{}.bit{}", b.parent, b.bit),
         }
     }
 
     pub fn type_name(&self) -> String {
         match self {
+            Channel::Aem(_) => "AEM".to_string(),
             Channel::Aim(_) => "AIM".to_string(),
+            Channel::BlueDriver(_) => "BlueDriver".to_string(),
+            Channel::CanSignal(_) => "CAN".to_string(),
             Channel::Emerald(_) => "Emerald".to_string(),
             Channel::Haltech(h) => h.r#type.as_ref().to_string(),
             Channel::EcuMaster(e) => e.path.clone(),
             Channel::Link(_) => "Link".to_string(),
             Channel::Locomotive(_) => "Locomotive".to_string(),
+            Channel::MaxxEcu(_) => "MaxxECU".to_string(),
+            Channel::MegaSquirt(_) => "MegaSquirt".to_string(),
+            Channel::Motec(_) => "MoTeC".to_string(),
             Channel::RomRaider(_) => "RomRaider".to_string(),
+            Channel::SpecCsv(_) => "Adapter Spec".to_string(),
             Channel::Speeduino(_) => "Speeduino/rusEFI".to_string(),
             Channel::Computed(_) => "Computed".to_string(),
+            Channel::Bit(_) => "Bit".to_string(),
         }
     }
 
@@ -120,15 +372,23 @@ impl Channel {
     pub fn display_min(&self) -> Option<f64> {
         // First check parser-specific min
         let parser_min = match self {
+            Channel::Aem(a) => a.display_min,
             Channel::Aim(_) => None,
+            Channel::BlueDriver(_) => None,
+            Channel::CanSignal(c) => c.display_min,
             Channel::Emerald(_) => None,
             Channel::Haltech(h) => h.display_min,
             Channel::EcuMaster(_) => None,
             Channel::Link(_) => None,
             Channel::Locomotive(_) => None,
+            Channel::MaxxEcu(m) => m.display_min,
+            Channel::MegaSquirt(_) => None,
+            Channel::Motec(_) => None,
             Channel::RomRaider(_) => None,
+            Channel::SpecCsv(_) => None,
             Channel::Speeduino(_) => None,
             Channel::Computed(_) => None,
+            Channel::Bit(_) => Some(0.0),
         };
 
         // Fall back to spec metadata if parser doesn't provide min
@@ -140,15 +400,23 @@ impl Channel {
     pub fn display_max(&self) -> Option<f64> {
         // First check parser-specific max
         let parser_max = match self {
+            Channel::Aem(a) => a.display_max,
             Channel::Aim(_) => None,
+            Channel::BlueDriver(_) => None,
+            Channel::CanSignal(c) => c.display_max,
             Channel::Emerald(_) => None,
             Channel::Haltech(h) => h.display_max,
             Channel::EcuMaster(_) => None,
             Channel::Link(_) => None,
             Channel::Locomotive(_) => None,
+            Channel::MaxxEcu(m) => m.display_max,
+            Channel::MegaSquirt(_) => None,
+            Channel::Motec(_) => None,
             Channel::RomRaider(_) => None,
+            Channel::SpecCsv(_) => None,
             Channel::Speeduino(_) => None,
             Channel::Computed(_) => None,
+            Channel::Bit(_) => Some(1.0),
         };
 
         // Fall back to spec metadata if parser doesn't provide max
@@ -172,18 +440,42 @@ impl Channel {
 
     pub fn unit(&self) -> &str {
         match self {
+            Channel::Aem(a) => a.unit(),
             Channel::Aim(a) => a.unit(),
+            Channel::BlueDriver(b) => b.unit(),
+            Channel::CanSignal(c) => c.unit(),
             Channel::Emerald(e) => e.unit(),
             Channel::Haltech(h) => h.unit(),
             Channel::EcuMaster(e) => e.unit(),
             Channel::Link(l) => l.unit(),
             Channel::Locomotive(l) => l.unit(),
+            Channel::MaxxEcu(m) => m.unit(),
+            Channel::MegaSquirt(m) => m.unit(),
+            Channel::Motec(m) => m.unit(),
             Channel::RomRaider(r) => r.unit(),
+            Channel::SpecCsv(c) => c.unit(),
             Channel::Speeduino(s) => s.unit(),
             Channel::Computed(c) => &c.unit,
+            Channel::Bit(_) => "",
         }
     }
 
+    /// How the channel's values should be displayed
+    pub fn value_kind(&self) -> ValueKind {
+        match self {
+            Channel::Speeduino(s) => s.value_kind(),
+            Channel::SpecCsv(c) => c.value_kind.clone(),
+            Channel::CanSignal(c) => c.value_kind.clone(),
+            Channel::Bit(_) => ValueKind::boolean(),
+            _ => ValueKind::Numeric,
+        }
+    }
+
+    /// Label for a value of this channel, or None if it is shown as a number
+    pub fn value_label(&self, value: f64) -> Option<String> {
+        self.value_kind().label(value)
+    }
+
     /// Check if this is a computed channel
     pub fn is_computed(&self) -> bool {
         matches!(self, Channel::Computed(_))
@@ -220,23 +512,331 @@ impl Serialize for Value {
     }
 }
 
+/// Sample value meaning "this channel has no sample at this record"
+///
+/// Missing samples are stored as NaN so a real 0.0 reading can be told
+/// apart from a gap. Use [`is_missing`] to test for it, since NaN never
+/// compares equal to itself.
+pub const MISSING: f64 = f64::NAN;
+
+/// Whether a sample is [`MISSING`]
+#[inline]
+pub fn is_missing(value: f64) -> bool {
+    value.is_nan()
+}
+
+/// A channel's samples at its own logging rate
+///
+/// Kept for channels logged slower or faster than the log's shared
+/// timebase, so consumers can use the real sample times instead of the
+/// values aligned onto `Log::times`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NativeSeries {
+    /// Sample times in seconds, on the same origin as `Log::times`
+    pub times: Vec<f64>,
+    /// One value per entry in `times`
+    pub values: Vec<f64>,
+}
+
+/// What a recovering parse had to skip to load a damaged log
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct RecoveryReport {
+    /// Records dropped because they were truncated or failed their checks
+    pub skipped_records: usize,
+    /// Times the parser scanned ahead for the next valid block
+    pub resyncs: usize,
+    /// Byte offset of the first damaged data, if any was found
+    pub first_error_offset: Option<u64>,
+}
+
+impl RecoveryReport {
+    /// Note damaged data at `offset` that cost `records` records
+    pub fn skip(&mut self, offset: u64, records: usize) {
+        self.first_error_offset.get_or_insert(offset);
+        self.skipped_records += records;
+    }
+
+    /// Whether the data was intact
+    pub fn is_clean(&self) -> bool {
+        self.first_error_offset.is_none()
+    }
+}
+
+/// A time-stamped note in a log, such as a driver-pressed marker
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Annotation {
+    /// Time in seconds, on the same timebase as [`Log::times`]
+    pub time: f64,
+    /// Text recorded with the marker
+    pub label: String,
+}
+
 /// Parsed log file structure
+///
+/// Samples are stored column-major: one contiguous `Vec<f64>` per channel,
+/// indexed by record. Every column has one value per entry in `times`;
+/// records where a channel has no sample hold [`MISSING`]. Channels logged
+/// at their own rate may also carry a [`NativeSeries`].
 #[derive(Clone, Debug, Default)]
 pub struct Log {
-    #[allow(dead_code)]
     pub meta: Meta,
     pub channels: Vec<Channel>,
     /// Time values stored directly as f64 (seconds) for efficiency
     pub times: Vec<f64>,
-    pub data: Vec<Vec<Value>>,
+    /// Per-channel sample columns, `columns[channel][record]`
+    columns: Vec<Vec<f64>>,
+    /// Optional per-channel native timebases, indexed like `columns`
+    native: Vec<Option<NativeSeries>>,
+    /// Set when the log was loaded in recovery mode
+    pub recovery: Option<RecoveryReport>,
+    /// Markers recorded while logging, in time order
+    pub annotations: Vec<Annotation>,
+    /// Wall-clock time of the first record, in the logger's local time
+    pub start: Option<NaiveDateTime>,
 }
 
 impl Log {
+    /// Create a log from per-channel sample columns
+    ///
+    /// Every column gets one value per timestamp: a shorter column is padded
+    /// with [`MISSING`] and a longer one truncated.
+    pub fn new(
+        meta: Meta,
+        channels: Vec<Channel>,
+        times: Vec<f64>,
+        mut columns: Vec<Vec<f64>>,
+    ) -> Self {
+        for (index, column) in columns.iter_mut().enumerate() {
+            if column.len() != times.len() {
+                tracing::warn!(
+                    "Column {} has {} values for {} timestamps",
+                    index,
+                    column.len(),
+                    times.len()
+                );
+                column.resize(times.len(), MISSING);
+            }
+        }
+        let start = meta
+            .info()
+            .start_datetime
+            .as_deref()
+            .and_then(datetime::parse_start);
+        Self {
+            meta,
+            channels,
+            times,
+            columns,
+            native: Vec::new(),
+            recovery: None,
+            annotations: Vec::new(),
+            start,
+        }
+    }
+
+    /// Attach a channel's samples at its own logging rate
+    pub fn set_native_series(&mut self, channel_index: usize, series: NativeSeries) {
+        debug_assert_eq!(
+            series.times.len(),
+            series.values.len(),
+            "native series needs one value per timestamp"
+        );
+        if self.native.len() <= channel_index {
+            self.native.resize(channel_index + 1, None);
+        }
+        self.native[channel_index] = Some(series);
+    }
+
+    /// A channel's native timebase, if it was logged at its own rate
+    pub fn native_series(&self, channel_index: usize) -> Option<&NativeSeries> {
+        self.native.get(channel_index)?.as_ref()
+    }
+
+    /// Add a channel to a log that is still growing, returning its index
+    ///
+    /// Records already in the log read as [`MISSING`] for the new channel.
+    pub fn push_channel(&mut self, channel: Channel) -> usize {
+        self.channels.push(channel);
+        self.columns.push(vec![MISSING; self.times.len()]);
+        self.channels.len() - 1
+    }
+
+    /// Append a record at `time`. Missing trailing values are stored as
+    /// [`MISSING`] and values beyond the channel count are ignored.
+    pub fn push_record(&mut self, time: f64, values: &[f64]) {
+        self.times.push(time);
+        for (idx, column) in self.columns.iter_mut().enumerate() {
+            column.push(values.get(idx).copied().unwrap_or(MISSING));
+        }
+    }
+
+    /// Append a sample to a channel's native series, starting one if needed
+    pub fn push_native_sample(&mut self, channel_index: usize, time: f64, value: f64) {
+        if self.native.len() <= channel_index {
+            self.native.resize(channel_index + 1, None);
+        }
+        let series = self.native[channel_index].get_or_insert_with(NativeSeries::default);
+        series.times.push(time);
+        series.values.push(value);
+    }
+
+    /// Time of the newest sample, including native series that run ahead
+    /// of the shared timebase
+    pub fn end_time(&self) -> Option<f64> {
+        self.native
+            .iter()
+            .flatten()
+            .filter_map(|series| series.times.last())
+            .chain(self.times.last())
+            .copied()
+            .reduce(f64::max)
+    }
+
+    /// Present `(time, value)` samples of a channel, skipping gaps
+    ///
+    /// Uses the channel's native timebase when it has one, otherwise the
+    /// shared `times` with missing records left out.
+    pub fn channel_samples(&self, channel_index: usize) -> Vec<[f64; 2]> {
+        let (times, values) = match self.native_series(channel_index) {
+            Some(series) => (series.times.as_slice(), series.values.as_slice()),
+            None => (self.times.as_slice(), self.channel_data(channel_index)),
+        };
+        times
+            .iter()
+            .zip(values)
+            .filter(|(_, v)| !is_missing(**v))
+            .map(|(t, v)| [*t, *v])
+            .collect()
+    }
+
+    /// Whether a channel has any missing records on the shared timebase
+    pub fn has_gaps(&self, channel_index: usize) -> bool {
+        self.channel_data(channel_index)
+            .iter()
+            .any(|v| is_missing(*v))
+    }
+
+    /// Times of records where every channel is missing: pauses in logging,
+    /// which charts show as breaks rather than lines drawn across them
+    pub fn gap_times(&self) -> Vec<f64> {
+        if self.columns.is_empty() {
+            return Vec::new();
+        }
+        self.times
+            .iter()
+            .enumerate()
+            .filter(|&(record, _)| self.columns.iter().all(|c| is_missing(c[record])))
+            .map(|(_, &time)| time)
+            .collect()
+    }
+
+    /// Create a log from row-major records (`data[record][channel]`)
+    #[deprecated(
+        since = "2.3.0",
+        note = "build per-channel columns and use `Log::new` instead"
+    )]
+    pub fn from_rows(
+        meta: Meta,
+        channels: Vec<Channel>,
+        times: Vec<f64>,
+        data: Vec<Vec<Value>>,
+    ) -> Self {
+        let channel_count = data.iter().map(Vec::len).max().unwrap_or(0);
+        let mut columns = ColumnBuilder::with_capacity(channel_count, data.len());
+        for row in &data {
+            let values: Vec<f64> = row.iter().map(Value::as_f64).collect();
+            columns.push_record(&values);
+        }
+        Self::new(meta, channels, times, columns.into_columns())
+    }
+
+    /// Borrow the samples of a channel, or an empty slice if it doesn't exist
+    #[inline]
+    pub fn channel_data(&self, channel_index: usize) -> &[f64] {
+        self.columns
+            .get(channel_index)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Borrow every channel column, `columns()[channel][record]`
+    #[inline]
+    pub fn columns(&self) -> &[Vec<f64>] {
+        &self.columns
+    }
+
+    /// Get a single sample
+    #[inline]
+    pub fn value(&self, channel_index: usize, record: usize) -> Option<f64> {
+        self.columns.get(channel_index)?.get(record).copied()
+    }
+
+    /// Common metadata view, including the log's size and duration
+    pub fn info(&self) -> LogInfo {
+        let mut info = self.meta.info();
+        info.channel_count = self.channels.len();
+        info.record_count = self.record_count();
+        info.duration_seconds = match (self.times.first(), self.times.last()) {
+            (Some(first), Some(last)) => last - first,
+            _ => 0.0,
+        };
+        if let Some(report) = &self.recovery {
+            info.push_vendor("Skipped records", report.skipped_records);
+        }
+        // Show the start the clock axis uses unless the header text agrees
+        // with it; Haltech's header can be on a 12-hour clock
+        let header_start = info
+            .start_datetime
+            .as_deref()
+            .and_then(datetime::parse_start);
+        if self.start.is_some() && header_start != self.start {
+            info.start_datetime = self
+                .start
+                .map(|start| start.format("%Y-%m-%d %H:%M:%S").to_string());
+        }
+        info
+    }
+
+    /// Wall-clock time of a point on the log's timebase, when the start is known
+    pub fn wall_clock(&self, time: f64) -> Option<NaiveDateTime> {
+        let offset = time - self.times.first().copied().unwrap_or(0.0);
+        Some(self.start? + Duration::microseconds((offset * 1e6).round() as i64))
+    }
+
+    /// Number of records (samples per channel)
+    #[inline]
+    pub fn record_count(&self) -> usize {
+        self.times.len()
+    }
+
+    /// Whether the log has no records
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
     /// Get data for a specific channel by index
+    #[deprecated(
+        since = "2.3.0",
+        note = "use `Log::channel_data`, which borrows instead of copying"
+    )]
     pub fn get_channel_data(&self, channel_index: usize) -> Vec<f64> {
-        self.data
-            .iter()
-            .filter_map(|row| row.get(channel_index).map(|v| v.as_f64()))
+        self.channel_data(channel_index).to_vec()
+    }
+
+    /// Row-major copy of the samples (`data()[record][channel]`)
+    #[deprecated(
+        since = "2.3.0",
+        note = "use `Log::channel_data` or `Log::value`; this copies every sample"
+    )]
+    pub fn data(&self) -> Vec<Vec<Value>> {
+        (0..self.record_count())
+            .map(|record| {
+                self.columns
+                    .iter()
+                    .map(|column| Value::Float(column[record]))
+                    .collect()
+            })
             .collect()
     }
 
@@ -250,20 +850,108 @@ impl Log {
     pub fn find_channel_index(&self, name: &str) -> Option<usize> {
         self.channels.iter().position(|c| c.name() == name)
     }
+
+    /// Split every bitfield channel into one on/off channel per bit
+    ///
+    /// The bit channels are appended after the existing channels. Bits that
+    /// are never set anywhere in the log carry no information and are left
+    /// out. Returns the number of channels added.
+    pub fn expand_bitfields(&mut self) -> usize {
+        let mut added = 0;
+        for idx in 0..self.channels.len() {
+            let ValueKind::Bitfield(names) = self.channels[idx].value_kind() else {
+                continue;
+            };
+            let parent = self.channels[idx].name();
+            for (bit, bit_name) in names.iter().enumerate().take(64) {
+                let column: Vec<f64> = self
+                    .channel_data(idx)
+                    .iter()
+                    .map(|&v| {
+                        if is_missing(v) {
+                            MISSING
+                        } else {
+                            ((v as u64 >> bit) & 1) as f64
+                        }
+                    })
+                    .collect();
+                if !column.contains(&1.0) {
+                    continue;
+                }
+                self.channels.push(Channel::Bit(BitChannel {
+                    name: format!("{} [{}]", parent, bit_name),
+                    parent: parent.clone(),
+                    bit: bit as u8,
+                }));
+                self.columns.push(column);
+                added += 1;
+            }
+        }
+        added
+    }
+}
+
+/// Column-major sample buffer that parsers fill one record at a time
+#[derive(Clone, Debug, Default)]
+pub struct ColumnBuilder {
+    columns: Vec<Vec<f64>>,
+    records: usize,
+}
+
+impl ColumnBuilder {
+    /// Create an empty buffer for `channel_count` channels
+    pub fn new(channel_count: usize) -> Self {
+        Self::with_capacity(channel_count, 0)
+    }
+
+    /// Create an empty buffer, reserving room for `records` records
+    pub fn with_capacity(channel_count: usize, records: usize) -> Self {
+        Self {
+            columns: (0..channel_count)
+                .map(|_| Vec::with_capacity(records))
+                .collect(),
+            records: 0,
+        }
+    }
+
+    /// Append one record. Missing trailing values are stored as
+    /// [`MISSING`] and values beyond the channel count are ignored.
+    pub fn push_record(&mut self, values: &[f64]) {
+        for (idx, column) in self.columns.iter_mut().enumerate() {
+            column.push(values.get(idx).copied().unwrap_or(MISSING));
+        }
+        self.records += 1;
+    }
+
+    /// Number of records pushed so far
+    pub fn len(&self) -> usize {
+        self.records
+    }
+
+    /// Whether no records have been pushed
+    pub fn is_empty(&self) -> bool {
+        self.records == 0
+    }
+
+    /// Finish building and return the columns
+    pub fn into_columns(self) -> Vec<Vec<f64>> {
+        self.columns
+    }
 }
 
 /// Trait for log file parsers
 pub trait Parseable {
-    fn parse(&self, data: &str) -> Result<Log, Box<dyn Error>>;
+    fn parse(&self, data: &str) -> Result<Log, ParseError>;
 }
 
 /// Supported ECU types
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Deserialize, Serialize)]
 #[allow(dead_code)]
 pub enum EcuType {
     #[default]
     Haltech,
     Aim,
+    BlueDriver,
     Emerald,
     EcuMaster,
     MegaSquirt,
@@ -282,6 +970,7 @@ impl EcuType {
         match self {
             EcuType::Haltech => "Haltech",
             EcuType::Aim => "AIM",
+            EcuType::BlueDriver => "BlueDriver",
             EcuType::Emerald => "Emerald",
             EcuType::EcuMaster => "ECUMaster",
             EcuType::MegaSquirt => "MegaSquirt",
@@ -365,51 +1054,289 @@ mod tests {
         let log = Log::default();
         assert!(log.channels.is_empty());
         assert!(log.times.is_empty());
-        assert!(log.data.is_empty());
+        assert!(log.columns().is_empty());
+        assert_eq!(log.record_count(), 0);
     }
 
     #[test]
-    fn test_log_get_channel_data() {
-        let log = Log {
-            meta: Meta::Empty,
-            channels: vec![],
-            times: vec![0.0, 1.0, 2.0],
-            data: vec![
-                vec![Value::Float(100.0), Value::Float(200.0)],
-                vec![Value::Float(110.0), Value::Float(210.0)],
-                vec![Value::Float(120.0), Value::Float(220.0)],
+    fn test_log_channel_data() {
+        let log = Log::new(
+            Meta::Empty,
+            vec![],
+            vec![0.0, 1.0, 2.0],
+            vec![vec![100.0, 110.0, 120.0], vec![200.0, 210.0, 220.0]],
+        );
+
+        assert_eq!(log.channel_data(0), &[100.0, 110.0, 120.0]);
+        assert_eq!(log.channel_data(1), &[200.0, 210.0, 220.0]);
+        assert_eq!(log.record_count(), 3);
+    }
+
+    #[test]
+    fn test_log_channel_data_out_of_bounds() {
+        let log = Log::new(
+            Meta::Empty,
+            vec![],
+            vec![0.0, 1.0],
+            vec![vec![100.0, 110.0]],
+        );
+
+        // Out of bounds should return empty
+        assert!(log.channel_data(5).is_empty());
+    }
+
+    #[test]
+    fn test_log_value() {
+        let log = Log::new(
+            Meta::Empty,
+            vec![],
+            vec![0.0, 1.0],
+            vec![vec![100.0, 110.0], vec![200.0, 210.0]],
+        );
+
+        assert_eq!(log.value(1, 0), Some(200.0));
+        assert_eq!(log.value(0, 1), Some(110.0));
+        assert_eq!(log.value(2, 0), None);
+        assert_eq!(log.value(0, 2), None);
+    }
+
+    #[test]
+    fn test_column_builder() {
+        let mut builder = ColumnBuilder::new(2);
+        assert!(builder.is_empty());
+        builder.push_record(&[1.0, 2.0]);
+        builder.push_record(&[3.0]);
+        builder.push_record(&[5.0, 6.0, 7.0]);
+
+        assert_eq!(builder.len(), 3);
+        let columns = builder.into_columns();
+        assert_eq!(columns[0], vec![1.0, 3.0, 5.0]);
+        assert_eq!(columns[1][0], 2.0);
+        assert!(is_missing(columns[1][1]));
+        assert_eq!(columns[1][2], 6.0);
+    }
+
+    #[test]
+    fn test_log_channel_samples_skip_gaps() {
+        let log = Log::new(
+            Meta::Empty,
+            vec![],
+            vec![0.0, 1.0, 2.0],
+            vec![vec![MISSING, 0.0, 5.0]],
+        );
+
+        assert!(log.has_gaps(0));
+        assert_eq!(log.channel_samples(0), vec![[1.0, 0.0], [2.0, 5.0]]);
+        assert!(log.native_series(0).is_none());
+    }
+
+    #[test]
+    fn test_log_fits_columns_to_times() {
+        let log = Log::new(
+            Meta::Empty,
+            vec![],
+            vec![0.0, 1.0, 2.0],
+            vec![vec![1.0], vec![1.0, 2.0, 3.0, 4.0]],
+        );
+
+        assert_eq!(log.channel_data(0).len(), 3);
+        assert!(is_missing(log.channel_data(0)[2]));
+        assert_eq!(log.channel_data(1), &[1.0, 2.0, 3.0]);
+        #[allow(deprecated)]
+        let rows = log.data();
+        assert_eq!(rows.len(), 3);
+    }
+
+    #[test]
+    fn test_log_gap_times() {
+        let log = Log::new(
+            Meta::Empty,
+            vec![],
+            vec![0.0, 1.0, 2.0, 3.0],
+            vec![
+                vec![1.0, MISSING, MISSING, 4.0],
+                vec![MISSING, 2.0, MISSING, 4.0],
             ],
-        };
+        );
 
-        let channel0 = log.get_channel_data(0);
-        assert_eq!(channel0, vec![100.0, 110.0, 120.0]);
+        // Only a record missing from every channel is a gap
+        assert_eq!(log.gap_times(), vec![2.0]);
+    }
 
-        let channel1 = log.get_channel_data(1);
-        assert_eq!(channel1, vec![200.0, 210.0, 220.0]);
+    #[test]
+    fn test_value_kind_labels() {
+        let labels: HashMap<String, String> = [
+            ("0", "Neutral"),
+            ("1", "First"),
+            ("0x02", "Second"),
+            ("reverse", "Ignored"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let gear = ValueKind::from_labels(&labels);
+
+        assert!(gear.is_discrete());
+        assert_eq!(gear.label(1.0).as_deref(), Some("First"));
+        assert_eq!(gear.label(2.0).as_deref(), Some("Second"));
+        assert_eq!(gear.label(7.0).as_deref(), Some("7"));
+        assert_eq!(gear.label(MISSING), None);
+
+        assert_eq!(ValueKind::boolean().label(1.0).as_deref(), Some("On"));
+        assert_eq!(ValueKind::Numeric.label(37.0), None);
     }
 
     #[test]
-    fn test_log_get_channel_data_out_of_bounds() {
-        let log = Log {
-            meta: Meta::Empty,
-            channels: vec![],
-            times: vec![0.0, 1.0],
-            data: vec![vec![Value::Float(100.0)], vec![Value::Float(110.0)]],
-        };
+    fn test_value_kind_bitfield_label() {
+        let status = ValueKind::Bitfield(vec![
+            "running".to_string(),
+            "cranking".to_string(),
+            "ase".to_string(),
+        ]);
+
+        assert_eq!(status.label(5.0).as_deref(), Some("running, ase"));
+        assert_eq!(status.label(0.0).as_deref(), Some("none"));
+    }
 
-        // Out of bounds should return empty
-        let channel_oob = log.get_channel_data(5);
-        assert!(channel_oob.is_empty());
+    #[test]
+    fn test_log_expand_bitfields() {
+        use super::super::speeduino::SpeeduinoChannel;
+
+        let status = Channel::Speeduino(SpeeduinoChannel {
+            name: "Status".to_string(),
+            unit: String::new(),
+            scale: 1.0,
+            transform: 0.0,
+            field_type: 10,
+            bit_names: vec![
+                "running".to_string(),
+                "unused".to_string(),
+                "ase".to_string(),
+            ],
+        });
+        let mut log = Log::new(
+            Meta::Empty,
+            vec![status],
+            vec![0.0, 1.0, 2.0],
+            vec![vec![1.0, 5.0, MISSING]],
+        );
+
+        // The never-set "unused" bit is skipped
+        assert_eq!(log.expand_bitfields(), 2);
+        assert_eq!(log.channels.len(), 3);
+        assert_eq!(log.channels[1].name(), "Status [running]");
+        assert_eq!(log.channels[2].name(), "Status [ase]");
+        assert_eq!(log.channels[2].value_label(1.0).as_deref(), Some("On"));
+        assert_eq!(&log.channel_data(1)[..2], &[1.0, 1.0]);
+        assert_eq!(&log.channel_data(2)[..2], &[0.0, 1.0]);
+        assert!(is_missing(log.channel_data(2)[2]));
+    }
+
+    #[test]
+    fn test_log_info() {
+        let log = Log::new(
+            Meta::Link(crate::parsers::link::LinkMeta {
+                ecu_model: "G4X".to_string(),
+                log_date: "01/06/2025".to_string(),
+                log_time: "09:30:00".to_string(),
+                software_version: String::new(),
+                source: "PCLink".to_string(),
+            }),
+            vec![Channel::Computed(ComputedChannelInfo {
+                name: "A".to_string(),
+                formula: String::new(),
+                unit: String::new(),
+            })],
+            vec![2.0, 2.5, 4.0],
+            vec![vec![1.0, 2.0, 3.0]],
+        );
+        let info = log.info();
+
+        assert_eq!(info.start_datetime.as_deref(), Some("01/06/2025 09:30:00"));
+        assert_eq!(info.ecu_model.as_deref(), Some("G4X"));
+        assert_eq!(info.driver, None);
+        assert_eq!(info.channel_count, 1);
+        assert_eq!(info.record_count, 3);
+        assert_eq!(info.duration_seconds, 2.0);
+        // Blank vendor fields are dropped
+        assert_eq!(
+            info.vendor,
+            vec![("Source".to_string(), "PCLink".to_string())]
+        );
+        assert_eq!(
+            info.summary(),
+            "Link | Start: 01/06/2025 09:30:00 | ECU: G4X"
+        );
+        assert_eq!(Log::default().info().summary(), "");
+    }
+
+    #[test]
+    fn test_log_native_series() {
+        let mut log = Log::new(
+            Meta::Empty,
+            vec![],
+            vec![0.0, 0.5, 1.0],
+            vec![vec![1.0, 2.0, 3.0], vec![10.0, 10.0, 20.0]],
+        );
+        log.set_native_series(
+            1,
+            NativeSeries {
+                times: vec![0.0, 1.0],
+                values: vec![10.0, 20.0],
+            },
+        );
+
+        assert!(log.native_series(0).is_none());
+        assert_eq!(log.native_series(1).unwrap().times, vec![0.0, 1.0]);
+        assert_eq!(log.channel_samples(1), vec![[0.0, 10.0], [1.0, 20.0]]);
+        assert!(log.native_series(5).is_none());
+    }
+
+    #[test]
+    fn test_log_growth() {
+        let mut log = Log::default();
+        log.push_record(0.0, &[]);
+        let first = log.push_channel(Channel::CanSignal(CanSignalChannel::default()));
+        log.push_record(0.5, &[1.0, 2.0]);
+        let second = log.push_channel(Channel::CanSignal(CanSignalChannel::default()));
+        log.push_record(1.0, &[3.0]);
+
+        assert_eq!((first, second), (0, 1));
+        assert_eq!(log.times, vec![0.0, 0.5, 1.0]);
+        assert!(is_missing(log.channel_data(0)[0]));
+        assert_eq!(&log.channel_data(0)[1..], &[1.0, 3.0]);
+        assert!(log.channel_data(1).iter().all(|v| is_missing(*v)));
+
+        // Native samples can run ahead of the last record
+        log.push_native_sample(1, 1.2, 4.0);
+        assert_eq!(log.native_series(1).unwrap().values, vec![4.0]);
+        assert_eq!(log.end_time(), Some(1.2));
+        assert_eq!(Log::default().end_time(), None);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_log_row_shim() {
+        let log = Log::from_rows(
+            Meta::Empty,
+            vec![],
+            vec![0.0, 1.0],
+            vec![
+                vec![Value::Float(100.0), Value::Float(200.0)],
+                vec![Value::Float(110.0), Value::Float(210.0)],
+            ],
+        );
+
+        assert_eq!(log.channel_data(1), &[200.0, 210.0]);
+        assert_eq!(log.channel_data(0), vec![100.0, 110.0]);
+        let rows = log.data();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1][0].as_f64(), 110.0);
     }
 
     #[test]
     fn test_log_get_times_as_f64() {
-        let log = Log {
-            meta: Meta::Empty,
-            channels: vec![],
-            times: vec![0.0, 0.5, 1.0, 1.5, 2.0],
-            data: vec![],
-        };
+        let log = Log::new(Meta::Empty, vec![], vec![0.0, 0.5, 1.0, 1.5, 2.0], vec![]);
 
         let times = log.get_times_as_f64();
         assert_eq!(times.len(), 5);
@@ -422,9 +1349,9 @@ mod tests {
     fn test_log_find_channel_index() {
         use super::super::haltech::{ChannelType, HaltechChannel};
 
-        let log = Log {
-            meta: Meta::Empty,
-            channels: vec![
+        let log = Log::new(
+            Meta::Empty,
+            vec![
                 Channel::Haltech(HaltechChannel {
                     name: "RPM".to_string(),
                     id: "1".to_string(),
@@ -447,9 +1374,9 @@ mod tests {
                     display_max: None,
                 }),
             ],
-            times: vec![],
-            data: vec![],
-        };
+            vec![],
+            vec![],
+        );
 
         assert_eq!(log.find_channel_index("RPM"), Some(0));
         assert_eq!(log.find_channel_index("Manifold Pressure"), Some(1));
@@ -5,7 +5,11 @@
 
 use std::path::PathBuf;
 
-use crate::parsers::{Channel, EcuType, Log};
+use crate::cache::SourceKey;
+use crate::computed::ComputedChannelTemplate;
+use crate::live::LiveStream;
+use crate::parsers::detection::Candidate;
+use crate::parsers::{CanStream, Channel, EcuType, Log, ParseError};
 
 // ============================================================================
 // Constants
@@ -19,7 +23,8 @@ pub const MAX_CHART_POINTS: usize = 2000;
 
 /// Supported log file extensions (used in file dialogs)
 pub const SUPPORTED_EXTENSIONS: &[&str] = &[
-    "csv", "log", "txt", "mlg", "llg", "llg5", "xrk", "drk", "lg1", "lg2",
+    "csv", "log", "txt", "mlg", "llg", "llg5", "xrk", "drk", "lg1", "lg2", "emublog", "daq", "msl",
+    "ld", "asc", "gz", "zip",
 ];
 
 /// Color palette for chart lines (matches original theme)
@@ -69,6 +74,10 @@ pub struct LoadedFile {
     /// Cached flag for each channel: true if channel has non-zero data
     /// Computed once on load for UI performance
     pub channels_with_data: Vec<bool>,
+    /// Key of the file's entry in the parsed-log cache, if it has one
+    pub cache_key: Option<SourceKey>,
+    /// Computed channels read back from the cache, applied once the file is open
+    pub cached_computed: Vec<ComputedChannelTemplate>,
 }
 
 impl LoadedFile {
@@ -76,10 +85,7 @@ impl LoadedFile {
     pub fn new(path: PathBuf, name: String, ecu_type: EcuType, log: Log) -> Self {
         // Pre-compute which channels have data (any non-zero values)
         let channels_with_data: Vec<bool> = (0..log.channels.len())
-            .map(|idx| {
-                let data = log.get_channel_data(idx);
-                data.iter().any(|&v| v.abs() > 0.0001)
-            })
+            .map(|idx| log.channel_data(idx).iter().any(|&v| v.abs() > 0.0001))
             .collect();
 
         Self {
@@ -88,6 +94,8 @@ impl LoadedFile {
             ecu_type,
             log,
             channels_with_data,
+            cache_key: None,
+            cached_computed: Vec::new(),
         }
     }
 
@@ -118,6 +126,110 @@ pub struct SelectedChannel {
 pub enum LoadResult {
     Success(Box<LoadedFile>),
     Error(String),
+    /// Parse progress from 0.0 to 1.0, sent before the final result
+    Progress(f32),
+    /// Archive contains several log files and the user must choose one
+    ArchiveSelection(PendingArchive),
+    /// Several formats match the file equally well and the user must choose one
+    FormatSelection(PendingFormat),
+    /// The format was recognised but its parser rejected the file
+    ParseFailed(Box<ParseFailure>),
+    /// The file is a raw CAN trace and the user must choose a protocol to decode it with
+    ProtocolSelection(PendingProtocol),
+}
+
+/// A zip archive waiting for the user to pick which log to open
+#[derive(Clone, Debug)]
+pub struct PendingArchive {
+    /// Path to the archive on disk
+    pub path: PathBuf,
+    /// Names of the log files inside the archive
+    pub entries: Vec<String>,
+}
+
+/// A file matching several log formats, waiting for the user to pick one
+#[derive(Clone, Debug)]
+pub struct PendingFormat {
+    /// Path to the file (or archive) on disk
+    pub path: PathBuf,
+    /// Entry inside a zip archive, if the file came from one
+    pub entry: Option<String>,
+    /// Formats that recognised the file, best first
+    pub candidates: Vec<Candidate>,
+}
+
+/// A raw CAN trace waiting for the user to pick the protocol spec to decode it with
+#[derive(Clone, Debug)]
+pub struct PendingProtocol {
+    /// Path to the file (or archive) on disk
+    pub path: PathBuf,
+    /// Entry inside a zip archive, if the file came from one
+    pub entry: Option<String>,
+    /// Distinct frame identifiers in the trace
+    pub trace_ids: usize,
+    /// Loaded protocols, best match first
+    pub candidates: Vec<ProtocolCandidate>,
+}
+
+/// A protocol spec offered for decoding a CAN trace
+#[derive(Clone, Debug)]
+pub struct ProtocolCandidate {
+    /// Protocol spec id
+    pub id: String,
+    /// Display name of the protocol
+    pub name: String,
+    /// How many of the trace's identifiers the protocol defines
+    pub matched_ids: usize,
+}
+
+/// Choices in the live CAN dialog, kept while it is open
+#[derive(Clone, Debug)]
+pub struct LiveSetup {
+    /// SocketCAN interface to read (e.g. `can0` or `vcan0`)
+    pub interface: String,
+    /// Id of the protocol spec frames are decoded with
+    pub protocol: Option<String>,
+}
+
+/// A loaded file being filled from a live CAN bus
+pub struct LiveSession {
+    /// Frames arriving from the bus
+    pub stream: LiveStream,
+    /// Decoder appending the frames to the file's log
+    pub decoder: CanStream,
+    /// Index of the file the log belongs to
+    pub file_index: usize,
+}
+
+/// A file its parser rejected, kept whole for the diagnostics dialog
+#[derive(Debug)]
+pub struct ParseFailure {
+    /// Path to the file (or archive) on disk
+    pub path: PathBuf,
+    /// Entry inside a zip archive, if the file came from one
+    pub entry: Option<String>,
+    /// Registered id of the format the file was parsed as
+    pub format_id: &'static str,
+    /// Display name of the format the file was parsed as
+    pub format: &'static str,
+    /// Whether the failed parse was already in recovery mode
+    pub recovery: bool,
+    /// What the parser found wrong
+    pub error: ParseError,
+}
+
+impl ParseFailure {
+    /// Name of the file that failed, without its directory
+    pub fn file_name(&self) -> String {
+        match &self.entry {
+            Some(entry) => entry.rsplit('/').next().unwrap_or(entry).to_string(),
+            None => self
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        }
+    }
 }
 
 /// Current state of file loading
@@ -537,6 +649,8 @@ pub struct Tab {
     pub histogram_state: HistogramState,
     /// Request to jump the view to a specific time (used for min/max jump buttons)
     pub jump_to_time: Option<f64>,
+    /// Whether the cursor and chart keep up with the newest data of a live log
+    pub follow_live: bool,
 }
 
 impl Tab {
@@ -559,6 +673,7 @@ impl Tab {
             scatter_plot_state,
             histogram_state: HistogramState::default(),
             jump_to_time: None,
+            follow_live: false,
         }
     }
 }
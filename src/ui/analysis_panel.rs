@@ -7,7 +7,9 @@
 use eframe::egui;
 use rust_i18n::t;
 
-use crate::analysis::{AnalysisResult, Analyzer, AnalyzerConfig, LogDataAccess};
+use crate::analysis::{
+    analyze_skipping_gaps, AnalysisResult, Analyzer, AnalyzerConfig, LogDataAccess,
+};
 use crate::app::UltraLogApp;
 use crate::computed::{ComputedChannel, ComputedChannelTemplate};
 use crate::normalize::sort_channels_by_priority;
@@ -785,7 +787,7 @@ impl UltraLogApp {
             .map(|a| a.clone_box());
 
         if let Some(analyzer) = analyzer_clone {
-            match analyzer.analyze(log) {
+            match analyze_skipping_gaps(analyzer.as_ref(), log) {
                 Ok(result) => {
                     let result_name = result.name.clone();
                     self.analysis_results
@@ -829,7 +831,7 @@ impl UltraLogApp {
             .map(|a| a.clone_box());
 
         if let Some(analyzer) = analyzer_clone {
-            match analyzer.analyze(log) {
+            match analyze_skipping_gaps(analyzer.as_ref(), log) {
                 Ok(result) => {
                     let result_name = result.name.clone();
 
@@ -0,0 +1,71 @@
+//! Archive entry picker dialog.
+//!
+//! Shown when a zip archive contains more than one log file, so the user can
+//! choose which one to open.
+
+use eframe::egui;
+use rust_i18n::t;
+
+use crate::app::UltraLogApp;
+
+impl UltraLogApp {
+    /// Render the archive entry selection dialog
+    pub fn render_archive_dialog(&mut self, ctx: &egui::Context) {
+        let Some(archive) = &self.pending_archive else {
+            return;
+        };
+
+        let archive_name = archive
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let mut open = true;
+        let mut should_close = false;
+        let mut selected: Option<String> = None;
+
+        egui::Window::new(t!("files.archive_select_title"))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .default_width(380.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.label(t!("files.archive_select_prompt", archive = archive_name));
+                ui.add_space(8.0);
+
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        for entry in &archive.entries {
+                            if ui
+                                .add(
+                                    egui::Button::new(entry.as_str())
+                                        .min_size(egui::vec2(ui.available_width(), 0.0)),
+                                )
+                                .clicked()
+                            {
+                                selected = Some(entry.clone());
+                            }
+                        }
+                    });
+
+                ui.add_space(8.0);
+                ui.vertical_centered(|ui| {
+                    if ui.button(t!("common.close")).clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+
+        if let Some(entry) = selected {
+            if let Some(archive) = self.pending_archive.take() {
+                self.start_loading_archive_entry(archive.path, entry);
+            }
+        } else if !open || should_close {
+            self.pending_archive = None;
+        }
+    }
+}
@@ -305,7 +305,7 @@ impl UltraLogApp {
                     let times = file.log.get_times_as_f64();
 
                     // Get data from either regular channel or computed channel
-                    let data: Vec<f64> = if selected.channel.is_computed() {
+                    let data: &[f64] = if selected.channel.is_computed() {
                         // For computed channels, get data from file_computed_channels
                         let regular_count = file.log.channels.len();
                         if selected.channel_index >= regular_count {
@@ -313,14 +313,14 @@ impl UltraLogApp {
                             self.file_computed_channels
                                 .get(&selected.file_index)
                                 .and_then(|channels| channels.get(computed_idx))
-                                .and_then(|c| c.cached_data.clone())
+                                .and_then(|c| c.cached_data.as_deref())
                                 .unwrap_or_default()
                         } else {
-                            Vec::new()
+                            &[]
                         }
                     } else {
                         // Regular channel data
-                        file.log.get_channel_data(selected.channel_index)
+                        file.log.channel_data(selected.channel_index)
                     };
 
                     if !data.is_empty() {
@@ -357,9 +357,17 @@ impl UltraLogApp {
                                 format!(" {}", display_unit)
                             };
 
+                            // Enum and bitfield channels show state labels
+                            let format_extreme = |raw: f64, converted: f64| {
+                                selected
+                                    .channel
+                                    .value_label(raw)
+                                    .unwrap_or_else(|| format!("{:.1}{}", converted, unit_str))
+                            };
+
                             (
-                                Some(format!("{:.1}{}", conv_min, unit_str)),
-                                Some(format!("{:.1}{}", conv_max, unit_str)),
+                                Some(format_extreme(min_val, conv_min)),
+                                Some(format_extreme(max_val, conv_max)),
                                 Some(min_idx),
                                 Some(max_idx),
                                 times.get(min_idx).copied(),
@@ -518,9 +526,10 @@ impl UltraLogApp {
             self.set_cursor_record(Some(record));
             // Request the chart to center on this time
             self.set_jump_to_time(Some(time));
-            // Stop playback when jumping
+            // Stop playback, and following a live log, when jumping
             self.is_playing = false;
             self.last_frame_time = None;
+            self.set_follow_live(false);
         }
 
         if let Some(index) = channel_to_remove {
@@ -1,13 +1,17 @@
 //! Chart rendering and data processing utilities.
 
+use chrono::{Duration, NaiveDateTime};
 use eframe::egui;
-use egui_plot::{Line, Plot, PlotBounds, PlotPoints, VLine};
+use egui_plot::{Line, LineStyle, Plot, PlotBounds, PlotPoint, PlotPoints, Text, VLine};
 use rust_i18n::t;
 
 use crate::app::UltraLogApp;
 use crate::normalize::normalize_channel_name_with_custom;
 use crate::state::{CacheKey, CHART_COLORS, COLORBLIND_COLORS, MAX_CHART_POINTS};
 
+/// Colour of log marker lines on the chart and timeline
+pub(crate) const MARKER_COLOR: egui::Color32 = egui::Color32::from_rgb(253, 193, 73);
+
 impl UltraLogApp {
     /// Render the main chart with cached downsampled data
     pub fn render_chart(&mut self, ui: &mut egui::Ui) {
@@ -37,13 +41,23 @@ impl UltraLogApp {
             };
 
             if !self.downsample_cache.contains_key(&cache_key) {
-                let file = &self.files[selected.file_index];
-                let times = file.log.get_times_as_f64();
-                // Use app method to get channel data (handles both regular and computed channels)
-                let data = self.get_channel_data(selected.file_index, selected.channel_index);
-
-                if times.len() == data.len() && !times.is_empty() {
-                    let downsampled = Self::downsample_lttb(times, &data, MAX_CHART_POINTS);
+                // Use app method to get channel samples (handles both regular and
+                // computed channels); gaps are skipped rather than drawn as zeros
+                let samples = self.get_channel_samples(selected.file_index, selected.channel_index);
+
+                if !samples.is_empty() {
+                    // Pauses in logging break the line; a NaN point marks each break
+                    let gaps = self.files[selected.file_index].log.gap_times();
+                    let mut downsampled = Vec::new();
+                    for segment in Self::split_at_gaps(&samples, &gaps) {
+                        if let Some(first) = segment.first().filter(|_| !downsampled.is_empty()) {
+                            downsampled.push([first[0], f64::NAN]);
+                        }
+                        let (times, data): (Vec<f64>, Vec<f64>) =
+                            segment.iter().map(|p| (p[0], p[1])).unzip();
+                        let target = (MAX_CHART_POINTS * segment.len()).div_ceil(samples.len());
+                        downsampled.extend(Self::downsample_lttb(&times, &data, target.max(3)));
+                    }
                     // Normalize Y values to 0-1 range so all channels overlay
                     let normalized = Self::normalize_points(&downsampled);
                     self.downsample_cache.insert(cache_key, normalized);
@@ -69,14 +83,11 @@ impl UltraLogApp {
                         selected.channel_index,
                         record,
                     ) {
-                        let source_unit = selected.channel.unit();
-                        let (converted_value, display_unit) =
-                            self.unit_preferences.convert_value(value, source_unit);
-                        if display_unit.is_empty() {
-                            format!("{}: {:.2}", base_name, converted_value)
-                        } else {
-                            format!("{}: {:.2} {}", base_name, converted_value, display_unit)
-                        }
+                        format!(
+                            "{}: {}",
+                            base_name,
+                            self.format_channel_value(&selected.channel, value)
+                        )
                     } else {
                         base_name
                     }
@@ -98,6 +109,9 @@ impl UltraLogApp {
         let chart_interacted = self.get_chart_interacted();
         let initial_view_seconds = self.initial_view_seconds;
         let jump_to_time = self.get_jump_to_time();
+        let follow_live = self.get_follow_live();
+        let annotations = self.get_annotations();
+        let clock_origin = self.clock_origin();
 
         // Fixed Y bounds for normalized data (0-1 with small padding)
         const Y_MIN: f64 = -0.05;
@@ -112,6 +126,14 @@ impl UltraLogApp {
             .allow_drag([!cursor_tracking, false]) // Only allow X-axis drag, never Y
             .allow_scroll([!cursor_tracking, false]); // Only allow X-axis scroll, never Y
 
+        // Label the time axis with clock time when it's enabled
+        let plot = match clock_origin {
+            Some(origin) => plot.x_axis_formatter(move |mark, _range| {
+                Self::format_clock_time(origin, mark.value, "%H:%M:%S")
+            }),
+            None => plot,
+        };
+
         let response = plot.show(ui, |plot_ui| {
             // Get current bounds
             let current_bounds = plot_ui.plot_bounds();
@@ -133,6 +155,17 @@ impl UltraLogApp {
                         x_min = (max_t - current_width).max(min_t);
                     }
                 }
+            } else if follow_live {
+                // Following a live log: scroll so the newest data stays at the right edge
+                if let Some((_, max_t)) = time_range {
+                    let width = if chart_interacted {
+                        x_max - x_min
+                    } else {
+                        initial_view_seconds
+                    };
+                    x_max = max_t.max(width);
+                    x_min = x_max - width;
+                }
             } else if cursor_tracking {
                 // In cursor tracking mode, center on cursor
                 if let (Some(cursor), Some((min_t, max_t))) = (cursor_time, time_range) {
@@ -186,7 +219,6 @@ impl UltraLogApp {
                 };
 
                 if let Some(points) = cache.get(&cache_key) {
-                    let plot_points: PlotPoints = points.iter().copied().collect();
                     let palette = if color_blind_mode {
                         COLORBLIND_COLORS
                     } else {
@@ -197,14 +229,37 @@ impl UltraLogApp {
                     // Use legend name with value if available
                     let name = &legend_names[i];
 
-                    plot_ui.line(
-                        Line::new(name.clone(), plot_points)
-                            .color(egui::Color32::from_rgb(color[0], color[1], color[2]))
-                            .width(1.5),
-                    );
+                    // One line per stretch between gaps, sharing a legend entry
+                    for segment in points.split(|p| p[1].is_nan()) {
+                        let plot_points: PlotPoints = segment.iter().copied().collect();
+                        plot_ui.line(
+                            Line::new(name.clone(), plot_points)
+                                .color(egui::Color32::from_rgb(color[0], color[1], color[2]))
+                                .width(1.5),
+                        );
+                    }
                 }
             }
 
+            // Draw log markers as labelled dashed lines (unnamed, so they stay out of the legend)
+            for annotation in annotations {
+                plot_ui.vline(
+                    VLine::new("", annotation.time)
+                        .color(MARKER_COLOR)
+                        .style(LineStyle::dashed_loose())
+                        .width(1.0),
+                );
+                plot_ui.text(
+                    Text::new(
+                        "",
+                        PlotPoint::new(annotation.time, Y_MAX),
+                        egui::RichText::new(&annotation.label).small(),
+                    )
+                    .color(MARKER_COLOR)
+                    .anchor(egui::Align2::LEFT_TOP),
+                );
+            }
+
             // Draw vertical cursor line
             if let Some(time) = cursor_time {
                 plot_ui.vline(
@@ -228,6 +283,13 @@ impl UltraLogApp {
             self.set_chart_interacted(true);
         }
 
+        // Panning back through a live log stops following it; zooming keeps following
+        if follow_live
+            && (response.response.dragged() || ui.input(|i| i.smooth_scroll_delta.x != 0.0))
+        {
+            self.set_follow_live(false);
+        }
+
         // Clear jump-to-time request after it's been processed
         if self.get_jump_to_time().is_some() {
             self.clear_jump_to_time();
@@ -241,9 +303,10 @@ impl UltraLogApp {
                 let clicked_time = pos.x;
                 // Clamp to time range
                 if let Some((min, max)) = self.get_time_range() {
-                    // Stop playback when user clicks on chart
+                    // Stop playback (and following a live log) when user clicks on chart
                     self.is_playing = false;
                     self.last_frame_time = None;
+                    self.set_follow_live(false);
 
                     let clamped_time = clicked_time.clamp(min, max);
                     self.set_cursor_time(Some(clamped_time));
@@ -256,6 +319,38 @@ impl UltraLogApp {
         }
     }
 
+    /// Wall-clock time of the active tab's first record and the log time it
+    /// was recorded at, when clock time is shown and the start is known
+    pub fn clock_origin(&self) -> Option<(NaiveDateTime, f64)> {
+        if !self.absolute_time {
+            return None;
+        }
+        let log = &self.files.get(self.tabs[self.active_tab?].file_index)?.log;
+        Some((log.start?, log.times.first().copied().unwrap_or(0.0)))
+    }
+
+    /// Whether the active tab's log has a known wall-clock start
+    pub fn has_wall_clock(&self) -> bool {
+        self.active_tab
+            .and_then(|idx| self.files.get(self.tabs[idx].file_index))
+            .is_some_and(|file| file.log.start.is_some())
+    }
+
+    /// Format a log time for display: clock time when enabled, else relative
+    pub fn display_time(&self, seconds: f64) -> String {
+        match self.clock_origin() {
+            Some(origin) => Self::format_clock_time(origin, seconds, "%H:%M:%S%.3f"),
+            None => Self::format_time(seconds),
+        }
+    }
+
+    /// Format a log time as the wall-clock time it was recorded at
+    fn format_clock_time(origin: (NaiveDateTime, f64), seconds: f64, format: &str) -> String {
+        let (start, first) = origin;
+        let offset = Duration::microseconds(((seconds - first) * 1e6).round() as i64);
+        (start + offset).format(format).to_string()
+    }
+
     /// Format time in seconds to a human-readable string (h:mm:ss.xxx or m:ss.xxx or s.xxx)
     pub fn format_time(seconds: f64) -> String {
         let total_seconds = seconds.abs();
@@ -283,10 +378,10 @@ impl UltraLogApp {
             return Vec::new();
         }
 
-        // Find min and max Y values
+        // Find min and max Y values (NaN gap markers are skipped)
         let mut min_y = f64::MAX;
         let mut max_y = f64::MIN;
-        for point in points {
+        for point in points.iter().filter(|p| !p[1].is_nan()) {
             min_y = min_y.min(point[1]);
             max_y = max_y.max(point[1]);
         }
@@ -295,7 +390,10 @@ impl UltraLogApp {
         let range = max_y - min_y;
         if range.abs() < f64::EPSILON {
             // All values are the same, put at 0.5
-            return points.iter().map(|p| [p[0], 0.5]).collect();
+            return points
+                .iter()
+                .map(|p| [p[0], if p[1].is_nan() { p[1] } else { 0.5 }])
+                .collect();
         }
 
         // Normalize to 0-1 range
@@ -305,6 +403,24 @@ impl UltraLogApp {
             .collect()
     }
 
+    /// Split time-ordered samples into the stretches between gap times
+    pub fn split_at_gaps<'a>(samples: &'a [[f64; 2]], gaps: &[f64]) -> Vec<&'a [[f64; 2]]> {
+        let mut segments = Vec::new();
+        let mut rest = samples;
+        for &gap in gaps {
+            let split = rest.partition_point(|p| p[0] < gap);
+            let (before, after) = rest.split_at(split);
+            if !before.is_empty() {
+                segments.push(before);
+            }
+            rest = after;
+        }
+        if !rest.is_empty() {
+            segments.push(rest);
+        }
+        segments
+    }
+
     /// Downsample data using the LTTB (Largest Triangle Three Buckets) algorithm.
     /// This preserves visual characteristics while reducing point count for performance.
     pub fn downsample_lttb(times: &[f64], values: &[f64], target_points: usize) -> Vec<[f64; 2]> {
@@ -14,7 +14,7 @@ use crate::expression::{
 };
 use crate::parsers::types::ComputedChannelInfo;
 use crate::parsers::Channel;
-use crate::state::{SelectedChannel, CHART_COLORS};
+use crate::state::{LoadedFile, SelectedChannel, CHART_COLORS};
 
 impl UltraLogApp {
     /// Render the computed channels manager window
@@ -547,20 +547,54 @@ impl UltraLogApp {
         };
 
         let file_idx = self.tabs[tab_idx].file_index;
-        let file = &self.files[file_idx];
+        let channel = match Self::evaluate_computed_channel(&self.files[file_idx], template) {
+            Ok(channel) => channel,
+            Err(e) => {
+                self.show_toast_error(&e);
+                return;
+            }
+        };
+
+        // Add to file's computed channels
+        self.file_computed_channels
+            .entry(file_idx)
+            .or_default()
+            .push(channel);
 
+        self.show_toast_success(&t!("toast.applied_template", name = template.name.as_str()));
+    }
+
+    /// Re-apply the computed channels a file had when it was last open
+    pub fn restore_computed_channels(
+        &mut self,
+        file_idx: usize,
+        templates: &[ComputedChannelTemplate],
+    ) {
+        for template in templates {
+            match Self::evaluate_computed_channel(&self.files[file_idx], template) {
+                Ok(channel) => self
+                    .file_computed_channels
+                    .entry(file_idx)
+                    .or_default()
+                    .push(channel),
+                Err(e) => tracing::warn!("Could not restore computed channel: {}", e),
+            }
+        }
+    }
+
+    /// Bind a template's channel references to a file and evaluate it over
+    /// every record. Errors are ready to show to the user.
+    fn evaluate_computed_channel(
+        file: &LoadedFile,
+        template: &ComputedChannelTemplate,
+    ) -> Result<ComputedChannel, String> {
         // Get available channel names
         let available_channels: Vec<String> = file.log.channels.iter().map(|c| c.name()).collect();
 
         // Extract channel references and build bindings
         let refs = extract_channel_references(&template.formula);
-        let bindings = match build_channel_bindings(&refs, &available_channels) {
-            Ok(b) => b,
-            Err(e) => {
-                self.show_toast_error(&t!("toast.failed_to_apply", error = e));
-                return;
-            }
-        };
+        let bindings = build_channel_bindings(&refs, &available_channels)
+            .map_err(|e| t!("toast.failed_to_apply", error = e).to_string())?;
 
         // Check if formula uses statistical variables (for z-score anomaly detection)
         let needs_statistics = template.formula.contains("_mean_")
@@ -572,48 +606,31 @@ impl UltraLogApp {
         // Evaluate the formula (with or without statistics)
         let cached_data = if needs_statistics {
             // Compute statistics for all channels
-            let statistics = compute_all_channel_statistics(&available_channels, &file.log.data);
+            let statistics =
+                compute_all_channel_statistics(&available_channels, file.log.columns());
 
-            match evaluate_all_records_with_stats(
+            evaluate_all_records_with_stats(
                 &template.formula,
                 &bindings,
-                &file.log.data,
+                file.log.columns(),
                 &file.log.times,
                 Some(&statistics),
-            ) {
-                Ok(data) => Some(data),
-                Err(e) => {
-                    self.show_toast_error(&t!("toast.evaluation_failed", error = e));
-                    return;
-                }
-            }
+            )
         } else {
-            match evaluate_all_records(
+            evaluate_all_records(
                 &template.formula,
                 &bindings,
-                &file.log.data,
+                file.log.columns(),
                 &file.log.times,
-            ) {
-                Ok(data) => Some(data),
-                Err(e) => {
-                    self.show_toast_error(&t!("toast.evaluation_failed", error = e));
-                    return;
-                }
-            }
-        };
+            )
+        }
+        .map_err(|e| t!("toast.evaluation_failed", error = e).to_string())?;
 
         // Create the computed channel
         let mut channel = ComputedChannel::from_template(template.clone());
         channel.channel_bindings = bindings;
-        channel.cached_data = cached_data;
-
-        // Add to file's computed channels
-        self.file_computed_channels
-            .entry(file_idx)
-            .or_default()
-            .push(channel);
-
-        self.show_toast_success(&t!("toast.applied_template", name = template.name.as_str()));
+        channel.cached_data = Some(cached_data);
+        Ok(channel)
     }
 
     /// Add a computed channel to the chart
@@ -0,0 +1,137 @@
+//! Parse diagnostics dialog.
+//!
+//! Shown when a file's format was recognised but its parser rejected it,
+//! so the user sees what was wrong and where instead of a one-line toast.
+//! Formats with a recovery mode offer to load whatever is still intact.
+
+use eframe::egui;
+use rust_i18n::t;
+
+use crate::app::UltraLogApp;
+use crate::parsers::{detection, ParseError};
+use crate::state::ParseFailure;
+
+impl UltraLogApp {
+    /// Render the parse diagnostics dialog
+    pub fn render_diagnostics_dialog(&mut self, ctx: &egui::Context) {
+        let Some(failure) = &self.parse_failure else {
+            return;
+        };
+
+        let file_name = failure.file_name();
+        let location = match (failure.error.offset(), failure.error.line()) {
+            (Some(offset), _) => Some(t!("diagnostics.byte", offset = offset).to_string()),
+            (_, Some(line)) => Some(t!("diagnostics.line", line = line).to_string()),
+            _ => None,
+        };
+
+        let can_recover = !failure.recovery
+            && detection::find_detector(failure.format_id)
+                .is_some_and(|detector| detector.supports_recovery());
+
+        let mut open = true;
+        let mut should_close = false;
+        let mut recover = false;
+
+        egui::Window::new(t!("diagnostics.title"))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .default_width(420.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.label(t!(
+                    "diagnostics.prompt",
+                    file = file_name,
+                    format = failure.format
+                ));
+                ui.add_space(8.0);
+
+                egui::Grid::new("parse_diagnostics")
+                    .num_columns(2)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label(t!("diagnostics.file"));
+                        ui.label(&file_name);
+                        ui.end_row();
+
+                        ui.label(t!("diagnostics.format"));
+                        ui.label(failure.format);
+                        ui.end_row();
+
+                        ui.label(t!("diagnostics.problem"));
+                        ui.label(kind_label(&failure.error));
+                        ui.end_row();
+
+                        if let Some(location) = &location {
+                            ui.label(t!("diagnostics.location"));
+                            ui.label(location);
+                            ui.end_row();
+                        }
+                    });
+
+                ui.add_space(8.0);
+                ui.label(
+                    egui::RichText::new(t!("diagnostics.details"))
+                        .small()
+                        .weak(),
+                );
+                ui.label(egui::RichText::new(failure.error.to_string()).monospace());
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if can_recover && ui.button(t!("diagnostics.recover")).clicked() {
+                        recover = true;
+                    }
+                    if ui.button(t!("diagnostics.copy")).clicked() {
+                        ctx.copy_text(report(failure));
+                    }
+                    if ui.button(t!("common.close")).clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+
+        if recover {
+            if let Some(failure) = self.parse_failure.take() {
+                self.start_recovering(failure);
+            }
+        } else if !open || should_close {
+            self.parse_failure = None;
+        }
+    }
+}
+
+/// Translated name of the kind of parse error
+fn kind_label(error: &ParseError) -> String {
+    match error {
+        ParseError::BadMagic { .. } => t!("diagnostics.kind_bad_magic"),
+        ParseError::Truncated { .. } => t!("diagnostics.kind_truncated"),
+        ParseError::BadHeader { .. } => t!("diagnostics.kind_bad_header"),
+        ParseError::UnsupportedVersion { .. } => t!("diagnostics.kind_unsupported_version"),
+        ParseError::EncodingError(_) => t!("diagnostics.kind_encoding"),
+        ParseError::Corrupt { .. } => t!("diagnostics.kind_corrupt"),
+        ParseError::Invalid(_) => t!("diagnostics.kind_invalid"),
+        ParseError::Io(_) => t!("diagnostics.kind_io"),
+    }
+    .to_string()
+}
+
+/// Plain-text summary for pasting into a bug report
+fn report(failure: &ParseFailure) -> String {
+    let mut text = format!(
+        "File: {}\nFormat: {}\nError: {}\n",
+        failure.file_name(),
+        failure.format,
+        failure.error.kind()
+    );
+    if let Some(offset) = failure.error.offset() {
+        text.push_str(&format!("Offset: {}\n", offset));
+    }
+    if let Some(line) = failure.error.line() {
+        text.push_str(&format!("Line: {}\n", line));
+    }
+    text.push_str(&failure.error.to_string());
+    text
+}
@@ -1,4 +1,4 @@
-//! Chart export functionality (PNG, PDF).
+//! Chart export functionality (PNG, PDF), plus DBC export of CAN protocol specs.
 
 use printpdf::path::{PaintMode, WindingOrder};
 use printpdf::*;
@@ -9,9 +9,11 @@ use std::io::BufWriter;
 // Use fully qualified path to disambiguate from printpdf's image module
 use ::image::{Rgba, RgbaImage};
 
+use crate::adapters::{dbc, ProtocolSpec};
 use crate::analytics;
 use crate::app::UltraLogApp;
 use crate::normalize::normalize_channel_name_with_custom;
+use crate::parsers::types::is_missing;
 use crate::state::HistogramMode;
 
 impl UltraLogApp {
@@ -36,6 +38,22 @@ impl UltraLogApp {
         }
     }
 
+    /// Export a CAN protocol spec as a DBC file
+    pub fn export_protocol_dbc(&mut self, protocol: &ProtocolSpec) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("CAN Database", &["dbc"])
+            .set_file_name(format!("{}.dbc", protocol.id))
+            .save_file()
+        else {
+            return;
+        };
+
+        match std::fs::write(&path, dbc::write_dbc(protocol)) {
+            Ok(_) => self.show_toast_success(&t!("toast.export_dbc_success")),
+            Err(e) => self.show_toast_error(&t!("toast.export_failed", error = e.to_string())),
+        }
+    }
+
     /// Export the current chart view as PDF
     pub fn export_chart_pdf(&mut self) {
         // Show save dialog
@@ -108,7 +126,7 @@ impl UltraLogApp {
             }
             let file = &self.files[selected.file_index];
             let times = file.log.get_times_as_f64();
-            let data = file.log.get_channel_data(selected.channel_index);
+            let data = file.log.channel_data(selected.channel_index);
 
             if data.is_empty() {
                 continue;
@@ -117,7 +135,7 @@ impl UltraLogApp {
             // Find min/max for normalization
             let mut data_min = f64::MAX;
             let mut data_max = f64::MIN;
-            for &val in &data {
+            for &val in data {
                 data_min = data_min.min(val);
                 data_max = data_max.max(val);
             }
@@ -138,6 +156,13 @@ impl UltraLogApp {
                     continue;
                 }
 
+                // Break the line at gaps
+                if is_missing(value) {
+                    prev_x = None;
+                    prev_y = None;
+                    continue;
+                }
+
                 let x_ratio = (time - min_time) / time_span;
                 let y_ratio = (value - data_min) / data_range;
 
@@ -212,6 +237,13 @@ impl UltraLogApp {
                 max_time
             );
             current_layer.use_text(&subtitle, 10.0, Mm(margin as f32), Mm(192.0), &font_regular);
+            current_layer.use_text(
+                file.log.info().summary(),
+                8.0,
+                Mm(margin as f32),
+                Mm(186.0),
+                &font_regular,
+            );
         }
 
         // Draw chart border
@@ -261,7 +293,7 @@ impl UltraLogApp {
             }
             let file = &self.files[selected.file_index];
             let times = file.log.get_times_as_f64();
-            let data = file.log.get_channel_data(selected.channel_index);
+            let data = file.log.channel_data(selected.channel_index);
 
             if data.is_empty() {
                 continue;
@@ -270,7 +302,7 @@ impl UltraLogApp {
             // Find min/max for normalization
             let mut data_min = f64::MAX;
             let mut data_max = f64::MIN;
-            for &val in &data {
+            for &val in data {
                 data_min = data_min.min(val);
                 data_max = data_max.max(val);
             }
@@ -290,7 +322,7 @@ impl UltraLogApp {
                     continue;
                 }
 
-                if time < min_time || time > max_time {
+                if time < min_time || time > max_time || is_missing(value) {
                     continue;
                 }
 
@@ -333,6 +365,19 @@ impl UltraLogApp {
                 channel_name
             };
 
+            // Include the value at the cursor, as the on-screen legend does
+            let cursor_value = self.get_cursor_record().and_then(|record| {
+                self.get_value_at_record(selected.file_index, selected.channel_index, record)
+            });
+            let display_name = match cursor_value {
+                Some(value) => format!(
+                    "{}: {}",
+                    display_name,
+                    self.format_channel_value(&selected.channel, value)
+                ),
+                None => display_name,
+            };
+
             current_layer.set_fill_color(text_color);
             current_layer.use_text(
                 &display_name,
@@ -465,10 +510,10 @@ impl UltraLogApp {
         };
 
         // Get channel data
-        let x_data = file.log.get_channel_data(x_idx);
-        let y_data = file.log.get_channel_data(y_idx);
+        let x_data = file.log.channel_data(x_idx);
+        let y_data = file.log.channel_data(y_idx);
         let z_data = if mode == HistogramMode::AverageZ {
-            Some(file.log.get_channel_data(z_idx))
+            Some(file.log.channel_data(z_idx))
         } else {
             None
         };
@@ -499,13 +544,19 @@ impl UltraLogApp {
         let mut z_sums = vec![vec![0.0f64; grid_cols]; grid_rows];
 
         for i in 0..x_data.len() {
+            if is_missing(x_data[i])
+                || is_missing(y_data[i])
+                || z_data.is_some_and(|z| is_missing(z[i]))
+            {
+                continue;
+            }
             let x_bin = (((x_data[i] - x_min) / x_range) * (grid_cols - 1) as f64).round() as usize;
             let y_bin = (((y_data[i] - y_min) / y_range) * (grid_rows - 1) as f64).round() as usize;
             let x_bin = x_bin.min(grid_cols - 1);
             let y_bin = y_bin.min(grid_rows - 1);
 
             hit_counts[y_bin][x_bin] += 1;
-            if let Some(ref z) = z_data {
+            if let Some(z) = z_data {
                 z_sums[y_bin][x_bin] += z[i];
             }
         }
@@ -603,6 +654,13 @@ impl UltraLogApp {
             Mm(186.0),
             &font_regular,
         );
+        current_layer.use_text(
+            file.log.info().summary(),
+            8.0,
+            Mm(margin as f32),
+            Mm(181.0),
+            &font_regular,
+        );
 
         // Draw histogram cells
         for y_bin in 0..grid_rows {
@@ -951,10 +1009,10 @@ impl UltraLogApp {
         };
 
         // Get channel data
-        let x_data = file.log.get_channel_data(x_idx);
-        let y_data = file.log.get_channel_data(y_idx);
+        let x_data = file.log.channel_data(x_idx);
+        let y_data = file.log.channel_data(y_idx);
         let z_data = if mode == HistogramMode::AverageZ {
-            Some(file.log.get_channel_data(z_idx))
+            Some(file.log.channel_data(z_idx))
         } else {
             None
         };
@@ -985,13 +1043,19 @@ impl UltraLogApp {
         let mut z_sums = vec![vec![0.0f64; grid_cols]; grid_rows];
 
         for i in 0..x_data.len() {
+            if is_missing(x_data[i])
+                || is_missing(y_data[i])
+                || z_data.is_some_and(|z| is_missing(z[i]))
+            {
+                continue;
+            }
             let x_bin = (((x_data[i] - x_min) / x_range) * (grid_cols - 1) as f64).round() as usize;
             let y_bin = (((y_data[i] - y_min) / y_range) * (grid_rows - 1) as f64).round() as usize;
             let x_bin = x_bin.min(grid_cols - 1);
             let y_bin = y_bin.min(grid_rows - 1);
 
             hit_counts[y_bin][x_bin] += 1;
-            if let Some(ref z) = z_data {
+            if let Some(z) = z_data {
                 z_sums[y_bin][x_bin] += z[i];
             }
         }
@@ -1196,8 +1260,8 @@ impl UltraLogApp {
         }
 
         let file = &self.files[file_idx];
-        let x_data = file.log.get_channel_data(x_idx);
-        let y_data = file.log.get_channel_data(y_idx);
+        let x_data = file.log.channel_data(x_idx);
+        let y_data = file.log.channel_data(y_idx);
 
         if x_data.is_empty() || y_data.is_empty() || x_data.len() != y_data.len() {
             return Err("No data available".into());
@@ -1226,6 +1290,9 @@ impl UltraLogApp {
         let mut max_hits: u32 = 0;
 
         for (&x, &y) in x_data.iter().zip(y_data.iter()) {
+            if is_missing(x) || is_missing(y) {
+                continue;
+            }
             let x_bin = (((x - x_min) / x_range) * (HEATMAP_BINS - 1) as f64).round() as usize;
             let y_bin = (((y - y_min) / y_range) * (HEATMAP_BINS - 1) as f64).round() as usize;
 
@@ -1316,6 +1383,13 @@ impl UltraLogApp {
             if file_idx < self.files.len() {
                 let file = &self.files[file_idx];
                 current_layer.use_text(&file.name, 10.0, Mm(20.0), Mm(192.0), &font_regular);
+                current_layer.use_text(
+                    file.log.info().summary(),
+                    8.0,
+                    Mm(20.0),
+                    Mm(187.0),
+                    &font_regular,
+                );
             }
         }
 
@@ -1398,8 +1472,8 @@ impl UltraLogApp {
         }
 
         let file = &self.files[file_idx];
-        let x_data = file.log.get_channel_data(x_idx);
-        let y_data = file.log.get_channel_data(y_idx);
+        let x_data = file.log.channel_data(x_idx);
+        let y_data = file.log.channel_data(y_idx);
 
         if x_data.is_empty() || y_data.is_empty() || x_data.len() != y_data.len() {
             return Err("No data available".into());
@@ -1441,6 +1515,9 @@ impl UltraLogApp {
         let mut max_hits: u32 = 0;
 
         for (&x, &y) in x_data.iter().zip(y_data.iter()) {
+            if is_missing(x) || is_missing(y) {
+                continue;
+            }
             let x_bin = (((x - x_min) / x_range) * (PDF_BINS - 1) as f64).round() as usize;
             let y_bin = (((y - y_min) / y_range) * (PDF_BINS - 1) as f64).round() as usize;
 
@@ -16,6 +16,9 @@ impl UltraLogApp {
                 ui.spinner();
                 ui.label(t!("files.loading", filename = filename));
             });
+            if let Some(fraction) = self.loading_progress {
+                ui.add(egui::ProgressBar::new(fraction).show_percentage());
+            }
             ui.add_space(8.0);
             ui.separator();
             ui.add_space(8.0);
@@ -29,6 +32,10 @@ impl UltraLogApp {
 
             ui.add_space(10.0);
 
+            self.render_log_info(ui);
+
+            ui.add_space(10.0);
+
             // Add more files button
             ui.add_enabled_ui(!is_loading, |ui| {
                 self.render_add_file_button(ui);
@@ -55,7 +62,7 @@ impl UltraLogApp {
                     self.selected_file == Some(i),
                     file.ecu_type.name().to_string(),
                     file.log.channels.len(),
-                    file.log.data.len(),
+                    file.log.record_count(),
                 )
             })
             .collect();
@@ -160,6 +167,62 @@ impl UltraLogApp {
         }
     }
 
+    /// Render the common metadata of the selected log
+    fn render_log_info(&self, ui: &mut egui::Ui) {
+        let Some(file) = self.selected_file.and_then(|i| self.files.get(i)) else {
+            return;
+        };
+        let info = file.log.info();
+        let font_12 = self.scaled_font(12.0);
+
+        let mut rows: Vec<(String, String)> =
+            vec![(t!("log_info.format").to_string(), info.format)];
+        let common = [
+            (t!("log_info.start"), info.start_datetime),
+            (t!("log_info.ecu_model"), info.ecu_model),
+            (t!("log_info.ecu_serial"), info.ecu_serial),
+            (t!("log_info.firmware"), info.firmware),
+            (t!("log_info.vehicle"), info.vehicle),
+            (t!("log_info.driver"), info.driver),
+            (t!("log_info.track"), info.track),
+            (t!("log_info.laps"), info.lap_count.map(|n| n.to_string())),
+        ];
+        rows.extend(
+            common
+                .into_iter()
+                .filter_map(|(label, value)| value.map(|v| (label.to_string(), v))),
+        );
+        rows.push((
+            t!("log_info.duration").to_string(),
+            Self::format_time(info.duration_seconds),
+        ));
+        rows.extend(info.vendor);
+
+        egui::CollapsingHeader::new(
+            egui::RichText::new(t!("log_info.title"))
+                .size(self.scaled_font(13.0))
+                .color(egui::Color32::GRAY),
+        )
+        .id_salt("log_info")
+        .default_open(false)
+        .show(ui, |ui| {
+            egui::Grid::new("log_info_grid")
+                .num_columns(2)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    for (label, value) in &rows {
+                        ui.label(
+                            egui::RichText::new(label)
+                                .size(font_12)
+                                .color(egui::Color32::GRAY),
+                        );
+                        ui.label(egui::RichText::new(value).size(font_12));
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
     /// Render the "Add File" button
     fn render_add_file_button(&mut self, ui: &mut egui::Ui) {
         let primary_color = egui::Color32::from_rgb(113, 120, 78); // Olive green
@@ -0,0 +1,75 @@
+//! Log format picker dialog.
+//!
+//! Shown when several formats recognise a file about equally well, so the
+//! user can say which format it is instead of UltraLog guessing.
+
+use eframe::egui;
+use rust_i18n::t;
+
+use crate::app::UltraLogApp;
+
+impl UltraLogApp {
+    /// Render the format selection dialog
+    pub fn render_format_dialog(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &self.pending_format else {
+            return;
+        };
+
+        let file_name = match &pending.entry {
+            Some(entry) => entry.rsplit('/').next().unwrap_or(entry).to_string(),
+            None => pending
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        };
+
+        let mut open = true;
+        let mut should_close = false;
+        let mut selected: Option<&'static str> = None;
+
+        egui::Window::new(t!("files.format_select_title"))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .default_width(380.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.label(t!("files.format_select_prompt", file = file_name));
+                ui.add_space(8.0);
+
+                for candidate in &pending.candidates {
+                    let label = t!(
+                        "files.format_candidate",
+                        format = candidate.detector.name,
+                        confidence = format!("{:.0}", candidate.confidence * 100.0)
+                    );
+                    if ui
+                        .add(
+                            egui::Button::new(label.as_ref())
+                                .min_size(egui::vec2(ui.available_width(), 0.0)),
+                        )
+                        .clicked()
+                    {
+                        selected = Some(candidate.detector.id);
+                    }
+                }
+
+                ui.add_space(8.0);
+                ui.vertical_centered(|ui| {
+                    if ui.button(t!("common.close")).clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+
+        if let Some(format) = selected {
+            if let Some(pending) = self.pending_format.take() {
+                self.start_loading_with_format(pending, format);
+            }
+        } else if !open || should_close {
+            self.pending_format = None;
+        }
+    }
+}
@@ -333,7 +333,7 @@ impl UltraLogApp {
                             if let Ok(preview) = generate_preview(
                                 &formula,
                                 &bindings,
-                                &file.log.data,
+                                file.log.columns(),
                                 &file.log.times,
                                 100,
                             ) {
@@ -9,6 +9,7 @@ use rust_i18n::t;
 
 use crate::app::UltraLogApp;
 use crate::normalize::sort_channels_by_priority;
+use crate::parsers::types::is_missing;
 use crate::state::{
     HistogramMode, PastedTable, SampleFilter, SelectedHistogramCell, TableOperation,
 };
@@ -837,7 +838,7 @@ impl UltraLogApp {
         };
 
         // Pre-fetch filter channel data for efficiency
-        let filter_data: Vec<(&SampleFilter, Vec<f64>)> = sample_filters
+        let filter_data: Vec<(&SampleFilter, &[f64])> = sample_filters
             .iter()
             .filter(|f| f.enabled)
             .map(|f| (f, self.get_channel_data(file_idx, f.channel_idx)))
@@ -858,6 +859,9 @@ impl UltraLogApp {
                     continue 'sample_loop;
                 }
                 let val = data[i];
+                if is_missing(val) {
+                    continue 'sample_loop;
+                }
                 if let Some(min) = filter.min_value {
                     if val < min {
                         continue 'sample_loop;
@@ -870,6 +874,14 @@ impl UltraLogApp {
                 }
             }
 
+            // Skip records where any plotted channel has no sample
+            if is_missing(x_data[i])
+                || is_missing(y_data[i])
+                || z_data.is_some_and(|z| is_missing(z[i]))
+            {
+                continue;
+            }
+
             // Skip samples outside custom range (if set)
             if custom_x_range.is_some() && (x_data[i] < x_min || x_data[i] > x_max) {
                 continue;
@@ -882,7 +894,7 @@ impl UltraLogApp {
             let y_bin = calculate_data_bin(y_data[i], y_min, y_range, grid_rows);
 
             hit_counts[y_bin][x_bin] += 1;
-            if let Some(ref z) = z_data {
+            if let Some(z) = z_data {
                 let z_val = z[i];
                 z_sums[y_bin][x_bin] += z_val;
                 z_sum_sq[y_bin][x_bin] += z_val * z_val;
@@ -2163,10 +2175,16 @@ impl UltraLogApp {
         let mut z_sums = vec![vec![0.0f64; grid_cols]; grid_rows];
 
         for i in 0..x_data.len() {
+            if is_missing(x_data[i])
+                || is_missing(y_data[i])
+                || z_data.is_some_and(|z| is_missing(z[i]))
+            {
+                continue;
+            }
             let x_bin = calculate_data_bin(x_data[i], x_min, x_range, grid_cols);
             let y_bin = calculate_data_bin(y_data[i], y_min, y_range, grid_rows);
             hit_counts[y_bin][x_bin] += 1;
-            if let Some(ref z) = z_data {
+            if let Some(z) = z_data {
                 z_sums[y_bin][x_bin] += z[i];
             }
         }
@@ -2284,10 +2302,16 @@ impl UltraLogApp {
         let mut z_sums = vec![vec![0.0f64; grid_cols]; grid_rows];
 
         for i in 0..x_data.len() {
+            if is_missing(x_data[i])
+                || is_missing(y_data[i])
+                || z_data.is_some_and(|z| is_missing(z[i]))
+            {
+                continue;
+            }
             let x_bin = calculate_data_bin(x_data[i], x_min, x_range, grid_cols);
             let y_bin = calculate_data_bin(y_data[i], y_min, y_range, grid_rows);
             hit_counts[y_bin][x_bin] += 1;
-            if let Some(ref z) = z_data {
+            if let Some(z) = z_data {
                 z_sums[y_bin][x_bin] += z[i];
             }
         }
@@ -0,0 +1,95 @@
+//! Live CAN connection dialog.
+//!
+//! Asks for the SocketCAN interface to stream from and the protocol spec
+//! its frames are decoded with, then opens a live tab.
+
+use eframe::egui;
+use rust_i18n::t;
+
+use crate::adapters;
+use crate::app::UltraLogApp;
+
+impl UltraLogApp {
+    /// Render the live CAN connection dialog
+    pub fn render_live_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut setup) = self.live_setup.take() else {
+            return;
+        };
+
+        let protocols = adapters::get_protocols();
+        let mut open = true;
+        let mut should_close = false;
+        let mut connect = false;
+
+        egui::Window::new(t!("live.title"))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .default_width(380.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.label(t!("live.prompt"));
+                ui.add_space(8.0);
+
+                egui::Grid::new("live_dialog_grid")
+                    .num_columns(2)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(t!("live.interface"));
+                        ui.text_edit_singleline(&mut setup.interface);
+                        ui.end_row();
+
+                        ui.label(t!("live.protocol"));
+                        let selected_name = setup
+                            .protocol
+                            .as_ref()
+                            .and_then(|id| protocols.iter().find(|p| &p.id == id))
+                            .map_or_else(
+                                || t!("live.choose_protocol").to_string(),
+                                |p| p.name.clone(),
+                            );
+                        egui::ComboBox::from_id_salt("live_protocol")
+                            .selected_text(selected_name)
+                            .width(220.0)
+                            .show_ui(ui, |ui| {
+                                for protocol in &protocols {
+                                    ui.selectable_value(
+                                        &mut setup.protocol,
+                                        Some(protocol.id.clone()),
+                                        &protocol.name,
+                                    );
+                                }
+                            });
+                        ui.end_row();
+                    });
+
+                if protocols.is_empty() {
+                    ui.add_space(4.0);
+                    ui.label(t!("files.no_protocols"));
+                }
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    let ready = setup.protocol.is_some() && !setup.interface.trim().is_empty();
+                    if ui
+                        .add_enabled(ready, egui::Button::new(t!("live.connect")))
+                        .clicked()
+                    {
+                        connect = true;
+                    }
+                    if ui.button(t!("live.cancel")).clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+
+        if connect {
+            if let Some(protocol) = &setup.protocol {
+                self.start_live(setup.interface.trim(), protocol);
+            }
+        } else if open && !should_close {
+            self.live_setup = Some(setup);
+        }
+    }
+}
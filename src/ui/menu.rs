@@ -5,8 +5,9 @@
 use eframe::egui;
 use rust_i18n::t;
 
+use crate::adapters;
 use crate::app::UltraLogApp;
-use crate::state::{ActivePanel, ActiveTool, LoadingState};
+use crate::state::{ActivePanel, ActiveTool, LiveSetup, LoadingState};
 
 impl UltraLogApp {
     /// Render the application menu bar
@@ -50,6 +51,15 @@ impl UltraLogApp {
                     ui.close();
                 }
 
+                // Stream a CAN bus into a live tab
+                if ui.button(t!("menu.live_can")).clicked() {
+                    self.live_setup = Some(LiveSetup {
+                        interface: crate::live::DEFAULT_INTERFACE.to_string(),
+                        protocol: None,
+                    });
+                    ui.close();
+                }
+
                 ui.separator();
 
                 // Close current tab
@@ -104,6 +114,40 @@ impl UltraLogApp {
                         }
                     });
                 });
+
+                // Vendor CAN databases decode broadcasts without a hand-written spec
+                if ui.button(t!("menu.import_dbc")).clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("CAN Database", &["dbc"])
+                        .pick_file()
+                    {
+                        self.import_dbc_file(&path);
+                    }
+                    ui.close();
+                }
+
+                // CAN protocol specs can be written out for dashes and loggers
+                ui.menu_button(t!("menu.export_dbc"), |ui| {
+                    ui.style_mut()
+                        .text_styles
+                        .insert(egui::TextStyle::Button, egui::FontId::proportional(font_14));
+
+                    let mut protocols = adapters::get_protocols();
+                    if protocols.is_empty() {
+                        ui.label(t!("files.no_protocols"));
+                    }
+                    protocols.sort_by(|a, b| a.name.cmp(&b.name));
+                    egui::ScrollArea::vertical()
+                        .max_height(400.0)
+                        .show(ui, |ui| {
+                            for protocol in &protocols {
+                                if ui.button(&protocol.name).clicked() {
+                                    self.export_protocol_dbc(protocol);
+                                    ui.close();
+                                }
+                            }
+                        });
+                });
             });
 
             // View menu - tool modes and panels
@@ -25,6 +25,11 @@
 //! - `histogram` - Histogram visualization view
 //! - `tab_bar` - Chrome-style tabs for managing multiple log files
 //! - `update_dialog` - Auto-update dialog window
+//! - `archive_dialog` - Picker for zip archives holding several logs
+//! - `format_dialog` - Picker for files matching several log formats
+//! - `protocol_dialog` - Picker for the protocol a CAN trace is decoded with
+//! - `diagnostics_dialog` - Details of a file its parser rejected
+//! - `live_dialog` - Interface and protocol picker for live CAN streaming
 //! - `analysis_panel` - Signal analysis tools window
 //! - `computed_channels_manager` - Computed channels library manager
 //! - `formula_editor` - Formula creation and editing
@@ -39,15 +44,20 @@ pub mod tools_panel;
 
 // Core UI components
 pub mod analysis_panel;
+pub mod archive_dialog;
 pub mod channels;
 pub mod chart;
 pub mod computed_channels_manager;
+pub mod diagnostics_dialog;
 pub mod export;
+pub mod format_dialog;
 pub mod formula_editor;
 pub mod histogram;
 pub mod icons;
+pub mod live_dialog;
 pub mod menu;
 pub mod normalization_editor;
+pub mod protocol_dialog;
 pub mod scatter_plot;
 pub mod sidebar;
 pub mod tab_bar;
@@ -0,0 +1,81 @@
+//! CAN protocol picker dialog.
+//!
+//! Shown when a CAN bus trace is opened, so the user can choose which
+//! protocol spec its frames are decoded with. Protocols are listed by how
+//! many of the trace's identifiers they define.
+
+use eframe::egui;
+use rust_i18n::t;
+
+use crate::app::UltraLogApp;
+
+impl UltraLogApp {
+    /// Render the protocol selection dialog
+    pub fn render_protocol_dialog(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &self.pending_protocol else {
+            return;
+        };
+
+        let file_name = match &pending.entry {
+            Some(entry) => entry.rsplit('/').next().unwrap_or(entry).to_string(),
+            None => pending
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        };
+
+        let mut open = true;
+        let mut should_close = false;
+        let mut selected: Option<String> = None;
+
+        egui::Window::new(t!("files.protocol_select_title"))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .default_width(420.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.label(t!("files.protocol_select_prompt", file = file_name));
+                ui.add_space(8.0);
+
+                if pending.candidates.is_empty() {
+                    ui.label(t!("files.no_protocols"));
+                }
+
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        for candidate in &pending.candidates {
+                            let label = t!(
+                                "files.protocol_candidate",
+                                protocol = candidate.name,
+                                matched = candidate.matched_ids,
+                                total = pending.trace_ids
+                            );
+                            let button = egui::Button::new(label.as_ref())
+                                .min_size(egui::vec2(ui.available_width(), 0.0));
+                            if ui.add_enabled(candidate.matched_ids > 0, button).clicked() {
+                                selected = Some(candidate.id.clone());
+                            }
+                        }
+                    });
+
+                ui.add_space(8.0);
+                ui.vertical_centered(|ui| {
+                    if ui.button(t!("common.close")).clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+
+        if let Some(protocol) = selected {
+            if let Some(pending) = self.pending_protocol.take() {
+                self.start_loading_with_protocol(pending, protocol);
+            }
+        } else if !open || should_close {
+            self.pending_protocol = None;
+        }
+    }
+}
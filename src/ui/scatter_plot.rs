@@ -8,6 +8,7 @@ use rust_i18n::t;
 
 use crate::app::UltraLogApp;
 use crate::normalize::{normalize_channel_name_with_custom, sort_channels_by_priority};
+use crate::parsers::types::is_missing;
 use crate::state::{ScatterPlotConfig, SelectedHeatmapPoint};
 
 /// Heat map color gradient from blue (low) to red (high)
@@ -183,8 +184,8 @@ impl UltraLogApp {
         }
 
         let file = &self.files[file_idx];
-        let x_data = file.log.get_channel_data(x_idx);
-        let y_data = file.log.get_channel_data(y_idx);
+        let x_data = file.log.channel_data(x_idx);
+        let y_data = file.log.channel_data(y_idx);
 
         if x_data.is_empty() || y_data.is_empty() || x_data.len() != y_data.len() {
             return;
@@ -212,6 +213,9 @@ impl UltraLogApp {
         let mut max_hits: u32 = 0;
 
         for (&x, &y) in x_data.iter().zip(y_data.iter()) {
+            if is_missing(x) || is_missing(y) {
+                continue;
+            }
             let x_bin = (((x - x_min) / x_range) * (HEATMAP_BINS - 1) as f64).round() as usize;
             let y_bin = (((y - y_min) / y_range) * (HEATMAP_BINS - 1) as f64).round() as usize;
 
@@ -39,7 +39,7 @@ impl UltraLogApp {
                         self.selected_file == Some(i),
                         file.ecu_type.name().to_string(),
                         file.log.channels.len(),
-                        file.log.data.len(),
+                        file.log.record_count(),
                     )
                 })
                 .collect();
@@ -24,13 +24,13 @@ impl UltraLogApp {
         // Time labels row
         ui.horizontal(|ui| {
             ui.label(
-                egui::RichText::new(Self::format_time(min_time))
+                egui::RichText::new(self.display_time(min_time))
                     .color(egui::Color32::LIGHT_GRAY)
                     .size(font_12),
             );
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 ui.label(
-                    egui::RichText::new(Self::format_time(max_time))
+                    egui::RichText::new(self.display_time(max_time))
                         .color(egui::Color32::LIGHT_GRAY)
                         .size(font_12),
                 );
@@ -55,10 +55,37 @@ impl UltraLogApp {
         // Restore original slider width
         ui.spacing_mut().slider_width = old_slider_width;
 
+        // Tick each log marker along the slider rail, labelled on hover
+        let rect = slider_response.rect;
+        let rail = rect.x_range().shrink(rect.height() / 2.5);
+        let hover_x = slider_response.hover_pos().map(|pos| pos.x);
+        let mut hovered_labels: Vec<&str> = Vec::new();
+        for annotation in self.get_annotations() {
+            let fraction = ((annotation.time - min_time) / total_duration).clamp(0.0, 1.0);
+            let x = egui::lerp(rail, fraction as f32);
+            ui.painter().vline(
+                x,
+                rect.y_range(),
+                egui::Stroke::new(2.0, crate::ui::chart::MARKER_COLOR),
+            );
+            if hover_x.is_some_and(|hover| (hover - x).abs() <= 3.0) {
+                hovered_labels.push(&annotation.label);
+            }
+        }
+        if !hovered_labels.is_empty() {
+            slider_response
+                .clone()
+                .on_hover_text(hovered_labels.join("\n"));
+        }
+
         if slider_response.changed() {
             // Stop playback when user manually scrubs
             self.is_playing = false;
             self.last_frame_time = None;
+            // A live log is followed again once scrubbed back to its end
+            if self.is_live_tab() {
+                self.set_follow_live(slider_value >= max_time);
+            }
 
             self.set_cursor_time(Some(slider_value));
             let record = self.find_record_at_time(slider_value);
@@ -123,6 +150,7 @@ impl UltraLogApp {
             if ui.add(stop_button).clicked() {
                 self.is_playing = false;
                 self.last_frame_time = None;
+                self.set_follow_live(false);
                 // Reset cursor to beginning
                 if let Some((min, _)) = self.get_time_range() {
                     self.set_cursor_time(Some(min));
@@ -131,6 +159,37 @@ impl UltraLogApp {
                 }
             }
 
+            // Live bus controls
+            if self.is_live_tab() {
+                ui.separator();
+                self.render_live_controls(ui, button_size);
+            }
+
+            // Marker navigation, when the log has any
+            let marker_count = self.get_annotations().len();
+            if marker_count > 0 {
+                ui.separator();
+                if ui
+                    .add(egui::Button::new("\u{23EE}").min_size(button_size))
+                    .on_hover_text(t!("timeline.previous_marker"))
+                    .clicked()
+                {
+                    self.jump_to_annotation(false);
+                }
+                ui.label(
+                    egui::RichText::new(t!("timeline.markers", count = marker_count))
+                        .color(crate::ui::chart::MARKER_COLOR)
+                        .size(font_14),
+                );
+                if ui
+                    .add(egui::Button::new("\u{23ED}").min_size(button_size))
+                    .on_hover_text(t!("timeline.next_marker"))
+                    .clicked()
+                {
+                    self.jump_to_annotation(true);
+                }
+            }
+
             ui.separator();
 
             // Playback speed selector
@@ -152,10 +211,23 @@ impl UltraLogApp {
 
             ui.separator();
 
+            // Relative/clock time toggle, for logs with a known start time
+            if self.has_wall_clock()
+                && ui
+                    .selectable_label(
+                        self.absolute_time,
+                        egui::RichText::new(t!("timeline.clock_time")).size(font_14),
+                    )
+                    .on_hover_text(t!("timeline.clock_time_desc"))
+                    .clicked()
+            {
+                self.absolute_time = !self.absolute_time;
+            }
+
             // Current time display
             if let Some(time) = self.get_cursor_time() {
                 ui.label(
-                    egui::RichText::new(t!("timeline.time", time = Self::format_time(time)))
+                    egui::RichText::new(t!("timeline.time", time = self.display_time(time)))
                         .strong()
                         .color(egui::Color32::from_rgb(0, 255, 255)) // Cyan to match cursor
                         .size(font_14),
@@ -169,7 +241,7 @@ impl UltraLogApp {
                 if let Some(tab_idx) = self.active_tab {
                     let file_index = self.tabs[tab_idx].file_index;
                     if file_index < self.files.len() {
-                        let total_records = self.files[file_index].log.data.len();
+                        let total_records = self.files[file_index].log.record_count();
                         ui.label(
                             egui::RichText::new(t!(
                                 "timeline.record",
@@ -185,6 +257,77 @@ impl UltraLogApp {
         });
     }
 
+    /// Render the badge, follow toggle, recording and disconnect buttons of a live tab
+    fn render_live_controls(&mut self, ui: &mut egui::Ui, button_size: egui::Vec2) {
+        let font_14 = self.scaled_font(14.0);
+        let live_red = egui::Color32::from_rgb(230, 60, 60);
+
+        ui.label(
+            egui::RichText::new(format!("\u{25CF} {}", t!("live.badge")))
+                .strong()
+                .color(live_red)
+                .size(font_14),
+        );
+
+        let follow = self.get_follow_live();
+        if ui
+            .selectable_label(follow, egui::RichText::new(t!("live.follow")).size(font_14))
+            .on_hover_text(t!("live.follow_desc"))
+            .clicked()
+        {
+            self.set_follow_live(!follow);
+            if !follow {
+                // Jump to the newest data straight away
+                self.is_playing = false;
+                self.last_frame_time = None;
+                if let Some((_, max)) = self.get_time_range() {
+                    self.set_cursor_time(Some(max));
+                    let record = self.find_record_at_time(max);
+                    self.set_cursor_record(record);
+                }
+            }
+        }
+
+        if self.is_live_recording() {
+            let stop_recording = egui::Button::new(
+                egui::RichText::new(format!("\u{23F9} {}", t!("live.stop_recording")))
+                    .color(live_red)
+                    .size(font_14),
+            )
+            .min_size(button_size);
+            if ui.add(stop_recording).clicked() {
+                self.stop_live_recording();
+            }
+        } else if ui
+            .add(
+                egui::Button::new(
+                    egui::RichText::new(format!("\u{23FA} {}", t!("live.record"))).size(font_14),
+                )
+                .min_size(button_size),
+            )
+            .on_hover_text(t!("live.record_desc"))
+            .clicked()
+        {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("candump log", &["log"])
+                .set_file_name("live.log")
+                .save_file()
+            {
+                self.start_live_recording(&path);
+            }
+        }
+
+        if ui
+            .add(
+                egui::Button::new(egui::RichText::new(t!("live.disconnect")).size(font_14))
+                    .min_size(button_size),
+            )
+            .clicked()
+        {
+            self.disconnect_live();
+        }
+    }
+
     /// Update playback state - advances cursor based on elapsed time
     pub fn update_playback(&mut self, ctx: &egui::Context) {
         if !self.is_playing {
@@ -209,12 +352,15 @@ impl UltraLogApp {
             let new_time = current_time + (delta * self.playback_speed);
 
             if new_time >= max_time {
-                // Reached end - stop playback
+                // Reached end - stop playback, following a live log from here on
                 self.set_cursor_time(Some(max_time));
                 let record = self.find_record_at_time(max_time);
                 self.set_cursor_record(record);
                 self.is_playing = false;
                 self.last_frame_time = None;
+                if self.is_live_tab() {
+                    self.set_follow_live(true);
+                }
             } else {
                 self.set_cursor_time(Some(new_time));
                 let record = self.find_record_at_time(new_time);
@@ -2,6 +2,9 @@
 //!
 //! This module provides helper functions for reading example files,
 //! creating test fixtures, and other common testing operations.
+//!
+//! Each test crate root declares it once as `pub mod common` (each uses only
+//! part of it) and test modules import it through `crate::common`.
 
 use std::path::Path;
 
@@ -46,6 +49,7 @@ pub mod example_files {
     // ECUMaster example files
     pub const ECUMASTER_STANDARD: &str = "exampleLogs/ecumaster/2025_1218_1903.csv";
     pub const ECUMASTER_LARGE: &str = "exampleLogs/ecumaster/Largest.csv";
+    pub const ECUMASTER_EMUBLOG: &str = "exampleLogs/ecumaster/220kpa.emublog";
 
     // Speeduino example files
     pub const SPEEDUINO_MLG: &str = "exampleLogs/speeduino/speeduino.mlg";
@@ -68,25 +72,28 @@ pub mod example_files {
     // RomRaider example files
     pub const ROMRAIDER_EUROPEAN: &str = "exampleLogs/romraider/romraiderlog_20251031_170713.csv";
 
+    // BlueDriver example files
+    pub const BLUEDRIVER_AUG: &str =
+        "exampleLogs/bluedriver/SBM22GCA4MW001405_Aug_17__2025_08_45_PM_LiveData.csv";
+    pub const BLUEDRIVER_NOV: &str =
+        "exampleLogs/bluedriver/SBM22GCA4MW001405_Nov_09__2025_04_20_PM_LiveData.csv";
+
     // Emerald ECU example files
     pub const EMERALD_IDLE_REV: &str = "exampleLogs/emerald/EM Log MG ZS Turbo idle and rev.lg1";
     pub const EMERALD_SHORT_DRIVE: &str = "exampleLogs/emerald/EM Log MG ZS Turbo short drive.lg1";
     pub const EMERALD_DIFF_CHANNELS: &str =
         "exampleLogs/emerald/EM Log MG ZS Turbo short drive back diff channels.lg1";
+
+    // AEM example files
+    pub const AEM_DAQ: &str = "exampleLogs/aem/aemlog.daq";
 }
 
 /// Test data generators for synthetic tests
 pub mod synthetic {
-    use ultralog::parsers::types::Value;
-
-    /// Create a simple data matrix with linear values
-    pub fn linear_data(channels: usize, records: usize) -> Vec<Vec<Value>> {
-        (0..records)
-            .map(|r| {
-                (0..channels)
-                    .map(|c| Value::Float((r * channels + c) as f64))
-                    .collect()
-            })
+    /// Create per-channel data columns with linear values
+    pub fn linear_data(channels: usize, records: usize) -> Vec<Vec<f64>> {
+        (0..channels)
+            .map(|c| (0..records).map(|r| (r * channels + c) as f64).collect())
             .collect()
     }
 
@@ -119,23 +126,22 @@ pub mod assertions {
     pub fn assert_valid_log_structure(log: &Log) {
         assert!(!log.channels.is_empty(), "Log should have channels");
         assert!(!log.times.is_empty(), "Log should have timestamps");
-        assert!(!log.data.is_empty(), "Log should have data records");
+        assert!(!log.is_empty(), "Log should have data records");
 
         assert_eq!(
-            log.times.len(),
-            log.data.len(),
-            "Times and data should have same length"
+            log.columns().len(),
+            log.channels.len(),
+            "Every channel should have a data column"
         );
 
-        let channel_count = log.channels.len();
-        for (i, record) in log.data.iter().enumerate() {
+        for (i, column) in log.columns().iter().enumerate() {
             assert_eq!(
-                record.len(),
-                channel_count,
-                "Record {} should have {} values, got {}",
+                column.len(),
+                log.times.len(),
+                "Channel {} should have {} values, got {}",
                 i,
-                channel_count,
-                record.len()
+                log.times.len(),
+                column.len()
             );
         }
     }
@@ -156,9 +162,8 @@ pub mod assertions {
 
     /// Assert that all data values are finite (not NaN or Infinity)
     pub fn assert_finite_values(log: &Log) {
-        for (row_idx, row) in log.data.iter().enumerate() {
-            for (col_idx, value) in row.iter().enumerate() {
-                let f = value.as_f64();
+        for (col_idx, column) in log.columns().iter().enumerate() {
+            for (row_idx, &f) in column.iter().enumerate() {
                 assert!(
                     f.is_finite(),
                     "Value at row {}, col {} should be finite, got {}",
@@ -170,13 +175,29 @@ pub mod assertions {
         }
     }
 
+    /// Assert that every value is finite or a missing sample (NaN), for
+    /// formats whose channels can stop or start partway through the log
+    pub fn assert_finite_or_missing_values(log: &Log) {
+        for (col_idx, column) in log.columns().iter().enumerate() {
+            for (row_idx, &f) in column.iter().enumerate() {
+                assert!(
+                    !f.is_infinite(),
+                    "Value at row {}, col {} should be finite or missing, got {}",
+                    row_idx,
+                    col_idx,
+                    f
+                );
+            }
+        }
+    }
+
     /// Assert that a log has at least the minimum expected records
     pub fn assert_minimum_records(log: &Log, min_records: usize) {
         assert!(
-            log.data.len() >= min_records,
+            log.record_count() >= min_records,
             "Expected at least {} records, got {}",
             min_records,
-            log.data.len()
+            log.record_count()
         );
     }
 
@@ -0,0 +1,299 @@
+//! Tests for the parsed-log cache
+//!
+//! Tests cover:
+//! - Round trips of example logs from several formats
+//! - Rejecting entries whose source has changed or that another version wrote
+//! - Rewriting stored computed-channel definitions
+//! - Ignoring damaged cache files
+//! - Pruning the cache directory
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ultralog::cache::{self, SourceKey};
+use ultralog::computed::ComputedChannelTemplate;
+use ultralog::parsers::detection::{self, Detection, DetectionInput};
+use ultralog::parsers::{EcuType, Log};
+
+use crate::common::example_files::*;
+use crate::common::{example_file_exists, read_example_binary};
+
+/// Fresh, empty directory for one test's cache entries
+fn temp_cache_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ultralog-cache-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn parse_example(path: &str) -> (Vec<u8>, Log, EcuType) {
+    let data = read_example_binary(path);
+    let input = DetectionInput::new(&data, Path::new(path));
+    let Detection::Match(detector) = detection::detect(&input) else {
+        panic!("{} should be detected", path);
+    };
+    let log = detector.parse(&input).unwrap();
+    (data, log, detector.ecu_type)
+}
+
+fn key_for(data: &[u8], modified_secs: u64) -> SourceKey {
+    SourceKey::new(data, None, UNIX_EPOCH + Duration::from_secs(modified_secs))
+}
+
+/// Bit-for-bit column equality, so missing (NaN) samples compare equal
+fn same_samples(a: &[f64], b: &[f64]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.to_bits() == y.to_bits())
+}
+
+fn assert_same_log(cached: &Log, parsed: &Log) {
+    assert_eq!(cached.channels.len(), parsed.channels.len());
+    for (a, b) in cached.channels.iter().zip(&parsed.channels) {
+        assert_eq!(a.name(), b.name());
+        assert_eq!(a.unit(), b.unit());
+        assert_eq!(a.type_name(), b.type_name());
+    }
+    assert!(same_samples(&cached.times, &parsed.times));
+    for (a, b) in cached.columns().iter().zip(parsed.columns()) {
+        assert!(same_samples(a, b));
+    }
+    assert_eq!(cached.info(), parsed.info());
+    assert_eq!(cached.annotations, parsed.annotations);
+    assert_eq!(cached.start, parsed.start);
+}
+
+// ============================================
+// Round Trip Tests
+// ============================================
+
+#[test]
+fn test_cache_round_trips_example_logs() {
+    let dir = temp_cache_dir("round-trip");
+
+    for path in [
+        HALTECH_SMALL,
+        LINK_STANDARD,
+        SPEEDUINO_MLG,
+        RUSEFI_LOG1,
+        AEM_DAQ,
+    ] {
+        if !example_file_exists(path) {
+            eprintln!("Skipping {}: not found", path);
+            continue;
+        }
+
+        let (data, parsed, ecu_type) = parse_example(path);
+        let key = key_for(&data, 1_700_000_000);
+        cache::store(&dir, &key, &parsed, ecu_type).unwrap();
+
+        let cached = cache::load(&dir, &key).expect("cache entry should load");
+        assert_eq!(cached.ecu_type, ecu_type, "{}", path);
+        assert!(cached.computed.is_empty());
+        assert_same_log(&cached.log, &parsed);
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_cache_keeps_native_series() {
+    if !example_file_exists(AEM_DAQ) {
+        eprintln!("Skipping test: {} not found", AEM_DAQ);
+        return;
+    }
+
+    let dir = temp_cache_dir("native");
+    let (data, parsed, ecu_type) = parse_example(AEM_DAQ);
+    let key = key_for(&data, 1);
+    cache::store(&dir, &key, &parsed, ecu_type).unwrap();
+    let cached = cache::load(&dir, &key).unwrap().log;
+
+    for index in 0..parsed.channels.len() {
+        match (cached.native_series(index), parsed.native_series(index)) {
+            (Some(a), Some(b)) => {
+                assert!(same_samples(&a.times, &b.times));
+                assert!(same_samples(&a.values, &b.values));
+            }
+            (None, None) => {}
+            _ => panic!("native series of channel {} differs", index),
+        }
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+// ============================================
+// Invalidation Tests
+// ============================================
+
+#[test]
+fn test_cache_misses_when_source_changes() {
+    if !example_file_exists(HALTECH_SMALL) {
+        eprintln!("Skipping test: {} not found", HALTECH_SMALL);
+        return;
+    }
+
+    let dir = temp_cache_dir("stale");
+    let (data, parsed, ecu_type) = parse_example(HALTECH_SMALL);
+    let key = key_for(&data, 100);
+    cache::store(&dir, &key, &parsed, ecu_type).unwrap();
+
+    // Same contents, touched later
+    assert!(cache::load(&dir, &key_for(&data, 200)).is_none());
+
+    // Edited contents
+    let mut edited = data.clone();
+    edited.push(b'\n');
+    assert!(cache::load(&dir, &key_for(&edited, 100)).is_none());
+
+    assert!(cache::load(&dir, &key).is_some());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_cache_misses_when_parser_version_changes() {
+    if !example_file_exists(HALTECH_SMALL) {
+        eprintln!("Skipping test: {} not found", HALTECH_SMALL);
+        return;
+    }
+
+    let dir = temp_cache_dir("version");
+    let (data, parsed, ecu_type) = parse_example(HALTECH_SMALL);
+    let key = key_for(&data, 1);
+    let path = cache::store(&dir, &key, &parsed, ecu_type).unwrap();
+
+    // Same entry as written by another release (same length keeps it in place)
+    let stored = format!(r#""parser_version":"{}""#, env!("CARGO_PKG_VERSION"));
+    let other = stored.replace(|c: char| c.is_ascii_digit(), "9");
+    assert_ne!(stored, other);
+    let mut bytes = fs::read(&path).unwrap();
+    let at = bytes
+        .windows(stored.len())
+        .position(|window| window == stored.as_bytes())
+        .unwrap();
+    bytes[at..at + other.len()].copy_from_slice(other.as_bytes());
+    fs::write(&path, &bytes).unwrap();
+
+    assert!(cache::load(&dir, &key).is_none());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_cache_ignores_damaged_entries() {
+    if !example_file_exists(HALTECH_SMALL) {
+        eprintln!("Skipping test: {} not found", HALTECH_SMALL);
+        return;
+    }
+
+    let dir = temp_cache_dir("damaged");
+    let (data, parsed, ecu_type) = parse_example(HALTECH_SMALL);
+    let key = key_for(&data, 1);
+    let path = cache::store(&dir, &key, &parsed, ecu_type).unwrap();
+
+    let bytes = fs::read(&path).unwrap();
+    fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+    assert!(cache::load(&dir, &key).is_none());
+
+    fs::write(&path, b"not a cache file").unwrap();
+    assert!(cache::load(&dir, &key).is_none());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+// ============================================
+// Computed Channel Tests
+// ============================================
+
+#[test]
+fn test_cache_stores_computed_channels() {
+    if !example_file_exists(HALTECH_SMALL) {
+        eprintln!("Skipping test: {} not found", HALTECH_SMALL);
+        return;
+    }
+
+    let dir = temp_cache_dir("computed");
+    let (data, parsed, ecu_type) = parse_example(HALTECH_SMALL);
+    let key = key_for(&data, 1);
+    cache::store(&dir, &key, &parsed, ecu_type).unwrap();
+
+    let template = ComputedChannelTemplate::new(
+        "Double RPM".to_string(),
+        "RPM * 2".to_string(),
+        "RPM".to_string(),
+        String::new(),
+    );
+    cache::store_computed(&dir, &key, std::slice::from_ref(&template)).unwrap();
+
+    let cached = cache::load(&dir, &key).unwrap();
+    assert_eq!(cached.computed.len(), 1);
+    assert_eq!(cached.computed[0].id, template.id);
+    assert_eq!(cached.computed[0].formula, "RPM * 2");
+    assert_same_log(&cached.log, &parsed);
+
+    // Replacing the list again leaves the samples intact
+    cache::store_computed(&dir, &key, &[]).unwrap();
+    let cached = cache::load(&dir, &key).unwrap();
+    assert!(cached.computed.is_empty());
+    assert_same_log(&cached.log, &parsed);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cache_computed_rewrite_leaves_open_entries_whole() {
+    if !example_file_exists(HALTECH_SMALL) {
+        eprintln!("Skipping test: {} not found", HALTECH_SMALL);
+        return;
+    }
+
+    let dir = temp_cache_dir("rewrite");
+    let (data, parsed, ecu_type) = parse_example(HALTECH_SMALL);
+    let key = key_for(&data, 1);
+    let path = cache::store(&dir, &key, &parsed, ecu_type).unwrap();
+
+    // Another instance with the entry open keeps the file it opened
+    let open = fs::File::open(&path).unwrap();
+    let before = open.metadata().unwrap().len();
+    let template = ComputedChannelTemplate::new(
+        "Double RPM".to_string(),
+        "RPM * 2".to_string(),
+        "RPM".to_string(),
+        String::new(),
+    );
+    cache::store_computed(&dir, &key, &[template]).unwrap();
+
+    assert_eq!(open.metadata().unwrap().len(), before);
+    assert_ne!(fs::metadata(&path).unwrap().len(), before);
+    assert!(!path.with_extension("partial").exists());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+// ============================================
+// Pruning Tests
+// ============================================
+
+#[test]
+fn test_cache_prune_removes_oldest_entries() {
+    if !example_file_exists(HALTECH_SMALL) {
+        eprintln!("Skipping test: {} not found", HALTECH_SMALL);
+        return;
+    }
+
+    let dir = temp_cache_dir("prune");
+    let (data, parsed, ecu_type) = parse_example(HALTECH_SMALL);
+
+    let old_key = SourceKey::new(&data, Some("old"), SystemTime::now());
+    let old = cache::store(&dir, &old_key, &parsed, ecu_type).unwrap();
+    // Make sure the entries' modification times differ
+    std::thread::sleep(Duration::from_millis(20));
+    let new_key = SourceKey::new(&data, Some("new"), SystemTime::now());
+    let new = cache::store(&dir, &new_key, &parsed, ecu_type).unwrap();
+
+    let entry_size = fs::metadata(&new).unwrap().len();
+    cache::prune(&dir, entry_size).unwrap();
+
+    assert!(!old.exists());
+    assert!(new.exists());
+    let _ = fs::remove_dir_all(&dir);
+}
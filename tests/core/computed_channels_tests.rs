@@ -12,7 +12,7 @@ use ultralog::expression::{
     validate_formula,
 };
 use ultralog::parsers::haltech::Haltech;
-use ultralog::parsers::types::{Parseable, Value};
+use ultralog::parsers::types::Parseable;
 
 /// Helper function to read a file, panicking with a clear message if not found.
 fn read_example_file(file_path: &str) -> String {
@@ -243,11 +243,7 @@ fn test_validate_case_insensitive_channel_match() {
 
 #[test]
 fn test_evaluate_simple_addition() {
-    let data = vec![
-        vec![Value::Float(100.0), Value::Float(10.0)],
-        vec![Value::Float(200.0), Value::Float(20.0)],
-        vec![Value::Float(300.0), Value::Float(30.0)],
-    ];
+    let data = vec![vec![100.0, 200.0, 300.0], vec![10.0, 20.0, 30.0]];
     let times = vec![0.0, 0.1, 0.2];
     let mut bindings = HashMap::new();
     bindings.insert("A".to_string(), 0);
@@ -263,10 +259,7 @@ fn test_evaluate_simple_addition() {
 
 #[test]
 fn test_evaluate_multiplication() {
-    let data = vec![
-        vec![Value::Float(2.0), Value::Float(3.0)],
-        vec![Value::Float(4.0), Value::Float(5.0)],
-    ];
+    let data = vec![vec![2.0, 4.0], vec![3.0, 5.0]];
     let times = vec![0.0, 0.1];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -281,7 +274,7 @@ fn test_evaluate_multiplication() {
 
 #[test]
 fn test_evaluate_with_constants() {
-    let data = vec![vec![Value::Float(100.0)], vec![Value::Float(200.0)]];
+    let data = vec![vec![100.0, 200.0]];
     let times = vec![0.0, 0.1];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -295,11 +288,7 @@ fn test_evaluate_with_constants() {
 
 #[test]
 fn test_evaluate_with_math_functions() {
-    let data = vec![
-        vec![Value::Float(4.0)],
-        vec![Value::Float(9.0)],
-        vec![Value::Float(16.0)],
-    ];
+    let data = vec![vec![4.0, 9.0, 16.0]];
     let times = vec![0.0, 0.1, 0.2];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -314,11 +303,7 @@ fn test_evaluate_with_math_functions() {
 
 #[test]
 fn test_evaluate_index_offset_previous() {
-    let data = vec![
-        vec![Value::Float(1000.0)],
-        vec![Value::Float(2000.0)],
-        vec![Value::Float(3000.0)],
-    ];
+    let data = vec![vec![1000.0, 2000.0, 3000.0]];
     let times = vec![0.0, 0.1, 0.2];
     let mut bindings = HashMap::new();
     bindings.insert("RPM".to_string(), 0);
@@ -334,11 +319,7 @@ fn test_evaluate_index_offset_previous() {
 
 #[test]
 fn test_evaluate_index_offset_future() {
-    let data = vec![
-        vec![Value::Float(1000.0)],
-        vec![Value::Float(2000.0)],
-        vec![Value::Float(3000.0)],
-    ];
+    let data = vec![vec![1000.0, 2000.0, 3000.0]];
     let times = vec![0.0, 0.1, 0.2];
     let mut bindings = HashMap::new();
     bindings.insert("RPM".to_string(), 0);
@@ -355,13 +336,7 @@ fn test_evaluate_index_offset_future() {
 #[test]
 fn test_evaluate_time_offset() {
     // Create data with known time steps
-    let data = vec![
-        vec![Value::Float(100.0)],
-        vec![Value::Float(200.0)],
-        vec![Value::Float(300.0)],
-        vec![Value::Float(400.0)],
-        vec![Value::Float(500.0)],
-    ];
+    let data = vec![vec![100.0, 200.0, 300.0, 400.0, 500.0]];
     let times = vec![0.0, 0.1, 0.2, 0.3, 0.4];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -380,10 +355,8 @@ fn test_evaluate_time_offset() {
 
 #[test]
 fn test_evaluate_handles_nan_and_infinity() {
-    let data = vec![
-        vec![Value::Float(0.0)], // Division by zero case
-        vec![Value::Float(1.0)],
-    ];
+    // X = 0 is the division by zero case
+    let data = vec![vec![0.0, 1.0]];
     let times = vec![0.0, 0.1];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -398,7 +371,7 @@ fn test_evaluate_handles_nan_and_infinity() {
 
 #[test]
 fn test_evaluate_empty_data() {
-    let data: Vec<Vec<Value>> = vec![];
+    let data: Vec<Vec<f64>> = vec![];
     let times: Vec<f64> = vec![];
     let bindings = HashMap::new();
 
@@ -412,18 +385,7 @@ fn test_evaluate_empty_data() {
 
 #[test]
 fn test_generate_preview() {
-    let data = vec![
-        vec![Value::Float(1.0)],
-        vec![Value::Float(2.0)],
-        vec![Value::Float(3.0)],
-        vec![Value::Float(4.0)],
-        vec![Value::Float(5.0)],
-        vec![Value::Float(6.0)],
-        vec![Value::Float(7.0)],
-        vec![Value::Float(8.0)],
-        vec![Value::Float(9.0)],
-        vec![Value::Float(10.0)],
-    ];
+    let data = vec![vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0]];
     let times: Vec<f64> = (0..10).map(|i| i as f64 * 0.1).collect();
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -724,17 +686,17 @@ fn test_computed_channel_with_haltech_log() {
     let bindings = build_channel_bindings(&refs, &available_channels).unwrap();
 
     // Evaluate the formula
-    let result = evaluate_all_records(&formula, &bindings, &log.data, &log.times).unwrap();
+    let result = evaluate_all_records(&formula, &bindings, log.columns(), &log.times).unwrap();
 
     // Verify results
     assert_eq!(
         result.len(),
-        log.data.len(),
+        log.record_count(),
         "Result should have same length as input data"
     );
 
     // Verify the formula calculation is correct
-    let original_data = log.get_channel_data(0);
+    let original_data = log.channel_data(0);
     for (i, (&computed, &original)) in result.iter().zip(original_data.iter()).enumerate() {
         let expected = original * 2.0;
         assert!(
@@ -772,15 +734,15 @@ fn test_rate_of_change_formula_with_haltech_log() {
 
     let refs = extract_channel_references(&formula);
     let bindings = build_channel_bindings(&refs, &available_channels).unwrap();
-    let result = evaluate_all_records(&formula, &bindings, &log.data, &log.times).unwrap();
+    let result = evaluate_all_records(&formula, &bindings, log.columns(), &log.times).unwrap();
 
-    assert_eq!(result.len(), log.data.len());
+    assert_eq!(result.len(), log.record_count());
 
     // First value should be 0 (current - clamped previous = current - current)
     assert_eq!(result[0], 0.0);
 
     // Subsequent values should be the difference from previous
-    let original_data = log.get_channel_data(0);
+    let original_data = log.channel_data(0);
     for i in 1..result.len() {
         let expected = original_data[i] - original_data[i - 1];
         assert!(
@@ -826,13 +788,13 @@ fn test_multi_channel_formula_with_haltech_log() {
 
     let refs = extract_channel_references(&formula);
     let bindings = build_channel_bindings(&refs, &available_channels).unwrap();
-    let result = evaluate_all_records(&formula, &bindings, &log.data, &log.times).unwrap();
+    let result = evaluate_all_records(&formula, &bindings, log.columns(), &log.times).unwrap();
 
-    assert_eq!(result.len(), log.data.len());
+    assert_eq!(result.len(), log.record_count());
 
     // Verify the average calculation
-    let data1 = log.get_channel_data(0);
-    let data2 = log.get_channel_data(1);
+    let data1 = log.channel_data(0);
+    let data2 = log.channel_data(1);
     for i in 0..result.len().min(10) {
         // Check first 10 records
         let expected = (data1[i] + data2[i]) / 2.0;
@@ -879,11 +841,7 @@ fn test_formula_with_scientific_notation() {
 
 #[test]
 fn test_large_index_offset_clamping() {
-    let data = vec![
-        vec![Value::Float(1.0)],
-        vec![Value::Float(2.0)],
-        vec![Value::Float(3.0)],
-    ];
+    let data = vec![vec![1.0, 2.0, 3.0]];
     let times = vec![0.0, 0.1, 0.2];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -903,11 +861,7 @@ fn test_large_index_offset_clamping() {
 
 #[test]
 fn test_time_offset_out_of_range_clamping() {
-    let data = vec![
-        vec![Value::Float(100.0)],
-        vec![Value::Float(200.0)],
-        vec![Value::Float(300.0)],
-    ];
+    let data = vec![vec![100.0, 200.0, 300.0]];
     let times = vec![0.0, 0.5, 1.0];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -927,7 +881,7 @@ fn test_time_offset_out_of_range_clamping() {
 
 #[test]
 fn test_single_record_data() {
-    let data = vec![vec![Value::Float(42.0)]];
+    let data = vec![vec![42.0]];
     let times = vec![0.0];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
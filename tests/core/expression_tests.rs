@@ -13,7 +13,6 @@ use ultralog::expression::{
     build_channel_bindings, evaluate_all_records, extract_channel_references, generate_preview,
     validate_formula,
 };
-use ultralog::parsers::types::Value;
 
 // ============================================
 // Channel Reference Extraction Tests
@@ -243,12 +242,8 @@ fn test_validate_negative_numbers() {
 // Formula Evaluation Tests
 // ============================================
 
-fn create_test_data() -> (Vec<Vec<Value>>, Vec<f64>) {
-    let data = vec![
-        vec![Value::Float(100.0), Value::Float(10.0)],
-        vec![Value::Float(200.0), Value::Float(20.0)],
-        vec![Value::Float(300.0), Value::Float(30.0)],
-    ];
+fn create_test_data() -> (Vec<Vec<f64>>, Vec<f64>) {
+    let data = vec![vec![100.0, 200.0, 300.0], vec![10.0, 20.0, 30.0]];
     let times = vec![0.0, 0.1, 0.2];
     (data, times)
 }
@@ -297,11 +292,7 @@ fn test_evaluate_with_constants() {
 
 #[test]
 fn test_evaluate_sqrt() {
-    let data = vec![
-        vec![Value::Float(4.0)],
-        vec![Value::Float(9.0)],
-        vec![Value::Float(16.0)],
-    ];
+    let data = vec![vec![4.0, 9.0, 16.0]];
     let times = vec![0.0, 0.1, 0.2];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -315,11 +306,7 @@ fn test_evaluate_sqrt() {
 
 #[test]
 fn test_evaluate_index_offset_previous() {
-    let data = vec![
-        vec![Value::Float(1000.0)],
-        vec![Value::Float(2000.0)],
-        vec![Value::Float(3000.0)],
-    ];
+    let data = vec![vec![1000.0, 2000.0, 3000.0]];
     let times = vec![0.0, 0.1, 0.2];
     let mut bindings = HashMap::new();
     bindings.insert("RPM".to_string(), 0);
@@ -333,11 +320,7 @@ fn test_evaluate_index_offset_previous() {
 
 #[test]
 fn test_evaluate_index_offset_future() {
-    let data = vec![
-        vec![Value::Float(1000.0)],
-        vec![Value::Float(2000.0)],
-        vec![Value::Float(3000.0)],
-    ];
+    let data = vec![vec![1000.0, 2000.0, 3000.0]];
     let times = vec![0.0, 0.1, 0.2];
     let mut bindings = HashMap::new();
     bindings.insert("RPM".to_string(), 0);
@@ -351,13 +334,7 @@ fn test_evaluate_index_offset_future() {
 
 #[test]
 fn test_evaluate_time_offset() {
-    let data = vec![
-        vec![Value::Float(100.0)],
-        vec![Value::Float(200.0)],
-        vec![Value::Float(300.0)],
-        vec![Value::Float(400.0)],
-        vec![Value::Float(500.0)],
-    ];
+    let data = vec![vec![100.0, 200.0, 300.0, 400.0, 500.0]];
     let times = vec![0.0, 0.1, 0.2, 0.3, 0.4];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -371,7 +348,7 @@ fn test_evaluate_time_offset() {
 
 #[test]
 fn test_evaluate_division_by_zero() {
-    let data = vec![vec![Value::Float(0.0)], vec![Value::Float(1.0)]];
+    let data = vec![vec![0.0, 1.0]];
     let times = vec![0.0, 0.1];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -385,7 +362,7 @@ fn test_evaluate_division_by_zero() {
 
 #[test]
 fn test_evaluate_empty_data() {
-    let data: Vec<Vec<Value>> = vec![];
+    let data: Vec<Vec<f64>> = vec![];
     let times: Vec<f64> = vec![];
     let bindings = HashMap::new();
 
@@ -395,11 +372,7 @@ fn test_evaluate_empty_data() {
 
 #[test]
 fn test_evaluate_large_offset_clamping() {
-    let data = vec![
-        vec![Value::Float(1.0)],
-        vec![Value::Float(2.0)],
-        vec![Value::Float(3.0)],
-    ];
+    let data = vec![vec![1.0, 2.0, 3.0]];
     let times = vec![0.0, 0.1, 0.2];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -419,7 +392,7 @@ fn test_evaluate_large_offset_clamping() {
 
 #[test]
 fn test_evaluate_single_record() {
-    let data = vec![vec![Value::Float(42.0)]];
+    let data = vec![vec![42.0]];
     let times = vec![0.0];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -472,7 +445,7 @@ fn test_build_bindings_missing_channel() {
 
 #[test]
 fn test_generate_preview() {
-    let data: Vec<Vec<Value>> = (0..10).map(|i| vec![Value::Float(i as f64)]).collect();
+    let data: Vec<Vec<f64>> = vec![(0..10).map(|i| i as f64).collect()];
     let times: Vec<f64> = (0..10).map(|i| i as f64 * 0.1).collect();
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -487,7 +460,7 @@ fn test_generate_preview() {
 
 #[test]
 fn test_generate_preview_more_than_available() {
-    let data = vec![vec![Value::Float(1.0)], vec![Value::Float(2.0)]];
+    let data = vec![vec![1.0, 2.0]];
     let times = vec![0.0, 0.1];
     let mut bindings = HashMap::new();
     bindings.insert("X".to_string(), 0);
@@ -0,0 +1,245 @@
+//! Tests for live CAN streaming
+//!
+//! Tests cover:
+//! - Forwarding frames from the reader thread
+//! - Recording frames to a candump log that opens again as a trace
+//! - Reporting read failures
+//! - Streaming from a SocketCAN interface (`vcan0`, when present)
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+
+use ultralog::adapters::can::CanFrame;
+use ultralog::adapters::ProtocolSpec;
+use ultralog::live::{FrameSource, LiveEvent, LiveStream, Recording};
+use ultralog::parsers::can_trace::{read_trace, CanStream, CanTrace, TraceFormat, TraceFrame};
+use ultralog::parsers::Parseable;
+
+const PROTOCOL_YAML: &str = r#"
+openecualliance: "1.0"
+type: protocol
+id: acme-live
+name: Acme Live
+version: "1.0.0"
+vendor: acme
+protocol:
+  type: can
+  baudrate: 500000
+messages:
+  - id: "0x360"
+    name: Engine
+    length: 4
+    signals:
+      - { name: Engine RPM, start_bit: 0, length: 16, byte_order: big_endian, data_type: unsigned, unit: rpm }
+      - { name: Throttle, start_bit: 16, length: 16, byte_order: big_endian, data_type: unsigned, scale: 0.1, unit: "%" }
+"#;
+
+/// Frames handed to the stream by the test, as if read from a bus
+struct TestBus(Receiver<TraceFrame>);
+
+impl FrameSource for TestBus {
+    fn read_frame(&mut self, timeout: Duration) -> io::Result<Option<TraceFrame>> {
+        match self.0.recv_timeout(timeout) {
+            Ok(frame) => Ok(Some(frame)),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => Err(io::Error::other("bus went down")),
+        }
+    }
+}
+
+fn test_stream() -> (Sender<TraceFrame>, LiveStream) {
+    let (sender, receiver) = channel();
+    (sender, LiveStream::start("test0", TestBus(receiver)))
+}
+
+fn engine_frame(time: f64, rpm: u16) -> TraceFrame {
+    let [high, low] = rpm.to_be_bytes();
+    TraceFrame {
+        time,
+        frame: CanFrame::new(0x360, false, vec![high, low, 0x01, 0xF4]),
+    }
+}
+
+/// Poll until `count` frames have arrived, collecting any other events too
+fn wait_for_frames(stream: &LiveStream, count: usize) -> (Vec<TraceFrame>, Vec<LiveEvent>) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let mut frames = Vec::new();
+    let mut others = Vec::new();
+    while frames.len() < count && Instant::now() < deadline {
+        for event in stream.poll() {
+            match event {
+                LiveEvent::Frame(frame) => frames.push(frame),
+                other => others.push(other),
+            }
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    (frames, others)
+}
+
+fn temp_log(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ultralog-live-{}-{}.log", name, std::process::id()))
+}
+
+// ============================================
+// Streaming Tests
+// ============================================
+
+#[test]
+fn test_live_stream_forwards_frames() {
+    let (bus, stream) = test_stream();
+    assert_eq!(stream.interface(), "test0");
+    assert!(stream.is_running());
+
+    for (i, rpm) in [900, 1500, 2200].into_iter().enumerate() {
+        bus.send(engine_frame(1_700_000_000.0 + i as f64 * 0.01, rpm))
+            .unwrap();
+    }
+
+    let (frames, others) = wait_for_frames(&stream, 3);
+    assert!(others.is_empty());
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[2], engine_frame(1_700_000_000.02, 2200));
+}
+
+#[test]
+fn test_live_stream_decodes_into_growing_log() {
+    let protocol: ProtocolSpec = serde_yaml::from_str(PROTOCOL_YAML).unwrap();
+    let mut decoder = CanStream::new(protocol);
+    let mut log = decoder.empty_log("SocketCAN test0");
+    let (bus, stream) = test_stream();
+
+    for i in 0..10 {
+        bus.send(engine_frame(
+            1_700_000_000.0 + i as f64 * 0.02,
+            1000 + i * 100,
+        ))
+        .unwrap();
+    }
+    let (frames, _) = wait_for_frames(&stream, 10);
+    for frame in &frames {
+        decoder.push(&mut log, frame);
+    }
+
+    assert_eq!(log.channels.len(), 2);
+    let rpm = log.native_series(0).unwrap();
+    assert_eq!(rpm.values.last(), Some(&1900.0));
+    assert!((log.end_time().unwrap() - 0.18).abs() < 1e-6);
+    assert!(log.record_count() >= 3);
+}
+
+#[test]
+fn test_live_stream_reports_read_errors() {
+    let (bus, stream) = test_stream();
+    drop(bus);
+
+    // The reader reports the failure and exits
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while stream.is_running() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(!stream.is_running());
+    let events = stream.poll();
+    assert!(matches!(events.as_slice(), [LiveEvent::Error(e)] if e.contains("bus went down")));
+}
+
+// ============================================
+// Recording Tests
+// ============================================
+
+#[test]
+fn test_live_stream_records_candump() {
+    let path = temp_log("record");
+    let (bus, stream) = test_stream();
+
+    // Frames before recording starts aren't written
+    bus.send(engine_frame(1_700_000_000.0, 800)).unwrap();
+    wait_for_frames(&stream, 1);
+
+    assert_eq!(stream.start_recording(&path).unwrap(), None);
+    assert!(stream.is_recording());
+    let sent: Vec<TraceFrame> = (0..5)
+        .map(|i| engine_frame(1_700_000_001.0 + i as f64 * 0.02, 3000 + i * 10))
+        .collect();
+    for frame in &sent {
+        bus.send(frame.clone()).unwrap();
+    }
+    wait_for_frames(&stream, sent.len());
+
+    let recording = stream.stop_recording().unwrap();
+    assert_eq!(
+        recording,
+        Some(Recording {
+            path: path.clone(),
+            frames: 5
+        })
+    );
+    assert!(!stream.is_recording());
+
+    // The recording opens again like any other trace
+    let text = fs::read_to_string(&path).unwrap();
+    assert_eq!(TraceFormat::detect(&text), Some(TraceFormat::Candump));
+    let trace = read_trace(TraceFormat::Candump, &text);
+    assert_eq!(trace.frames.len(), 5);
+    for (read, sent) in trace.frames.iter().zip(&sent) {
+        assert_eq!(read.frame, sent.frame);
+        assert!((read.time - sent.time).abs() < 1e-6);
+    }
+
+    let protocol: ProtocolSpec = serde_yaml::from_str(PROTOCOL_YAML).unwrap();
+    let log = CanTrace::new(protocol).parse(&text).unwrap();
+    assert_eq!(log.channel_data(0).first(), Some(&3000.0));
+
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn test_live_recording_finishes_on_drop() {
+    let path = temp_log("drop");
+    let (bus, stream) = test_stream();
+    stream.start_recording(&path).unwrap();
+    bus.send(engine_frame(1_700_000_000.0, 1200)).unwrap();
+    wait_for_frames(&stream, 1);
+    drop(stream);
+
+    let text = fs::read_to_string(&path).unwrap();
+    assert_eq!(read_trace(TraceFormat::Candump, &text).frames.len(), 1);
+
+    let _ = fs::remove_file(&path);
+}
+
+// ============================================
+// SocketCAN Tests
+// ============================================
+
+/// Round trip through `vcan0`. Skipped unless the interface is up:
+/// `sudo modprobe vcan && sudo ip link add dev vcan0 type vcan && sudo ip link set up vcan0`
+#[cfg(target_os = "linux")]
+#[test]
+fn test_live_stream_vcan() {
+    use ultralog::live::socketcan::SocketCan;
+
+    let sender = match SocketCan::open("vcan0") {
+        Ok(socket) => socket,
+        Err(e) => {
+            eprintln!("Skipping vcan0 test: {}", e);
+            return;
+        }
+    };
+    let stream = LiveStream::open("vcan0").expect("vcan0 opened once already");
+
+    let frame = CanFrame::new(0x360, false, vec![0x0B, 0xB8, 0x03, 0xE8]);
+    let extended = CanFrame::new(0x18FF00E5, true, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+    sender.write(&frame).unwrap();
+    sender.write(&extended).unwrap();
+
+    let (frames, others) = wait_for_frames(&stream, 2);
+    assert!(others.is_empty());
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].frame, frame);
+    assert_eq!(frames[1].frame, extended);
+    assert!(frames[0].time > 1_000_000_000.0, "Kernel receive times");
+}
@@ -8,10 +8,14 @@
 //! - Computed channels
 //! - Internationalization (i18n)
 //! - User settings persistence
+//! - Parsed-log cache
+//! - Live CAN streaming
 
+pub mod cache_tests;
 pub mod computed_channels_tests;
 pub mod expression_tests;
 pub mod i18n_tests;
+pub mod live_tests;
 pub mod normalize_tests;
 pub mod settings_tests;
 pub mod state_tests;
@@ -9,7 +9,7 @@
 
 use std::path::PathBuf;
 use ultralog::parsers::haltech::{ChannelType, HaltechChannel};
-use ultralog::parsers::types::{EcuType, Log, Value};
+use ultralog::parsers::types::{EcuType, Log};
 use ultralog::parsers::Channel;
 use ultralog::state::{
     ActiveTool, CacheKey, HistogramConfig, HistogramGridSize, HistogramMode, HistogramState,
@@ -24,18 +24,20 @@ use ultralog::state::{
 
 #[test]
 fn test_max_channels_reasonable() {
-    assert!(MAX_CHANNELS >= 1, "Should allow at least 1 channel");
-    assert!(MAX_CHANNELS <= 20, "Should not allow too many channels");
+    const { assert!(MAX_CHANNELS >= 1, "Should allow at least 1 channel") };
+    const { assert!(MAX_CHANNELS <= 20, "Should not allow too many channels") };
     assert_eq!(MAX_CHANNELS, 10, "Expected 10 max channels");
 }
 
 #[test]
 fn test_max_chart_points_reasonable() {
-    assert!(
-        MAX_CHART_POINTS >= 100,
-        "Should have minimum points for visualization"
-    );
-    assert!(MAX_CHART_POINTS <= 10000, "Should not have too many points");
+    const {
+        assert!(
+            MAX_CHART_POINTS >= 100,
+            "Should have minimum points for visualization"
+        )
+    };
+    const { assert!(MAX_CHART_POINTS <= 10000, "Should not have too many points") };
     assert_eq!(MAX_CHART_POINTS, 2000, "Expected 2000 max chart points");
 }
 
@@ -53,6 +55,13 @@ fn test_supported_extensions_contains_common() {
     assert!(SUPPORTED_EXTENSIONS.contains(&"mlg"), "Should support MLG");
     assert!(SUPPORTED_EXTENSIONS.contains(&"xrk"), "Should support XRK");
     assert!(SUPPORTED_EXTENSIONS.contains(&"llg"), "Should support LLG");
+    assert!(
+        SUPPORTED_EXTENSIONS.contains(&"emublog"),
+        "Should support EMUBLOG"
+    );
+    assert!(SUPPORTED_EXTENSIONS.contains(&"daq"), "Should support DAQ");
+    assert!(SUPPORTED_EXTENSIONS.contains(&"msl"), "Should support MSL");
+    assert!(SUPPORTED_EXTENSIONS.contains(&"ld"), "Should support LD");
 }
 
 // ============================================
@@ -310,11 +319,13 @@ fn test_scatter_plot_config_default() {
 
 #[test]
 fn test_scatter_plot_config_with_values() {
-    let mut config = ScatterPlotConfig::default();
-    config.file_index = Some(0);
-    config.x_channel = Some(1);
-    config.y_channel = Some(2);
-    config.z_channel = Some(3);
+    let config = ScatterPlotConfig {
+        file_index: Some(0),
+        x_channel: Some(1),
+        y_channel: Some(2),
+        z_channel: Some(3),
+        ..Default::default()
+    };
 
     assert_eq!(config.file_index, Some(0));
     assert_eq!(config.x_channel, Some(1));
@@ -391,6 +402,7 @@ fn test_tab_new() {
     assert!(!tab.chart_interacted);
     assert!(tab.time_range.is_none());
     assert!(tab.jump_to_time.is_none());
+    assert!(!tab.follow_live);
 }
 
 #[test]
@@ -448,9 +460,9 @@ fn test_selected_channel_clone() {
 // ============================================
 
 fn create_test_log() -> Log {
-    Log {
-        meta: ultralog::parsers::types::Meta::Empty,
-        channels: vec![
+    Log::new(
+        ultralog::parsers::types::Meta::Empty,
+        vec![
             Channel::Haltech(HaltechChannel {
                 name: "Engine Speed".to_string(),
                 id: "0".to_string(),
@@ -466,13 +478,9 @@ fn create_test_log() -> Log {
                 display_max: Some(100.0),
             }),
         ],
-        times: vec![0.0, 0.1, 0.2],
-        data: vec![
-            vec![Value::Float(5000.0), Value::Float(50.0)],
-            vec![Value::Float(5100.0), Value::Float(0.0)],
-            vec![Value::Float(0.0), Value::Float(0.0)],
-        ],
-    }
+        vec![0.0, 0.1, 0.2],
+        vec![vec![5000.0, 5100.0, 0.0], vec![50.0, 0.0, 0.0]],
+    )
 }
 
 #[test]
@@ -527,22 +535,18 @@ fn test_loaded_file_channel_has_data() {
 
 #[test]
 fn test_loaded_file_all_zero_channel() {
-    let log = Log {
-        meta: ultralog::parsers::types::Meta::Empty,
-        channels: vec![Channel::Haltech(HaltechChannel {
+    let log = Log::new(
+        ultralog::parsers::types::Meta::Empty,
+        vec![Channel::Haltech(HaltechChannel {
             name: "Zero Channel".to_string(),
             id: "0".to_string(),
             r#type: ChannelType::Raw,
             display_min: Some(0.0),
             display_max: Some(100.0),
         })],
-        times: vec![0.0, 0.1, 0.2],
-        data: vec![
-            vec![Value::Float(0.0)],
-            vec![Value::Float(0.0)],
-            vec![Value::Float(0.0)],
-        ],
-    };
+        vec![0.0, 0.1, 0.2],
+        vec![vec![0.0, 0.0, 0.0]],
+    );
 
     let file = LoadedFile::new(
         PathBuf::from("/test/path.csv"),
@@ -558,21 +562,19 @@ fn test_loaded_file_all_zero_channel() {
 #[test]
 fn test_loaded_file_near_zero_channel() {
     // Values very close to zero should be considered as no data
-    let log = Log {
-        meta: ultralog::parsers::types::Meta::Empty,
-        channels: vec![Channel::Haltech(HaltechChannel {
+    let log = Log::new(
+        ultralog::parsers::types::Meta::Empty,
+        vec![Channel::Haltech(HaltechChannel {
             name: "Near Zero".to_string(),
             id: "0".to_string(),
             r#type: ChannelType::Raw,
             display_min: Some(0.0),
             display_max: Some(100.0),
         })],
-        times: vec![0.0, 0.1],
-        data: vec![
-            vec![Value::Float(0.00001)], // Below threshold
-            vec![Value::Float(0.00002)], // Below threshold
-        ],
-    };
+        vec![0.0, 0.1],
+        // Both values are below the threshold
+        vec![vec![0.00001, 0.00002]],
+    );
 
     let file = LoadedFile::new(
         PathBuf::from("/test/path.csv"),
@@ -775,13 +777,15 @@ fn test_histogram_config_default() {
 
 #[test]
 fn test_histogram_config_with_values() {
-    let mut config = HistogramConfig::default();
-    config.x_channel = Some(0);
-    config.y_channel = Some(1);
-    config.z_channel = Some(2);
-    config.mode = HistogramMode::HitCount;
-    config.grid_size = HistogramGridSize::Size64;
-    config.selected_cell = Some(SelectedHistogramCell::default());
+    let config = HistogramConfig {
+        x_channel: Some(0),
+        y_channel: Some(1),
+        z_channel: Some(2),
+        mode: HistogramMode::HitCount,
+        grid_size: HistogramGridSize::Size64,
+        selected_cell: Some(SelectedHistogramCell::default()),
+        ..Default::default()
+    };
 
     assert_eq!(config.x_channel, Some(0));
     assert_eq!(config.y_channel, Some(1));
@@ -793,10 +797,12 @@ fn test_histogram_config_with_values() {
 
 #[test]
 fn test_histogram_config_clone() {
-    let mut config = HistogramConfig::default();
-    config.x_channel = Some(5);
-    config.y_channel = Some(10);
-    config.mode = HistogramMode::HitCount;
+    let config = HistogramConfig {
+        x_channel: Some(5),
+        y_channel: Some(10),
+        mode: HistogramMode::HitCount,
+        ..Default::default()
+    };
 
     let cloned = config.clone();
 
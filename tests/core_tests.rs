@@ -4,7 +4,7 @@
 //! and computed channels.
 
 #[path = "common/mod.rs"]
-mod common;
+pub mod common;
 
 #[path = "core/mod.rs"]
 mod core_tests;
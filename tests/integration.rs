@@ -4,7 +4,7 @@
 //! and cross-format data integrity.
 
 #[path = "common/mod.rs"]
-mod common;
+pub mod common;
 
 #[path = "integration/mod.rs"]
 mod integration_tests;
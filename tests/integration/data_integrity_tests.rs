@@ -2,11 +2,10 @@
 //!
 //! Tests verify that parsed data meets quality and consistency requirements.
 
-#[path = "../common/mod.rs"]
-mod common;
-
-use common::example_files::*;
-use common::{example_file_exists, get_example_file_path, read_example_binary, read_example_file};
+use crate::common::example_files::*;
+use crate::common::{
+    example_file_exists, get_example_file_path, read_example_binary, read_example_file,
+};
 use std::path::Path;
 use ultralog::parsers::aim::Aim;
 use ultralog::parsers::ecumaster::EcuMaster;
@@ -93,9 +92,8 @@ fn test_link_time_monotonicity() {
 // ============================================
 
 fn assert_all_values_finite(log: &Log, format: &str) {
-    for (row_idx, row) in log.data.iter().enumerate() {
-        for (col_idx, value) in row.iter().enumerate() {
-            let f = value.as_f64();
+    for (col_idx, column) in log.columns().iter().enumerate() {
+        for (row_idx, &f) in column.iter().enumerate() {
             assert!(
                 f.is_finite(),
                 "{}: Value at row {}, col {} should be finite, got {}",
@@ -168,25 +166,25 @@ fn test_link_values_finite() {
 // ============================================
 
 fn assert_data_alignment(log: &Log, format: &str) {
-    // Times and data should have same length
+    // Every channel should have a data column
+    let channel_count = log.channels.len();
     assert_eq!(
-        log.times.len(),
-        log.data.len(),
-        "{}: Times and data should have same length",
+        log.columns().len(),
+        channel_count,
+        "{}: Every channel should have a data column",
         format
     );
 
-    // Each record should have correct number of values
-    let channel_count = log.channels.len();
-    for (i, record) in log.data.iter().enumerate() {
+    // Each column should have one value per timestamp
+    for (i, column) in log.columns().iter().enumerate() {
         assert_eq!(
-            record.len(),
-            channel_count,
-            "{}: Record {} should have {} values, got {}",
+            column.len(),
+            log.times.len(),
+            "{}: Channel {} should have {} values, got {}",
             format,
             i,
-            channel_count,
-            record.len()
+            log.times.len(),
+            column.len()
         );
     }
 }
@@ -331,16 +329,16 @@ fn test_channel_data_extraction_consistency() {
     let content = read_example_file(HALTECH_SMALL);
     let log = Haltech.parse(&content).expect("Should parse");
 
-    // Verify get_channel_data returns consistent data
+    // Verify channel_data returns consistent data
     for idx in 0..log.channels.len() {
-        let data = log.get_channel_data(idx);
+        let data = log.channel_data(idx);
 
         // Length should match data rows
-        assert_eq!(data.len(), log.data.len());
+        assert_eq!(data.len(), log.record_count());
 
         // Values should match direct access
         for (i, &value) in data.iter().enumerate() {
-            let direct = log.data[i][idx].as_f64();
+            let direct = log.channel_data(idx)[i];
             assert_eq!(value, direct, "Channel {} data at row {} mismatch", idx, i);
         }
     }
@@ -356,10 +354,10 @@ fn test_out_of_bounds_channel_access() {
     let log = Haltech.parse(&content).expect("Should parse");
 
     // Out of bounds should return empty, not panic
-    let oob = log.get_channel_data(999);
+    let oob = log.channel_data(999);
     assert!(oob.is_empty());
 
-    let oob = log.get_channel_data(usize::MAX);
+    let oob = log.channel_data(usize::MAX);
     assert!(oob.is_empty());
 }
 
@@ -408,31 +406,31 @@ fn test_all_formats_have_data() {
     if example_file_exists(HALTECH_SMALL) {
         let content = read_example_file(HALTECH_SMALL);
         let log = Haltech.parse(&content).expect("Haltech");
-        assert!(!log.data.is_empty(), "Haltech should have data");
+        assert!(!log.is_empty(), "Haltech should have data");
     }
 
     if example_file_exists(ECUMASTER_STANDARD) {
         let content = read_example_file(ECUMASTER_STANDARD);
         let log = EcuMaster.parse(&content).expect("ECUMaster");
-        assert!(!log.data.is_empty(), "ECUMaster should have data");
+        assert!(!log.is_empty(), "ECUMaster should have data");
     }
 
     // Binary formats
     if example_file_exists(SPEEDUINO_MLG) {
         let data = read_example_binary(SPEEDUINO_MLG);
         let log = Speeduino::parse_binary(&data).expect("Speeduino");
-        assert!(!log.data.is_empty(), "Speeduino should have data");
+        assert!(!log.is_empty(), "Speeduino should have data");
     }
 
     if example_file_exists(AIM_GENERIC) {
         let path = get_example_file_path(AIM_GENERIC);
         let log = Aim::parse_file(Path::new(&path)).expect("AiM");
-        assert!(!log.data.is_empty(), "AiM should have data");
+        assert!(!log.is_empty(), "AiM should have data");
     }
 
     if example_file_exists(LINK_STANDARD) {
         let data = read_example_binary(LINK_STANDARD);
         let log = Link::parse_binary(&data).expect("Link");
-        assert!(!log.data.is_empty(), "Link should have data");
+        assert!(!log.is_empty(), "Link should have data");
     }
 }
@@ -2,15 +2,17 @@
 //!
 //! Tests for end-to-end file loading cycles across all supported formats.
 
-#[path = "../common/mod.rs"]
-mod common;
-
-use common::assertions::*;
-use common::example_files::*;
-use common::{example_file_exists, get_example_file_path, read_example_binary, read_example_file};
+use crate::common::assertions::*;
+use crate::common::example_files::*;
+use crate::common::{
+    example_file_exists, get_example_file_path, read_example_binary, read_example_file,
+};
+use std::io::{Cursor, Write};
 use std::path::Path;
+use ultralog::decompress::{self, Compression};
 use ultralog::parsers::aim::Aim;
 use ultralog::parsers::ecumaster::EcuMaster;
+use ultralog::parsers::emublog::EmuBlog;
 use ultralog::parsers::haltech::Haltech;
 use ultralog::parsers::link::Link;
 use ultralog::parsers::romraider::RomRaider;
@@ -44,8 +46,8 @@ fn test_load_haltech_complete_cycle() {
 
     // Access data
     for idx in 0..log.channels.len() {
-        let data = log.get_channel_data(idx);
-        assert_eq!(data.len(), log.data.len());
+        let data = log.channel_data(idx);
+        assert_eq!(data.len(), log.record_count());
     }
 }
 
@@ -71,8 +73,8 @@ fn test_load_ecumaster_complete_cycle() {
 
     // Access data
     for idx in 0..log.channels.len() {
-        let data = log.get_channel_data(idx);
-        assert_eq!(data.len(), log.data.len());
+        let data = log.channel_data(idx);
+        assert_eq!(data.len(), log.record_count());
     }
 }
 
@@ -121,8 +123,8 @@ fn test_load_speeduino_complete_cycle() {
 
     // Access data
     for idx in 0..log.channels.len() {
-        let channel_data = log.get_channel_data(idx);
-        assert_eq!(channel_data.len(), log.data.len());
+        let channel_data = log.channel_data(idx);
+        assert_eq!(channel_data.len(), log.record_count());
     }
 }
 
@@ -178,7 +180,7 @@ fn test_load_empty_text_file() {
 
     // Should return error or empty log
     match result {
-        Ok(log) => assert!(log.data.is_empty()),
+        Ok(log) => assert!(log.is_empty()),
         Err(_) => { /* Expected */ }
     }
 }
@@ -188,7 +190,7 @@ fn test_load_empty_binary_file() {
     let result = Speeduino::parse_binary(b"");
 
     match result {
-        Ok(log) => assert!(log.data.is_empty()),
+        Ok(log) => assert!(log.is_empty()),
         Err(_) => { /* Expected */ }
     }
 }
@@ -217,6 +219,106 @@ fn test_load_corrupted_binary() {
     let _ = Link::parse_binary(corrupted);
 }
 
+// ============================================
+// Compressed File Loading Tests
+// ============================================
+
+fn zip_archive(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, contents) in entries {
+        writer
+            .start_file(*name, zip::write::SimpleFileOptions::default())
+            .expect("Should start zip entry");
+        writer.write_all(contents).expect("Should write zip entry");
+    }
+    writer.finish().expect("Should finish zip").into_inner()
+}
+
+#[test]
+fn test_load_gzipped_haltech_log() {
+    if !example_file_exists(HALTECH_SMALL) {
+        eprintln!("Skipping: {} not found", HALTECH_SMALL);
+        return;
+    }
+
+    let original = read_example_binary(HALTECH_SMALL);
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&original).expect("Should compress");
+    let compressed = encoder.finish().expect("Should finish gzip");
+
+    assert_eq!(
+        decompress::detect_compression(&compressed),
+        Some(Compression::Gzip)
+    );
+    let inner = decompress::decompress_gzip(&compressed).expect("Should decompress");
+    assert_eq!(inner, original);
+
+    let content = String::from_utf8(inner).expect("Should be UTF-8");
+    let log = Haltech
+        .parse(&content)
+        .expect("Should parse decompressed log");
+    assert_valid_log_structure(&log);
+}
+
+#[test]
+fn test_load_gzipped_emublog_after_decompression() {
+    if !example_file_exists(ECUMASTER_EMUBLOG) {
+        eprintln!("Skipping: {} not found", ECUMASTER_EMUBLOG);
+        return;
+    }
+
+    let data = read_example_binary(ECUMASTER_EMUBLOG);
+    assert_eq!(
+        decompress::detect_compression(&data),
+        Some(Compression::Gzip)
+    );
+
+    // Detection must still work once the loader has stripped the gzip layer
+    let inner = decompress::decompress_gzip(&data).expect("Should decompress");
+    assert!(EmuBlog::detect(&inner));
+    let log = EmuBlog::parse_binary(&inner).expect("Should parse");
+    assert_valid_log_structure(&log);
+}
+
+#[test]
+fn test_load_zip_with_single_log() {
+    let csv = b"TIME;engine/rpm\n0.0;1000\n0.1;1100\n";
+    let archive = zip_archive(&[("notes.txt.pdf", b"ignored"), ("run.csv", csv)]);
+
+    assert_eq!(
+        decompress::detect_compression(&archive),
+        Some(Compression::Zip)
+    );
+    let entries = decompress::zip_log_entries(&archive).expect("Should list entries");
+    assert_eq!(entries, vec!["run.csv"]);
+
+    let inner = decompress::extract_zip_entry(&archive, &entries[0]).expect("Should extract");
+    let content = String::from_utf8(inner).expect("Should be UTF-8");
+    assert!(EcuMaster::detect(&content));
+    let log = EcuMaster.parse(&content).expect("Should parse");
+    assert_eq!(log.record_count(), 2);
+}
+
+#[test]
+fn test_load_zip_with_several_logs_lists_all() {
+    let archive = zip_archive(&[
+        ("day1/run1.csv", b"a"),
+        ("day1/run2.csv", b"b"),
+        ("day2/run3.mlg", b"c"),
+    ]);
+
+    let entries = decompress::zip_log_entries(&archive).expect("Should list entries");
+    assert_eq!(
+        entries.len(),
+        3,
+        "Every log should be offered for selection"
+    );
+    assert_eq!(
+        decompress::extract_zip_entry(&archive, "day1/run2.csv").unwrap(),
+        b"b"
+    );
+}
+
 // ============================================
 // Large File Tests
 // ============================================
@@ -242,7 +344,7 @@ fn test_load_large_haltech_file() {
 
     eprintln!(
         "Large Haltech: {} records, read: {:?}, parse: {:?}",
-        log.data.len(),
+        log.record_count(),
         read_time,
         parse_time
     );
@@ -269,7 +371,7 @@ fn test_load_large_ecumaster_file() {
 
     eprintln!(
         "Large ECUMaster: {} records, read: {:?}, parse: {:?}",
-        log.data.len(),
+        log.record_count(),
         read_time,
         parse_time
     );
@@ -37,23 +37,24 @@ fn test_haltech_example_log_parsing() {
     // Verify structure
     assert!(!log.channels.is_empty(), "Should have channels");
     assert!(!log.times.is_empty(), "Should have timestamps");
-    assert!(!log.data.is_empty(), "Should have data records");
+    assert!(!log.is_empty(), "Should have data records");
 
     // Verify data integrity
     assert_eq!(
         log.times.len(),
-        log.data.len(),
+        log.record_count(),
         "Times and data should have same length"
     );
 
     // All records should have same number of values as channels
     let channel_count = log.channels.len();
-    for record in &log.data {
+    assert_eq!(log.columns().len(), channel_count);
+    for column in log.columns() {
         assert_eq!(
-            record.len(),
-            channel_count,
-            "Each record should have {} values",
-            channel_count
+            column.len(),
+            log.times.len(),
+            "Each channel should have {} values",
+            log.times.len()
         );
     }
 
@@ -69,7 +70,7 @@ fn test_haltech_example_log_parsing() {
     eprintln!(
         "Haltech log: {} channels, {} records, time range: {:.2}s to {:.2}s",
         log.channels.len(),
-        log.data.len(),
+        log.record_count(),
         times.first().unwrap_or(&0.0),
         times.last().unwrap_or(&0.0)
     );
@@ -87,12 +88,12 @@ fn test_haltech_multi_log_file() {
         .expect("Should parse multi-log Haltech file");
 
     assert!(!log.channels.is_empty(), "Should have channels");
-    assert!(!log.data.is_empty(), "Should have data records");
+    assert!(!log.is_empty(), "Should have data records");
 
     eprintln!(
         "Haltech multi-log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -116,19 +117,19 @@ fn test_ecumaster_example_log_parsing() {
 
     assert!(!log.channels.is_empty(), "Should have channels");
     assert!(!log.times.is_empty(), "Should have timestamps");
-    assert!(!log.data.is_empty(), "Should have data records");
+    assert!(!log.is_empty(), "Should have data records");
 
     // Verify data integrity
     assert_eq!(
         log.times.len(),
-        log.data.len(),
+        log.record_count(),
         "Times and data should have same length"
     );
 
     eprintln!(
         "ECUMaster log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -150,12 +151,12 @@ fn test_ecumaster_large_file() {
         .expect("Should parse large ECUMaster log");
 
     assert!(!log.channels.is_empty(), "Should have channels");
-    assert!(!log.data.is_empty(), "Should have data records");
+    assert!(!log.is_empty(), "Should have data records");
 
     eprintln!(
         "ECUMaster large log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -217,7 +218,7 @@ fn test_romraider_parsing() {
     // Verify structure
     assert_eq!(log.channels.len(), 4, "Should have 4 channels");
     assert_eq!(log.times.len(), 4, "Should have 4 timestamps");
-    assert_eq!(log.data.len(), 4, "Should have 4 data records");
+    assert_eq!(log.record_count(), 4, "Should have 4 data records");
 
     // Verify channel names (units should be stripped)
     assert_eq!(log.channels[0].name(), "Engine Speed");
@@ -241,31 +242,32 @@ fn test_romraider_parsing() {
     );
 
     // Verify data values
-    assert_eq!(log.data[0][0].as_f64(), 850.0);
-    assert_eq!(log.data[3][0].as_f64(), 1000.0);
+    assert_eq!(log.channel_data(0)[0], 850.0);
+    assert_eq!(log.channel_data(0)[3], 1000.0);
 
     // Verify data integrity
     assert_eq!(
         log.times.len(),
-        log.data.len(),
+        log.record_count(),
         "Times and data should have same length"
     );
 
     let channel_count = log.channels.len();
-    for (i, record) in log.data.iter().enumerate() {
+    assert_eq!(log.columns().len(), channel_count);
+    for (i, column) in log.columns().iter().enumerate() {
         assert_eq!(
-            record.len(),
-            channel_count,
-            "Record {} should have {} values",
+            column.len(),
+            log.times.len(),
+            "Channel {} should have {} values",
             i,
-            channel_count
+            log.times.len()
         );
     }
 
     eprintln!(
         "RomRaider log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -284,31 +286,32 @@ fn test_speeduino_mlg_parsing() {
 
     assert!(!log.channels.is_empty(), "Should have channels");
     assert!(!log.times.is_empty(), "Should have timestamps");
-    assert!(!log.data.is_empty(), "Should have data records");
+    assert!(!log.is_empty(), "Should have data records");
 
     // Verify data integrity
     assert_eq!(
         log.times.len(),
-        log.data.len(),
+        log.record_count(),
         "Times and data should have same length"
     );
 
     // Verify all records have correct number of values
     let channel_count = log.channels.len();
-    for (i, record) in log.data.iter().enumerate() {
+    assert_eq!(log.columns().len(), channel_count);
+    for (i, column) in log.columns().iter().enumerate() {
         assert_eq!(
-            record.len(),
-            channel_count,
-            "Record {} should have {} values",
+            column.len(),
+            log.times.len(),
+            "Channel {} should have {} values",
             i,
-            channel_count
+            log.times.len()
         );
     }
 
     eprintln!(
         "Speeduino log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -323,15 +326,18 @@ fn test_rusefi_mlg_parsing() {
 
     assert!(!log.channels.is_empty(), "Should have channels");
     assert!(!log.times.is_empty(), "Should have timestamps");
-    assert!(!log.data.is_empty(), "Should have data records");
+    assert!(!log.is_empty(), "Should have data records");
 
     // rusEFI logs can be large - verify we parsed a reasonable amount
-    assert!(log.data.len() > 100, "Should have substantial data records");
+    assert!(
+        log.record_count() > 100,
+        "Should have substantial data records"
+    );
 
     eprintln!(
         "rusEFI log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -407,12 +413,12 @@ fn test_channel_data_extraction() {
     let parser = Haltech;
     let log = parser.parse(&content).expect("Should parse log");
 
-    // Test get_channel_data for each channel
+    // Test channel_data for each channel
     for (idx, channel) in log.channels.iter().enumerate() {
-        let data = log.get_channel_data(idx);
+        let data = log.channel_data(idx);
         assert_eq!(
             data.len(),
-            log.data.len(),
+            log.record_count(),
             "Channel {} ({}) data length should match record count",
             idx,
             channel.name()
@@ -420,7 +426,7 @@ fn test_channel_data_extraction() {
     }
 
     // Test out of bounds access
-    let oob_data = log.get_channel_data(999);
+    let oob_data = log.channel_data(999);
     assert!(
         oob_data.is_empty(),
         "Out of bounds access should return empty"
@@ -486,9 +492,8 @@ fn test_data_values_are_finite() {
     let log = parser.parse(&content).expect("Should parse log");
 
     // Verify all data values are finite (not NaN or Infinity)
-    for (row_idx, row) in log.data.iter().enumerate() {
-        for (col_idx, value) in row.iter().enumerate() {
-            let f = value.as_f64();
+    for (col_idx, column) in log.columns().iter().enumerate() {
+        for (row_idx, &f) in column.iter().enumerate() {
             assert!(
                 f.is_finite(),
                 "Value at row {}, col {} should be finite, got {}",
@@ -3,7 +3,7 @@
 //! This module includes comprehensive tests for each ECU format parser.
 
 #[path = "common/mod.rs"]
-mod common;
+pub mod common;
 
 #[path = "parsers/mod.rs"]
 mod parser_tests;
@@ -0,0 +1,130 @@
+//! Comprehensive tests for the AEM .daq parser
+//!
+//! Tests cover:
+//! - Format detection
+//! - Rejection of other binary formats
+//! - Real example file parsing (channels, scaling, rate groups)
+//! - Metadata extraction
+
+use crate::common::assertions::*;
+use crate::common::example_files::*;
+use crate::common::{example_file_exists, read_example_binary};
+use ultralog::parsers::aem::Aem;
+use ultralog::parsers::aim::Aim;
+use ultralog::parsers::link::Link;
+use ultralog::parsers::speeduino::Speeduino;
+use ultralog::parsers::types::Meta;
+
+// ============================================
+// Format Detection Tests
+// ============================================
+
+#[test]
+fn test_aem_detection() {
+    assert!(Aem::detect(b"EMERALD v1.00 file\x1a\xf9\x23\x07\x00"));
+}
+
+#[test]
+fn test_aem_detection_rejects_other_formats() {
+    assert!(!Aem::detect(b"MLVLG\x00\x01"));
+    assert!(!Aem::detect(b"<hCNF\x00"));
+    assert!(!Aem::detect(b"%DataLog%\n"));
+    assert!(!Aem::detect(b"EMERALD"));
+    assert!(!Aem::detect(b""));
+}
+
+#[test]
+fn test_aem_not_detected_as_others() {
+    let daq = b"EMERALD v1.00 file\x1a\xf9\x23\x07\x00";
+    assert!(!Speeduino::detect(daq), "AEM should not be MLG");
+    assert!(!Link::detect(daq), "AEM should not be Link");
+    assert!(!Aim::detect(daq), "AEM should not be AiM");
+}
+
+#[test]
+fn test_aem_truncated_header_fails() {
+    assert!(Aem::parse_binary(b"EMERALD v1.00 file\x1a").is_err());
+}
+
+// ============================================
+// Real File Tests
+// ============================================
+
+#[test]
+fn test_aem_example_file() {
+    if !example_file_exists(AEM_DAQ) {
+        eprintln!("Skipping test: {} not found", AEM_DAQ);
+        return;
+    }
+
+    let data = read_example_binary(AEM_DAQ);
+    assert!(Aem::detect(&data), "Should detect AEM .daq format");
+
+    let log = Aem::parse_binary(&data).expect("Should parse AEM log");
+
+    assert_valid_log_structure(&log);
+    assert_monotonic_times(&log);
+    // Slower rate groups can end a few rows before the fastest one
+    assert_finite_or_missing_values(&log);
+    assert_minimum_channels(&log, 60);
+    assert_minimum_records(&log, 19_000);
+
+    // Fastest rate group is sampled at 100 Hz
+    let duration = *log.times.last().unwrap();
+    assert!(
+        (190.0..210.0).contains(&duration),
+        "Unexpected duration: {}",
+        duration
+    );
+
+    match &log.meta {
+        Meta::Aem(meta) => {
+            assert_eq!(meta.ecu_model, "AEM25");
+            assert_eq!(meta.firmware_version, "02v02");
+            assert_eq!(meta.serial_number, "21C9DD287038401590AD853002161777");
+            assert!(meta.description.contains("7 May 2017"));
+        }
+        _ => panic!("Expected AEM metadata"),
+    }
+}
+
+#[test]
+fn test_aem_example_channel_values() {
+    if !example_file_exists(AEM_DAQ) {
+        eprintln!("Skipping test: {} not found", AEM_DAQ);
+        return;
+    }
+
+    let log = Aem::parse_binary(&read_example_binary(AEM_DAQ)).expect("Should parse");
+
+    let range = |name: &str| {
+        let idx = log
+            .find_channel_index(name)
+            .unwrap_or_else(|| panic!("Missing channel {}", name));
+        let values = log.channel_data(idx);
+        let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        (log.channels[idx].unit().to_string(), min, max)
+    };
+
+    let (unit, min, max) = range("Engine Speed");
+    assert_eq!(unit, "rpm");
+    assert!(min > 1000.0 && max > 7000.0 && max < 9000.0);
+
+    let (unit, min, max) = range("Coolant Temp");
+    assert_eq!(unit, "°F");
+    assert!(min > 150.0 && max < 230.0, "Signed temperature scaling");
+
+    let (unit, min, max) = range("O2 #1");
+    assert_eq!(unit, "AFR");
+    assert!(min > 7.0 && max < 22.0);
+
+    // 1 Hz rate group, held between updates
+    let (unit, min, max) = range("Battery Raw");
+    assert_eq!(unit, "V");
+    assert!(min > 11.0 && max < 15.0);
+
+    // Bitmask channels decode to on/off flags
+    let (_, min, max) = range("Fuel Pump");
+    assert!((0.0..=1.0).contains(&min) && (0.0..=1.0).contains(&max));
+}
@@ -5,12 +5,9 @@
 //! - Real file parsing with example logs
 //! - Channel and data validation
 
-#[path = "../common/mod.rs"]
-mod common;
-
-use common::assertions::*;
-use common::example_files::*;
-use common::{example_file_exists, get_example_file_path, read_example_binary};
+use crate::common::assertions::*;
+use crate::common::example_files::*;
+use crate::common::{example_file_exists, get_example_file_path, read_example_binary};
 use std::path::Path;
 use ultralog::parsers::aim::Aim;
 
@@ -98,7 +95,7 @@ fn test_aim_generic_example_file() {
     eprintln!(
         "AiM generic log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -121,7 +118,7 @@ fn test_aim_race_file_1() {
     eprintln!(
         "AiM race 1 log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -144,7 +141,7 @@ fn test_aim_race_file_2() {
     eprintln!(
         "AiM race 2 log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -190,19 +187,19 @@ fn test_aim_channel_data_extraction() {
     let path = get_example_file_path(AIM_GENERIC);
     let log = Aim::parse_file(Path::new(&path)).expect("Should parse");
 
-    // Test get_channel_data for each channel
+    // Test channel_data for each channel
     for idx in 0..log.channels.len() {
-        let channel_data = log.get_channel_data(idx);
+        let channel_data = log.channel_data(idx);
         assert_eq!(
             channel_data.len(),
-            log.data.len(),
+            log.record_count(),
             "Channel {} data length should match record count",
             idx
         );
     }
 
     // Out of bounds should return empty
-    let oob = log.get_channel_data(999);
+    let oob = log.channel_data(999);
     assert!(oob.is_empty(), "Out of bounds should return empty");
 }
 
@@ -243,17 +240,18 @@ fn test_aim_data_structure() {
     let log = Aim::parse_file(Path::new(&path)).expect("Should parse");
 
     // Verify times and data match
-    assert_eq!(log.times.len(), log.data.len());
+    assert_eq!(log.times.len(), log.record_count());
 
     // Verify each record has correct channel count
     let channel_count = log.channels.len();
-    for (i, record) in log.data.iter().enumerate() {
+    assert_eq!(log.columns().len(), channel_count);
+    for (i, column) in log.columns().iter().enumerate() {
         assert_eq!(
-            record.len(),
-            channel_count,
-            "Record {} should have {} values",
+            column.len(),
+            log.times.len(),
+            "Channel {} should have {} values",
             i,
-            channel_count
+            log.times.len()
         );
     }
 }
@@ -338,13 +336,13 @@ fn test_aim_all_example_files() {
             "{} should have channels",
             file_path
         );
-        assert!(!log.data.is_empty(), "{} should have data", file_path);
+        assert!(!log.is_empty(), "{} should have data", file_path);
 
         eprintln!(
             "{}: {} channels, {} records",
             file_path,
             log.channels.len(),
-            log.data.len()
+            log.record_count()
         );
     }
 }
@@ -367,7 +365,7 @@ fn test_aim_large_file_performance() {
     let log = Aim::parse_file(Path::new(&path)).expect("Should parse large file");
     let elapsed = start.elapsed();
 
-    eprintln!("Parsed {} records in {:?}", log.data.len(), elapsed);
+    eprintln!("Parsed {} records in {:?}", log.record_count(), elapsed);
 
     // Should complete in reasonable time
     assert!(
@@ -0,0 +1,176 @@
+//! Comprehensive tests for the BlueDriver LiveData parser
+//!
+//! Tests cover:
+//! - Format detection (UTF-16 and UTF-8 content)
+//! - UTF-16 decoding with byte order marks
+//! - Header parsing with units in parentheses
+//! - Carry-forward of empty cells
+//! - Real example file parsing
+
+use crate::common::assertions::*;
+use crate::common::example_files::*;
+use crate::common::float_cmp::*;
+use crate::common::{example_file_exists, read_example_binary};
+use ultralog::parsers::bluedriver::BlueDriver;
+use ultralog::parsers::ecumaster::EcuMaster;
+use ultralog::parsers::romraider::RomRaider;
+use ultralog::parsers::types::{Meta, Parseable};
+use ultralog::parsers::ParseError;
+
+fn encode_utf16le(s: &str) -> Vec<u8> {
+    let mut bytes = vec![0xFF, 0xFE];
+    for unit in s.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    bytes
+}
+
+// ============================================
+// Format Detection Tests
+// ============================================
+
+#[test]
+fn test_bluedriver_detection() {
+    let content = "BlueDriver Data Log\nAug 17, 2025\nTime(s), Engine RPM (rpm)\n0.00, 800\n";
+    assert!(
+        BlueDriver::detect(content),
+        "Should detect BlueDriver LiveData format"
+    );
+}
+
+#[test]
+fn test_bluedriver_detection_rejects_other_csv() {
+    assert!(!BlueDriver::detect("Time,RPM,Load\n0,1000,50\n"));
+    assert!(!BlueDriver::detect("TIME;engine/rpm\n0.0;1000\n"));
+    assert!(!BlueDriver::detect("%DataLog%\nDataLogVersion : 1.1\n"));
+    assert!(!BlueDriver::detect(""));
+}
+
+#[test]
+fn test_bluedriver_not_detected_as_others() {
+    let content = "BlueDriver Data Log\nAug 17, 2025\nTime(s), Engine RPM (rpm)\n0.00, 800\n";
+    assert!(
+        !RomRaider::detect(content),
+        "Should not detect as RomRaider"
+    );
+    assert!(
+        !EcuMaster::detect(content),
+        "Should not detect as ECUMaster"
+    );
+}
+
+#[test]
+fn test_bluedriver_decode_requires_bom() {
+    let content = "BlueDriver Data Log\n";
+    assert!(BlueDriver::decode_utf16(content.as_bytes()).is_none());
+    assert_eq!(
+        BlueDriver::decode_utf16(&encode_utf16le(content)).as_deref(),
+        Some(content)
+    );
+}
+
+// ============================================
+// Parsing Tests
+// ============================================
+
+#[test]
+fn test_bluedriver_units_from_headers() {
+    let content = "BlueDriver Data Log\n\
+        Aug 17, 2025\n\
+        Time(s), O2 Bank 2 - Sensor 2 Oxygen Sensor Voltage (V), Long Term Secondary Oxygen Sensor Trim Bank 2 (%)\n\
+        0.00, 0.301, 0.8\n";
+
+    let log = BlueDriver.parse(content).expect("Should parse");
+
+    assert_eq!(log.channels.len(), 2);
+    assert_eq!(
+        log.channels[0].name(),
+        "O2 Bank 2 - Sensor 2 Oxygen Sensor Voltage"
+    );
+    assert_eq!(log.channels[0].unit(), "V");
+    assert_eq!(
+        log.channels[1].name(),
+        "Long Term Secondary Oxygen Sensor Trim Bank 2"
+    );
+    assert_eq!(log.channels[1].unit(), "%");
+}
+
+#[test]
+fn test_bluedriver_empty_cells_carry_forward() {
+    let content = "BlueDriver Data Log\n\
+        Nov 9, 2025\n\
+        Time(s), A (V), B (%)\n\
+        57.20, 0.260, 0.0\n\
+        57.60, , \n";
+
+    let log = BlueDriver.parse(content).expect("Should parse");
+
+    assert_eq!(log.record_count(), 2);
+    assert_approx_eq(log.channel_data(0)[1], 0.260, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(1)[1], 0.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.times[1], 0.4, DEFAULT_TOLERANCE);
+}
+
+#[test]
+fn test_bluedriver_missing_preamble_fails() {
+    let content = "Time(s), A (V)\n0.00, 0.1\n";
+    assert!(BlueDriver.parse(content).is_err());
+}
+
+#[test]
+fn test_bluedriver_error_reports_header_line() {
+    let content = "BlueDriver Data Log\nCaptured: 2024-05-01\nRPM,Speed\n900,0\n";
+    let error = BlueDriver.parse(content).unwrap_err();
+
+    assert!(matches!(error, ParseError::BadHeader { .. }));
+    assert_eq!(error.line(), Some(3));
+}
+
+#[test]
+fn test_bluedriver_header_only() {
+    let content = "BlueDriver Data Log\nAug 17, 2025\nTime(s), A (V)\n";
+    let log = BlueDriver
+        .parse(content)
+        .expect("Should parse header-only file");
+    assert_eq!(log.channels.len(), 1);
+    assert!(log.is_empty());
+}
+
+// ============================================
+// Real File Tests
+// ============================================
+
+#[test]
+fn test_bluedriver_example_files() {
+    for path in [BLUEDRIVER_AUG, BLUEDRIVER_NOV] {
+        if !example_file_exists(path) {
+            eprintln!("Skipping test: {} not found", path);
+            continue;
+        }
+
+        let data = read_example_binary(path);
+        let contents = BlueDriver::decode_utf16(&data).expect("Should be UTF-16 with BOM");
+        assert!(
+            BlueDriver::detect(&contents),
+            "Should detect BlueDriver format"
+        );
+
+        let log = BlueDriver::parse_binary(&data).expect("Should parse BlueDriver log");
+
+        assert_valid_log_structure(&log);
+        assert_monotonic_times(&log);
+        assert_finite_values(&log);
+        assert_minimum_channels(&log, 4);
+        assert_minimum_records(&log, 100);
+
+        assert!(
+            log.channels.iter().any(|c| c.unit() == "V"),
+            "Should extract voltage units from headers"
+        );
+
+        match &log.meta {
+            Meta::BlueDriver(meta) => assert!(meta.capture_date.contains("2025")),
+            _ => panic!("Expected BlueDriver metadata"),
+        }
+    }
+}
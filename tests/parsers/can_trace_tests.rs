@@ -0,0 +1,419 @@
+//! Tests for CAN bus traces decoded through a protocol spec
+//!
+//! Tests cover:
+//! - Detection of candump, Vector ASC and SavvyCAN layouts
+//! - Channels, units and display ranges from `SignalSpec`
+//! - Signal name normalization
+//! - Extended identifiers and unmatched frames
+//! - The shared timebase, native series and wall-clock start
+//! - Dropping stray clusters of outlier timestamps, keeping long pauses
+//! - Ranking protocols against a trace
+//! - Growing a log frame by frame from a live bus
+
+use crate::common::assertions::*;
+use crate::common::float_cmp::*;
+use ultralog::adapters::ProtocolSpec;
+use ultralog::parsers::can_trace::{
+    candump_line, read_trace, CanStream, CanTrace, TraceFormat, LIVE_RECORD_INTERVAL,
+};
+use ultralog::parsers::detection::{self, DetectionInput};
+use ultralog::parsers::types::{Meta, Parseable, ValueKind};
+
+const PROTOCOL_YAML: &str = r#"
+openecualliance: "1.0"
+type: protocol
+id: acme-broadcast
+name: Acme Broadcast
+version: "1.0.0"
+vendor: acme
+protocol:
+  type: can
+  baudrate: 500000
+messages:
+  - id: "0x360"
+    name: Engine
+    length: 4
+    signals:
+      - { name: Engine RPM4, start_bit: 0, length: 16, byte_order: big_endian, data_type: unsigned, unit: rpm, min: 0, max: 9000 }
+      - { name: Manifold Pressure, start_bit: 16, length: 16, byte_order: big_endian, data_type: unsigned, scale: 0.1, unit: kPa, min: 0, max: 300 }
+  - id: "0x3E0"
+    name: Temperatures
+    length: 3
+    signals:
+      - { name: Coolant Temperature, start_bit: 0, length: 16, byte_order: big_endian, data_type: signed, offset: -40, unit: °C }
+      - { name: Gear, start_bit: 16, length: 4, byte_order: little_endian, data_type: unsigned, enum_ref: gears }
+      - { name: Fan, start_bit: 23, length: 1, byte_order: little_endian, data_type: unsigned }
+  - id: "0x1F0A000"
+    name: Oil
+    length: 2
+    signals:
+      - { name: Oil Pressure, start_bit: 0, length: 16, byte_order: big_endian, data_type: unsigned, scale: 0.1, unit: kPa }
+enums:
+  - name: gears
+    values:
+      "0": Neutral
+      "1": First
+      "2": Second
+"#;
+
+const CANDUMP_TRACE: &str = "\
+(1700000000.000000) can0 360#0BB803E8
+(1700000000.010000) can0 3E0#007882
+(1700000000.020000) can0 360#0C1C03F2
+(1700000000.030000) can0 1F0A000#0190
+(1700000000.040000) can0 360#0C8003FC
+(1700000000.050000) can0 7FF#00
+";
+
+const ASC_TRACE: &str = "\
+date Wed Jun 14 09:30:12.123 pm 2023
+base hex  timestamps absolute
+internal events logged
+Begin Triggerblock Wed Jun 14 09:30:12.123 pm 2023
+   0.000000 Start of measurement
+   0.010000 1  360             Rx   d 4 0B B8 03 E8
+   0.020000 1  1F0A000x        Rx   d 2 01 90
+   0.030000 1  360             Rx   d 4 0C 1C 03 F2
+End TriggerBlock
+";
+
+const SAVVYCAN_TRACE: &str = "\
+Time Stamp,ID,Extended,Dir,Bus,LEN,D1,D2,D3,D4,D5,D6,D7,D8
+5000000,00000360,false,Rx,0,4,0B,B8,03,E8,
+5100000,01F0A000,true,Rx,0,2,01,90,
+5200000,00000360,false,Rx,0,4,0C,1C,03,F2,
+";
+
+fn acme_protocol() -> ProtocolSpec {
+    serde_yaml::from_str(PROTOCOL_YAML).expect("Protocol YAML should deserialize")
+}
+
+fn channel_index(log: &ultralog::parsers::Log, name: &str) -> usize {
+    log.channels
+        .iter()
+        .position(|c| c.name() == name)
+        .unwrap_or_else(|| panic!("Missing channel {}", name))
+}
+
+// ============================================
+// Format Detection Tests
+// ============================================
+
+#[test]
+fn test_can_trace_detection() {
+    assert_eq!(
+        TraceFormat::detect(CANDUMP_TRACE),
+        Some(TraceFormat::Candump)
+    );
+    assert_eq!(TraceFormat::detect(ASC_TRACE), Some(TraceFormat::VectorAsc));
+    assert_eq!(
+        TraceFormat::detect(SAVVYCAN_TRACE),
+        Some(TraceFormat::SavvyCan)
+    );
+    assert!(!CanTrace::detect("Time,RPM\n0,900\n"));
+}
+
+#[test]
+fn test_can_trace_registered_format() {
+    for trace in [CANDUMP_TRACE, ASC_TRACE, SAVVYCAN_TRACE] {
+        let input = DetectionInput::new(trace.as_bytes(), std::path::Path::new("bus.log"));
+        let best = detection::rank(&input);
+        assert_eq!(best[0].detector.id, CanTrace::FORMAT_ID);
+    }
+}
+
+// ============================================
+// Parsing Tests
+// ============================================
+
+#[test]
+fn test_can_trace_parse_structure() {
+    let log = CanTrace::new(acme_protocol())
+        .parse(CANDUMP_TRACE)
+        .expect("Should decode candump trace");
+
+    assert_valid_log_structure(&log);
+    assert_monotonic_times(&log);
+    assert_eq!(log.channels.len(), 6, "One channel per decoded signal");
+    assert_eq!(log.channels[0].type_name(), "CAN");
+}
+
+#[test]
+fn test_can_trace_units_and_ranges_from_spec() {
+    let log = CanTrace::new(acme_protocol()).parse(CANDUMP_TRACE).unwrap();
+
+    let rpm = &log.channels[channel_index(&log, "RPM")];
+    assert_eq!(rpm.unit(), "rpm");
+    assert_eq!(rpm.display_min(), Some(0.0));
+    assert_eq!(rpm.display_max(), Some(9000.0));
+
+    let coolant = &log.channels[channel_index(&log, "Coolant Temp")];
+    assert_eq!(coolant.unit(), "°C");
+    assert_eq!(coolant.display_max(), None);
+}
+
+#[test]
+fn test_can_trace_names_are_normalized() {
+    let log = CanTrace::new(acme_protocol()).parse(CANDUMP_TRACE).unwrap();
+
+    // "Engine RPM4" and "Manifold Pressure" take the names analyzers look for
+    let map = channel_index(&log, "MAP");
+    assert_eq!(log.channels[map].id(), "360.Manifold Pressure");
+    channel_index(&log, "RPM");
+}
+
+#[test]
+fn test_can_trace_decodes_values() {
+    let log = CanTrace::new(acme_protocol()).parse(CANDUMP_TRACE).unwrap();
+
+    let rpm = log.channel_data(channel_index(&log, "RPM"));
+    assert_eq!(rpm, vec![3000.0, 3100.0, 3200.0]);
+    let map = log.channel_data(channel_index(&log, "MAP"));
+    assert_approx_eq(map[2], 102.0, DEFAULT_TOLERANCE);
+
+    // Coolant was first broadcast after the first record
+    let coolant = log.channel_data(channel_index(&log, "Coolant Temp"));
+    assert!(coolant[0].is_nan());
+    assert_approx_eq(coolant[1], 80.0, DEFAULT_TOLERANCE);
+}
+
+#[test]
+fn test_can_trace_enum_and_flag_signals() {
+    let log = CanTrace::new(acme_protocol()).parse(CANDUMP_TRACE).unwrap();
+
+    let gear = &log.channels[channel_index(&log, "Gear")];
+    assert!(matches!(gear.value_kind(), ValueKind::Enum(_)));
+    assert_eq!(gear.value_label(2.0).as_deref(), Some("Second"));
+
+    let fan = channel_index(&log, "Fan");
+    assert_eq!(log.channel_data(fan)[1], 1.0);
+    assert!(!matches!(
+        log.channels[fan].value_kind(),
+        ValueKind::Numeric
+    ));
+}
+
+#[test]
+fn test_can_trace_extended_ids_and_unmatched_frames() {
+    let log = CanTrace::new(acme_protocol()).parse(CANDUMP_TRACE).unwrap();
+
+    let oil = channel_index(&log, "Oil Pressure");
+    assert_approx_eq(log.channel_data(oil)[2], 40.0, DEFAULT_TOLERANCE);
+
+    match &log.meta {
+        Meta::CanTrace(meta) => {
+            assert_eq!(meta.trace_format, "candump");
+            assert_eq!(meta.protocol_id, "acme-broadcast");
+            assert_eq!(meta.vendor, "acme");
+            assert_eq!(meta.frames, 6);
+            assert_eq!(meta.unmatched_frames, 1);
+        }
+        _ => panic!("Expected CAN trace metadata"),
+    }
+}
+
+#[test]
+fn test_can_trace_timebase_and_native_series() {
+    let log = CanTrace::new(acme_protocol()).parse(CANDUMP_TRACE).unwrap();
+
+    // Resampled at the engine message's 20 ms period
+    assert_eq!(log.record_count(), 3);
+    assert_approx_eq(log.times[1], 0.02, 1e-9);
+
+    // Exact broadcast times are kept alongside
+    let series = log.native_series(channel_index(&log, "RPM")).unwrap();
+    assert_eq!(series.values, vec![3000.0, 3100.0, 3200.0]);
+    assert_approx_eq(series.times[2], 0.04, 1e-6);
+}
+
+#[test]
+fn test_can_trace_start_times() {
+    let candump = CanTrace::new(acme_protocol()).parse(CANDUMP_TRACE).unwrap();
+    assert!(candump.start.is_some(), "Epoch timestamps give a start");
+
+    let asc = CanTrace::new(acme_protocol()).parse(ASC_TRACE).unwrap();
+    assert_eq!(
+        asc.start.map(|s| s.to_string()).as_deref(),
+        Some("2023-06-14 21:30:12.123")
+    );
+
+    let savvycan = CanTrace::new(acme_protocol())
+        .parse(SAVVYCAN_TRACE)
+        .unwrap();
+    assert!(savvycan.start.is_none());
+}
+
+#[test]
+fn test_can_trace_other_layouts() {
+    for trace in [ASC_TRACE, SAVVYCAN_TRACE] {
+        let log = CanTrace::new(acme_protocol()).parse(trace).unwrap();
+        assert_eq!(log.channels.len(), 3, "Engine and oil signals");
+
+        let rpm = log.channel_data(channel_index(&log, "RPM"));
+        assert_eq!(rpm.first(), Some(&3000.0));
+        let oil = log.channel_data(channel_index(&log, "Oil Pressure"));
+        assert_approx_eq(*oil.last().unwrap(), 40.0, DEFAULT_TOLERANCE);
+    }
+}
+
+#[test]
+fn test_can_trace_drops_outlier_timestamps() {
+    // A relative frame among epoch ones, and a bogus time years later
+    for stray in [
+        "(12.500000) can0 360#0D0003E8\n",
+        "(1800000000.000000) can0 360#0D0003E8\n",
+    ] {
+        let text = format!("{}{}", CANDUMP_TRACE, stray);
+        let trace = read_trace(TraceFormat::Candump, &text);
+        assert_eq!(trace.frames.len(), 6);
+        assert_eq!(trace.outlier_frames, 1);
+
+        let log = CanTrace::new(acme_protocol()).parse(&text).unwrap();
+        assert_eq!(log.record_count(), 3);
+        assert_approx_eq(log.end_time().unwrap(), 0.04, 1e-6);
+        assert!(log.start.is_some());
+        let rpm = log.native_series(channel_index(&log, "RPM")).unwrap();
+        assert_eq!(rpm.values, vec![3000.0, 3100.0, 3200.0]);
+
+        // The log info panel reports the dropped frame
+        let info = log.info();
+        assert!(info
+            .vendor
+            .contains(&("Outlier frames dropped".to_string(), "1".to_string())));
+    }
+}
+
+#[test]
+fn test_can_trace_keeps_both_sides_of_a_long_pause() {
+    // Twenty engine frames, a two hour break, then twenty more
+    let mut text = String::new();
+    for (start, rpm) in [(1_700_000_000.0, 0x0BB8), (1_700_007_200.0, 0x0FA0)] {
+        for i in 0..20 {
+            let time = start + i as f64 * 0.02;
+            text.push_str(&format!("({:.6}) can0 360#{:04X}03E8\n", time, rpm));
+        }
+    }
+
+    let trace = read_trace(TraceFormat::Candump, &text);
+    assert_eq!(trace.frames.len(), 40);
+    assert_eq!(trace.outlier_frames, 0);
+
+    let log = CanTrace::new(acme_protocol()).parse(&text).unwrap();
+    assert_approx_eq(log.end_time().unwrap(), 7200.38, 1e-3);
+    let rpm = log.native_series(channel_index(&log, "RPM")).unwrap();
+    assert_eq!(rpm.values.len(), 40);
+    assert_eq!(rpm.values.last(), Some(&4000.0));
+
+    // Charts plot the native samples, not the held timebase
+    assert_eq!(log.channel_samples(channel_index(&log, "RPM")).len(), 40);
+}
+
+// ============================================
+// Protocol Matching Tests
+// ============================================
+
+#[test]
+fn test_can_trace_ranks_protocols() {
+    let mut other = acme_protocol();
+    other.id = "other".to_string();
+    other.messages.truncate(1);
+
+    let ranked = CanTrace::rank_protocols(CANDUMP_TRACE, vec![other, acme_protocol()]);
+    assert_eq!(ranked[0].protocol.id, "acme-broadcast");
+    assert_eq!(ranked[0].matched_ids, 3);
+    assert_eq!(ranked[1].matched_ids, 1);
+    assert_eq!(CanTrace::trace_ids(CANDUMP_TRACE).len(), 4);
+}
+
+// ============================================
+// Live Stream Tests
+// ============================================
+
+#[test]
+fn test_can_stream_grows_log() {
+    let mut stream = CanStream::new(acme_protocol());
+    let mut log = stream.empty_log("SocketCAN vcan0");
+    assert!(log.channels.is_empty());
+    assert_eq!(log.end_time(), None);
+
+    let frames = read_trace(TraceFormat::Candump, CANDUMP_TRACE).frames;
+    assert!(stream.push(&mut log, &frames[0]));
+    assert_eq!(log.channels.len(), 2, "Engine signals on first sight");
+    assert!(log.start.is_some());
+
+    for frame in &frames[1..] {
+        stream.push(&mut log, frame);
+    }
+    assert_valid_log_structure(&log);
+    assert_monotonic_times(&log);
+    assert_eq!(log.channels.len(), 6, "Later messages add their channels");
+
+    // Samples keep their broadcast times; records follow at a fixed interval
+    let rpm = log.native_series(channel_index(&log, "RPM")).unwrap();
+    assert_eq!(rpm.values, vec![3000.0, 3100.0, 3200.0]);
+    // The last frame is unmatched, so the newest sample is the 40 ms engine frame
+    assert_approx_eq(log.end_time().unwrap(), 0.04, 1e-6);
+    assert_approx_eq(log.times[0], 0.0, 1e-9);
+
+    match &log.meta {
+        Meta::CanTrace(meta) => {
+            assert_eq!(meta.trace_format, "SocketCAN vcan0");
+            assert_eq!(meta.frames, 6);
+            assert_eq!(meta.unmatched_frames, 1);
+        }
+        _ => panic!("Expected CAN trace metadata"),
+    }
+}
+
+#[test]
+fn test_can_stream_records_hold_latest_values() {
+    let mut stream = CanStream::new(acme_protocol());
+    let mut log = stream.empty_log("SocketCAN vcan0");
+    for frame in read_trace(TraceFormat::Candump, CANDUMP_TRACE).frames {
+        stream.push(&mut log, &frame);
+    }
+
+    // The record at 50 ms is written once a later frame arrives
+    let rpm = channel_index(&log, "RPM");
+    assert_eq!(log.record_count(), 1);
+    assert_eq!(log.value(rpm, 0), Some(3000.0));
+
+    let later = read_trace(
+        TraceFormat::Candump,
+        "(1700000000.120000) can0 360#0CE403FC\n",
+    );
+    assert!(stream.push(&mut log, &later.frames[0]));
+    assert_eq!(log.record_count(), 3);
+    assert_approx_eq(log.times[2], 2.0 * LIVE_RECORD_INTERVAL, 1e-9);
+    assert_eq!(log.value(rpm, 2), Some(3200.0));
+    assert_eq!(log.native_series(rpm).unwrap().values.len(), 4);
+}
+
+#[test]
+fn test_candump_line_round_trip() {
+    let trace = read_trace(TraceFormat::Candump, CANDUMP_TRACE);
+    let lines: Vec<String> = trace
+        .frames
+        .iter()
+        .map(|frame| candump_line("can0", frame))
+        .collect();
+    assert_eq!(lines[0], "(1700000000.000000) can0 360#0BB803E8");
+    assert_eq!(lines[3], "(1700000000.030000) can0 01F0A000#0190");
+
+    let reread = read_trace(TraceFormat::Candump, &lines.join("\n"));
+    assert_eq!(reread.frames, trace.frames);
+}
+
+// ============================================
+// Error Handling Tests
+// ============================================
+
+#[test]
+fn test_can_trace_rejects_unmatched_traces() {
+    let parser = CanTrace::new(acme_protocol());
+
+    assert!(parser
+        .parse("(1.0) can0 123#00\n(1.1) can0 124#00\n")
+        .is_err());
+    assert!(parser.parse("Time,RPM\n0,900\n").is_err());
+}
@@ -6,16 +6,15 @@
 //! - Unit inference from channel names
 //! - Path parsing for nested channel names
 //! - Real file parsing with example logs
+//! - EMU Black binary .emublog files
 
-#[path = "../common/mod.rs"]
-mod common;
-
-use common::assertions::*;
-use common::example_files::*;
-use common::float_cmp::*;
-use common::{example_file_exists, read_example_file};
+use crate::common::assertions::*;
+use crate::common::example_files::*;
+use crate::common::float_cmp::*;
+use crate::common::{example_file_exists, read_example_binary, read_example_file};
 use ultralog::parsers::ecumaster::EcuMaster;
-use ultralog::parsers::types::Parseable;
+use ultralog::parsers::emublog::EmuBlog;
+use ultralog::parsers::types::{is_missing, Parseable};
 
 // ============================================
 // Format Detection Tests
@@ -95,7 +94,7 @@ fn test_parse_ecumaster_minimal() {
         .expect("Should parse minimal ECUMaster");
 
     assert_eq!(log.channels.len(), 1);
-    assert_eq!(log.data.len(), 2);
+    assert_eq!(log.record_count(), 2);
     assert_eq!(log.times.len(), 2);
 }
 
@@ -108,10 +107,11 @@ fn test_parse_ecumaster_multiple_channels() {
     let log = parser.parse(sample).expect("Should parse multi-channel");
 
     assert_eq!(log.channels.len(), 3);
-    assert_eq!(log.data.len(), 3);
+    assert_eq!(log.record_count(), 3);
 
-    for record in &log.data {
-        assert_eq!(record.len(), 3);
+    assert_eq!(log.columns().len(), 3);
+    for column in log.columns() {
+        assert_eq!(column.len(), log.times.len());
     }
 }
 
@@ -123,7 +123,7 @@ fn test_parse_ecumaster_tab_delimiter() {
     let log = parser.parse(sample).expect("Should parse tab-delimited");
 
     assert_eq!(log.channels.len(), 2);
-    assert_eq!(log.data.len(), 2);
+    assert_eq!(log.record_count(), 2);
 }
 
 // ============================================
@@ -167,11 +167,12 @@ fn test_ecumaster_sparse_data_interpolation() {
     let parser = EcuMaster;
     let log = parser.parse(sample).expect("Should parse sparse data");
 
-    assert_eq!(log.data.len(), 3);
+    assert_eq!(log.record_count(), 3);
 
     // All records should have 2 values
-    for record in &log.data {
-        assert_eq!(record.len(), 2);
+    assert_eq!(log.columns().len(), 2);
+    for column in log.columns() {
+        assert_eq!(column.len(), log.times.len());
     }
 
     // Sparse values should be filled (either with last value or 0)
@@ -188,7 +189,27 @@ fn test_ecumaster_all_sparse_column() {
         .expect("Should parse all-sparse column");
 
     // Should handle column where all values are sparse
-    assert_eq!(log.data.len(), 3);
+    assert_eq!(log.record_count(), 3);
+
+    // A channel that was never logged has no samples, not zeros
+    assert!(log.channel_data(0).iter().all(|v| is_missing(*v)));
+    assert_eq!(log.channel_data(1), &[50.0, 55.0, 60.0]);
+}
+
+#[test]
+fn test_ecumaster_leading_empty_values_are_missing() {
+    let sample = "TIME;engine/rpm;sensors/tps1
+0.0;;50
+0.1;1000;55
+0.2;;60
+";
+
+    let log = EcuMaster.parse(sample).expect("Should parse");
+
+    // Nothing to hold before the first sample; held afterwards
+    assert!(is_missing(log.channel_data(0)[0]));
+    assert_eq!(log.channel_data(0)[1], 1000.0);
+    assert_eq!(log.channel_data(0)[2], 1000.0);
 }
 
 #[test]
@@ -199,7 +220,7 @@ fn test_ecumaster_trailing_empty_values() {
     let log = parser.parse(sample).expect("Should parse trailing empty");
 
     assert_eq!(log.channels.len(), 3);
-    assert_eq!(log.data.len(), 3);
+    assert_eq!(log.record_count(), 3);
 }
 
 // ============================================
@@ -288,7 +309,7 @@ fn test_ecumaster_standard_example_file() {
     eprintln!(
         "ECUMaster standard log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -319,7 +340,7 @@ fn test_ecumaster_large_example_file() {
     eprintln!(
         "ECUMaster large log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -333,7 +354,7 @@ fn test_ecumaster_empty_file() {
     let result = parser.parse("");
 
     match result {
-        Ok(log) => assert!(log.data.is_empty()),
+        Ok(log) => assert!(log.is_empty()),
         Err(_) => { /* Also acceptable */ }
     }
 }
@@ -347,7 +368,7 @@ fn test_ecumaster_header_only() {
 
     assert!(result.is_ok());
     let log = result.unwrap();
-    assert!(log.data.is_empty());
+    assert!(log.is_empty());
 }
 
 #[test]
@@ -357,7 +378,7 @@ fn test_ecumaster_negative_values() {
     let parser = EcuMaster;
     let log = parser.parse(sample).expect("Should parse negative values");
 
-    assert!(log.data[0][0].as_f64() < 0.0);
+    assert!(log.channel_data(0)[0] < 0.0);
 }
 
 #[test]
@@ -367,7 +388,7 @@ fn test_ecumaster_decimal_precision() {
     let parser = EcuMaster;
     let log = parser.parse(sample).expect("Should parse decimals");
 
-    assert_approx_eq(log.data[0][0].as_f64(), 12.3456789, 0.0000001);
+    assert_approx_eq(log.channel_data(0)[0], 12.3456789, 0.0000001);
 }
 
 #[test]
@@ -391,7 +412,7 @@ fn test_ecumaster_many_channels() {
     let log = parser.parse(&sample).expect("Should parse many channels");
 
     assert_eq!(log.channels.len(), 50);
-    assert_eq!(log.data[0].len(), 50);
+    assert_eq!(log.columns().len(), 50);
 }
 
 #[test]
@@ -420,13 +441,13 @@ fn test_ecumaster_data_alignment() {
     let log = parser.parse(sample).expect("Should parse");
 
     // Verify data alignment
-    assert_approx_eq(log.data[0][0].as_f64(), 1.0, DEFAULT_TOLERANCE);
-    assert_approx_eq(log.data[0][1].as_f64(), 2.0, DEFAULT_TOLERANCE);
-    assert_approx_eq(log.data[0][2].as_f64(), 3.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(0)[0], 1.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(1)[0], 2.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(2)[0], 3.0, DEFAULT_TOLERANCE);
 
-    assert_approx_eq(log.data[2][0].as_f64(), 7.0, DEFAULT_TOLERANCE);
-    assert_approx_eq(log.data[2][1].as_f64(), 8.0, DEFAULT_TOLERANCE);
-    assert_approx_eq(log.data[2][2].as_f64(), 9.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(0)[2], 7.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(1)[2], 8.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(2)[2], 9.0, DEFAULT_TOLERANCE);
 }
 
 #[test]
@@ -436,8 +457,8 @@ fn test_ecumaster_channel_data_extraction() {
     let parser = EcuMaster;
     let log = parser.parse(sample).expect("Should parse");
 
-    let channel_a = log.get_channel_data(0);
-    let channel_b = log.get_channel_data(1);
+    let channel_a = log.channel_data(0);
+    let channel_b = log.channel_data(1);
 
     assert_eq!(channel_a.len(), 3);
     assert_eq!(channel_b.len(), 3);
@@ -445,3 +466,70 @@ fn test_ecumaster_channel_data_extraction() {
     assert_approx_eq(channel_a[0], 10.0, DEFAULT_TOLERANCE);
     assert_approx_eq(channel_b[2], 22.0, DEFAULT_TOLERANCE);
 }
+
+// ============================================
+// Binary .emublog Tests
+// ============================================
+
+#[test]
+fn test_emublog_detection_rejects_csv() {
+    let content = "TIME;engine/rpm;sensors/tps1\n0.0;1000;50\n";
+    assert!(
+        !EmuBlog::detect(content.as_bytes()),
+        "Should not detect CSV export as .emublog"
+    );
+}
+
+#[test]
+fn test_emublog_example_file() {
+    if !example_file_exists(ECUMASTER_EMUBLOG) {
+        eprintln!("Skipping test: {} not found", ECUMASTER_EMUBLOG);
+        return;
+    }
+
+    let data = read_example_binary(ECUMASTER_EMUBLOG);
+
+    assert!(
+        EmuBlog::detect(&data),
+        "Should detect gzip-wrapped .emublog"
+    );
+
+    let log = EmuBlog::parse_binary(&data).expect("Should parse .emublog");
+
+    assert_valid_log_structure(&log);
+    assert_monotonic_times(&log);
+    assert_finite_or_missing_values(&log);
+
+    // The header's 1740 records, plus a gap record at each of three pauses
+    assert_eq!(log.record_count(), 1743);
+    assert_eq!(log.gap_times().len(), 3);
+    assert_eq!(log.annotations.len(), 3);
+
+    // Pauses keep their length: counters 29065 to 46978 at 20 Hz
+    assert_approx_eq(log.end_time().unwrap(), (46978 - 29065) as f64 / 20.0, 1e-9);
+
+    // Channels share paths and units with the CSV export
+    let rpm_idx = log.find_channel_index("rpm").expect("Should have rpm");
+    assert_eq!(log.channels[rpm_idx].unit(), "RPM");
+    assert_eq!(log.channels[rpm_idx].type_name(), "engine/rpm");
+
+    let rpm = log.channel_data(rpm_idx);
+    let max_rpm = rpm.iter().cloned().fold(f64::MIN, f64::max);
+    assert!(
+        (5000.0..9000.0).contains(&max_rpm),
+        "Max RPM should be plausible, got {}",
+        max_rpm
+    );
+
+    let map_idx = log.find_channel_index("map").expect("Should have map");
+    assert_eq!(log.channels[map_idx].unit(), "kPa");
+    let max_map = log
+        .channel_data(map_idx)
+        .iter()
+        .fold(f64::MIN, |a, &b| a.max(b));
+    assert!(
+        max_map > 200.0 && max_map < 250.0,
+        "220kpa log should peak near 220 kPa, got {}",
+        max_map
+    );
+}
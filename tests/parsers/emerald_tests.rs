@@ -8,12 +8,9 @@
 //! - Real file parsing with example logs
 //! - Edge cases and error handling
 
-#[path = "../common/mod.rs"]
-mod common;
-
-use common::assertions::*;
-use common::example_files::*;
-use common::{example_file_exists, read_example_binary};
+use crate::common::assertions::*;
+use crate::common::example_files::*;
+use crate::common::{example_file_exists, read_example_binary};
 use std::path::Path;
 use ultralog::parsers::emerald::Emerald;
 use ultralog::parsers::types::Meta;
@@ -321,12 +318,12 @@ fn test_emerald_parse_via_lg2_file() {
     let log = Emerald::parse_file(Path::new(lg2_path)).expect("Should parse from LG2 path");
 
     assert_eq!(log.channels.len(), 8, "Should have 8 channels");
-    assert!(!log.data.is_empty(), "Should have data records");
+    assert!(!log.is_empty(), "Should have data records");
 
     eprintln!(
         "Parsed via LG2: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -364,7 +361,7 @@ fn test_emerald_idle_and_rev_file() {
     eprintln!(
         "Emerald idle/rev log: {} channels, {} records, {:.1}s duration",
         log.channels.len(),
-        log.data.len(),
+        log.record_count(),
         log.times.last().unwrap_or(&0.0)
     );
 }
@@ -397,7 +394,7 @@ fn test_emerald_short_drive_file() {
     eprintln!(
         "Emerald short drive log: {} channels, {} records, {:.1}s duration",
         log.channels.len(),
-        log.data.len(),
+        log.record_count(),
         log.times.last().unwrap_or(&0.0)
     );
 }
@@ -435,7 +432,7 @@ fn test_emerald_diff_channels_file() {
     eprintln!(
         "Emerald diff channels log: {} channels, {} records, {:.1}s duration",
         log.channels.len(),
-        log.data.len(),
+        log.record_count(),
         log.times.last().unwrap_or(&0.0)
     );
 }
@@ -516,19 +513,19 @@ fn test_emerald_channel_data_extraction() {
 
     let log = Emerald::parse_file(Path::new(EMERALD_IDLE_REV)).expect("Should parse");
 
-    // Test get_channel_data for each channel
+    // Test channel_data for each channel
     for idx in 0..log.channels.len() {
-        let channel_data = log.get_channel_data(idx);
+        let channel_data = log.channel_data(idx);
         assert_eq!(
             channel_data.len(),
-            log.data.len(),
+            log.record_count(),
             "Channel {} data length should match record count",
             idx
         );
     }
 
     // Out of bounds should return empty
-    let oob = log.get_channel_data(999);
+    let oob = log.channel_data(999);
     assert!(oob.is_empty(), "Out of bounds should return empty");
 }
 
@@ -569,7 +566,7 @@ fn test_emerald_data_values_reasonable() {
     // Find RPM channel and check values are reasonable
     for (idx, channel) in log.channels.iter().enumerate() {
         if channel.name().contains("RPM") {
-            let rpm_data = log.get_channel_data(idx);
+            let rpm_data = log.channel_data(idx);
             let min = rpm_data.iter().cloned().fold(f64::INFINITY, f64::min);
             let max = rpm_data.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
 
@@ -585,7 +582,7 @@ fn test_emerald_data_values_reasonable() {
     // Find Coolant Temp and check values are reasonable
     for (idx, channel) in log.channels.iter().enumerate() {
         if channel.name().contains("Coolant") {
-            let temp_data = log.get_channel_data(idx);
+            let temp_data = log.channel_data(idx);
             let min = temp_data.iter().cloned().fold(f64::INFINITY, f64::min);
             let max = temp_data.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
 
@@ -660,17 +657,18 @@ fn test_emerald_data_structure() {
     let log = Emerald::parse_file(Path::new(EMERALD_IDLE_REV)).expect("Should parse");
 
     // Verify times and data match
-    assert_eq!(log.times.len(), log.data.len());
+    assert_eq!(log.times.len(), log.record_count());
 
     // Verify each record has correct channel count
     let channel_count = log.channels.len();
-    for (i, record) in log.data.iter().enumerate() {
+    assert_eq!(log.columns().len(), channel_count);
+    for (i, column) in log.columns().iter().enumerate() {
         assert_eq!(
-            record.len(),
-            channel_count,
-            "Record {} should have {} values",
+            column.len(),
+            log.times.len(),
+            "Channel {} should have {} values",
             i,
-            channel_count
+            log.times.len()
         );
     }
 }
@@ -769,13 +767,13 @@ fn test_emerald_all_example_files() {
             "{} should have 8 channels",
             file_path
         );
-        assert!(!log.data.is_empty(), "{} should have data", file_path);
+        assert!(!log.is_empty(), "{} should have data", file_path);
 
         eprintln!(
             "{}: {} channels, {} records, {:.1}s",
             file_path,
             log.channels.len(),
-            log.data.len(),
+            log.record_count(),
             log.times.last().unwrap_or(&0.0)
         );
     }
@@ -824,7 +822,7 @@ fn test_emerald_parse_performance() {
     let log = Emerald::parse_file(Path::new(EMERALD_SHORT_DRIVE)).expect("Should parse");
     let elapsed = start.elapsed();
 
-    eprintln!("Parsed {} records in {:?}", log.data.len(), elapsed);
+    eprintln!("Parsed {} records in {:?}", log.record_count(), elapsed);
 
     // Should complete in reasonable time (well under 1 second for small files)
     assert!(
@@ -3,12 +3,11 @@
 //! These tests verify that format detection is mutually exclusive
 //! and correctly identifies each ECU format.
 
-#[path = "../common/mod.rs"]
-mod common;
-
-use common::example_files::*;
-use common::{example_file_exists, read_example_binary, read_example_file};
+use crate::common::example_files::*;
+use crate::common::{example_file_exists, read_example_binary, read_example_file};
+use std::path::Path;
 use ultralog::parsers::aim::Aim;
+use ultralog::parsers::detection::{self, Detection, DetectionInput};
 use ultralog::parsers::ecumaster::EcuMaster;
 use ultralog::parsers::link::Link;
 use ultralog::parsers::romraider::RomRaider;
@@ -400,3 +399,112 @@ fn test_text_formats_handle_binary() {
         let _ = RomRaider::detect(&as_str);
     }
 }
+
+// ============================================
+// Detection Registry Tests
+// ============================================
+
+/// Detect raw bytes through the registry, returning the matched format id
+fn registry_match(data: &[u8], path: &str) -> Option<&'static str> {
+    match detection::detect(&DetectionInput::new(data, Path::new(path))) {
+        Detection::Match(detector) => Some(detector.id),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_registry_detects_example_files() {
+    let cases = [
+        (HALTECH_SMALL, "haltech"),
+        (ECUMASTER_EMUBLOG, "emublog"),
+        (SPEEDUINO_MLG, "speeduino"),
+        (RUSEFI_LOG1, "speeduino"),
+        (LINK_STANDARD, "link"),
+        (LINK_SMALL, "link"),
+        (ROMRAIDER_EUROPEAN, "romraider"),
+        (BLUEDRIVER_AUG, "bluedriver"),
+        (EMERALD_IDLE_REV, "emerald"),
+        (AEM_DAQ, "aem"),
+    ];
+
+    for (file, expected) in cases {
+        if !example_file_exists(file) {
+            eprintln!("Skipping test: {} not found", file);
+            continue;
+        }
+        let data = read_example_binary(file);
+        assert_eq!(
+            registry_match(&data, file),
+            Some(expected),
+            "{} should be detected as {}",
+            file,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_registry_detects_synthetic_text_formats() {
+    let cases: &[(&str, &str)] = &[
+        ("%DataLog%\nDataLogVersion : 1.1\n", "haltech"),
+        ("TIME;engine/rpm\n0.0;1000\n", "ecumaster"),
+        ("Time (msec),Engine Speed (rpm)\n0,900\n", "romraider"),
+        ("Time [s],RPM [rpm]\n0.0,900\n", "maxxecu"),
+        (
+            "\"MS3 Format 0566.03\"\nTime\tRPM\ns\trpm\n0.0\t900\n",
+            "megasquirt",
+        ),
+    ];
+
+    for (contents, expected) in cases {
+        assert_eq!(
+            registry_match(contents.as_bytes(), "log.csv"),
+            Some(*expected),
+            "{:?} should be detected as {}",
+            contents,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_registry_unknown_file_is_not_haltech() {
+    let input = DetectionInput::new(b"just,some,numbers\n1,2,3\n", Path::new("data.csv"));
+    assert!(matches!(detection::detect(&input), Detection::Unknown));
+    assert!(detection::rank(&input).is_empty());
+}
+
+#[test]
+fn test_registry_reports_ambiguous_files() {
+    // A RomRaider-style header with Haltech metadata but no %DataLog% marker
+    let contents = b"Time,RPM\nDataLogVersion : 1.1\n0,900\n";
+    let input = DetectionInput::new(contents, Path::new("log.csv"));
+
+    let Detection::Ambiguous(candidates) = detection::detect(&input) else {
+        panic!("Expected an ambiguous detection");
+    };
+    let ids: Vec<&str> = candidates.iter().map(|c| c.detector.id).collect();
+    assert!(ids.contains(&"haltech"));
+    assert!(ids.contains(&"romraider"));
+}
+
+#[test]
+fn test_registry_forced_format_parses() {
+    let contents = b"Time,RPM\n0,900\n1,950\n";
+    let input = DetectionInput::new(contents, Path::new("log.csv"));
+    let detector = detection::find_detector("romraider").expect("RomRaider is registered");
+
+    let log = detector.parse(&input).expect("Should parse as RomRaider");
+    assert_eq!(log.record_count(), 2);
+}
+
+#[test]
+fn test_registry_ranks_best_first() {
+    let input = DetectionInput::new(b"Time [s],RPM [rpm]\n0.0,900\n", Path::new("log.csv"));
+    let candidates = detection::rank(&input);
+
+    assert_eq!(candidates[0].detector.id, "maxxecu");
+    assert!(candidates
+        .windows(2)
+        .all(|pair| pair[0].confidence >= pair[1].confidence));
+}
@@ -7,16 +7,17 @@
 //! - Data row detection and parsing
 //! - Sparse data handling
 //! - Real file parsing with example logs
-
-#[path = "../common/mod.rs"]
-mod common;
-
-use common::assertions::*;
-use common::example_files::*;
-use common::float_cmp::*;
-use common::{example_file_exists, read_example_file};
+//! - Start times agreeing between log info and the clock axis
+//! - Streaming parses with progress and decimation
+
+use crate::common::assertions::*;
+use crate::common::example_files::*;
+use crate::common::float_cmp::*;
+use crate::common::{example_file_exists, read_example_file};
+use std::io::Cursor;
 use ultralog::parsers::haltech::Haltech;
-use ultralog::parsers::types::Parseable;
+use ultralog::parsers::streaming::{LogBuilder, StreamProgress, StreamingParseable};
+use ultralog::parsers::types::{is_missing, Parseable};
 
 // ============================================
 // Format Detection Tests
@@ -71,7 +72,7 @@ DisplayMaxMin : 10000, 0
         .expect("Should parse minimal Haltech log");
 
     assert_eq!(log.channels.len(), 1);
-    assert_eq!(log.data.len(), 2);
+    assert_eq!(log.record_count(), 2);
     assert_eq!(log.times.len(), 2);
 }
 
@@ -106,11 +107,12 @@ DisplayMaxMin : 100, 0
         .expect("Should parse multi-channel log");
 
     assert_eq!(log.channels.len(), 3);
-    assert_eq!(log.data.len(), 3);
+    assert_eq!(log.record_count(), 3);
 
     // Verify all records have 3 values
-    for record in &log.data {
-        assert_eq!(record.len(), 3);
+    assert_eq!(log.columns().len(), 3);
+    for column in log.columns() {
+        assert_eq!(column.len(), log.times.len());
     }
 }
 
@@ -160,7 +162,7 @@ DisplayMaxMin : 10000, 0
     let log = parser.parse(sample).expect("Should parse log");
 
     // RPM should be stored as-is (no conversion)
-    assert_approx_eq(log.data[0][0].as_f64(), 5000.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(0)[0], 5000.0, DEFAULT_TOLERANCE);
 }
 
 #[test]
@@ -180,7 +182,7 @@ DisplayMaxMin : 100, 0
     let log = parser.parse(sample).expect("Should parse log");
 
     // Percentage is converted (raw/10), so 755 -> 75.5
-    assert_approx_eq(log.data[0][0].as_f64(), 75.5, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(0)[0], 75.5, DEFAULT_TOLERANCE);
 }
 
 // ============================================
@@ -243,9 +245,13 @@ DisplayMaxMin : 100, 0
     let parser = Haltech;
     let result = parser.parse(sample);
 
-    // Parser should handle empty values gracefully
-    // The implementation may skip rows with missing values or fill with 0
-    assert!(result.is_ok());
+    // Empty cells are missing samples and don't shift later columns
+    let log = result.expect("Should parse empty values");
+    assert_eq!(log.record_count(), 3);
+    assert!(is_missing(log.channel_data(0)[1]));
+    assert_eq!(log.channel_data(1)[1], 2.0);
+    assert_eq!(log.channel_data(0)[2], 3.0);
+    assert!(is_missing(log.channel_data(1)[2]));
 }
 
 // ============================================
@@ -278,7 +284,29 @@ fn test_haltech_small_example_file() {
     eprintln!(
         "Haltech small log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
+    );
+}
+
+#[test]
+fn test_haltech_example_start_matches_clock_axis() {
+    if !example_file_exists(HALTECH_SMALL) {
+        eprintln!("Skipping test: {} not found", HALTECH_SMALL);
+        return;
+    }
+
+    let content = read_example_file(HALTECH_SMALL);
+    let log = Haltech.parse(&content).unwrap();
+
+    // The header's "02:15:46" is the afternoon, like the rows
+    let axis_start = log.wall_clock(log.times[0]).unwrap();
+    assert_eq!(
+        axis_start.format("%Y-%m-%d %H:%M:%S").to_string(),
+        "2025-07-18 14:15:46"
+    );
+    assert_eq!(
+        log.info().start_datetime.as_deref(),
+        Some("2025-07-18 14:15:46")
     );
 }
 
@@ -305,7 +333,7 @@ fn test_haltech_large_multi_log_file() {
     eprintln!(
         "Haltech large log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -322,7 +350,7 @@ fn test_haltech_empty_file() {
     // Implementation dependent
     match result {
         Ok(log) => {
-            assert!(log.data.is_empty() || log.channels.is_empty());
+            assert!(log.is_empty() || log.channels.is_empty());
         }
         Err(_) => {
             // Also acceptable
@@ -348,7 +376,7 @@ DisplayMaxMin : 10000, 0
     // Header-only file should parse but have no data
     assert!(result.is_ok());
     let log = result.unwrap();
-    assert!(log.data.is_empty());
+    assert!(log.is_empty());
 }
 
 #[test]
@@ -392,7 +420,7 @@ DisplayMaxMin : 200, -50
     let log = parser.parse(sample).expect("Should parse negative values");
 
     // Verify negative values are preserved (Raw type, no conversion)
-    assert!(log.data[0][0].as_f64() < 0.0);
+    assert!(log.channel_data(0)[0] < 0.0);
 }
 
 #[test]
@@ -411,7 +439,7 @@ DisplayMaxMin : 1000000000, 0
     let parser = Haltech;
     let log = parser.parse(sample).expect("Should parse large values");
 
-    assert_approx_eq(log.data[0][0].as_f64(), 999999999.0, 1.0);
+    assert_approx_eq(log.channel_data(0)[0], 999999999.0, 1.0);
 }
 
 #[test]
@@ -430,7 +458,7 @@ DisplayMaxMin : 100, 0
     let parser = Haltech;
     let log = parser.parse(sample).expect("Should parse decimal values");
 
-    assert_approx_eq(log.data[0][0].as_f64(), 12.345678, 0.000001);
+    assert_approx_eq(log.channel_data(0)[0], 12.345678, 0.000001);
 }
 
 // ============================================
@@ -460,9 +488,9 @@ DisplayMaxMin : 100, 0
     let parser = Haltech;
     let log = parser.parse(sample).expect("Should parse log");
 
-    // Test get_channel_data
-    let channel_a = log.get_channel_data(0);
-    let channel_b = log.get_channel_data(1);
+    // Test channel_data
+    let channel_a = log.channel_data(0);
+    let channel_b = log.channel_data(1);
 
     assert_eq!(channel_a.len(), 3);
     assert_eq!(channel_b.len(), 3);
@@ -490,7 +518,7 @@ DisplayMaxMin : 100, 0
     let log = parser.parse(sample).expect("Should parse log");
 
     // Out of bounds should return empty
-    let oob = log.get_channel_data(999);
+    let oob = log.channel_data(999);
     assert!(oob.is_empty());
 }
 
@@ -516,3 +544,57 @@ fn test_haltech_find_channel_index() {
     let not_found = log.find_channel_index("NonExistentChannel12345");
     assert_eq!(not_found, None);
 }
+
+// ============================================
+// Streaming Tests
+// ============================================
+
+#[test]
+fn test_haltech_stream_matches_parse() {
+    if !example_file_exists(HALTECH_SMALL) {
+        eprintln!("Skipping test: {} not found", HALTECH_SMALL);
+        return;
+    }
+
+    let content = read_example_file(HALTECH_SMALL);
+    let expected = Haltech.parse(&content).unwrap();
+
+    let mut builder = LogBuilder::new();
+    let mut reports: Vec<StreamProgress> = Vec::new();
+    Haltech
+        .parse_stream(Cursor::new(content.as_bytes()), &mut builder, &mut |p| {
+            reports.push(p)
+        })
+        .unwrap();
+    let log = builder.finish();
+
+    assert_eq!(log.times, expected.times);
+    assert_eq!(log.channels.len(), expected.channels.len());
+    assert_eq!(log.channel_data(0), expected.channel_data(0));
+
+    // Progress only moves forward and ends at the full file
+    assert!(reports
+        .windows(2)
+        .all(|w| w[0].bytes_read <= w[1].bytes_read));
+    let last = reports.last().expect("Should report progress");
+    assert_eq!(last.fraction(), 1.0);
+    assert_eq!(last.records, expected.record_count());
+}
+
+#[test]
+fn test_haltech_stream_decimation() {
+    let mut sample = String::from("%DataLog%\nChannel : RPM\nType : EngineSpeed\n");
+    for i in 0..10 {
+        sample.push_str(&format!("14:15:46.{:03},{}\n", i * 10, 1000 + i));
+    }
+
+    let mut builder = LogBuilder::new().with_decimation(4);
+    Haltech
+        .parse_stream(Cursor::new(sample.as_bytes()), &mut builder, &mut |_| {})
+        .unwrap();
+    let log = builder.finish();
+
+    assert_eq!(log.record_count(), 3);
+    assert_eq!(log.channel_data(0), &[1000.0, 1004.0, 1008.0]);
+    assert_approx_eq(log.times[1], 0.04, DEFAULT_TOLERANCE);
+}
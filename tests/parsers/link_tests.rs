@@ -7,14 +7,12 @@
 //! - Timeline interpolation
 //! - Real file parsing with example logs
 
-#[path = "../common/mod.rs"]
-mod common;
-
-use common::assertions::*;
-use common::example_files::*;
-use common::{example_file_exists, read_example_binary};
+use crate::common::assertions::*;
+use crate::common::example_files::*;
+use crate::common::{example_file_exists, read_example_binary};
 use ultralog::parsers::link::Link;
 use ultralog::parsers::types::Parseable;
+use ultralog::parsers::ParseError;
 
 // ============================================
 // Format Detection Tests
@@ -130,7 +128,7 @@ fn test_link_standard_example_file() {
     eprintln!(
         "Link standard log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -153,7 +151,7 @@ fn test_link_small_example_file() {
     eprintln!(
         "Link small log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -176,7 +174,7 @@ fn test_link_medium_example_file() {
     eprintln!(
         "Link medium log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -202,10 +200,76 @@ fn test_link_large_example_file() {
     eprintln!(
         "Link large log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
+// ============================================
+// Recovery Tests
+// ============================================
+
+#[test]
+fn test_link_recover_corrupt_header_size() {
+    if !example_file_exists(LINK_STANDARD) {
+        eprintln!("Skipping test: {} not found", LINK_STANDARD);
+        return;
+    }
+
+    let data = read_example_binary(LINK_STANDARD);
+    let expected = Link::parse_binary(&data).unwrap();
+
+    let mut damaged = data.clone();
+    damaged[..4].copy_from_slice(&u32::MAX.to_le_bytes());
+
+    let error = Link::parse_binary(&damaged).unwrap_err();
+    assert!(matches!(error, ParseError::Corrupt { .. }));
+
+    let log = Link::recover_binary(&damaged).unwrap();
+    assert_eq!(log.channels.len(), expected.channels.len());
+    assert_eq!(log.record_count(), expected.record_count());
+    assert!(!log.recovery.as_ref().unwrap().is_clean());
+}
+
+#[test]
+fn test_link_recover_clean_example_file() {
+    if !example_file_exists(LINK_STANDARD) {
+        eprintln!("Skipping test: {} not found", LINK_STANDARD);
+        return;
+    }
+
+    let data = read_example_binary(LINK_STANDARD);
+    let expected = Link::parse_binary(&data).unwrap();
+
+    // An intact file recovers to the same log with nothing skipped
+    let log = Link::recover_binary(&data).unwrap();
+    assert_eq!(log.record_count(), expected.record_count());
+    assert!(log.recovery.as_ref().unwrap().is_clean());
+}
+
+#[test]
+fn test_link_recover_truncated_channel_header() {
+    if !example_file_exists(LINK_STANDARD) {
+        eprintln!("Skipping test: {} not found", LINK_STANDARD);
+        return;
+    }
+
+    let data = read_example_binary(LINK_STANDARD);
+    let expected = Link::parse_binary(&data).unwrap();
+
+    // Cut the file part way through the last channel's name
+    let last = expected.channels.last().unwrap().name();
+    let name: Vec<u8> = last.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let name_start = data
+        .windows(name.len())
+        .rposition(|window| window == name.as_slice())
+        .unwrap();
+    let damaged = &data[..name_start + name.len()];
+
+    let log = Link::recover_binary(damaged).unwrap();
+    assert_eq!(log.channels.len(), expected.channels.len() - 1);
+    assert!(!log.recovery.as_ref().unwrap().is_clean());
+}
+
 // ============================================
 // Channel Tests
 // ============================================
@@ -258,19 +322,19 @@ fn test_link_channel_data_extraction() {
     let data = read_example_binary(LINK_STANDARD);
     let log = Link::parse_binary(&data).expect("Should parse");
 
-    // Test get_channel_data for each channel
+    // Test channel_data for each channel
     for idx in 0..log.channels.len() {
-        let channel_data = log.get_channel_data(idx);
+        let channel_data = log.channel_data(idx);
         assert_eq!(
             channel_data.len(),
-            log.data.len(),
+            log.record_count(),
             "Channel {} data length should match record count",
             idx
         );
     }
 
     // Out of bounds should return empty
-    let oob = log.get_channel_data(999);
+    let oob = log.channel_data(999);
     assert!(oob.is_empty(), "Out of bounds should return empty");
 }
 
@@ -311,17 +375,18 @@ fn test_link_data_structure() {
     let log = Link::parse_binary(&data).expect("Should parse");
 
     // Verify times and data match
-    assert_eq!(log.times.len(), log.data.len());
+    assert_eq!(log.times.len(), log.record_count());
 
     // Verify each record has correct channel count
     let channel_count = log.channels.len();
-    for (i, record) in log.data.iter().enumerate() {
+    assert_eq!(log.columns().len(), channel_count);
+    for (i, column) in log.columns().iter().enumerate() {
         assert_eq!(
-            record.len(),
-            channel_count,
-            "Record {} should have {} values",
+            column.len(),
+            log.times.len(),
+            "Channel {} should have {} values",
             i,
-            channel_count
+            log.times.len()
         );
     }
 }
@@ -364,7 +429,7 @@ fn test_link_parse_invalid_data() {
     let result = Link::parse_binary(invalid);
 
     match result {
-        Ok(log) => assert!(log.data.is_empty()),
+        Ok(log) => assert!(log.is_empty()),
         Err(_) => { /* Expected */ }
     }
 }
@@ -380,7 +445,7 @@ fn test_link_parse_truncated_file() {
 
     // Should handle gracefully
     match result {
-        Ok(log) => assert!(log.data.is_empty()),
+        Ok(log) => assert!(log.is_empty()),
         Err(_) => { /* Also acceptable */ }
     }
 }
@@ -390,7 +455,7 @@ fn test_link_parse_empty_data() {
     let result = Link::parse_binary(b"");
 
     match result {
-        Ok(log) => assert!(log.data.is_empty()),
+        Ok(log) => assert!(log.is_empty()),
         Err(_) => { /* Expected */ }
     }
 }
@@ -446,13 +511,13 @@ fn test_link_all_example_files() {
             "{} should have channels",
             file_path
         );
-        assert!(!log.data.is_empty(), "{} should have data", file_path);
+        assert!(!log.is_empty(), "{} should have data", file_path);
 
         eprintln!(
             "{}: {} channels, {} records",
             file_path,
             log.channels.len(),
-            log.data.len()
+            log.record_count()
         );
     }
 }
@@ -474,7 +539,7 @@ fn test_link_large_file_performance() {
     let log = Link::parse_binary(&data).expect("Should parse large file");
     let elapsed = start.elapsed();
 
-    eprintln!("Parsed {} records in {:?}", log.data.len(), elapsed);
+    eprintln!("Parsed {} records in {:?}", log.record_count(), elapsed);
 
     // Should complete in reasonable time
     assert!(
@@ -0,0 +1,148 @@
+//! Comprehensive tests for the MaxxECU MTune CSV parser
+//!
+//! Tests cover:
+//! - Format detection on MTune's bracketed-unit header
+//! - Comma, semicolon (decimal comma) and tab delimited exports
+//! - Unit extraction and time conversion
+//! - Carry-forward of empty cells
+//! - Error handling for malformed logs
+
+use crate::common::assertions::*;
+use crate::common::float_cmp::*;
+use ultralog::parsers::ecumaster::EcuMaster;
+use ultralog::parsers::maxxecu::MaxxEcu;
+use ultralog::parsers::megasquirt::MegaSquirt;
+use ultralog::parsers::types::{Meta, Parseable};
+
+const MTUNE_LOG: &str = "Time [s],RPM [rpm],Manifold pressure [kPa],Throttle position [%],Lambda A [λ],Coolant temp [°C],Gear\n\
+    12.000,910,34.5,0.0,1.01,84.2,0\n\
+    12.020,925,34.8,0.0,1.00,84.2,0\n\
+    12.040,1310,52.0,14.1,0.97,84.3,1\n\
+    12.060,1880,,21.6,0.93,84.3,1\n\
+    12.080,2440,77.4,28.0,,84.4,1\n";
+
+const MTUNE_LOG_EU: &str = "Time [ms];RPM [rpm];Lambda A [λ];Battery voltage [V]\n\
+    0;905;1,02;14,1\n\
+    20;918;1,01;14,1\n\
+    40;1200;0,98;14,0\n";
+
+// ============================================
+// Format Detection Tests
+// ============================================
+
+#[test]
+fn test_maxxecu_detection() {
+    assert!(MaxxEcu::detect(MTUNE_LOG), "Should detect comma export");
+    assert!(
+        MaxxEcu::detect(MTUNE_LOG_EU),
+        "Should detect semicolon export"
+    );
+    assert!(
+        MaxxEcu::detect("Time [s]\tRPM [rpm]\n0.0\t900\n"),
+        "Should detect tab export"
+    );
+}
+
+#[test]
+fn test_maxxecu_detection_rejects_other_formats() {
+    assert!(!MaxxEcu::detect("Time (msec),Engine Speed (rpm)\n0,900\n"));
+    assert!(!MaxxEcu::detect("TIME;engine/rpm\n0.0;1000\n"));
+    assert!(!MaxxEcu::detect("Time\tRPM\ns\trpm\n0.0\t900\n"));
+    assert!(!MaxxEcu::detect("%DataLog%\nDataLogVersion : 1.1\n"));
+    assert!(!MaxxEcu::detect(""));
+}
+
+#[test]
+fn test_maxxecu_not_detected_as_others() {
+    assert!(
+        !EcuMaster::detect(MTUNE_LOG),
+        "Should not detect as ECUMaster"
+    );
+    assert!(
+        !MegaSquirt::detect(MTUNE_LOG),
+        "Should not detect as MegaSquirt"
+    );
+}
+
+// ============================================
+// Parsing Tests
+// ============================================
+
+#[test]
+fn test_maxxecu_parse_structure() {
+    let log = MaxxEcu.parse(MTUNE_LOG).expect("Should parse MTune log");
+
+    assert_valid_log_structure(&log);
+    assert_monotonic_times(&log);
+    assert_finite_values(&log);
+    assert_eq!(log.channels.len(), 6, "Time column is not a channel");
+    assert_eq!(log.record_count(), 5);
+}
+
+#[test]
+fn test_maxxecu_parse_units() {
+    let log = MaxxEcu.parse(MTUNE_LOG).unwrap();
+
+    let units: Vec<&str> = log.channels.iter().map(|c| c.unit()).collect();
+    assert_eq!(units, vec!["rpm", "kPa", "%", "λ", "°C", ""]);
+    assert_eq!(log.channels[0].type_name(), "MaxxECU");
+}
+
+#[test]
+fn test_maxxecu_parse_times_relative() {
+    let log = MaxxEcu.parse(MTUNE_LOG).unwrap();
+
+    assert_approx_eq(log.times[0], 0.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.times[4], 0.08, DEFAULT_TOLERANCE);
+}
+
+#[test]
+fn test_maxxecu_parse_carry_forward() {
+    let log = MaxxEcu.parse(MTUNE_LOG).unwrap();
+
+    // Empty MAP and lambda cells keep the previous sample
+    assert_approx_eq(log.channel_data(1)[3], 52.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(3)[4], 0.93, DEFAULT_TOLERANCE);
+}
+
+#[test]
+fn test_maxxecu_parse_european_export() {
+    let log = MaxxEcu.parse(MTUNE_LOG_EU).unwrap();
+
+    assert_eq!(log.channels.len(), 3);
+    assert_approx_eq(log.times[2], 0.04, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(1)[2], 0.98, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(2)[0], 14.1, DEFAULT_TOLERANCE);
+
+    match &log.meta {
+        Meta::MaxxEcu(meta) => {
+            assert_eq!(meta.delimiter, ';');
+            assert_eq!(meta.time_unit, "ms");
+            assert_eq!(meta.channel_count, 3);
+            assert_eq!(meta.data_points, 3);
+        }
+        _ => panic!("Expected MaxxECU metadata"),
+    }
+}
+
+// ============================================
+// Error Handling Tests
+// ============================================
+
+#[test]
+fn test_maxxecu_parse_empty() {
+    assert!(MaxxEcu.parse("").is_err());
+}
+
+#[test]
+fn test_maxxecu_parse_missing_time_column() {
+    assert!(MaxxEcu.parse("RPM [rpm],MAP [kPa]\n900,35\n").is_err());
+}
+
+#[test]
+fn test_maxxecu_parse_skips_bad_rows() {
+    let log = MaxxEcu
+        .parse("Time [s],RPM [rpm]\n0.00,900\nlog paused\n0.02,950\n")
+        .unwrap();
+    assert_eq!(log.record_count(), 2);
+}
@@ -0,0 +1,163 @@
+//! Comprehensive tests for the MegaSquirt / TunerStudio .msl parser
+//!
+//! Tests cover:
+//! - Format detection (with and without the quoted preamble)
+//! - Preamble, header and units row parsing
+//! - MARK lines recorded as markers
+//! - Carry-forward of unparsable cells
+//! - Error handling for malformed logs
+
+use crate::common::assertions::*;
+use crate::common::float_cmp::*;
+use ultralog::parsers::ecumaster::EcuMaster;
+use ultralog::parsers::megasquirt::MegaSquirt;
+use ultralog::parsers::romraider::RomRaider;
+use ultralog::parsers::types::{Meta, Parseable};
+
+const MS3_LOG: &str = "\"MS3 Format 0262.09 \"\n\
+    \"Capture Date: Sun Jan 31 16:17:01 EST 2021\"\n\
+    Time\tSecL\tRPM\tMAP\tTPS\tAFR\tCLT\tBatt V\n\
+    s\ts\tRPM\tkPa\t%\tAFR\tdeg F\tv\n\
+    100.016\t100\t812\t34.1\t0.0\t14.7\t181.2\t14.1\n\
+    100.066\t100\t825\t34.6\t0.0\t14.6\t181.2\t14.1\n\
+    100.116\t100\t1403\t58.0\t12.5\t13.8\t181.3\t14.0\n\
+    MARK 000 - Manual - Sun Jan 31 16:17:14 EST 2021\n\
+    100.166\t100\t2210\t72.4\t24.9\t13.1\t181.3\t14.0\n\
+    100.216\t100\t2650\t80.2\t31.0\t12.9\t181.4\t13.9\n\
+    MARK 001 - Manual - Sun Jan 31 16:17:16 EST 2021\n\
+    100.266\t100\t2890\t85.0\t35.5\t12.7\t181.4\t13.9\n";
+
+// ============================================
+// Format Detection Tests
+// ============================================
+
+#[test]
+fn test_megasquirt_detection() {
+    assert!(MegaSquirt::detect(MS3_LOG), "Should detect TunerStudio log");
+}
+
+#[test]
+fn test_megasquirt_detection_without_preamble() {
+    let content = "Time\tRPM\tMAP\ns\tRPM\tkPa\n0.000\t800\t35\n";
+    assert!(
+        MegaSquirt::detect(content),
+        "Should detect MegaLogViewer-style log without preamble"
+    );
+}
+
+#[test]
+fn test_megasquirt_detection_rejects_other_formats() {
+    assert!(!MegaSquirt::detect("TIME\tengine/rpm\n0.0\t1000\n"));
+    assert!(!MegaSquirt::detect("TIME;engine/rpm\n0.0;1000\n"));
+    assert!(!MegaSquirt::detect("Time,RPM,Load\n0,1000,50\n"));
+    assert!(!MegaSquirt::detect("%DataLog%\nDataLogVersion : 1.1\n"));
+    assert!(!MegaSquirt::detect(""));
+}
+
+#[test]
+fn test_megasquirt_not_detected_as_others() {
+    assert!(
+        !EcuMaster::detect(MS3_LOG),
+        "Should not detect as ECUMaster"
+    );
+    assert!(
+        !RomRaider::detect(MS3_LOG),
+        "Should not detect as RomRaider"
+    );
+}
+
+// ============================================
+// Parsing Tests
+// ============================================
+
+#[test]
+fn test_megasquirt_parse_structure() {
+    let log = MegaSquirt.parse(MS3_LOG).expect("Should parse MS3 log");
+
+    assert_valid_log_structure(&log);
+    assert_monotonic_times(&log);
+    assert_finite_values(&log);
+    assert_eq!(log.channels.len(), 7, "Time column is not a channel");
+    assert_eq!(log.record_count(), 6);
+}
+
+#[test]
+fn test_megasquirt_parse_metadata() {
+    let log = MegaSquirt.parse(MS3_LOG).unwrap();
+
+    match &log.meta {
+        Meta::MegaSquirt(meta) => {
+            assert_eq!(meta.signature, "MS3 Format 0262.09");
+            assert_eq!(meta.capture_date, "Sun Jan 31 16:17:01 EST 2021");
+            assert_eq!(meta.channel_count, 7);
+            assert_eq!(meta.data_points, 6);
+        }
+        _ => panic!("Expected MegaSquirt metadata"),
+    }
+}
+
+#[test]
+fn test_megasquirt_parse_units() {
+    let log = MegaSquirt.parse(MS3_LOG).unwrap();
+
+    let unit_of = |name: &str| {
+        log.channels
+            .iter()
+            .find(|c| c.name() == name)
+            .map(|c| c.unit().to_string())
+            .unwrap_or_else(|| panic!("Missing channel {}", name))
+    };
+    assert_eq!(unit_of("RPM"), "RPM");
+    assert_eq!(unit_of("MAP"), "kPa");
+    assert_eq!(unit_of("TPS"), "%");
+    assert_eq!(unit_of("CLT"), "deg F");
+}
+
+#[test]
+fn test_megasquirt_parse_times_relative() {
+    let log = MegaSquirt.parse(MS3_LOG).unwrap();
+
+    assert_approx_eq(log.times[0], 0.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.times[5], 0.25, DEFAULT_TOLERANCE);
+}
+
+#[test]
+fn test_megasquirt_parse_markers() {
+    let log = MegaSquirt.parse(MS3_LOG).unwrap();
+
+    let Meta::MegaSquirt(meta) = &log.meta else {
+        panic!("Expected MegaSquirt metadata");
+    };
+    assert_eq!(meta.markers.len(), 2);
+    assert_approx_eq(meta.markers[0].time, 0.1, DEFAULT_TOLERANCE);
+    assert_approx_eq(meta.markers[1].time, 0.2, DEFAULT_TOLERANCE);
+    assert!(meta.markers[1].label.starts_with("MARK 001"));
+}
+
+#[test]
+fn test_megasquirt_parse_time_column_not_first() {
+    let content =
+        "\"rusEFI 2020.07.06\"\nRPM\tTime\tMAP\nRPM\ts\tkPa\n900\t5.0\t40\n950\t5.1\t42\n";
+    let log = MegaSquirt.parse(content).unwrap();
+
+    assert_eq!(log.channels.len(), 2);
+    assert_eq!(log.channels[1].name(), "MAP");
+    assert_eq!(log.channel_data(0)[1], 950.0);
+    assert_approx_eq(log.times[1], 0.1, DEFAULT_TOLERANCE);
+}
+
+// ============================================
+// Error Handling Tests
+// ============================================
+
+#[test]
+fn test_megasquirt_parse_preamble_only() {
+    assert!(MegaSquirt.parse("\"MS2Extra comms342h2\"\n").is_err());
+}
+
+#[test]
+fn test_megasquirt_parse_header_only() {
+    let log = MegaSquirt.parse("Time\tRPM\ns\tRPM\n").unwrap();
+    assert_eq!(log.channels.len(), 1);
+    assert!(log.is_empty());
+}
@@ -6,11 +6,18 @@
 //! - Edge cases and error handling
 //! - Data integrity validation
 
+pub mod aem_tests;
 pub mod aim_tests;
+pub mod bluedriver_tests;
+pub mod can_trace_tests;
 pub mod ecumaster_tests;
 pub mod emerald_tests;
 pub mod format_detection_tests;
 pub mod haltech_tests;
 pub mod link_tests;
+pub mod maxxecu_tests;
+pub mod megasquirt_tests;
+pub mod motec_tests;
 pub mod romraider_tests;
+pub mod spec_csv_tests;
 pub mod speeduino_tests;
@@ -0,0 +1,270 @@
+//! Comprehensive tests for the MoTeC i2 .ld parser
+//!
+//! Tests cover:
+//! - Format detection
+//! - Event/venue/vehicle header metadata
+//! - Per-channel sample rates aligned onto one timebase
+//! - Integer scaling and float channels
+//! - Error handling for truncated files
+
+use crate::common::assertions::*;
+use crate::common::float_cmp::*;
+use ultralog::parsers::aem::Aem;
+use ultralog::parsers::link::Link;
+use ultralog::parsers::motec::Motec;
+use ultralog::parsers::speeduino::Speeduino;
+use ultralog::parsers::types::Meta;
+
+const HEADER_SIZE: usize = 1762;
+const CHANNEL_SIZE: usize = 124;
+
+/// Sample storage for a synthetic channel
+enum Samples<'a> {
+    Int16(&'a [i16]),
+    Float32(&'a [f32]),
+}
+
+/// Synthetic channel: name, unit, rate (Hz), (shift, multiplier, divisor, decimal places), samples
+struct TestChannel<'a> {
+    name: &'a str,
+    unit: &'a str,
+    rate: u16,
+    scaling: (i16, i16, i16, i16),
+    samples: Samples<'a>,
+}
+
+fn put(buf: &mut [u8], offset: usize, bytes: &[u8]) {
+    buf[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
+
+/// Build an .ld file with the event block directly after the header
+fn ld_file(channels: &[TestChannel]) -> Vec<u8> {
+    let event_ptr = HEADER_SIZE;
+    let meta_ptr = event_ptr + 1154;
+    let data_ptr = meta_ptr + channels.len() * CHANNEL_SIZE;
+
+    let mut buf = vec![0u8; data_ptr];
+    put(&mut buf, 0, &0x40u32.to_le_bytes());
+    put(&mut buf, 8, &(meta_ptr as u32).to_le_bytes());
+    put(&mut buf, 12, &(data_ptr as u32).to_le_bytes());
+    put(&mut buf, 36, &(event_ptr as u32).to_le_bytes());
+    put(&mut buf, 74, b"M150");
+    put(&mut buf, 94, b"05/04/2025");
+    put(&mut buf, 126, b"10:15:00");
+    put(&mut buf, 158, b"A. Driver");
+    put(&mut buf, 222, b"Cooper S");
+    put(&mut buf, 350, b"Castle Combe");
+    put(&mut buf, 1572, b"Baseline map");
+    put(&mut buf, event_ptr, b"Sprint Series");
+    put(&mut buf, event_ptr + 64, b"Qualifying");
+
+    let mut sample_ptr = data_ptr;
+    for (i, ch) in channels.iter().enumerate() {
+        let desc = meta_ptr + i * CHANNEL_SIZE;
+        let next = if i + 1 < channels.len() {
+            desc + CHANNEL_SIZE
+        } else {
+            0
+        };
+        let (kind, size, bytes, count): (u16, u16, Vec<u8>, usize) = match ch.samples {
+            Samples::Int16(s) => (
+                3,
+                2,
+                s.iter().flat_map(|v| v.to_le_bytes()).collect(),
+                s.len(),
+            ),
+            Samples::Float32(s) => (
+                7,
+                4,
+                s.iter().flat_map(|v| v.to_le_bytes()).collect(),
+                s.len(),
+            ),
+        };
+        let (shift, mul, div, dec) = ch.scaling;
+
+        put(&mut buf, desc + 4, &(next as u32).to_le_bytes());
+        put(&mut buf, desc + 8, &(sample_ptr as u32).to_le_bytes());
+        put(&mut buf, desc + 12, &(count as u32).to_le_bytes());
+        put(&mut buf, desc + 18, &kind.to_le_bytes());
+        put(&mut buf, desc + 20, &size.to_le_bytes());
+        put(&mut buf, desc + 22, &ch.rate.to_le_bytes());
+        put(&mut buf, desc + 24, &shift.to_le_bytes());
+        put(&mut buf, desc + 26, &mul.to_le_bytes());
+        put(&mut buf, desc + 28, &div.to_le_bytes());
+        put(&mut buf, desc + 30, &dec.to_le_bytes());
+        put(&mut buf, desc + 32, ch.name.as_bytes());
+        put(&mut buf, desc + 72, ch.unit.as_bytes());
+
+        sample_ptr += bytes.len();
+        buf.extend_from_slice(&bytes);
+    }
+    buf
+}
+
+fn sample_log() -> Vec<u8> {
+    ld_file(&[
+        TestChannel {
+            name: "Engine Speed",
+            unit: "rpm",
+            rate: 50,
+            scaling: (0, 1, 1, 0),
+            samples: Samples::Int16(&[900, 1500, 2100, 2700, 3300, 3900, 4500, 5100, 5700, 6300]),
+        },
+        TestChannel {
+            name: "Engine Temp",
+            unit: "C",
+            rate: 10,
+            scaling: (0, 1, 1, 1),
+            samples: Samples::Int16(&[881, 883]),
+        },
+        TestChannel {
+            name: "Lambda 1",
+            unit: "LA",
+            rate: 25,
+            scaling: (0, 1, 1, 0),
+            samples: Samples::Float32(&[0.98, 0.95, 0.91, 0.88, 0.86]),
+        },
+    ])
+}
+
+// ============================================
+// Format Detection Tests
+// ============================================
+
+#[test]
+fn test_motec_detection() {
+    assert!(Motec::detect(&sample_log()), "Should detect MoTeC .ld file");
+}
+
+#[test]
+fn test_motec_detection_rejects_other_data() {
+    assert!(!Motec::detect(b""));
+    assert!(!Motec::detect(b"MLVLG\x00\x01"));
+    assert!(!Motec::detect(&vec![0u8; 4096]));
+
+    // Correct marker but a channel list pointing outside the file
+    let mut file = sample_log();
+    file[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(!Motec::detect(&file));
+}
+
+#[test]
+fn test_motec_not_detected_as_others() {
+    let file = sample_log();
+    assert!(!Speeduino::detect(&file), "Should not detect as Speeduino");
+    assert!(!Link::detect(&file), "Should not detect as Link");
+    assert!(!Aem::detect(&file), "Should not detect as AEM");
+}
+
+// ============================================
+// Parsing Tests
+// ============================================
+
+#[test]
+fn test_motec_parse_structure() {
+    let log = Motec::parse_binary(&sample_log()).expect("Should parse .ld file");
+
+    assert_valid_log_structure(&log);
+    assert_monotonic_times(&log);
+    assert_finite_values(&log);
+    assert_eq!(log.channels.len(), 3);
+}
+
+#[test]
+fn test_motec_parse_metadata() {
+    let log = Motec::parse_binary(&sample_log()).unwrap();
+
+    match &log.meta {
+        Meta::Motec(meta) => {
+            assert_eq!(meta.event, "Sprint Series");
+            assert_eq!(meta.session, "Qualifying");
+            assert_eq!(meta.venue, "Castle Combe");
+            assert_eq!(meta.vehicle, "Cooper S");
+            assert_eq!(meta.driver, "A. Driver");
+            assert_eq!(meta.short_comment, "Baseline map");
+            assert_eq!(meta.device_type, "M150");
+            assert_eq!(meta.date, "05/04/2025");
+            assert_eq!(meta.time, "10:15:00");
+            assert_eq!(meta.sample_rate_hz, 50.0);
+            assert_eq!(meta.channel_count, 3);
+        }
+        _ => panic!("Expected MoTeC metadata"),
+    }
+}
+
+#[test]
+fn test_motec_log_info() {
+    let info = Motec::parse_binary(&sample_log()).unwrap().info();
+
+    assert_eq!(info.format, "MoTeC");
+    assert_eq!(info.start_datetime.as_deref(), Some("05/04/2025 10:15:00"));
+    assert_eq!(info.ecu_model.as_deref(), Some("M150"));
+    assert_eq!(info.vehicle.as_deref(), Some("Cooper S"));
+    assert_eq!(info.driver.as_deref(), Some("A. Driver"));
+    assert_eq!(info.track.as_deref(), Some("Castle Combe"));
+    assert_eq!(info.lap_count, None);
+    assert_eq!(info.channel_count, 3);
+    assert!(info
+        .vendor
+        .contains(&("Session".to_string(), "Qualifying".to_string())));
+}
+
+#[test]
+fn test_motec_parse_channel_details() {
+    let log = Motec::parse_binary(&sample_log()).unwrap();
+
+    let names: Vec<String> = log.channels.iter().map(|c| c.name()).collect();
+    assert_eq!(names, vec!["Engine Speed", "Engine Temp", "Lambda 1"]);
+    assert_eq!(log.channels[1].unit(), "C");
+    assert_eq!(log.channels[2].type_name(), "MoTeC");
+}
+
+#[test]
+fn test_motec_parse_keeps_all_rates() {
+    let log = Motec::parse_binary(&sample_log()).unwrap();
+
+    // 10 samples at 50 Hz, 2 at 10 Hz and 5 at 25 Hz all span 0.2 s
+    assert_eq!(log.record_count(), 10);
+    assert_approx_eq(log.times[9], 0.18, DEFAULT_TOLERANCE);
+
+    assert_eq!(log.channel_data(0)[9], 6300.0);
+    assert_approx_eq(log.channel_data(1)[4], 88.1, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(1)[5], 88.3, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(2)[1], 0.98, 1e-6);
+    assert_approx_eq(log.channel_data(2)[2], 0.95, 1e-6);
+    assert_approx_eq(log.channel_data(2)[9], 0.86, 1e-6);
+}
+
+#[test]
+fn test_motec_parse_applies_scaling() {
+    let file = ld_file(&[TestChannel {
+        name: "Fuel Pressure",
+        unit: "kPa",
+        rate: 10,
+        // (raw / 4 * 0.1 + 100) * 2
+        scaling: (100, 2, 4, 1),
+        samples: Samples::Int16(&[400, -400]),
+    }]);
+    let log = Motec::parse_binary(&file).unwrap();
+
+    assert_approx_eq(log.channel_data(0)[0], 220.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(0)[1], 180.0, DEFAULT_TOLERANCE);
+}
+
+// ============================================
+// Error Handling Tests
+// ============================================
+
+#[test]
+fn test_motec_parse_truncated_samples() {
+    let mut file = sample_log();
+    // Drop the float channel's samples; the integer channels still load
+    file.truncate(file.len() - 20);
+    let log = Motec::parse_binary(&file).unwrap();
+    assert_eq!(log.channels.len(), 2);
+}
+
+#[test]
+fn test_motec_parse_rejects_non_ld() {
+    assert!(Motec::parse_binary(b"not a motec file").is_err());
+}
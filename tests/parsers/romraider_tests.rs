@@ -7,11 +7,8 @@
 //! - Unit inference for Subaru-specific channels
 //! - Synthetic data tests (no example files available)
 
-#[path = "../common/mod.rs"]
-mod common;
-
-use common::assertions::*;
-use common::float_cmp::*;
+use crate::common::assertions::*;
+use crate::common::float_cmp::*;
 use ultralog::parsers::romraider::RomRaider;
 use ultralog::parsers::types::Parseable;
 
@@ -127,7 +124,7 @@ fn test_romraider_unit_extraction() {
     for channel in &log.channels {
         let unit = channel.unit();
         // Units should be inferred or extracted
-        assert!(!unit.is_empty() || unit == "C" || unit == "V" || true);
+        assert!(!unit.is_empty(), "{} has no unit", channel.name());
     }
 }
 
@@ -268,7 +265,7 @@ fn test_romraider_minimal_log() {
     let log = parser.parse(sample).expect("Should parse minimal");
 
     assert_eq!(log.channels.len(), 1);
-    assert_eq!(log.data.len(), 1);
+    assert_eq!(log.record_count(), 1);
 }
 
 #[test]
@@ -284,10 +281,11 @@ fn test_romraider_multiple_channels() {
     let log = parser.parse(sample).expect("Should parse");
 
     assert_eq!(log.channels.len(), 4);
-    assert_eq!(log.data.len(), 4);
+    assert_eq!(log.record_count(), 4);
 
-    for record in &log.data {
-        assert_eq!(record.len(), 4);
+    assert_eq!(log.columns().len(), 4);
+    for column in log.columns() {
+        assert_eq!(column.len(), log.times.len());
     }
 }
 
@@ -298,10 +296,10 @@ fn test_romraider_data_values() {
     let parser = RomRaider;
     let log = parser.parse(sample).expect("Should parse");
 
-    assert_approx_eq(log.data[0][0].as_f64(), 1000.0, DEFAULT_TOLERANCE);
-    assert_approx_eq(log.data[0][1].as_f64(), 50.0, DEFAULT_TOLERANCE);
-    assert_approx_eq(log.data[1][0].as_f64(), 2000.0, DEFAULT_TOLERANCE);
-    assert_approx_eq(log.data[1][1].as_f64(), 75.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(0)[0], 1000.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(1)[0], 50.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(0)[1], 2000.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(1)[1], 75.0, DEFAULT_TOLERANCE);
 }
 
 // ============================================
@@ -314,7 +312,7 @@ fn test_romraider_empty_file() {
     let result = parser.parse("");
 
     match result {
-        Ok(log) => assert!(log.data.is_empty()),
+        Ok(log) => assert!(log.is_empty()),
         Err(_) => { /* Also acceptable */ }
     }
 }
@@ -328,7 +326,7 @@ fn test_romraider_header_only() {
 
     assert!(result.is_ok());
     let log = result.unwrap();
-    assert!(log.data.is_empty());
+    assert!(log.is_empty());
 }
 
 #[test]
@@ -338,7 +336,7 @@ fn test_romraider_negative_values() {
     let parser = RomRaider;
     let log = parser.parse(sample).expect("Should parse negative values");
 
-    assert!(log.data[0][0].as_f64() < 0.0);
+    assert!(log.channel_data(0)[0] < 0.0);
 }
 
 #[test]
@@ -348,7 +346,7 @@ fn test_romraider_decimal_precision() {
     let parser = RomRaider;
     let log = parser.parse(sample).expect("Should parse decimals");
 
-    assert_approx_eq(log.data[0][0].as_f64(), 12.345678, 0.000001);
+    assert_approx_eq(log.channel_data(0)[0], 12.345678, 0.000001);
 }
 
 #[test]
@@ -363,8 +361,9 @@ fn test_romraider_missing_values() {
     let log = result.unwrap();
 
     // All records should have 3 values (filled with 0 or skipped)
-    for record in &log.data {
-        assert_eq!(record.len(), 3);
+    assert_eq!(log.columns().len(), 3);
+    for column in log.columns() {
+        assert_eq!(column.len(), log.times.len());
     }
 }
 
@@ -386,7 +385,7 @@ fn test_romraider_large_values() {
     let parser = RomRaider;
     let log = parser.parse(sample).expect("Should parse large values");
 
-    assert_approx_eq(log.data[0][0].as_f64(), 999999999.0, 1.0);
+    assert_approx_eq(log.channel_data(0)[0], 999999999.0, 1.0);
 }
 
 // ============================================
@@ -417,8 +416,8 @@ fn test_romraider_channel_data_extraction() {
     let parser = RomRaider;
     let log = parser.parse(sample).expect("Should parse");
 
-    let channel_a = log.get_channel_data(0);
-    let channel_b = log.get_channel_data(1);
+    let channel_a = log.channel_data(0);
+    let channel_b = log.channel_data(1);
 
     assert_eq!(channel_a.len(), 3);
     assert_eq!(channel_b.len(), 3);
@@ -504,12 +503,12 @@ fn test_romraider_parse_european_locale() {
     let log = parser.parse(sample).expect("Should parse European locale");
 
     assert_eq!(log.channels.len(), 3);
-    assert_eq!(log.data.len(), 3);
+    assert_eq!(log.record_count(), 3);
 
     // Check first row values (European decimals converted)
-    assert_approx_eq(log.data[0][0].as_f64(), 14.0, DEFAULT_TOLERANCE);
-    assert_approx_eq(log.data[0][1].as_f64(), 860.0, DEFAULT_TOLERANCE);
-    assert_approx_eq(log.data[0][2].as_f64(), 0.38, 0.001);
+    assert_approx_eq(log.channel_data(0)[0], 14.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(1)[0], 860.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.channel_data(2)[0], 0.38, 0.001);
 
     // Check relative timestamps (converted from ms to seconds)
     let times = log.get_times_as_f64();
@@ -524,8 +523,8 @@ fn test_romraider_parse_european_locale() {
 
 #[test]
 fn test_romraider_european_example_file() {
-    use common::example_files::ROMRAIDER_EUROPEAN;
-    use common::{example_file_exists, read_example_file};
+    use crate::common::example_files::ROMRAIDER_EUROPEAN;
+    use crate::common::{example_file_exists, read_example_file};
 
     if !example_file_exists(ROMRAIDER_EUROPEAN) {
         eprintln!("Skipping test: {} not found", ROMRAIDER_EUROPEAN);
@@ -567,6 +566,6 @@ fn test_romraider_european_example_file() {
     eprintln!(
         "RomRaider European log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
@@ -0,0 +1,218 @@
+//! Comprehensive tests for the adapter spec driven CSV parser
+//!
+//! Tests cover:
+//! - Detection against an adapter's header layout
+//! - Delimiter, header row and data start row from `file_format`
+//! - Channel units and conversions from `ChannelSpec`
+//! - Timestamp unit scaling
+//! - Error handling for malformed logs
+
+use crate::common::assertions::*;
+use crate::common::float_cmp::*;
+use ultralog::adapters::AdapterSpec;
+use ultralog::parsers::haltech::Haltech;
+use ultralog::parsers::spec_csv::SpecCsv;
+use ultralog::parsers::types::{Meta, Parseable, ValueKind};
+
+const ADAPTER_YAML: &str = r#"
+openecualliance: "1.0"
+id: acme-logger
+name: Acme Logger
+version: "1.0.0"
+vendor: acme
+file_format:
+  type: csv
+  extensions: [".alg"]
+  delimiter: "\t"
+  header_row: 0
+  data_start_row: 2
+  timestamp_column: Seconds
+  timestamp_unit: s
+channels:
+  - id: rpm
+    name: Engine RPM
+    category: engine
+    data_type: float
+    unit: rpm
+    source_names: ["Engine Speed"]
+  - id: map
+    name: Manifold Pressure
+    category: pressure
+    data_type: float
+    unit: kPa
+    source_unit: psi
+    conversion: "x * 6.894757"
+    source_names: ["Boost Pressure"]
+  - id: iat
+    name: Intake Air Temperature
+    category: temperature
+    data_type: float
+    unit: °C
+    source_unit: °C
+    source_names: ["IAT"]
+"#;
+
+const ACME_LOG: &str = "Seconds\tEngine Speed\tBoost Pressure (psi)\tIAT\tAux 1\n\
+    s\trpm\tpsi\tC\tV\n\
+    10.0\t900\t0.0\t31\t1.2\n\
+    10.5\t1500\t1.5\t31\t1.3\n\
+    11.0\t2600\t\t32\t1.3\n";
+
+fn acme_adapter() -> AdapterSpec {
+    serde_yaml::from_str(ADAPTER_YAML).expect("Adapter YAML should deserialize")
+}
+
+// ============================================
+// Format Detection Tests
+// ============================================
+
+#[test]
+fn test_spec_csv_detection() {
+    let parser = SpecCsv::new(acme_adapter());
+    assert!(parser.detect(ACME_LOG), "Should detect Acme log");
+}
+
+#[test]
+fn test_spec_csv_detection_rejects_other_layouts() {
+    let parser = SpecCsv::new(acme_adapter());
+    assert!(
+        !parser.detect("Seconds\tFoo\tBar\n0\t1\t2\n"),
+        "No known channel"
+    );
+    assert!(
+        !parser.detect("Time\tEngine Speed\n0\t900\n"),
+        "No timestamp column"
+    );
+    assert!(
+        !parser.detect("Seconds,Engine Speed\n0,900\n"),
+        "Wrong delimiter"
+    );
+    assert!(!parser.detect("%DataLog%\nDataLogVersion : 1.1\n"));
+    assert!(!parser.detect(""));
+}
+
+#[test]
+fn test_spec_csv_detection_requires_csv_adapter() {
+    let mut adapter = acme_adapter();
+    adapter.file_format.format_type = "binary".to_string();
+    assert!(!SpecCsv::new(adapter).detect(ACME_LOG));
+}
+
+#[test]
+fn test_spec_csv_not_detected_as_haltech() {
+    assert!(!Haltech::detect(ACME_LOG));
+}
+
+// ============================================
+// Parsing Tests
+// ============================================
+
+#[test]
+fn test_spec_csv_parse_structure() {
+    let log = SpecCsv::new(acme_adapter())
+        .parse(ACME_LOG)
+        .expect("Should parse Acme log");
+
+    assert_valid_log_structure(&log);
+    assert_monotonic_times(&log);
+    assert_finite_values(&log);
+    assert_eq!(log.channels.len(), 4, "Timestamp column is not a channel");
+    assert_eq!(log.record_count(), 3, "Units row is skipped");
+}
+
+#[test]
+fn test_spec_csv_parse_units_from_spec() {
+    let log = SpecCsv::new(acme_adapter()).parse(ACME_LOG).unwrap();
+
+    let units: Vec<&str> = log.channels.iter().map(|c| c.unit()).collect();
+    assert_eq!(units, vec!["rpm", "kPa", "°C", ""]);
+    assert_eq!(log.channels[1].name(), "Boost Pressure (psi)");
+    assert_eq!(log.channels[1].id(), "map");
+    assert_eq!(log.channels[3].id(), "Aux 1");
+    assert_eq!(log.channels[0].type_name(), "Adapter Spec");
+}
+
+#[test]
+fn test_spec_csv_parse_conversion_and_carry_forward() {
+    let log = SpecCsv::new(acme_adapter()).parse(ACME_LOG).unwrap();
+
+    assert_approx_eq(log.channel_data(1)[1], 10.342, 1e-3);
+    // Empty boost cell keeps the previous converted value
+    assert_approx_eq(log.channel_data(1)[2], 10.342, 1e-3);
+    assert_approx_eq(log.channel_data(0)[2], 2600.0, DEFAULT_TOLERANCE);
+}
+
+#[test]
+fn test_spec_csv_parse_times_relative() {
+    let log = SpecCsv::new(acme_adapter()).parse(ACME_LOG).unwrap();
+
+    assert_approx_eq(log.times[0], 0.0, DEFAULT_TOLERANCE);
+    assert_approx_eq(log.times[2], 1.0, DEFAULT_TOLERANCE);
+}
+
+#[test]
+fn test_spec_csv_parse_timestamp_unit() {
+    let mut adapter = acme_adapter();
+    adapter.file_format.timestamp_unit = Some("ms".to_string());
+    let log = SpecCsv::new(adapter)
+        .parse("Seconds\tEngine Speed\nms\trpm\n0\t900\n250\t950\n")
+        .unwrap();
+
+    assert_approx_eq(log.times[1], 0.25, DEFAULT_TOLERANCE);
+}
+
+#[test]
+fn test_spec_csv_parse_metadata() {
+    let log = SpecCsv::new(acme_adapter()).parse(ACME_LOG).unwrap();
+
+    match &log.meta {
+        Meta::SpecCsv(meta) => {
+            assert_eq!(meta.adapter_id, "acme-logger");
+            assert_eq!(meta.adapter_name, "Acme Logger");
+            assert_eq!(meta.vendor, "acme");
+            assert_eq!(meta.channel_count, 4);
+            assert_eq!(meta.data_points, 3);
+        }
+        _ => panic!("Expected adapter spec metadata"),
+    }
+}
+
+#[test]
+fn test_spec_csv_parse_enum_labels() {
+    let yaml = ADAPTER_YAML.replace(
+        "channels:\n",
+        "enums:\n  - name: gear\n    values:\n      \"0\": Neutral\n      \"1\": First\n\
+         channels:\n  - id: gear\n    name: Gear\n    category: drivetrain\n    \
+         data_type: enum\n    unit: \"\"\n    enum_ref: gear\n    source_names: [\"Gear\"]\n",
+    );
+    let adapter: AdapterSpec = serde_yaml::from_str(&yaml).unwrap();
+    let log = SpecCsv::new(adapter)
+        .parse("Seconds\tEngine Speed\tGear\ns\trpm\t\n0\t900\t0\n1\t1500\t1\n")
+        .unwrap();
+
+    assert!(matches!(log.channels[1].value_kind(), ValueKind::Enum(_)));
+    assert_eq!(log.channels[1].value_label(0.0).as_deref(), Some("Neutral"));
+    assert_eq!(
+        log.channels[1]
+            .value_label(log.channel_data(1)[1])
+            .as_deref(),
+        Some("First")
+    );
+    assert_eq!(log.channels[0].value_label(900.0), None);
+}
+
+// ============================================
+// Error Handling Tests
+// ============================================
+
+#[test]
+fn test_spec_csv_parse_empty() {
+    assert!(SpecCsv::new(acme_adapter()).parse("").is_err());
+}
+
+#[test]
+fn test_spec_csv_parse_missing_timestamp_column() {
+    assert!(SpecCsv::new(acme_adapter())
+        .parse("Time\tEngine Speed\ns\trpm\n0\t900\n")
+        .is_err());
+}
@@ -7,15 +7,16 @@
 //! - Timestamp wraparound handling
 //! - Transform formula application
 //! - Real file parsing with example logs
+//! - Streaming parses straight from a file
+//! - Marker blocks kept as annotations
 
-#[path = "../common/mod.rs"]
-mod common;
-
-use common::assertions::*;
-use common::example_files::*;
-use common::{example_file_exists, read_example_binary};
+use crate::common::assertions::*;
+use crate::common::example_files::*;
+use crate::common::{example_file_exists, read_example_binary};
 use ultralog::parsers::speeduino::Speeduino;
+use ultralog::parsers::streaming::{LogBuilder, StreamProgress, StreamingParseable};
 use ultralog::parsers::types::Parseable;
+use ultralog::parsers::ParseError;
 
 // ============================================
 // Format Detection Tests
@@ -118,24 +119,41 @@ fn test_speeduino_parse_invalid_header() {
 }
 
 #[test]
-#[should_panic(expected = "index out of bounds")]
 fn test_speeduino_parse_truncated_header() {
-    // This tests that truncated data causes a panic (parser doesn't handle gracefully yet)
     let truncated = b"MLVLG\x00";
-    let _ = Speeduino::parse_binary(truncated);
+    assert!(Speeduino::parse_binary(truncated).is_err());
 }
 
 #[test]
-#[should_panic(expected = "index out of bounds")]
 fn test_speeduino_parse_unreasonable_field_count() {
-    // Create header with unreasonable field count - parser doesn't validate this yet
+    // Header ends before its offset and field count fields
     let mut data = b"MLVLG\x00".to_vec();
     data.extend_from_slice(&[0x00, 0x01]); // format version 1
     data.extend_from_slice(&[0x00, 0x00, 0x10, 0x00]); // timestamp
     data.extend_from_slice(&[0x00, 0x00]); // info string length
     data.extend_from_slice(&[0x05, 0x00]); // field count = 5 (but not enough data)
 
-    let _ = Speeduino::parse_binary(&data);
+    assert!(Speeduino::parse_binary(&data).is_err());
+}
+
+#[test]
+fn test_speeduino_parse_error_locations() {
+    let error = Speeduino::parse_binary(b"NOT_MLG_FORMAT").unwrap_err();
+    assert!(matches!(error, ParseError::BadMagic { .. }));
+
+    // Complete v1 header whose field count is out of range
+    let mut data = b"MLVLG\x00".to_vec();
+    data.extend_from_slice(&[0x00, 0x01]); // format version 1
+    data.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // timestamp
+    data.extend_from_slice(&[0x00, 0x16]); // info data start
+    data.extend_from_slice(&[0x00, 0x00, 0x00, 0x16]); // data begin index
+    data.extend_from_slice(&[0x00, 0x00]); // record length
+    data.extend_from_slice(&[0x13, 0x88]); // field count = 5000
+
+    let error = Speeduino::parse_binary(&data).unwrap_err();
+    assert!(matches!(error, ParseError::Corrupt { .. }));
+    assert_eq!(error.offset(), Some(20));
+    assert!(error.to_string().contains("Unreasonable field count"));
 }
 
 // ============================================
@@ -165,7 +183,7 @@ fn test_speeduino_example_file() {
     eprintln!(
         "Speeduino log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -202,17 +220,18 @@ fn test_speeduino_data_integrity() {
     let log = Speeduino::parse_binary(&data).expect("Should parse");
 
     // Verify times and data match
-    assert_eq!(log.times.len(), log.data.len());
+    assert_eq!(log.times.len(), log.record_count());
 
     // Verify each record has correct channel count
     let channel_count = log.channels.len();
-    for (i, record) in log.data.iter().enumerate() {
+    assert_eq!(log.columns().len(), channel_count);
+    for (i, column) in log.columns().iter().enumerate() {
         assert_eq!(
-            record.len(),
-            channel_count,
-            "Record {} should have {} values",
+            column.len(),
+            log.times.len(),
+            "Channel {} should have {} values",
             i,
-            channel_count
+            log.times.len()
         );
     }
 }
@@ -246,7 +265,7 @@ fn test_rusefi_example_file() {
     eprintln!(
         "rusEFI log: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -269,7 +288,7 @@ fn test_rusefi_log1_file() {
     eprintln!(
         "rusEFI Log1: {} channels, {} records",
         log.channels.len(),
-        log.data.len()
+        log.record_count()
     );
 }
 
@@ -327,19 +346,19 @@ fn test_speeduino_channel_data_extraction() {
     let data = read_example_binary(SPEEDUINO_MLG);
     let log = Speeduino::parse_binary(&data).expect("Should parse");
 
-    // Test get_channel_data for each channel
+    // Test channel_data for each channel
     for idx in 0..log.channels.len() {
-        let channel_data = log.get_channel_data(idx);
+        let channel_data = log.channel_data(idx);
         assert_eq!(
             channel_data.len(),
-            log.data.len(),
+            log.record_count(),
             "Channel {} data length should match record count",
             idx
         );
     }
 
     // Out of bounds should return empty
-    let oob = log.get_channel_data(999);
+    let oob = log.channel_data(999);
     assert!(oob.is_empty(), "Out of bounds should return empty");
 }
 
@@ -380,9 +399,8 @@ fn test_speeduino_values_are_reasonable() {
     let log = Speeduino::parse_binary(&data).expect("Should parse");
 
     // Check that values are within reasonable ranges (not corrupted)
-    for record in &log.data {
-        for value in record {
-            let v = value.as_f64();
+    for column in log.columns() {
+        for &v in column {
             assert!(v.is_finite(), "All values should be finite");
             // Most ECU values should be within reasonable bounds
             // (this is a sanity check, not a strict requirement)
@@ -408,7 +426,7 @@ fn test_speeduino_large_file_performance() {
     let log = Speeduino::parse_binary(&data).expect("Should parse large file");
     let elapsed = start.elapsed();
 
-    eprintln!("Parsed {} records in {:?}", log.data.len(), elapsed);
+    eprintln!("Parsed {} records in {:?}", log.record_count(), elapsed);
 
     // Should complete in reasonable time (less than 10 seconds)
     assert!(
@@ -416,3 +434,170 @@ fn test_speeduino_large_file_performance() {
         "Parsing should complete in reasonable time"
     );
 }
+
+// ============================================
+// Streaming Tests
+// ============================================
+
+#[test]
+fn test_speeduino_stream_from_file() {
+    if !example_file_exists(SPEEDUINO_MLG) {
+        eprintln!("Skipping test: {} not found", SPEEDUINO_MLG);
+        return;
+    }
+
+    let expected = Speeduino::parse_binary(&read_example_binary(SPEEDUINO_MLG)).unwrap();
+
+    // Read straight from disk rather than from a buffer
+    let file = std::fs::File::open(SPEEDUINO_MLG).unwrap();
+    let mut builder = LogBuilder::new().with_decimation(2);
+    let mut last = StreamProgress::default();
+    Speeduino
+        .parse_stream(file, &mut builder, &mut |p| last = p)
+        .unwrap();
+    let log = builder.finish();
+
+    assert_eq!(log.channels.len(), expected.channels.len());
+    assert_eq!(log.record_count(), expected.record_count().div_ceil(2));
+    assert_eq!(log.times[1], expected.times[2]);
+    assert_eq!(log.channel_data(0)[1], expected.channel_data(0)[2]);
+    assert_eq!(last.fraction(), 1.0);
+    assert_eq!(last.records, expected.record_count());
+}
+
+// ============================================
+// Recovery Tests
+// ============================================
+
+/// Offset of the first data block and the size of each one in the Speeduino example
+const SPEEDUINO_DATA_BEGIN: usize = 3359;
+const SPEEDUINO_BLOCK_BYTES: usize = 120;
+
+#[test]
+fn test_speeduino_recover_intact_file() {
+    if !example_file_exists(SPEEDUINO_MLG) {
+        eprintln!("Skipping test: {} not found", SPEEDUINO_MLG);
+        return;
+    }
+
+    let data = read_example_binary(SPEEDUINO_MLG);
+    let expected = Speeduino::parse_binary(&data).unwrap();
+    let log = Speeduino::recover_binary(&data).unwrap();
+
+    assert_eq!(log.record_count(), expected.record_count());
+    assert!(expected.recovery.is_none());
+    assert!(log.recovery.as_ref().unwrap().is_clean());
+}
+
+#[test]
+fn test_speeduino_recover_truncated_file() {
+    if !example_file_exists(SPEEDUINO_MLG) {
+        eprintln!("Skipping test: {} not found", SPEEDUINO_MLG);
+        return;
+    }
+
+    let data = read_example_binary(SPEEDUINO_MLG);
+    let records = (data.len() - SPEEDUINO_DATA_BEGIN) / SPEEDUINO_BLOCK_BYTES;
+    let truncated = &data[..data.len() - 50];
+
+    let log = Speeduino::recover_binary(truncated).unwrap();
+    let report = log.recovery.as_ref().unwrap();
+
+    assert_eq!(log.record_count(), records - 1);
+    assert_eq!(report.skipped_records, 1);
+    assert_eq!(report.resyncs, 0);
+    assert_eq!(
+        report.first_error_offset,
+        Some((SPEEDUINO_DATA_BEGIN + (records - 1) * SPEEDUINO_BLOCK_BYTES) as u64)
+    );
+}
+
+#[test]
+fn test_speeduino_recover_resyncs_after_garbled_block() {
+    if !example_file_exists(SPEEDUINO_MLG) {
+        eprintln!("Skipping test: {} not found", SPEEDUINO_MLG);
+        return;
+    }
+
+    let data = read_example_binary(SPEEDUINO_MLG);
+    let expected = Speeduino::parse_binary(&data).unwrap();
+
+    // Garble the header of block 50 and part of its field values
+    let mut garbled = data.clone();
+    let block = SPEEDUINO_DATA_BEGIN + 50 * SPEEDUINO_BLOCK_BYTES;
+    garbled[block..block + 20].fill(0xFF);
+
+    let strict = Speeduino::parse_binary(&garbled).unwrap();
+    assert_eq!(
+        strict.record_count(),
+        50,
+        "Strict parsing stops at the damage"
+    );
+
+    let log = Speeduino::recover_binary(&garbled).unwrap();
+    let report = log.recovery.as_ref().unwrap();
+
+    assert_eq!(log.record_count(), expected.record_count() - 1);
+    assert_eq!(report.skipped_records, 1);
+    assert_eq!(report.resyncs, 1);
+    assert_eq!(report.first_error_offset, Some(block as u64));
+    assert_eq!(log.times[50], expected.times[51]);
+    assert_eq!(log.channel_data(1)[50], expected.channel_data(1)[51]);
+}
+
+#[test]
+fn test_speeduino_recover_skips_bad_checksum() {
+    if !example_file_exists(SPEEDUINO_MLG) {
+        eprintln!("Skipping test: {} not found", SPEEDUINO_MLG);
+        return;
+    }
+
+    let data = read_example_binary(SPEEDUINO_MLG);
+    let expected = Speeduino::parse_binary(&data).unwrap();
+
+    // Flip a field byte in block 10 so its CRC no longer matches
+    let mut damaged = data.clone();
+    damaged[SPEEDUINO_DATA_BEGIN + 10 * SPEEDUINO_BLOCK_BYTES + 8] ^= 0x55;
+
+    let log = Speeduino::recover_binary(&damaged).unwrap();
+    assert_eq!(log.record_count(), expected.record_count() - 1);
+    assert_eq!(log.recovery.as_ref().unwrap().skipped_records, 1);
+    assert_eq!(log.times[10], expected.times[11]);
+}
+
+// ============================================
+// Marker Tests
+// ============================================
+
+#[test]
+fn test_speeduino_marker_block_becomes_annotation() {
+    if !example_file_exists(SPEEDUINO_MLG) {
+        eprintln!("Skipping test: {} not found", SPEEDUINO_MLG);
+        return;
+    }
+
+    let data = read_example_binary(SPEEDUINO_MLG);
+    let expected = Speeduino::parse_binary(&data).unwrap();
+    assert!(expected.annotations.is_empty());
+
+    // Insert a marker block stamped like block 10 straight after it
+    let block = SPEEDUINO_DATA_BEGIN + 10 * SPEEDUINO_BLOCK_BYTES;
+    let mut marker = vec![1, 0, data[block + 2], data[block + 3]];
+    let mut message = b"Wide open throttle".to_vec();
+    message.resize(50, 0);
+    marker.extend(message);
+
+    let mut marked = data.clone();
+    let next = block + SPEEDUINO_BLOCK_BYTES;
+    marked.splice(next..next, marker);
+
+    let log = Speeduino::parse_binary(&marked).unwrap();
+    assert_eq!(log.record_count(), expected.record_count());
+    assert_eq!(log.annotations.len(), 1);
+    assert_eq!(log.annotations[0].label, "Wide open throttle");
+    assert_eq!(log.annotations[0].time, expected.times[10]);
+
+    // Recovery mode keeps markers too
+    let recovered = Speeduino::recover_binary(&marked).unwrap();
+    assert_eq!(recovered.annotations, log.annotations);
+}